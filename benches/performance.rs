@@ -1,7 +1,11 @@
 //! Performance benchmarks for Lingo database
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use lingo::core::Coordinate3D;
+use lingo::core::{Coordinate3D, Layer};
+use lingo::plugins::Plugin;
+use lingo::storage::{DatabaseBuilder, LingoDatabase};
+use lingo::{FunctionExtractor, LingoExecutor, QueryBuilder};
+use std::sync::Arc;
 
 fn benchmark_coordinate_distance(c: &mut Criterion) {
     let coord1 = Coordinate3D::new(0.1, 0.2, 0.3);
@@ -25,5 +29,88 @@ fn benchmark_coordinate_distance_squared(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, benchmark_coordinate_distance, benchmark_coordinate_distance_squared);
+fn benchmark_batch_queries(c: &mut Criterion) {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("batch.lingo");
+    
+    let mut builder = DatabaseBuilder::new();
+    for i in 0..1000 {
+        let position = Coordinate3D::new(
+            (i % 10) as f32 / 10.0,
+            (i / 10 % 10) as f32 / 10.0,
+            (i / 100) as f32 / 10.0,
+        );
+        builder.add_node(&format!("w{}", i), Layer::Words, position).unwrap();
+    }
+    builder.build(&path).unwrap();
+    
+    let mut executor = LingoExecutor::new();
+    executor.load_database(&path).unwrap();
+    
+    let words: Vec<String> = (0..1000).map(|i| format!("w{}", i)).collect();
+    let inputs: Vec<&str> = words.iter().map(String::as_str).collect();
+    
+    let mut group = c.benchmark_group("1000_lookups");
+    group.sample_size(10);
+    group.bench_function("compile_and_execute_each", |b| {
+        b.iter(|| {
+            for word in &inputs {
+                let query = QueryBuilder::find(word).similar_threshold(0.8).limit(10).compile();
+                black_box(executor.execute(&query).unwrap());
+            }
+        })
+    });
+    
+    let template = QueryBuilder::find("")
+        .similar_threshold(0.8)
+        .limit(10)
+        .compile_template()
+        .unwrap();
+    group.bench_function("execute_batch", |b| {
+        b.iter(|| black_box(executor.execute_batch(&template, &inputs).unwrap()))
+    });
+    group.finish();
+}
+
+fn benchmark_function_extraction(c: &mut Criterion) {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("extraction.lingo");
+    
+    // 30 words, touching every detector
+    let sentence = "the manager will create a detailed report and then analyze the data \
+                    so that the team can improve performance if the market changes and \
+                    growth becomes stable after next quarter";
+    
+    let mut builder = DatabaseBuilder::new();
+    let mut words: Vec<&str> = sentence.split_whitespace().collect();
+    words.sort();
+    words.dedup();
+    for (i, word) in words.iter().enumerate() {
+        let position = Coordinate3D::new((i % 7) as f32 / 7.0, (i / 7) as f32 / 7.0, 0.5);
+        builder.add_node(word, Layer::Words, position).unwrap();
+    }
+    for i in 0..1000 {
+        let position = Coordinate3D::new((i % 10) as f32 / 10.0, (i / 10 % 10) as f32 / 10.0, 0.45);
+        builder.add_node(&format!("w{}", i), Layer::Words, position).unwrap();
+    }
+    builder.build(&path).unwrap();
+    
+    let mut extractor = FunctionExtractor::new();
+    extractor.initialize_shared(&Arc::new(LingoDatabase::open(&path).unwrap())).unwrap();
+    
+    let mut group = c.benchmark_group("function_extraction");
+    group.sample_size(20);
+    group.bench_function("30_word_sentence", |b| {
+        b.iter(|| black_box(extractor.extract_function_signature(black_box(sentence)).unwrap()))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_coordinate_distance,
+    benchmark_coordinate_distance_squared,
+    benchmark_batch_queries,
+    benchmark_function_extraction
+);
 criterion_main!(benches);
\ No newline at end of file
@@ -75,7 +75,7 @@ fn demo_find_word(executor: &mut LingoExecutor, word: &str) -> Result<()> {
     );
     
     // Print node details
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for (i, node_id) in result.nodes.as_slice().iter().enumerate() {
             if let Ok(word) = db.get_node_word(*node_id) {
                 println!("  [{}] {}", i + 1, word);
@@ -101,7 +101,7 @@ fn demo_find_similar(executor: &mut LingoExecutor, word: &str) -> Result<()> {
     println!("Results: {} similar nodes found", result.nodes.len());
     
     // Print similar words
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for (i, node_id) in result.nodes.as_slice().iter().enumerate() {
             if let Ok(word) = db.get_node_word(*node_id) {
                 if let Ok(node) = db.get_node(*node_id) {
@@ -134,7 +134,7 @@ fn demo_layer_navigation(executor: &mut LingoExecutor, word: &str) -> Result<()>
     println!("Results: {} parent concepts found", result.nodes.len());
     
     // Print parent concepts
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for (i, node_id) in result.nodes.as_slice().iter().enumerate() {
             if let Ok(word) = db.get_node_word(*node_id) {
                 if let Ok(node) = db.get_node(*node_id) {
@@ -164,7 +164,7 @@ fn demo_follow_connections(executor: &mut LingoExecutor, word: &str) -> Result<(
     println!("Results: {} connected nodes found", result.nodes.len());
     
     // Print connected nodes
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for (i, node_id) in result.nodes.as_slice().iter().enumerate() {
             if let Ok(word) = db.get_node_word(*node_id) {
                 println!("  [{}] {}", i + 1, word);
@@ -199,7 +199,7 @@ fn demo_complex_query(executor: &mut LingoExecutor) -> Result<()> {
     println!("Instructions executed: {}", result.instructions_executed);
     
     // Print results
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for (i, node_id) in result.nodes.as_slice().iter().enumerate() {
             if let Ok(word) = db.get_node_word(*node_id) {
                 if let Ok(node) = db.get_node(*node_id) {
@@ -23,7 +23,7 @@ fn main() -> Result<()> {
     let result = executor.execute(&query)?;
     println!("Results: {} nodes found", result.nodes.len());
     
-    if let Some(db) = &executor.database {
+    if let Some(db) = executor.database() {
         for (i, node_id) in result.nodes.as_slice().iter().enumerate() {
             if let Ok(word) = db.get_node_word(*node_id) {
                 if let Ok(node) = db.get_node(*node_id) {
@@ -43,7 +43,7 @@ fn main() -> Result<()> {
     let result = executor.execute(&query)?;
     println!("Results: {} nodes found", result.nodes.len());
     
-    if let Some(db) = &executor.database {
+    if let Some(db) = executor.database() {
         for (i, node_id) in result.nodes.as_slice().iter().enumerate() {
             if let Ok(word) = db.get_node_word(*node_id) {
                 if let Ok(node) = db.get_node(*node_id) {
@@ -64,7 +64,7 @@ fn main() -> Result<()> {
     let result = executor.execute(&query)?;
     println!("Results: {} nodes found", result.nodes.len());
     
-    if let Some(db) = &executor.database {
+    if let Some(db) = executor.database() {
         for (i, node_id) in result.nodes.as_slice().iter().enumerate() {
             if let Ok(word) = db.get_node_word(*node_id) {
                 println!("  [{}] {}", i + 1, word);
@@ -44,7 +44,7 @@ fn test_morphological_patterns(executor: &mut LingoExecutor) -> Result<(), Box<d
     let mut suffixes = Vec::new();
     let mut roots = Vec::new();
     
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for i in 0..db.node_count() {
             let node_id = NodeId(i as u32);
             if let Ok(node) = db.get_node(node_id) {
@@ -98,7 +98,7 @@ fn test_morpheme_combination(executor: &mut LingoExecutor, prefix: &str, root: &
     let suffix_result = executor.execute(&suffix_query)?;
     
     if !prefix_result.nodes.is_empty() && !root_result.nodes.is_empty() && !suffix_result.nodes.is_empty() {
-        if let Some(db) = executor.database.as_ref() {
+        if let Some(db) = executor.database() {
             if let (Ok(p_node), Ok(r_node), Ok(s_node)) = (
                 db.get_node(prefix_result.nodes.as_slice()[0]),
                 db.get_node(root_result.nodes.as_slice()[0]),
@@ -144,7 +144,7 @@ fn test_etymology_patterns(executor: &mut LingoExecutor) -> Result<(), Box<dyn s
     // For now, we'll skip etymology analysis since nodes don't have etymology field
     // Just count morphemes by type
     
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         let mut type_counts = std::collections::HashMap::new();
         
         for i in 0..db.node_count() {
@@ -185,7 +185,7 @@ fn test_semantic_oppositions(executor: &mut LingoExecutor) -> Result<(), Box<dyn
         let result2 = executor.execute(&query2)?;
         
         if !result1.nodes.is_empty() && !result2.nodes.is_empty() {
-            if let Some(db) = executor.database.as_ref() {
+            if let Some(db) = executor.database() {
                 if let (Ok(node1), Ok(node2)) = (
                     db.get_node(result1.nodes.as_slice()[0]),
                     db.get_node(result2.nodes.as_slice()[0])
@@ -229,7 +229,7 @@ fn test_productivity_patterns(executor: &mut LingoExecutor) -> Result<(), Box<dy
     let mut productive_morphemes = Vec::new();
     let mut by_type: HashMap<MorphemeType, Vec<String>> = HashMap::new();
     
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for i in 0..db.node_count() {
             let node_id = NodeId(i as u32);
             if let Ok(node) = db.get_node(node_id) {
@@ -261,7 +261,7 @@ fn test_productivity_patterns(executor: &mut LingoExecutor) -> Result<(), Box<dy
     let mut all_positions = Vec::new();
     
     // Collect positions
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for i in 0..db.node_count() {
             let node_id = NodeId(i as u32);
             if let Ok(node) = db.get_node(node_id) {
@@ -314,7 +314,7 @@ fn test_cross_layer_patterns(executor: &mut LingoExecutor) -> Result<(), Box<dyn
             print!("  {} → ", letter);
             let mut phoneme_count = 0;
             
-            if let Some(db) = executor.database.as_ref() {
+            if let Some(db) = executor.database() {
                 // Find connected phonemes
                 if let Ok(connections) = db.get_node_connections(letter_id) {
                     for conn in connections {
@@ -348,7 +348,7 @@ fn test_cross_layer_patterns(executor: &mut LingoExecutor) -> Result<(), Box<dyn
     let mut root_positions = Vec::new();
     
     // Collect morpheme positions by type
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for i in 0..db.node_count() {
             let node_id = NodeId(i as u32);
             if let Ok(node) = db.get_node(node_id) {
@@ -56,7 +56,7 @@ fn explore_gradient(
         return Ok(());
     }
     
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         let start_id = start_result.nodes.as_slice()[0];
         let end_id = end_result.nodes.as_slice()[0];
         
@@ -164,7 +164,7 @@ fn find_oppositional_pairs(executor: &mut LingoExecutor) -> Result<(), Box<dyn s
         let result2 = executor.execute(&QueryBuilder::find(word2).compile())?;
         
         if !result1.nodes.is_empty() && !result2.nodes.is_empty() {
-            if let Some(db) = executor.database.as_ref() {
+            if let Some(db) = executor.database() {
                 if let (Ok(node1), Ok(node2)) = (
                     db.get_node(result1.nodes.as_slice()[0]),
                     db.get_node(result2.nodes.as_slice()[0])
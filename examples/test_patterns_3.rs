@@ -137,7 +137,7 @@ fn get_opposition_coordinates(
         return None;
     }
     
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         let node1 = db.get_node(result1.nodes.as_slice()[0]).ok()?;
         let node2 = db.get_node(result2.nodes.as_slice()[0]).ok()?;
         
@@ -271,7 +271,7 @@ fn find_nearby_morphemes(
 ) -> Vec<String> {
     let mut results = Vec::new();
     
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for i in 0..db.node_count() {
             let node_id = NodeId(i as u32);
             if let Ok(node) = db.get_node(node_id) {
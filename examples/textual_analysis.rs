@@ -45,7 +45,7 @@ fn analyze_morpheme_distribution(executor: &LingoExecutor) -> Result<(), Box<dyn
     let mut stats = HashMap::new();
     let mut total_morphemes = 0;
     
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for i in 0..db.node_count() {
             let node_id = NodeId(i as u32);
             if let Ok(node) = db.get_node(node_id) {
@@ -96,7 +96,7 @@ fn show_spatial_layout(executor: &LingoExecutor) -> Result<(), Box<dyn std::erro
     // 10x20 ASCII grid
     let mut grid: Vec<Vec<char>> = vec![vec![' '; 40]; 10];
     
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for i in 0..db.node_count() {
             let node_id = NodeId(i as u32);
             if let Ok(node) = db.get_node(node_id) {
@@ -144,7 +144,7 @@ fn analyze_etymology_patterns(executor: &mut LingoExecutor) -> Result<(), Box<dy
     for morpheme in test_morphemes {
         if let Ok(result) = executor.execute(&QueryBuilder::find(morpheme).compile()) {
             if !result.nodes.is_empty() {
-                if let Some(db) = executor.database.as_ref() {
+                if let Some(db) = executor.database() {
                     if let Ok(node) = db.get_node(result.nodes.as_slice()[0]) {
                         // Infer etymology from position/flags
                         let etymology = if node.flags.contains(NodeFlags::IS_TECHNICAL) {
@@ -267,7 +267,7 @@ fn get_positions(executor: &mut LingoExecutor, word1: &str, word2: &str) -> (Opt
     
     if let Ok(result1) = executor.execute(&QueryBuilder::find(word1).compile()) {
         if !result1.nodes.is_empty() {
-            if let Some(db) = executor.database.as_ref() {
+            if let Some(db) = executor.database() {
                 if let Ok(node) = db.get_node(result1.nodes.as_slice()[0]) {
                     pos1 = Some(node.position);
                 }
@@ -277,7 +277,7 @@ fn get_positions(executor: &mut LingoExecutor, word1: &str, word2: &str) -> (Opt
     
     if let Ok(result2) = executor.execute(&QueryBuilder::find(word2).compile()) {
         if !result2.nodes.is_empty() {
-            if let Some(db) = executor.database.as_ref() {
+            if let Some(db) = executor.database() {
                 if let Ok(node) = db.get_node(result2.nodes.as_slice()[0]) {
                     pos2 = Some(node.position);
                 }
@@ -298,7 +298,7 @@ fn calculate_distance(a: &Coordinate3D, b: &Coordinate3D) -> f32 {
 fn find_nearby(executor: &LingoExecutor, target: Coordinate3D, radius: f32) -> Vec<String> {
     let mut results = Vec::new();
     
-    if let Some(db) = executor.database.as_ref() {
+    if let Some(db) = executor.database() {
         for i in 0..db.node_count() {
             let node_id = NodeId(i as u32);
             if let Ok(node) = db.get_node(node_id) {
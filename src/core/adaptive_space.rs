@@ -570,10 +570,10 @@ impl AdaptiveSpatialManager {
                     if dx == 0 && dy == 0 && dz == 0 { continue; }
                     
                     let candidate = Coordinate3D {
-                        x: (center.x + dx as f32 * step_size).clamp(0.0, 1.0),
-                        y: (center.y + dy as f32 * step_size).clamp(0.0, 1.0),
-                        z: (center.z + dz as f32 * step_size).clamp(0.0, 1.0),
-                    };
+                        x: center.x + dx as f32 * step_size,
+                        y: center.y + dy as f32 * step_size,
+                        z: center.z + dz as f32 * step_size,
+                    }.clamp_unit();
                     candidates.push(candidate);
                 }
             }
@@ -604,10 +604,7 @@ impl AdaptiveSpatialManager {
         let mut min_distance = f32::MAX;
         
         for (_, other_pos, _) in &self.position_history {
-            let distance = ((position.x - other_pos.x).powi(2) + 
-                           (position.y - other_pos.y).powi(2) + 
-                           (position.z - other_pos.z).powi(2)).sqrt();
-            min_distance = min_distance.min(distance);
+            min_distance = min_distance.min(position.distance_to(*other_pos));
         }
         
         // Bonus for maintaining good separation
@@ -13,6 +13,10 @@ pub enum SlangOp {
     GetCurrent = 2,
     /// Set current node set
     SetCurrent = 3,
+    /// Load a list of nodes by ID
+    LoadNodeIds = 4,
+    /// Load a multi-word phrase by string, falling back to its words
+    LoadPhrase = 5,
     
     // Layer Operations (16-31)
     /// Move up N layers
@@ -43,6 +47,11 @@ pub enum SlangOp {
     Bidirectional = 50,
     /// Explore connection neighborhood
     ConnectionNeighborhood = 51,
+    /// Follow strongest connection after per-type weighting
+    FollowWeighted = 52,
+    /// Follow every connection of a type (`operand1`, or `ANY_CONNECTION_TYPE`)
+    /// at least as strong as `operand2` (0-65535), at most `operand3` per node
+    FollowWhere = 53,
     
     // Spatial Operations (64-79)
     /// Find spatial neighbors
@@ -53,6 +62,12 @@ pub enum SlangOp {
     SpatialLayer = 66,
     /// Find cluster center
     SpatialCluster = 67,
+    /// Reflect through a point and find the nearest node
+    SpatialReflect = 68,
+    /// Load every node inside an axis-aligned box
+    SpatialBox = 69,
+    /// K nearest nodes to each current node
+    SpatialNearest = 70,
     
     // Search Operations (80-95)
     /// Semantic similarity search
@@ -97,9 +112,21 @@ pub enum SlangOp {
     Limit = 130,
     /// Remove duplicates
     Deduplicate = 131,
+    /// Remove nodes matching a word or ID list
+    Exclude = 132,
+    /// Remove nodes within a radius of a word's nodes
+    ExcludeNear = 133,
+    /// Skip the first nodes of the result set
+    Offset = 134,
+    /// Replace the top two sets with their union
+    Union = 135,
+    /// Replace the top two sets with the nodes of the lower one also in the top
+    Intersect = 136,
+    /// Replace the top two sets with the nodes of the lower one not in the top
+    Difference = 137,
     
     // Control Operations (144-159)
-    /// Conditional branch
+    /// Skip the next `operand2` instructions when the current set is empty
     Branch = 144,
     /// Loop operation
     Loop = 145,
@@ -225,16 +252,62 @@ impl std::fmt::Debug for SlangInstruction {
     }
 }
 
+/// Sort keys carried in `operand1` of a `Sort` instruction.
+///
+/// Each key sorts ascending; the `INVERSE` flag reverses it. Ties always
+/// fall back to ascending node ID.
+pub mod sort_keys {
+    /// Node ID, the order `Offset` pages over when no sort is given
+    pub const NODE_ID: u16 = 0;
+    /// Distance from the last similarity search's seed, closest first
+    pub const DISTANCE: u16 = 1;
+    /// Word frequency, least frequent first
+    pub const FREQUENCY: u16 = 2;
+    /// Layer, `Letters` first
+    pub const LAYER: u16 = 3;
+    /// Etymology origin, in `EtymologyOrigin` discriminant order
+    pub const ETYMOLOGY: u16 = 4;
+    /// Surface form, by byte order
+    pub const ALPHABETICAL: u16 = 5;
+    /// Strength of the node's strongest outgoing connection, weakest first
+    pub const CONNECTION_STRENGTH: u16 = 6;
+}
+
+/// Node predicates carried in `operand1` of a `Filter` instruction, with
+/// their argument in `operand2`
+pub mod filter_kinds {
+    /// Layer is in a bitmask, bit `n` for layer `n`
+    pub const LAYERS: u16 = 0;
+    /// `frequency_rank` is below a rank
+    pub const FREQUENCY_RANK_BELOW: u16 = 1;
+    /// Productivity is above a fraction, stored as `f32` bits
+    pub const PRODUCTIVITY_ABOVE: u16 = 2;
+    /// Etymology origin equals an [`EtymologyOrigin`](crate::core::EtymologyOrigin) byte
+    pub const ETYMOLOGY: u16 = 3;
+}
+
+/// `operand1` of a `FollowWhere` instruction that follows every type
+pub const ANY_CONNECTION_TYPE: u16 = u16::MAX;
+
 /// Flags for instruction execution
 pub mod instruction_flags {
     /// Has limit parameter
     pub const HAS_LIMIT: u8 = 0x01;
     /// Inverse operation
     pub const INVERSE: u8 = 0x02;
-    /// Case insensitive
+    /// Case insensitive; with `FOLD_ACCENTS` on a word lookup, resolve to
+    /// the single normalized match instead of every folded one
     pub const CASE_INSENSITIVE: u8 = 0x04;
     /// Include self in results
     pub const INCLUDE_SELF: u8 = 0x08;
+    /// Break near-ties in similarity by word frequency
+    pub const PREFER_FREQUENT: u8 = 0x10;
+    /// Match words with case and diacritics folded
+    pub const FOLD_ACCENTS: u8 = 0x20;
+    /// Match words by phonetic key instead of spelling
+    pub const PHONETIC: u8 = 0x40;
+    /// Operand indexes the query's node list table instead of its strings
+    pub const NODE_LIST: u8 = 0x80;
 }
 
 #[cfg(test)]
@@ -5,7 +5,7 @@ use bitflags::bitflags;
 
 /// Orthogonal connection between nodes (20 bytes packed)
 #[repr(C, packed)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct OrthogonalConnection {
     /// Target node ID (4 bytes)
     pub target_node: NodeId,
@@ -47,11 +47,20 @@ impl OrthogonalConnection {
     pub fn set_strength_normalized(&mut self, strength: f32) {
         self.strength = (strength.clamp(0.0, 1.0) * 65535.0) as u16;
     }
+    
+    /// Reverse the byte order of every multi-byte field in place
+    pub fn swap_bytes(&mut self) {
+        self.target_node = NodeId(self.target_node.0.swap_bytes());
+        self.strength = self.strength.swap_bytes();
+        let mut vector = self.transformation_vector;
+        vector.swap_bytes();
+        self.transformation_vector = vector;
+    }
 }
 
 /// Type of semantic/linguistic connection
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ConnectionType {
     /// Similar meaning
     Synonymy = 0,
@@ -81,9 +90,35 @@ pub enum ConnectionType {
     LexicalBridge = 12,
     /// Morphological pattern similarity
     MorphologicalPattern = 13,
+    /// Type byte this version doesn't recognize, e.g. from a newer file
+    Unknown = 255,
 }
 
 impl ConnectionType {
+    /// Decode a type byte as stored in a connection record.
+    ///
+    /// Returns `None` for bytes that don't name a type this version knows.
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Some(match value {
+            0 => ConnectionType::Synonymy,
+            1 => ConnectionType::Antonymy,
+            2 => ConnectionType::Hypernymy,
+            3 => ConnectionType::Hyponymy,
+            4 => ConnectionType::Meronymy,
+            5 => ConnectionType::Derivation,
+            6 => ConnectionType::Etymology,
+            7 => ConnectionType::Phonetic,
+            8 => ConnectionType::Analogy,
+            9 => ConnectionType::Collocation,
+            10 => ConnectionType::Causation,
+            11 => ConnectionType::Learned,
+            12 => ConnectionType::LexicalBridge,
+            13 => ConnectionType::MorphologicalPattern,
+            255 => ConnectionType::Unknown,
+            _ => return None,
+        })
+    }
+    
     /// Check if this connection type is bidirectional
     pub fn is_bidirectional(&self) -> bool {
         matches!(self, 
@@ -95,6 +130,31 @@ impl ConnectionType {
         )
     }
     
+    /// Typical strength for this relationship when none is given.
+    ///
+    /// Used by the builder for edges added without an explicit strength.
+    /// Tight lexical relations (synonymy, derivation) score high; looser
+    /// associations (analogy, learned links) sit near the middle.
+    pub fn default_strength(&self) -> f32 {
+        match self {
+            ConnectionType::Synonymy => 0.9,
+            ConnectionType::Antonymy => 0.9,
+            ConnectionType::Hypernymy => 0.8,
+            ConnectionType::Hyponymy => 0.8,
+            ConnectionType::Meronymy => 0.7,
+            ConnectionType::Derivation => 0.95,
+            ConnectionType::Etymology => 0.85,
+            ConnectionType::Phonetic => 0.6,
+            ConnectionType::Analogy => 0.5,
+            ConnectionType::Collocation => 0.6,
+            ConnectionType::Causation => 0.7,
+            ConnectionType::Learned => 0.5,
+            ConnectionType::LexicalBridge => 0.6,
+            ConnectionType::MorphologicalPattern => 0.7,
+            ConnectionType::Unknown => 0.5,
+        }
+    }
+    
     /// Get the inverse connection type (if applicable)
     pub fn inverse(&self) -> Option<ConnectionType> {
         match self {
@@ -114,7 +174,7 @@ impl ConnectionType {
 
 bitflags! {
     /// Context mask for domain-specific connections
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
     pub struct ContextMask: u8 {
         /// Medical domain
         const MEDICAL = 0b00000001;
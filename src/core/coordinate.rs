@@ -2,6 +2,18 @@
 
 use std::ops::{Add, Sub, Mul, Div};
 
+/// Clamp a scalar (confidence, weight, coordinate component) to [0, 1].
+///
+/// NaN maps to 0.0 so bad inputs can't escape the unit range.
+#[inline]
+pub fn clamp01(value: f32) -> f32 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, 1.0)
+    }
+}
+
 /// 3D coordinate in linguistic space
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -20,13 +32,37 @@ pub struct Coordinate3D {
 }
 
 impl Coordinate3D {
-    /// Create a new coordinate
+    /// Create a new coordinate, clamping each component to [0, 1] (NaN becomes 0.0)
     pub fn new(x: f32, y: f32, z: f32) -> Self {
-        Self {
-            x: x.clamp(0.0, 1.0),
-            y: y.clamp(0.0, 1.0),
-            z: z.clamp(0.0, 1.0),
-        }
+        Self { x, y, z }.clamp()
+    }
+    
+    /// Create a coordinate guaranteed to lie in the unit cube.
+    ///
+    /// Each component is clamped into [0, 1] and NaN becomes 0.0, so the
+    /// result always passes [`is_valid`](Self::is_valid). [`new`](Self::new)
+    /// does the same; struct literals don't clamp at all.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::core::Coordinate3D;
+    ///
+    /// let position = Coordinate3D::new_clamped(-0.5, f32::NAN, 3.0);
+    /// assert_eq!(position, Coordinate3D::new(0.0, 0.0, 1.0));
+    /// assert!(position.is_valid());
+    /// ```
+    #[inline]
+    pub fn new_clamped(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }.clamp()
+    }
+    
+    /// Whether every component is a number in [0, 1].
+    ///
+    /// Both bounds are inside the cube; NaN and infinities are not.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        [self.x, self.y, self.z].iter().all(|c| (0.0..=1.0).contains(c))
     }
     
     /// Zero coordinate
@@ -34,24 +70,37 @@ impl Coordinate3D {
         Self { x: 0.0, y: 0.0, z: 0.0 }
     }
     
-    /// Calculate Euclidean distance to another coordinate
+    /// Euclidean distance to another coordinate.
+    ///
+    /// This is the metric behind similarity search. When distances are only
+    /// compared, use [`distance_squared_to`](Self::distance_squared_to),
+    /// which ranks the same way without the `sqrt`.
     #[inline]
-    pub fn distance(&self, other: Coordinate3D) -> f32 {
-        let dx = self.x - other.x;
-        let dy = self.y - other.y;
-        let dz = self.z - other.z;
-        (dx * dx + dy * dy + dz * dz).sqrt()
+    pub fn distance_to(&self, other: Coordinate3D) -> f32 {
+        self.distance_squared_to(other).sqrt()
     }
     
-    /// Calculate squared distance (faster, no sqrt)
+    /// Squared Euclidean distance to another coordinate (no sqrt)
     #[inline]
-    pub fn distance_squared(&self, other: Coordinate3D) -> f32 {
+    pub fn distance_squared_to(&self, other: Coordinate3D) -> f32 {
         let dx = self.x - other.x;
         let dy = self.y - other.y;
         let dz = self.z - other.z;
         dx * dx + dy * dy + dz * dz
     }
     
+    /// Calculate Euclidean distance to another coordinate (alias for `distance_to`)
+    #[inline]
+    pub fn distance(&self, other: Coordinate3D) -> f32 {
+        self.distance_to(other)
+    }
+    
+    /// Calculate squared distance (alias for `distance_squared_to`)
+    #[inline]
+    pub fn distance_squared(&self, other: Coordinate3D) -> f32 {
+        self.distance_squared_to(other)
+    }
+    
     /// Linear interpolation between two coordinates
     pub fn lerp(&self, other: Coordinate3D, t: f32) -> Coordinate3D {
         Coordinate3D {
@@ -61,9 +110,94 @@ impl Coordinate3D {
         }
     }
     
-    /// Clamp all components to [0, 1] range
+    /// Clamp all components to [0, 1] range, mapping NaN to 0.0
     pub fn clamp(&self) -> Coordinate3D {
-        Coordinate3D::new(self.x, self.y, self.z)
+        Coordinate3D {
+            x: clamp01(self.x),
+            y: clamp01(self.y),
+            z: clamp01(self.z),
+        }
+    }
+    
+    /// Clamp all components into the unit cube [0, 1]³.
+    ///
+    /// Arithmetic operators don't clamp, so use this on positions computed
+    /// by composition or synthesis before storing them. Same as
+    /// [`clamp`](Self::clamp), taking `self` by value.
+    #[inline]
+    pub fn clamp_unit(self) -> Coordinate3D {
+        self.clamp()
+    }
+    
+    /// Reverse the byte order of each component in place
+    pub fn swap_bytes(&mut self) {
+        self.x = f32::from_bits(self.x.to_bits().swap_bytes());
+        self.y = f32::from_bits(self.y.to_bits().swap_bytes());
+        self.z = f32::from_bits(self.z.to_bits().swap_bytes());
+    }
+}
+
+/// How similarity searches measure the distance between two positions.
+///
+/// Similarity is `1 - distance`, so a threshold of 0.7 keeps nodes within
+/// 0.3 under the chosen metric.
+///
+/// # Examples
+///
+/// ```rust
+/// use lingo::core::{Coordinate3D, SimilarityMetric};
+///
+/// let a = Coordinate3D::new(0.5, 0.1, 0.5);
+/// let b = Coordinate3D::new(0.5, 0.9, 0.5);
+/// assert!(SimilarityMetric::Euclidean.distance(a, b) > 0.7);
+/// assert_eq!(SimilarityMetric::SemanticOnly.distance(a, b), 0.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SimilarityMetric {
+    /// Straight-line distance over the phonetic (x), etymology (y) and
+    /// abstraction (z) axes
+    #[default]
+    Euclidean,
+    /// Euclidean distance with each axis difference scaled by its weight
+    /// before squaring; a weight of 0 ignores the axis
+    WeightedAxes {
+        /// Weight of the phonetic (x) axis
+        wx: f32,
+        /// Weight of the etymology (y) axis
+        wy: f32,
+        /// Weight of the abstraction (z) axis
+        wz: f32,
+    },
+    /// Difference along the phonetic (x) axis alone, ignoring the
+    /// etymology (y) and abstraction (z) axes
+    SemanticOnly,
+    /// One minus the cosine of the angle between the two positions, taken
+    /// as vectors from the origin
+    Cosine,
+}
+
+impl SimilarityMetric {
+    /// Distance between `a` and `b` under this metric
+    pub fn distance(&self, a: Coordinate3D, b: Coordinate3D) -> f32 {
+        match *self {
+            SimilarityMetric::Euclidean => a.distance_to(b),
+            SimilarityMetric::WeightedAxes { wx, wy, wz } => {
+                let dx = (a.x - b.x) * wx;
+                let dy = (a.y - b.y) * wy;
+                let dz = (a.z - b.z) * wz;
+                (dx * dx + dy * dy + dz * dz).sqrt()
+            }
+            SimilarityMetric::SemanticOnly => (a.x - b.x).abs(),
+            SimilarityMetric::Cosine => {
+                let norms = (a.x * a.x + a.y * a.y + a.z * a.z).sqrt()
+                    * (b.x * b.x + b.y * b.y + b.z * b.z).sqrt();
+                if norms == 0.0 {
+                    // The origin has no direction; it only matches itself
+                    return if a == b { 0.0 } else { 1.0 };
+                }
+                1.0 - (a.x * b.x + a.y * b.y + a.z * b.z) / norms
+            }
+        }
     }
 }
 
@@ -92,6 +226,12 @@ impl BoundingBox3D {
         }
     }
     
+    /// Whether the box contains no points, because `min` exceeds `max`
+    /// on some axis (or a bound is NaN)
+    pub fn is_empty(&self) -> bool {
+        !(self.min.x <= self.max.x && self.min.y <= self.max.y && self.min.z <= self.max.z)
+    }
+    
     /// Check if a point is inside the bounding box
     #[inline]
     pub fn contains(&self, point: Coordinate3D) -> bool {
@@ -197,6 +337,17 @@ mod tests {
         assert!((distance - 1.732).abs() < 0.001); // √3 ≈ 1.732
     }
     
+    #[test]
+    fn test_distance_to_matches_squared() {
+        let a = Coordinate3D::new(0.1, 0.7, 0.3);
+        let b = Coordinate3D::new(0.9, 0.2, 0.45);
+        
+        assert_eq!(a.distance_to(b), a.distance_squared_to(b).sqrt());
+        assert_eq!(a.distance_squared_to(b), b.distance_squared_to(a));
+        assert_eq!(a.distance(b), a.distance_to(b));
+        assert_eq!(a.distance_to(a), 0.0);
+    }
+    
     #[test]
     fn test_bounding_box_contains() {
         let bbox = BoundingBox3D::new(
@@ -208,6 +359,13 @@ mod tests {
         assert!(!bbox.contains(Coordinate3D::new(0.1, 0.5, 0.5)));
     }
     
+    #[test]
+    fn test_clamp_unit() {
+        let c = Coordinate3D { x: 1.5, y: -0.25, z: f32::NAN }.clamp_unit();
+        assert_eq!(c, Coordinate3D::new(1.0, 0.0, 0.0));
+        assert_eq!(clamp01(0.4), 0.4);
+    }
+    
     #[test]
     fn test_coordinate_clamping() {
         let c = Coordinate3D::new(1.5, -0.5, 0.5);
@@ -16,7 +16,11 @@ pub enum LingoError {
     InvalidFormat(String),
     
     /// Version mismatch
-    #[error("Unsupported database version: {major}.{minor}")]
+    #[error(
+        "Unsupported database version: {major}.{minor} (this build reads versions up to {}.{})",
+        crate::MAX_FILE_VERSION.0,
+        crate::MAX_FILE_VERSION.1
+    )]
     UnsupportedVersion { major: u16, minor: u16 },
     
     /// Corrupted data
@@ -51,6 +55,15 @@ pub enum LingoError {
     #[error("Query execution error: {0}")]
     Execution(String),
     
+    /// Execution aborted for spending more work than its budget allows
+    #[error("Query exceeded its budget: spent {spent} of {budget} work units")]
+    BudgetExceeded {
+        /// Work units spent when execution stopped
+        spent: u64,
+        /// Work units the execution was allowed
+        budget: u64,
+    },
+    
     /// Memory allocation error
     #[error("Memory allocation failed: {0}")]
     MemoryAllocation(String),
@@ -78,6 +91,10 @@ pub enum LingoError {
     /// Security error
     #[error("Security error: {message}")]
     SecurityError { message: String },
+    
+    /// Text query that couldn't be parsed
+    #[error("Query parse error: {0}")]
+    Parse(#[from] ParseError),
 }
 
 /// Result type alias for Lingo operations
@@ -107,6 +124,35 @@ pub enum QueryError {
     InvalidOperand,
 }
 
+/// Error from [`query::parse`](crate::query::parse), locating the problem
+/// in the query text
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message} at {}..{}", span.start, span.end)]
+pub struct ParseError {
+    /// What was wrong
+    pub message: String,
+    /// Byte range of the offending text in the query
+    pub span: std::ops::Range<usize>,
+}
+
+impl ParseError {
+    /// Create an error covering `span`
+    pub fn new(message: impl Into<String>, span: std::ops::Range<usize>) -> Self {
+        Self { message: message.into(), span }
+    }
+    
+    /// The query with a line of carets under the offending text.
+    ///
+    /// Carets are placed by character, so they line up under non-ASCII
+    /// words in a monospace terminal. An empty span gets one caret.
+    pub fn highlight(&self, query: &str) -> String {
+        let column = |byte: usize| query.get(..byte).map_or(0, |before| before.chars().count());
+        let start = column(self.span.start);
+        let width = column(self.span.end).saturating_sub(start).max(1);
+        format!("{}\n{}{}", query, " ".repeat(start), "^".repeat(width))
+    }
+}
+
 /// Database building errors
 #[derive(Debug, Error)]
 pub enum BuildError {
@@ -129,4 +175,29 @@ pub enum BuildError {
     /// Invalid coordinate
     #[error("Invalid coordinate: {reason}")]
     InvalidCoordinate { reason: String },
+    
+    /// Composed word references a morpheme that isn't in the database
+    #[error("Missing morpheme '{morpheme}' in composition of '{word}'")]
+    MissingMorpheme {
+        /// Word whose composition was being built
+        word: String,
+        /// Morpheme that couldn't be found
+        morpheme: String,
+    },
+    
+    /// Row of an imported data file that can't be turned into a node
+    #[error("Invalid row at line {line}: {reason}")]
+    InvalidRow {
+        /// 1-based line number in the file
+        line: usize,
+        /// What was wrong with the row
+        reason: String,
+    },
+    
+    /// Octree subdivision settings that can't build a tree
+    #[error("Invalid octree configuration: {reason}")]
+    InvalidOctreeConfig {
+        /// Which setting was rejected and why
+        reason: String,
+    },
 }
\ No newline at end of file
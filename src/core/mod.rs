@@ -61,11 +61,16 @@ mod node;
 mod connection;
 mod coordinate;
 mod types;
+mod text;
+mod phoneme;
+mod serialization;
 
 pub use node::{LinguisticNode, NodeFlags, Layer, EtymologyOrigin, MorphemeType};
 pub use connection::{OrthogonalConnection, ConnectionType, ContextMask};
-pub use coordinate::{Coordinate3D, BoundingBox3D};
+pub use coordinate::{Coordinate3D, BoundingBox3D, SimilarityMetric, clamp01};
 pub use types::{NodeId, PhonemeId, Vector3D};
+pub use text::{fold_accents, is_stopword, phonetic_key, phrase_tokens};
+pub use phoneme::PhonemeFeatures;
 
 /// SLANG bytecode operations
 pub mod bytecode;
@@ -1,6 +1,9 @@
 //! Core linguistic node structure
 
-use crate::core::{Coordinate3D, NodeId};
+use crate::core::{
+    Coordinate3D, NodeId, PhonemeFeatures,
+    error::{BuildError, Result},
+};
 use bitflags::bitflags;
 
 /// Main linguistic node structure representing a single element in the database.
@@ -48,8 +51,9 @@ pub struct LinguisticNode {
     pub word_length: u16,
     /// Node flags (1 byte)
     pub flags: NodeFlags,
-    /// Alignment padding (4 bytes)
-    _padding: [u8; 4],
+    /// Layer-specific data (4 bytes): articulatory features on
+    /// Phonemes-layer nodes, zero elsewhere. Stored little-endian.
+    layer_data: [u8; 4],
     
     // Linguistic properties (16 bytes)
     /// Etymology origin (1 byte)
@@ -115,7 +119,7 @@ impl LinguisticNode {
             word_offset: 0,
             word_length: 0,
             flags: NodeFlags::empty(),
-            _padding: [0; 4],
+            layer_data: [0; 4],
             etymology_origin: EtymologyOrigin::Unknown,
             phonetic_signature: 0,
             morpheme_type: MorphemeType::Root,
@@ -128,6 +132,104 @@ impl LinguisticNode {
             spatial_bucket: 0,
         }
     }
+    
+    /// Creates a new linguistic node, checking that `position` lies in `layer`.
+    ///
+    /// Behaves like [`new`](Self::new) but rejects a position outside the
+    /// unit cube (see [`Coordinate3D::is_valid`]) or whose z falls outside
+    /// the layer's band (see [`Layer::contains_z`]), such as a `Words` node
+    /// at z = 0.1.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError::InvalidCoordinate`](crate::core::error::BuildError::InvalidCoordinate)
+    /// naming the out-of-range position, or the layer the z actually
+    /// belongs to, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::core::{LinguisticNode, NodeId, Layer, Coordinate3D};
+    ///
+    /// assert!(LinguisticNode::new_validated(NodeId(1), Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5)).is_ok());
+    /// assert!(LinguisticNode::new_validated(NodeId(2), Layer::Words, Coordinate3D::new(0.5, 0.5, 0.1)).is_err());
+    /// ```
+    pub fn new_validated(id: NodeId, layer: Layer, position: Coordinate3D) -> Result<Self> {
+        if !position.is_valid() {
+            return Err(BuildError::InvalidCoordinate {
+                reason: format!(
+                    "({}, {}, {}) is outside the unit cube",
+                    position.x, position.y, position.z
+                ),
+            }.into());
+        }
+        if !layer.contains_z(position.z) {
+            let found = match Layer::from_z(position.z) {
+                Some(actual) => format!("the {:?} layer", actual),
+                None => "no layer".to_string(),
+            };
+            let (min, max) = layer.z_range();
+            return Err(BuildError::InvalidCoordinate {
+                reason: format!(
+                    "z = {} is outside the {:?} band [{}, {}) and falls in {}",
+                    position.z, layer, min, max, found
+                ),
+            }.into());
+        }
+        Ok(Self::new(id, layer, position))
+    }
+    
+    /// Productivity as a fraction in [0, 1], decoded from `productivity_score`.
+    #[inline]
+    pub fn productivity(&self) -> f32 {
+        self.productivity_score as f32 / u16::MAX as f32
+    }
+    
+    /// Store a productivity fraction, clamped to [0, 1], in `productivity_score`.
+    pub fn set_productivity(&mut self, productivity: f32) {
+        self.productivity_score = (crate::core::clamp01(productivity) * u16::MAX as f32).round() as u16;
+    }
+    
+    /// Articulatory features of a Phonemes-layer node.
+    ///
+    /// Other layers have no features and return an empty set.
+    pub fn phoneme_features(&self) -> PhonemeFeatures {
+        if self.layer != Layer::Phonemes {
+            return PhonemeFeatures::empty();
+        }
+        PhonemeFeatures::from_bits_truncate(u32::from_le_bytes(self.layer_data))
+    }
+    
+    /// Store articulatory features on a Phonemes-layer node.
+    ///
+    /// The features live in four bytes that are padding on every other
+    /// layer, so the node stays 60 bytes. Does nothing on other layers.
+    pub fn set_phoneme_features(&mut self, features: PhonemeFeatures) {
+        if self.layer == Layer::Phonemes {
+            self.layer_data = features.bits().to_le_bytes();
+        }
+    }
+    
+    /// Reverse the byte order of every multi-byte field in place.
+    ///
+    /// Files store nodes little-endian; this converts between that layout
+    /// and host order when they differ.
+    pub fn swap_bytes(&mut self) {
+        let mut position = self.position;
+        position.swap_bytes();
+        self.position = position;
+        self.id = NodeId(self.id.0.swap_bytes());
+        self.word_offset = self.word_offset.swap_bytes();
+        self.word_length = self.word_length.swap_bytes();
+        self.phonetic_signature = self.phonetic_signature.swap_bytes();
+        self.productivity_score = self.productivity_score.swap_bytes();
+        self.frequency_rank = self.frequency_rank.swap_bytes();
+        self.children_offset = self.children_offset.swap_bytes();
+        self.children_count = self.children_count.swap_bytes();
+        self.connections_offset = self.connections_offset.swap_bytes();
+        self.connections_count = self.connections_count.swap_bytes();
+        self.spatial_bucket = self.spatial_bucket.swap_bytes();
+    }
 }
 
 /// Represents one of the seven linguistic layers in the database hierarchy.
@@ -157,7 +259,7 @@ impl LinguisticNode {
 /// assert_eq!(word_layer.z_center(), (z_min + z_max) / 2.0);
 /// ```
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Layer {
     /// Individual characters, digraphs (Layer 0)
     Letters = 0,
@@ -176,6 +278,17 @@ pub enum Layer {
 }
 
 impl Layer {
+    /// Every layer, from `Letters` up to `Domains`
+    pub const ALL: [Layer; 7] = [
+        Layer::Letters,
+        Layer::Phonemes,
+        Layer::Morphemes,
+        Layer::Words,
+        Layer::Phrases,
+        Layer::Concepts,
+        Layer::Domains,
+    ];
+    
     /// Returns the Z-coordinate range for this layer.
     ///
     /// Each layer occupies a specific range of Z-coordinates in the 3D space,
@@ -226,6 +339,51 @@ impl Layer {
         let (min, max) = self.z_range();
         (min + max) / 2.0
     }
+    
+    /// Returns true if `z` falls in this layer's band.
+    ///
+    /// Bands are half-open, so a boundary such as 0.45 belongs to the layer
+    /// above it; `Domains` also takes 1.0. Every z in [0, 1] is therefore in
+    /// exactly one layer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::core::Layer;
+    ///
+    /// assert!(Layer::Words.contains_z(0.45));
+    /// assert!(!Layer::Morphemes.contains_z(0.45));
+    /// assert!(Layer::Domains.contains_z(1.0));
+    /// ```
+    pub fn contains_z(&self, z: f32) -> bool {
+        let (min, max) = self.z_range();
+        z >= min && (z < max || (*self == Layer::Domains && z == max))
+    }
+    
+    /// Returns the layer whose band contains `z`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `z` is outside [0, 1] or NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::core::Layer;
+    ///
+    /// assert_eq!(Layer::from_z(0.525), Some(Layer::Words));
+    /// assert_eq!(Layer::from_z(3.0), None);
+    /// ```
+    pub fn from_z(z: f32) -> Option<Self> {
+        Self::ALL.into_iter().find(|layer| layer.contains_z(z))
+    }
+    
+    /// Decode a layer byte as stored in a node record.
+    ///
+    /// Returns `None` for bytes outside 0-6.
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Self::ALL.get(value as usize).copied()
+    }
 }
 
 bitflags! {
@@ -291,7 +449,7 @@ bitflags! {
 /// assert_eq!(y_base, 0.4);
 /// ```
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum EtymologyOrigin {
     /// Germanic origin
     Germanic = 0,
@@ -311,7 +469,9 @@ pub enum EtymologyOrigin {
     Japanese = 7,
     /// Modern coinage (20th+ century)
     Modern = 8,
-    /// Unknown origin
+    /// Old Norse origin
+    Norse = 9,
+    /// Unknown origin, or an origin byte this version doesn't recognize
     Unknown = 255,
 }
 
@@ -340,6 +500,7 @@ impl EtymologyOrigin {
     pub fn base_y_coordinate(&self) -> f32 {
         match self {
             EtymologyOrigin::Germanic => 0.0,
+            EtymologyOrigin::Norse => 0.1,
             EtymologyOrigin::French => 0.2,
             EtymologyOrigin::Latin => 0.4,
             EtymologyOrigin::Arabic => 0.6,
@@ -351,6 +512,27 @@ impl EtymologyOrigin {
             EtymologyOrigin::Unknown => 0.5,
         }
     }
+    
+    /// Decode an origin byte as stored in a node record.
+    ///
+    /// Returns `None` for bytes that don't name an origin this version
+    /// knows, such as ones added by a newer version.
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Some(match value {
+            0 => EtymologyOrigin::Germanic,
+            1 => EtymologyOrigin::Latin,
+            2 => EtymologyOrigin::Greek,
+            3 => EtymologyOrigin::French,
+            4 => EtymologyOrigin::Arabic,
+            5 => EtymologyOrigin::Sanskrit,
+            6 => EtymologyOrigin::Chinese,
+            7 => EtymologyOrigin::Japanese,
+            8 => EtymologyOrigin::Modern,
+            9 => EtymologyOrigin::Norse,
+            255 => EtymologyOrigin::Unknown,
+            _ => return None,
+        })
+    }
 }
 
 /// Classifies the type of morpheme for nodes in the morpheme layer.
@@ -377,7 +559,7 @@ impl EtymologyOrigin {
 /// assert_eq!(root.composition_weight(), 0.6); // Highest weight
 /// ```
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MorphemeType {
     /// Root morpheme
     Root = 0,
@@ -397,9 +579,33 @@ pub enum MorphemeType {
     VerbSuffix = 7,
     /// Tense suffix (e.g., -ed, -ing, -s)
     TenseSuffix = 8,
+    /// Action noun suffix (e.g., -tion, -ment, -al)
+    ActionSuffix = 9,
+    /// Quality noun suffix (e.g., -ness, -ity, -ship)
+    QualitySuffix = 10,
 }
 
 impl MorphemeType {
+    /// Decode a morpheme type byte as stored in a node record.
+    ///
+    /// Returns `None` for bytes outside 0-10.
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Some(match value {
+            0 => MorphemeType::Root,
+            1 => MorphemeType::Prefix,
+            2 => MorphemeType::Suffix,
+            3 => MorphemeType::Infix,
+            4 => MorphemeType::Circumfix,
+            5 => MorphemeType::Compound,
+            6 => MorphemeType::AgentSuffix,
+            7 => MorphemeType::VerbSuffix,
+            8 => MorphemeType::TenseSuffix,
+            9 => MorphemeType::ActionSuffix,
+            10 => MorphemeType::QualitySuffix,
+            _ => return None,
+        })
+    }
+    
     /// Returns the composition weight for this morpheme type.
     ///
     /// The weight determines the morpheme's relative importance when
@@ -430,6 +636,8 @@ impl MorphemeType {
             MorphemeType::AgentSuffix => 0.2,
             MorphemeType::VerbSuffix => 0.2,
             MorphemeType::TenseSuffix => 0.1,
+            MorphemeType::ActionSuffix => 0.2,
+            MorphemeType::QualitySuffix => 0.2,
         }
     }
 }
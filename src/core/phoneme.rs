@@ -0,0 +1,189 @@
+//! Articulatory features of phonemes
+
+use bitflags::bitflags;
+
+bitflags! {
+    /// Articulatory features of a phoneme, packed into 32 bits.
+    ///
+    /// Consonants set a place, a manner and possibly `VOICED`; vowels set
+    /// `VOWEL`, a height, a backness and possibly `ROUNDED`. Stored on
+    /// Phonemes-layer nodes with [`LinguisticNode::set_phoneme_features`](crate::core::LinguisticNode::set_phoneme_features).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::core::PhonemeFeatures;
+    ///
+    /// let p = PhonemeFeatures::from_description("voiceless_bilabial_plosive");
+    /// let b = PhonemeFeatures::from_description("voiced_bilabial_plosive");
+    /// assert_eq!(p, PhonemeFeatures::BILABIAL | PhonemeFeatures::PLOSIVE);
+    /// assert_eq!(p.distance(b), 1);
+    /// ```
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct PhonemeFeatures: u32 {
+        // Place of articulation
+        /// Both lips
+        const BILABIAL = 1 << 0;
+        /// Lower lip and upper teeth
+        const LABIODENTAL = 1 << 1;
+        /// Tongue and teeth
+        const DENTAL = 1 << 2;
+        /// Tongue and alveolar ridge
+        const ALVEOLAR = 1 << 3;
+        /// Just behind the alveolar ridge
+        const POSTALVEOLAR = 1 << 4;
+        /// Hard palate
+        const PALATAL = 1 << 5;
+        /// Soft palate
+        const VELAR = 1 << 6;
+        /// Glottis
+        const GLOTTAL = 1 << 7;
+
+        // Manner of articulation
+        /// Full closure then release
+        const PLOSIVE = 1 << 8;
+        /// Turbulent narrow channel
+        const FRICATIVE = 1 << 9;
+        /// Plosive released as a fricative
+        const AFFRICATE = 1 << 10;
+        /// Air through the nose
+        const NASAL = 1 << 11;
+        /// Air around the sides of the tongue
+        const LATERAL = 1 << 12;
+        /// Articulators close without turbulence
+        const APPROXIMANT = 1 << 13;
+
+        /// Vocal folds vibrate
+        const VOICED = 1 << 14;
+
+        /// Vowel rather than consonant
+        const VOWEL = 1 << 15;
+
+        // Vowel height
+        /// Tongue highest
+        const CLOSE = 1 << 16;
+        /// Between close and close-mid
+        const NEAR_CLOSE = 1 << 17;
+        /// Between close and mid
+        const CLOSE_MID = 1 << 18;
+        /// Mid height, as in schwa
+        const MID = 1 << 19;
+        /// Between mid and open
+        const OPEN_MID = 1 << 20;
+        /// Between open-mid and open
+        const NEAR_OPEN = 1 << 21;
+        /// Tongue lowest
+        const OPEN = 1 << 22;
+
+        // Vowel backness
+        /// Tongue forward
+        const FRONT = 1 << 23;
+        /// Tongue central
+        const CENTRAL = 1 << 24;
+        /// Tongue back
+        const BACK = 1 << 25;
+
+        /// Lips rounded
+        const ROUNDED = 1 << 26;
+        /// R-colored vowel
+        const RHOTIC = 1 << 27;
+        /// Glides between two vowel qualities
+        const DIPHTHONG = 1 << 28;
+    }
+}
+
+/// Description terms and the features they stand for. Compound terms come
+/// before the terms inside them, so "near_close" wins over "close"
+const DESCRIPTION_TERMS: &[(&str, PhonemeFeatures)] = &[
+    ("postalveolar", PhonemeFeatures::POSTALVEOLAR),
+    ("labiodental", PhonemeFeatures::LABIODENTAL),
+    ("approximant", PhonemeFeatures::APPROXIMANT),
+    ("labial_velar", PhonemeFeatures::BILABIAL.union(PhonemeFeatures::VELAR)),
+    ("near_close", PhonemeFeatures::NEAR_CLOSE),
+    ("fricative", PhonemeFeatures::FRICATIVE),
+    ("affricate", PhonemeFeatures::AFFRICATE),
+    ("diphthong", PhonemeFeatures::DIPHTHONG),
+    ("close_mid", PhonemeFeatures::CLOSE_MID),
+    ("near_open", PhonemeFeatures::NEAR_OPEN),
+    ("r_colored", PhonemeFeatures::RHOTIC),
+    ("unrounded", PhonemeFeatures::empty()),
+    ("voiceless", PhonemeFeatures::empty()),
+    ("bilabial", PhonemeFeatures::BILABIAL),
+    ("alveolar", PhonemeFeatures::ALVEOLAR),
+    ("open_mid", PhonemeFeatures::OPEN_MID),
+    ("central", PhonemeFeatures::CENTRAL),
+    ("glottal", PhonemeFeatures::GLOTTAL),
+    ("lateral", PhonemeFeatures::LATERAL),
+    ("palatal", PhonemeFeatures::PALATAL),
+    ("plosive", PhonemeFeatures::PLOSIVE),
+    ("rounded", PhonemeFeatures::ROUNDED),
+    ("dental", PhonemeFeatures::DENTAL),
+    ("voiced", PhonemeFeatures::VOICED),
+    ("schwa", PhonemeFeatures::MID.union(PhonemeFeatures::CENTRAL)),
+    ("close", PhonemeFeatures::CLOSE),
+    ("front", PhonemeFeatures::FRONT),
+    ("nasal", PhonemeFeatures::NASAL),
+    ("velar", PhonemeFeatures::VELAR),
+    ("back", PhonemeFeatures::BACK),
+    ("open", PhonemeFeatures::OPEN),
+    ("stop", PhonemeFeatures::PLOSIVE),
+];
+
+impl PhonemeFeatures {
+    const HEIGHTS: Self = Self::CLOSE
+        .union(Self::NEAR_CLOSE)
+        .union(Self::CLOSE_MID)
+        .union(Self::MID)
+        .union(Self::OPEN_MID)
+        .union(Self::NEAR_OPEN)
+        .union(Self::OPEN);
+    const SONORANTS: Self = Self::NASAL.union(Self::LATERAL).union(Self::APPROXIMANT);
+
+    /// Parse a description such as `"voiceless_bilabial_plosive"` or
+    /// `"near_close_front_unrounded"`.
+    ///
+    /// Terms are separated by underscores, spaces, commas or hyphens, as
+    /// in the descriptions of [`ENGLISH_PHONEMES`](crate::data::english_base::ENGLISH_PHONEMES).
+    /// Vowel terms imply `VOWEL`; vowels, nasals, laterals and approximants
+    /// are voiced unless marked "voiceless". Unknown terms are ignored.
+    pub fn from_description(description: &str) -> Self {
+        let mut normalized: String = description
+            .to_lowercase()
+            .chars()
+            .map(|c| if c == ' ' || c == ',' || c == '-' { '_' } else { c })
+            .collect();
+        let voiceless = normalized.contains("voiceless");
+
+        let mut features = Self::empty();
+        for &(term, feature) in DESCRIPTION_TERMS {
+            if let Some(at) = find_term(&normalized, term) {
+                features |= feature;
+                normalized.replace_range(at..at + term.len(), &"#".repeat(term.len()));
+            }
+        }
+
+        if features.intersects(Self::HEIGHTS | Self::FRONT | Self::CENTRAL | Self::BACK | Self::RHOTIC | Self::DIPHTHONG) {
+            features |= Self::VOWEL;
+        }
+        if !voiceless && features.intersects(Self::VOWEL | Self::SONORANTS) {
+            features |= Self::VOICED;
+        }
+        features
+    }
+
+    /// Number of features set in one but not the other.
+    ///
+    /// Zero for identical feature sets; /p/ and /b/ differ by one (voicing).
+    pub fn distance(self, other: Self) -> u32 {
+        (self ^ other).bits().count_ones()
+    }
+}
+
+/// Byte offset of `term` in `text` as a whole underscore-separated term
+fn find_term(text: &str, term: &str) -> Option<usize> {
+    text.match_indices(term).map(|(at, _)| at).find(|&at| {
+        let before = text[..at].chars().next_back();
+        let after = text[at + term.len()..].chars().next();
+        before.is_none_or(|c| c == '_') && after.is_none_or(|c| c == '_')
+    })
+}
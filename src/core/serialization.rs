@@ -0,0 +1,87 @@
+//! Serde formats for the core enums
+//!
+//! The enums are `#[repr(u8)]` with explicit discriminants, and derived
+//! serde impls would write non-self-describing formats by variant index.
+//! These impls always write the variant name, so payloads survive variants
+//! being reordered or inserted. Names match the Rust variants ("Words",
+//! "LexicalBridge") and are case-sensitive when read back.
+//!
+//! [`LinguisticNode`](super::LinguisticNode) and
+//! [`OrthogonalConnection`](super::OrthogonalConnection) derive their impls:
+//! serde copies each field out of the packed struct, and the enum fields
+//! go through the impls here.
+
+use super::{ConnectionType, EtymologyOrigin, Layer, MorphemeType};
+use serde::de::{Deserialize, Deserializer, Error};
+use serde::ser::{Serialize, Serializer};
+
+/// Serialize a fieldless enum as its variant name, listing every variant
+macro_rules! serde_by_name {
+    ($type:ident { $($variant:ident),+ $(,)? }) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(match self {
+                    $($type::$variant => stringify!($variant),)+
+                })
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let name = String::deserialize(deserializer)?;
+                match name.as_str() {
+                    $(stringify!($variant) => Ok($type::$variant),)+
+                    other => Err(D::Error::unknown_variant(other, &[$(stringify!($variant)),+])),
+                }
+            }
+        }
+    };
+}
+
+serde_by_name!(Layer { Letters, Phonemes, Morphemes, Words, Phrases, Concepts, Domains });
+
+serde_by_name!(MorphemeType {
+    Root,
+    Prefix,
+    Suffix,
+    Infix,
+    Circumfix,
+    Compound,
+    AgentSuffix,
+    VerbSuffix,
+    TenseSuffix,
+    ActionSuffix,
+    QualitySuffix,
+});
+
+serde_by_name!(EtymologyOrigin {
+    Germanic,
+    Latin,
+    Greek,
+    French,
+    Arabic,
+    Sanskrit,
+    Chinese,
+    Japanese,
+    Modern,
+    Norse,
+    Unknown,
+});
+
+serde_by_name!(ConnectionType {
+    Synonymy,
+    Antonymy,
+    Hypernymy,
+    Hyponymy,
+    Meronymy,
+    Derivation,
+    Etymology,
+    Phonetic,
+    Analogy,
+    Collocation,
+    Causation,
+    Learned,
+    LexicalBridge,
+    MorphologicalPattern,
+    Unknown,
+});
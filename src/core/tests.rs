@@ -28,6 +28,85 @@ mod tests {
         assert_eq!(Layer::Domains.z_range(), (0.90, 1.0));
     }
     
+    #[test]
+    fn test_layer_from_z() {
+        let layers = Layer::ALL;
+        for (i, &layer) in layers.iter().enumerate() {
+            let (min, max) = layer.z_range();
+            assert_eq!(Layer::from_z(layer.z_center()), Some(layer));
+            assert_eq!(Layer::from_z(min), Some(layer));
+            assert!(layer.contains_z(min));
+            // The upper edge belongs to the next layer up
+            if layer != Layer::Domains {
+                assert!(!layer.contains_z(max));
+                assert_eq!(Layer::from_z(max), layers.get(i + 1).copied());
+            }
+            assert_eq!(Layer::from_z(max - 0.001), Some(layer));
+        }
+        
+        assert_eq!(Layer::from_z(1.0), Some(Layer::Domains));
+        assert_eq!(Layer::from_z(-0.01), None);
+        assert_eq!(Layer::from_z(1.01), None);
+        assert_eq!(Layer::from_z(3.0), None);
+        assert_eq!(Layer::from_z(f32::NAN), None);
+    }
+    
+    #[test]
+    fn test_linguistic_node_new_validated() {
+        let node = LinguisticNode::new_validated(
+            NodeId(1),
+            Layer::Words,
+            Coordinate3D::new(0.5, 0.3, Layer::Words.z_center()),
+        ).unwrap();
+        let layer = node.layer;
+        assert_eq!(layer, Layer::Words);
+        
+        let err = LinguisticNode::new_validated(NodeId(2), Layer::Words, Coordinate3D::new(0.5, 0.3, 0.1))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Words") && err.contains("Letters"), "{}", err);
+        
+        let err = LinguisticNode::new_validated(NodeId(3), Layer::Words, Coordinate3D { x: 0.5, y: 0.5, z: 3.0 })
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("(0.5, 0.5, 3) is outside the unit cube"), "{}", err);
+        
+        // x and y have no layer band, but must still be in the cube
+        let words_z = Layer::Words.z_center();
+        for (x, y) in [(1.5, 0.5), (0.5, -0.1), (f32::NAN, 0.5)] {
+            let position = Coordinate3D { x, y, z: words_z };
+            assert!(LinguisticNode::new_validated(NodeId(4), Layer::Words, position).is_err(), "{:?}", position);
+        }
+        let corner = Coordinate3D { x: 0.0, y: 1.0, z: words_z };
+        assert!(LinguisticNode::new_validated(NodeId(5), Layer::Words, corner).is_ok());
+    }
+    
+    #[test]
+    fn test_coordinate_new_clamped_and_is_valid() {
+        // Boundaries are inside the cube
+        assert!(Coordinate3D { x: 0.0, y: 1.0, z: 0.0 }.is_valid());
+        assert!(Coordinate3D { x: 1.0, y: 1.0, z: 1.0 }.is_valid());
+        assert!(Coordinate3D::new_clamped(0.0, 0.5, 1.0).is_valid());
+        
+        let outside = [
+            (-0.001, 0.5, 0.5),
+            (0.5, 1.001, 0.5),
+            (0.5, 0.5, 3.0),
+            (f32::NAN, 0.5, 0.5),
+            (0.5, f32::INFINITY, 0.5),
+            (0.5, 0.5, f32::NEG_INFINITY),
+        ];
+        for (x, y, z) in outside {
+            assert!(!Coordinate3D { x, y, z }.is_valid(), "({}, {}, {})", x, y, z);
+            assert!(Coordinate3D::new_clamped(x, y, z).is_valid(), "({}, {}, {})", x, y, z);
+        }
+        
+        assert_eq!(Coordinate3D::new_clamped(-0.5, 1.5, 0.25), Coordinate3D { x: 0.0, y: 1.0, z: 0.25 });
+        assert_eq!(Coordinate3D::new_clamped(f32::NAN, f32::INFINITY, f32::NEG_INFINITY), Coordinate3D::new(0.0, 1.0, 0.0));
+        // In-range values are kept exactly
+        assert_eq!(Coordinate3D::new_clamped(0.3, 0.6, 0.9), Coordinate3D { x: 0.3, y: 0.6, z: 0.9 });
+    }
+    
     #[test]
     fn test_node_flags() {
         let flags = NodeFlags::IS_TECHNICAL | NodeFlags::IS_PRODUCTIVE;
@@ -85,4 +164,104 @@ mod tests {
         assert_eq!(layer, Layer::Words);
         assert_eq!(x, 0.5);
     }
+    
+    /// JSON round trip of a value that compares equal to itself
+    fn round_trip<T>(value: T) -> String
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value, "{}", json);
+        json
+    }
+    
+    #[test]
+    fn test_enums_serialize_as_names() {
+        for layer in Layer::ALL {
+            assert_eq!(round_trip(layer), format!("\"{:?}\"", layer));
+        }
+        let morpheme_types = [
+            MorphemeType::Root, MorphemeType::Prefix, MorphemeType::Suffix, MorphemeType::Infix,
+            MorphemeType::Circumfix, MorphemeType::Compound, MorphemeType::AgentSuffix,
+            MorphemeType::VerbSuffix, MorphemeType::TenseSuffix, MorphemeType::ActionSuffix,
+            MorphemeType::QualitySuffix,
+        ];
+        for morpheme_type in morpheme_types {
+            assert_eq!(round_trip(morpheme_type), format!("\"{:?}\"", morpheme_type));
+        }
+        for origin in (0..=u8::MAX).filter_map(EtymologyOrigin::from_u8) {
+            assert_eq!(round_trip(origin), format!("\"{:?}\"", origin));
+        }
+        for connection_type in (0..=u8::MAX).filter_map(ConnectionType::from_u8) {
+            assert_eq!(round_trip(connection_type), format!("\"{:?}\"", connection_type));
+        }
+        
+        assert_eq!(round_trip(Layer::Domains), "\"Domains\"");
+        assert_eq!(round_trip(ConnectionType::LexicalBridge), "\"LexicalBridge\"");
+        
+        // Indices and unknown or miscased names are refused
+        assert!(serde_json::from_str::<Layer>("3").is_err());
+        assert!(serde_json::from_str::<Layer>("\"words\"").is_err());
+        let err = serde_json::from_str::<ConnectionType>("\"Rhyme\"").unwrap_err();
+        assert!(err.to_string().contains("unknown variant `Rhyme`"), "{}", err);
+    }
+    
+    #[test]
+    fn test_coordinate_serde_round_trip() {
+        let json = round_trip(Coordinate3D::new(0.25, 0.5, 0.55));
+        assert_eq!(json, r#"{"x":0.25,"y":0.5,"z":0.55}"#);
+    }
+    
+    #[test]
+    fn test_linguistic_node_serde_round_trip() {
+        let mut node = LinguisticNode::new(NodeId(42), Layer::Phonemes, Coordinate3D::new(0.3, 0.4, 0.22));
+        node.word_offset = 128;
+        node.word_length = 4;
+        node.flags = NodeFlags::IS_TERMINAL | NodeFlags::IS_BORROWED;
+        node.etymology_origin = EtymologyOrigin::Greek;
+        node.phonetic_signature = 0xDEAD_BEEF_0123_4567;
+        node.morpheme_type = MorphemeType::AgentSuffix;
+        node.set_productivity(0.75);
+        node.frequency_rank = 1_234;
+        node.children_offset = 7;
+        node.children_count = 2;
+        node.connections_offset = 9;
+        node.connections_count = 3;
+        node.spatial_bucket = 11;
+        node.set_phoneme_features(PhonemeFeatures::BILABIAL | PhonemeFeatures::PLOSIVE);
+        
+        let json = serde_json::to_value(node).unwrap();
+        assert_eq!(json["layer"], "Phonemes");
+        assert_eq!(json["etymology_origin"], "Greek");
+        assert_eq!(json["morpheme_type"], "AgentSuffix");
+        
+        let back: LinguisticNode = serde_json::from_value(json).unwrap();
+        // Byte-for-byte, so every field including the private ones survived
+        let bytes = |node: &LinguisticNode| {
+            let size = std::mem::size_of::<LinguisticNode>();
+            unsafe { std::slice::from_raw_parts(node as *const LinguisticNode as *const u8, size).to_vec() }
+        };
+        assert_eq!(bytes(&back), bytes(&node));
+        assert_eq!(back.phoneme_features(), node.phoneme_features());
+    }
+    
+    #[test]
+    fn test_orthogonal_connection_serde_round_trip() {
+        let mut connection = OrthogonalConnection::new(NodeId(7), ConnectionType::Meronymy, 0.6);
+        connection.context_mask = ContextMask::MEDICAL | ContextMask::FORMAL;
+        connection.transformation_vector = Vector3D::new(0.1, -0.2, 0.3);
+        
+        let json = serde_json::to_value(connection).unwrap();
+        assert_eq!(json["connection_type"], "Meronymy");
+        assert_eq!(json["target_node"], 7);
+        
+        let back: OrthogonalConnection = serde_json::from_value(json).unwrap();
+        let (target, strength, kind) = (back.target_node, back.strength, back.connection_type);
+        let (context, vector) = (back.context_mask, back.transformation_vector);
+        assert_eq!(target, NodeId(7));
+        assert_eq!(strength, { connection.strength });
+        assert_eq!(kind, ConnectionType::Meronymy);
+        assert_eq!(context, ContextMask::MEDICAL | ContextMask::FORMAL);
+        assert_eq!(vector, Vector3D::new(0.1, -0.2, 0.3));
+    }
 }
\ No newline at end of file
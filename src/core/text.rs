@@ -0,0 +1,255 @@
+//! Text folding for accent-insensitive and phonetic lookup
+
+/// Lowercase `text` and strip diacritics from Latin letters.
+///
+/// "Café" and "Naïve" fold to "cafe" and "naive"; ligatures such as "æ" and
+/// "ß" expand to "ae" and "ss". Combining diacritical marks (U+0300 to
+/// U+036F) are dropped, so decomposed (NFD) input such as "cafe\u{301}"
+/// folds the same as its precomposed form.
+///
+/// This is a fixed table rather than NFD decomposition, so a word folds the
+/// same way on every platform and Rust version. It covers the precomposed
+/// letters of Latin-1 Supplement (U+00C0 to U+00FF) and Latin Extended-A
+/// (U+0100 to U+017F). Precomposed letters outside those blocks, such as
+/// "ǒ" (Latin Extended-B) or "ạ" (Latin Extended Additional), are only
+/// lowercased; decompose them before folding if they need to match.
+pub fn fold_accents(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => folded.push('a'),
+            'æ' => folded.push_str("ae"),
+            'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => folded.push('c'),
+            'ď' | 'đ' => folded.push('d'),
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => folded.push('e'),
+            'ĝ' | 'ğ' | 'ġ' | 'ģ' => folded.push('g'),
+            'ĥ' | 'ħ' => folded.push('h'),
+            'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => folded.push('i'),
+            'ĵ' => folded.push('j'),
+            'ķ' => folded.push('k'),
+            'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => folded.push('l'),
+            'ñ' | 'ń' | 'ņ' | 'ň' => folded.push('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => folded.push('o'),
+            'œ' => folded.push_str("oe"),
+            'ŕ' | 'ŗ' | 'ř' => folded.push('r'),
+            'ś' | 'ŝ' | 'ş' | 'š' => folded.push('s'),
+            'ß' => folded.push_str("ss"),
+            'ţ' | 'ť' | 'ŧ' => folded.push('t'),
+            'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => folded.push('u'),
+            'ŵ' => folded.push('w'),
+            'ý' | 'ÿ' | 'ŷ' => folded.push('y'),
+            'ź' | 'ż' | 'ž' => folded.push('z'),
+            '\u{300}'..='\u{36f}' => {}
+            other => folded.push(other),
+        }
+    }
+    folded
+}
+
+/// Compute a Metaphone-style phonetic key for `word`.
+///
+/// Words that sound alike in English share a key: "phone" and "fone" both
+/// map to "FN", "Smith" and "Smyth" to "SM0" ('0' stands for "th"). Accents
+/// are folded first and non-letters ignored. This is a simplified Metaphone
+/// and misses some irregular spellings.
+pub fn phonetic_key(word: &str) -> String {
+    let letters: Vec<u8> = fold_accents(word)
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_uppercase())
+        .collect();
+
+    // Silent or simplified leading letters
+    let start = match letters.as_slice() {
+        [b'K', b'N', ..] | [b'G', b'N', ..] | [b'P', b'N', ..] | [b'A', b'E', ..] | [b'W', b'R', ..] => 1,
+        _ => 0,
+    };
+    let w = &letters[start..];
+    let at = |i: usize| w.get(i).copied().unwrap_or(0);
+    let is_vowel = |c: u8| matches!(c, b'A' | b'E' | b'I' | b'O' | b'U');
+    let is_front = |c: u8| matches!(c, b'E' | b'I' | b'Y');
+
+    let mut key = String::new();
+    let mut i = 0;
+    while i < w.len() {
+        let c = w[i];
+        let prev = if i > 0 { w[i - 1] } else { 0 };
+        let next = at(i + 1);
+        // Doubled letters sound once, except "cc" as in "accent"
+        if c == prev && c != b'C' {
+            i += 1;
+            continue;
+        }
+        match c {
+            b'A' | b'E' | b'I' | b'O' | b'U' => {
+                if i == 0 {
+                    key.push(c as char);
+                }
+            }
+            b'B' => {
+                // Silent in a final "mb" ("lamb")
+                if !(prev == b'M' && i + 1 == w.len()) {
+                    key.push('B');
+                }
+            }
+            b'C' => {
+                if next == b'H' {
+                    key.push(if prev == b'S' { 'K' } else { 'X' });
+                    i += 1;
+                } else if next == b'I' && at(i + 2) == b'A' {
+                    key.push('X');
+                } else if is_front(next) {
+                    if prev != b'S' {
+                        key.push('S');
+                    }
+                } else {
+                    key.push('K');
+                }
+            }
+            b'D' => {
+                if next == b'G' && is_front(at(i + 2)) {
+                    key.push('J');
+                    i += 1;
+                } else {
+                    key.push('T');
+                }
+            }
+            b'G' => {
+                if next == b'H' && !is_vowel(at(i + 2)) {
+                    // Silent in "night", "though"
+                    i += 1;
+                } else if next == b'N' && (i + 2 == w.len() || (at(i + 2) == b'E' && at(i + 3) == b'D')) {
+                    // Silent in "sign", "signed"
+                } else if is_front(next) {
+                    key.push('J');
+                } else {
+                    key.push('K');
+                }
+            }
+            b'H' => {
+                if is_vowel(next) && !matches!(prev, b'C' | b'G' | b'P' | b'S' | b'T') {
+                    key.push('H');
+                }
+            }
+            b'K' => {
+                if prev != b'C' {
+                    key.push('K');
+                }
+            }
+            b'P' => {
+                if next == b'H' {
+                    key.push('F');
+                    i += 1;
+                } else {
+                    key.push('P');
+                }
+            }
+            b'Q' => key.push('K'),
+            b'S' => {
+                if next == b'H' {
+                    key.push('X');
+                    i += 1;
+                } else if next == b'I' && matches!(at(i + 2), b'O' | b'A') {
+                    key.push('X');
+                } else {
+                    key.push('S');
+                }
+            }
+            b'T' => {
+                if next == b'I' && matches!(at(i + 2), b'O' | b'A') {
+                    key.push('X');
+                } else if next == b'H' {
+                    key.push('0');
+                    i += 1;
+                } else if !(next == b'C' && at(i + 2) == b'H') {
+                    key.push('T');
+                }
+            }
+            b'V' => key.push('F'),
+            b'W' | b'Y' => {
+                if is_vowel(next) {
+                    key.push(c as char);
+                }
+            }
+            b'X' => {
+                if i == 0 {
+                    key.push('S');
+                } else {
+                    key.push_str("KS");
+                }
+            }
+            b'Z' => key.push('S'),
+            // F, J, L, M, N, R
+            other => key.push(other as char),
+        }
+        i += 1;
+    }
+    key
+}
+
+/// Function words skipped when a phrase is matched word by word
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "be", "but", "by", "for", "from", "in", "into", "is", "it",
+    "of", "on", "or", "that", "the", "this", "to", "with",
+];
+
+/// Whether `word` is a function word such as "the", "of" or "and".
+///
+/// Case is ignored. [`QueryBuilder::find_phrase`](crate::query::QueryBuilder::find_phrase)
+/// skips these when it falls back to matching a phrase word by word.
+pub fn is_stopword(word: &str) -> bool {
+    STOPWORDS.contains(&word.to_lowercase().as_str())
+}
+
+/// Split `text` into lowercase words.
+///
+/// Words are separated by whitespace and lose any punctuation at either
+/// end, so "Machine learning, again!" gives `["machine", "learning",
+/// "again"]`. Hyphens and apostrophes inside a word are kept.
+pub fn phrase_tokens(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| c.is_ascii_punctuation()))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_accents() {
+        assert_eq!(fold_accents("Café"), "cafe");
+        assert_eq!(fold_accents("Naïve"), "naive");
+        assert_eq!(fold_accents("Straße"), "strasse");
+        assert_eq!(fold_accents("plain"), "plain");
+        assert_eq!(fold_accents("Cafe\u{301}"), "cafe");
+
+        // Both ends of the supported blocks fold
+        assert_eq!(fold_accents("ÀÿĀž"), "ayaz");
+        // Precomposed letters past Latin Extended-A are only lowercased,
+        // but their decomposed forms fold
+        assert_eq!(fold_accents("Ǒ"), "ǒ");
+        assert_eq!(fold_accents("Ạ"), "ạ");
+        assert_eq!(fold_accents("o\u{30c}a\u{323}"), "oa");
+    }
+
+    #[test]
+    fn test_phonetic_key_matches_homophones() {
+        assert_eq!(phonetic_key("phone"), "FN");
+        assert_eq!(phonetic_key("fone"), "FN");
+        assert_eq!(phonetic_key("Smith"), phonetic_key("Smyth"));
+        assert_eq!(phonetic_key("knight"), phonetic_key("nite"));
+        assert_ne!(phonetic_key("phone"), phonetic_key("bone"));
+    }
+
+    #[test]
+    fn test_phrase_tokens_and_stopwords() {
+        assert_eq!(phrase_tokens("  Machine learning, (again)!"), ["machine", "learning", "again"]);
+        assert_eq!(phrase_tokens("state-of-the-art don't"), ["state-of-the-art", "don't"]);
+        assert!(phrase_tokens(" -- ").is_empty());
+        assert!(is_stopword("The"));
+        assert!(!is_stopword("machine"));
+    }
+}
@@ -30,7 +30,7 @@ pub struct PhonemeId(pub u16);
 
 /// 3D transformation vector for connections
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Vector3D {
     /// X-axis transformation
     pub dx: f32,
@@ -51,6 +51,22 @@ impl Vector3D {
         Self { dx: 0.0, dy: 0.0, dz: 0.0 }
     }
     
+    /// Reverse the byte order of each component in place
+    pub fn swap_bytes(&mut self) {
+        self.dx = f32::from_bits(self.dx.to_bits().swap_bytes());
+        self.dy = f32::from_bits(self.dy.to_bits().swap_bytes());
+        self.dz = f32::from_bits(self.dz.to_bits().swap_bytes());
+    }
+    
+    /// The same vector pointing the opposite way
+    pub fn reversed(&self) -> Self {
+        Self {
+            dx: -self.dx,
+            dy: -self.dy,
+            dz: -self.dz,
+        }
+    }
+    
     /// Calculate magnitude
     pub fn magnitude(&self) -> f32 {
         (self.dx * self.dx + self.dy * self.dy + self.dz * self.dz).sqrt()
@@ -0,0 +1,351 @@
+//! Bulk import of morphemes and words from CSV/TSV files
+//!
+//! Each file starts with a header row naming its columns, in any order:
+//!
+//! | column          | required             | contents                                |
+//! |-----------------|----------------------|-----------------------------------------|
+//! | `form`          | yes                  | surface form, e.g. `pre` or `happiness` |
+//! | `x`, `y`, `z`   | yes                  | position, clamped to [0, 1]             |
+//! | `meaning`       | no                   | gloss                                   |
+//! | `etymology`     | no (`Unknown`)       | an [`EtymologyOrigin`] name             |
+//! | `morpheme_type` | morphemes (`Root`)   | a [`MorphemeType`] name                 |
+//! | `productivity`  | no (0.0)             | 0.0-1.0                                 |
+//! | `semantic_field`| no                   | free text, e.g. `time`                  |
+//!
+//! Files ending in `.tsv`, or whose header contains a tab, are tab
+//! separated; everything else is comma separated. Fields may be quoted with
+//! `"` (doubling it inside quotes), blank lines and lines starting with `#`
+//! are skipped, and enum names ignore case, `_` and `-`.
+
+use crate::core::{
+    NodeId, LinguisticNode, OrthogonalConnection, ConnectionType, Coordinate3D, Layer, EtymologyOrigin, MorphemeType,
+    error::{BuildError, LingoError, Result},
+};
+use crate::discovery::semantic_field::FIELD_STRENGTH;
+use crate::storage::DatabaseWriter;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Every etymology name accepted in the `etymology` column
+pub(crate) const ETYMOLOGIES: &[(&str, EtymologyOrigin)] = &[
+    ("germanic", EtymologyOrigin::Germanic),
+    ("latin", EtymologyOrigin::Latin),
+    ("greek", EtymologyOrigin::Greek),
+    ("french", EtymologyOrigin::French),
+    ("arabic", EtymologyOrigin::Arabic),
+    ("sanskrit", EtymologyOrigin::Sanskrit),
+    ("chinese", EtymologyOrigin::Chinese),
+    ("japanese", EtymologyOrigin::Japanese),
+    ("modern", EtymologyOrigin::Modern),
+    ("norse", EtymologyOrigin::Norse),
+    ("unknown", EtymologyOrigin::Unknown),
+];
+
+/// Every morpheme type name accepted in the `morpheme_type` column
+const MORPHEME_TYPES: &[(&str, MorphemeType)] = &[
+    ("root", MorphemeType::Root),
+    ("prefix", MorphemeType::Prefix),
+    ("suffix", MorphemeType::Suffix),
+    ("infix", MorphemeType::Infix),
+    ("circumfix", MorphemeType::Circumfix),
+    ("compound", MorphemeType::Compound),
+    ("agentsuffix", MorphemeType::AgentSuffix),
+    ("verbsuffix", MorphemeType::VerbSuffix),
+    ("tensesuffix", MorphemeType::TenseSuffix),
+    ("actionsuffix", MorphemeType::ActionSuffix),
+    ("qualitysuffix", MorphemeType::QualitySuffix),
+];
+
+/// One imported row: a node plus the text the node itself can't hold.
+///
+/// The node's `word_offset`/`word_length` are unset until [`add_rows`]
+/// stores `form` in a writer's string table.
+#[derive(Debug, Clone)]
+pub struct ImportedNode {
+    /// Surface form
+    pub form: String,
+    /// Gloss from the `meaning` column
+    pub meaning: String,
+    /// Free-text field from the `semantic_field` column
+    pub semantic_field: String,
+    /// Node with layer, position, etymology, morpheme type and productivity set
+    pub node: LinguisticNode,
+}
+
+/// Add imported rows to `writer`, returning their IDs in row order.
+///
+/// Each row's form is stored in the writer's string table. A row with a
+/// semantic field gets a Hypernymy connection to a Concepts-layer node named
+/// after the field, which is what
+/// [`dominant_semantic_field`](crate::discovery::dominant_semantic_field)
+/// reads. One node is added per distinct field in `rows`, beside the first
+/// row in it.
+pub fn add_rows(rows: &[ImportedNode], writer: &mut DatabaseWriter) -> Result<Vec<NodeId>> {
+    let mut fields: HashMap<&str, NodeId> = HashMap::new();
+    let mut ids = Vec::with_capacity(rows.len());
+
+    for row in rows {
+        let mut node = row.node;
+        node.word_offset = writer.add_string(&row.form)?;
+        node.word_length = row.form.len() as u16;
+        let id = writer.add_node(node)?;
+        ids.push(id);
+
+        let field = row.semantic_field.as_str();
+        if field.is_empty() {
+            continue;
+        }
+        let concept = match fields.get(field) {
+            Some(&concept) => concept,
+            None => {
+                let position = Coordinate3D::new(node.position.x, node.position.y, Layer::Concepts.z_center());
+                let mut concept = LinguisticNode::new(NodeId(0), Layer::Concepts, position);
+                concept.word_offset = writer.add_string(field)?;
+                concept.word_length = field.len() as u16;
+                let concept = writer.add_node(concept)?;
+                fields.insert(field, concept);
+                concept
+            }
+        };
+        writer.add_connection(id, OrthogonalConnection::new(concept, ConnectionType::Hypernymy, FIELD_STRENGTH))?;
+    }
+
+    Ok(ids)
+}
+
+/// Import Morphemes-layer nodes from a CSV or TSV file.
+///
+/// # Errors
+///
+/// Fails if the file can't be read, a required column is missing, or a row
+/// has an unknown etymology or morpheme type, or a coordinate or
+/// productivity that isn't a finite number. Row errors name the line.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lingo::data::import::{add_rows, import_morphemes_csv};
+/// use lingo::storage::DatabaseWriter;
+///
+/// # fn main() -> lingo::core::error::Result<()> {
+/// let mut writer = DatabaseWriter::new();
+/// add_rows(&import_morphemes_csv("data/sample_morphemes.csv")?, &mut writer)?;
+/// writer.write("morphemes.lingo")?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn import_morphemes_csv<P: AsRef<Path>>(path: P) -> Result<Vec<ImportedNode>> {
+    import_csv(path.as_ref(), Layer::Morphemes)
+}
+
+/// Import Words-layer nodes from a CSV or TSV file.
+///
+/// Takes the same columns as [`import_morphemes_csv`]; `morpheme_type` is
+/// optional and usually left out.
+pub fn import_words_csv<P: AsRef<Path>>(path: P) -> Result<Vec<ImportedNode>> {
+    import_csv(path.as_ref(), Layer::Words)
+}
+
+/// Column positions found in the header
+struct Columns {
+    form: usize,
+    x: usize,
+    y: usize,
+    z: usize,
+    meaning: Option<usize>,
+    etymology: Option<usize>,
+    morpheme_type: Option<usize>,
+    productivity: Option<usize>,
+    semantic_field: Option<usize>,
+}
+
+fn import_csv(path: &Path, layer: Layer) -> Result<Vec<ImportedNode>> {
+    let text = fs::read_to_string(path)?;
+    let mut lines = text.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'));
+
+    let (header_line, header) = lines.next()
+        .ok_or_else(|| row_error(1, "missing header row"))?;
+    let is_tsv = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("tsv"))
+        || header.contains('\t');
+    let delimiter = if is_tsv { '\t' } else { ',' };
+    let names: Vec<String> = split_fields(header, delimiter)
+        .into_iter()
+        .map(|name| name.trim().to_lowercase())
+        .collect();
+
+    let find = |name: &str| names.iter().position(|column| column == name);
+    let require = |name: &str| {
+        find(name).ok_or_else(|| row_error(header_line, &format!("missing required column '{}'", name)))
+    };
+    let columns = Columns {
+        form: require("form")?,
+        x: require("x")?,
+        y: require("y")?,
+        z: require("z")?,
+        meaning: find("meaning"),
+        etymology: find("etymology"),
+        morpheme_type: find("morpheme_type"),
+        productivity: find("productivity"),
+        semantic_field: find("semantic_field"),
+    };
+    if layer == Layer::Morphemes && columns.morpheme_type.is_none() {
+        return Err(row_error(header_line, "missing required column 'morpheme_type'"));
+    }
+
+    lines.map(|(line, row)| parse_row(line, &split_fields(row, delimiter), &columns, layer))
+        .collect()
+}
+
+fn parse_row(line: usize, fields: &[String], columns: &Columns, layer: Layer) -> Result<ImportedNode> {
+    let get = |column: Option<usize>| {
+        column.and_then(|i| fields.get(i)).map_or("", |field| field.trim())
+    };
+
+    let form = get(Some(columns.form));
+    if form.is_empty() {
+        return Err(row_error(line, "empty form"));
+    }
+
+    let coordinate = |column: usize, axis: &str| -> Result<f32> {
+        let value = get(Some(column));
+        match value.parse::<f32>() {
+            Ok(v) if v.is_finite() => Ok(v),
+            _ => Err(row_error(line, &format!("invalid {} coordinate '{}'", axis, value))),
+        }
+    };
+    let position = Coordinate3D::new(
+        coordinate(columns.x, "x")?,
+        coordinate(columns.y, "y")?,
+        coordinate(columns.z, "z")?,
+    );
+
+    let etymology = match get(columns.etymology) {
+        "" => EtymologyOrigin::Unknown,
+        name => lookup(ETYMOLOGIES, name)
+            .ok_or_else(|| row_error(line, &format!("unknown etymology '{}'", name)))?,
+    };
+    let morpheme_type = match get(columns.morpheme_type) {
+        "" if layer == Layer::Morphemes => return Err(row_error(line, "empty morpheme_type")),
+        "" => MorphemeType::Root,
+        name => lookup(MORPHEME_TYPES, name)
+            .ok_or_else(|| row_error(line, &format!("unknown morpheme type '{}'", name)))?,
+    };
+    let productivity = match get(columns.productivity) {
+        "" => 0.0,
+        value => match value.parse::<f32>() {
+            Ok(v) if v.is_finite() => v,
+            _ => return Err(row_error(line, &format!("invalid productivity '{}'", value))),
+        },
+    };
+
+    let mut node = LinguisticNode::new(NodeId(0), layer, position);
+    node.etymology_origin = etymology;
+    node.morpheme_type = morpheme_type;
+    node.set_productivity(productivity);
+
+    Ok(ImportedNode {
+        form: form.to_string(),
+        meaning: get(columns.meaning).to_string(),
+        semantic_field: get(columns.semantic_field).to_string(),
+        node,
+    })
+}
+
+/// Find `name` in a table, ignoring case, `_`, `-` and spaces
+pub(crate) fn lookup<T: Copy>(table: &[(&str, T)], name: &str) -> Option<T> {
+    let key: String = name.chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect();
+    table.iter().find(|(entry, _)| *entry == key).map(|&(_, value)| value)
+}
+
+/// Split a row on `delimiter`, honouring `"`-quoted fields
+fn split_fields(row: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = row.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+fn row_error(line: usize, reason: &str) -> LingoError {
+    BuildError::InvalidRow { line, reason: reason.to_string() }.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::discovery::semantic_field::semantic_fields;
+    use crate::storage::MemoryMappedDatabase;
+    use tempfile::TempDir;
+
+    fn sample_path() -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("data/sample_morphemes.csv")
+    }
+
+    #[test]
+    fn test_import_sample_morphemes() {
+        let rows = import_morphemes_csv(sample_path()).unwrap();
+        assert_eq!(rows.len(), 8);
+
+        let chrono = rows.iter().find(|row| row.form == "chrono").unwrap();
+        let node = chrono.node;
+        assert_eq!(chrono.meaning, "time, order of events");
+        assert_eq!(chrono.semantic_field, "time");
+        assert_eq!({ node.layer }, Layer::Morphemes);
+        assert_eq!({ node.etymology_origin }, EtymologyOrigin::Greek);
+        assert_eq!({ node.morpheme_type }, MorphemeType::Root);
+        assert_eq!({ node.position }, Coordinate3D::new(0.35, 0.8, 0.375));
+        assert!((node.productivity() - 0.7).abs() < 1e-3);
+
+        let mut writer = DatabaseWriter::new();
+        let ids = add_rows(&rows, &mut writer).unwrap();
+        let fields: std::collections::HashSet<&str> = rows.iter().map(|row| row.semantic_field.as_str()).collect();
+        assert_eq!(writer.node_count(), 8 + fields.len());
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("imported.lingo");
+        writer.write(&path).unwrap();
+        let db = MemoryMappedDatabase::open(&path).unwrap();
+        let chrono_id = ids[rows.iter().position(|row| row.form == "chrono").unwrap()];
+        assert_eq!(semantic_fields(&db, chrono_id), ["time"]);
+    }
+
+    #[test]
+    fn test_import_rejects_bad_rows_with_line_number() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let path = temp_dir.path().join("bad.tsv");
+        fs::write(&path, "form\tx\ty\tz\tetymology\nrun\t0.5\t0.1\t0.5\tGermanic\nzap\t0.5\t0.1\t0.5\tKlingon\n").unwrap();
+        let err = import_words_csv(&path).unwrap_err().to_string();
+        assert!(err.contains("line 3") && err.contains("Klingon"), "{}", err);
+
+        let path = temp_dir.path().join("coords.csv");
+        fs::write(&path, "form,x,y,z\nfar,1.5,-2,0.5\nbad,abc,0,0\n").unwrap();
+        let err = import_words_csv(&path).unwrap_err().to_string();
+        assert!(err.contains("line 3") && err.contains("x coordinate"), "{}", err);
+
+        // Out-of-range coordinates are clamped rather than rejected
+        fs::write(&path, "form,x,y,z\nfar,1.5,-2,0.5\n").unwrap();
+        let rows = import_words_csv(&path).unwrap();
+        assert_eq!({ rows[0].node.position }, Coordinate3D::new(1.0, 0.0, 0.5));
+    }
+}
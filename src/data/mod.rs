@@ -2,4 +2,9 @@
 
 pub mod english_base;
 pub mod scraped_data;
-pub mod data_integration;
\ No newline at end of file
+pub mod data_integration;
+pub mod import;
+pub mod seeder;
+
+pub use import::{import_morphemes_csv, import_words_csv, ImportedNode};
+pub use seeder::seed_database;
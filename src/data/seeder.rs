@@ -0,0 +1,611 @@
+//! Core linguistic inventory for seeding a database
+//!
+//! Phonemes, prefixes, suffixes and roots with their etymology and
+//! productivity, words composed from those morphemes, and the etymology and
+//! opposition connections between them. [`seed_database`] adds all of it to a
+//! [`DatabaseBuilder`]; the `linguistic_database_seeder` example writes the
+//! result to a file. The morphological [`COMPOSITION_RULES`] are kept here as
+//! data only, since a database has nowhere to store them.
+//!
+//! Positions in the tables are relative to their layer: x and y are used as
+//! given, and z orders nodes within the layer's z band.
+
+use crate::core::{
+    Coordinate3D, ConnectionType, EtymologyOrigin, Layer, MorphemeType, NodeFlags, NodeId, PhonemeFeatures,
+    error::Result,
+};
+use crate::discovery::semantic_field::FIELD_STRENGTH;
+use crate::storage::DatabaseBuilder;
+use std::collections::HashMap;
+
+/// Productivity given to composed words, which the tables don't score
+const WORD_PRODUCTIVITY: f32 = 0.5;
+
+/// Strength of connections between etymologically related morphemes
+const ETYMOLOGY_STRENGTH: f32 = 0.85;
+
+/// Strength of connections between opposite morphemes
+const MORPHEME_OPPOSITION_STRENGTH: f32 = 0.9;
+
+/// A phoneme in the seed inventory
+#[derive(Debug, Clone, Copy)]
+pub struct SeedPhoneme {
+    /// IPA transcription, stored as the node's word
+    pub ipa: &'static str,
+    /// Articulatory description, parsed with [`PhonemeFeatures::from_description`]
+    pub description: &'static str,
+    /// Position within the Phonemes layer
+    pub position: [f32; 3],
+}
+
+/// A morpheme in the seed inventory
+#[derive(Debug, Clone, Copy)]
+pub struct SeedMorpheme {
+    /// Surface form
+    pub form: &'static str,
+    /// Gloss
+    pub meaning: &'static str,
+    /// Etymological origin
+    pub etymology: EtymologyOrigin,
+    /// Type of morpheme
+    pub morpheme_type: MorphemeType,
+    /// How productive the morpheme is in forming new words, 0.0-1.0
+    pub productivity: f32,
+    /// Position within the Morphemes layer
+    pub position: [f32; 3],
+    /// Semantic field, e.g. `temporal`
+    pub semantic_field: &'static str,
+}
+
+/// A word and the morphemes it's composed from
+#[derive(Debug, Clone, Copy)]
+pub struct SeedWord {
+    /// Surface form
+    pub word: &'static str,
+    /// Forms of its morphemes, in order
+    pub morphemes: &'static [&'static str],
+    /// Position within the Words layer
+    pub position: [f32; 3],
+    /// Semantic field, e.g. `leadership`
+    pub semantic_field: &'static str,
+}
+
+/// A word and the words opposed to it
+#[derive(Debug, Clone, Copy)]
+pub struct SeedOpposition {
+    /// The word being opposed
+    pub original: &'static str,
+    /// Its opposites
+    pub opposites: &'static [&'static str],
+    /// Strength of each Antonymy connection
+    pub confidence: f32,
+}
+
+/// What a composition rule's affix does to the meaning of its root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SemanticFunction {
+    /// Someone who does the action, e.g. `manager`
+    AgentFormation,
+    /// A practitioner of a field, e.g. `artist`
+    SpecialistFormation,
+    /// Causing a state, e.g. `modernize`
+    CausativeFormation,
+    /// Able to undergo the action, e.g. `scalable`
+    CapabilityFormation,
+    /// Having a lot of something, e.g. `helpful`
+    AbundanceFormation,
+    /// Lacking something, e.g. `hopeless`
+    AbsenceFormation,
+    /// The negation of the root, e.g. `unknown`
+    NegationFormation,
+    /// Undoing the action, e.g. `disconnect`
+    ReversalFormation,
+}
+
+/// A pattern for composing new words from a root and an affix
+#[derive(Debug, Clone, Copy)]
+pub struct SeedCompositionRule {
+    /// Pattern, with `{root}` standing for the root, e.g. `{root} + er`
+    pub pattern: &'static str,
+    /// What the affix does to the root's meaning
+    pub semantic_function: SemanticFunction,
+    /// How productive the pattern is in forming new words, 0.0-1.0
+    pub productivity: f32,
+    /// Example compositions, e.g. `manage + er = manager`
+    pub examples: &'static [&'static str],
+    /// Conditions on the root or the result
+    pub constraints: &'static [&'static str],
+}
+
+/// English phoneme inventory
+pub const PHONEMES: &[SeedPhoneme] = &[
+    // Consonants
+    SeedPhoneme { ipa: "/p/", description: "voiceless bilabial stop", position: [0.1, 0.1, 0.1] },
+    SeedPhoneme { ipa: "/b/", description: "voiced bilabial stop", position: [0.1, 0.2, 0.1] },
+    SeedPhoneme { ipa: "/t/", description: "voiceless alveolar stop", position: [0.2, 0.1, 0.1] },
+    SeedPhoneme { ipa: "/d/", description: "voiced alveolar stop", position: [0.2, 0.2, 0.1] },
+    SeedPhoneme { ipa: "/k/", description: "voiceless velar stop", position: [0.3, 0.1, 0.1] },
+    SeedPhoneme { ipa: "/g/", description: "voiced velar stop", position: [0.3, 0.2, 0.1] },
+    SeedPhoneme { ipa: "/f/", description: "voiceless labiodental fricative", position: [0.1, 0.3, 0.1] },
+    SeedPhoneme { ipa: "/v/", description: "voiced labiodental fricative", position: [0.1, 0.4, 0.1] },
+    SeedPhoneme { ipa: "/θ/", description: "voiceless dental fricative", position: [0.2, 0.3, 0.1] },
+    SeedPhoneme { ipa: "/ð/", description: "voiced dental fricative", position: [0.2, 0.4, 0.1] },
+    SeedPhoneme { ipa: "/s/", description: "voiceless alveolar fricative", position: [0.3, 0.3, 0.1] },
+    SeedPhoneme { ipa: "/z/", description: "voiced alveolar fricative", position: [0.3, 0.4, 0.1] },
+    SeedPhoneme { ipa: "/ʃ/", description: "voiceless postalveolar fricative", position: [0.4, 0.3, 0.1] },
+    SeedPhoneme { ipa: "/ʒ/", description: "voiced postalveolar fricative", position: [0.4, 0.4, 0.1] },
+    SeedPhoneme { ipa: "/h/", description: "voiceless glottal fricative", position: [0.5, 0.3, 0.1] },
+    SeedPhoneme { ipa: "/m/", description: "voiced bilabial nasal", position: [0.1, 0.5, 0.1] },
+    SeedPhoneme { ipa: "/n/", description: "voiced alveolar nasal", position: [0.2, 0.5, 0.1] },
+    SeedPhoneme { ipa: "/ŋ/", description: "voiced velar nasal", position: [0.3, 0.5, 0.1] },
+    SeedPhoneme { ipa: "/l/", description: "voiced alveolar lateral", position: [0.2, 0.6, 0.1] },
+    SeedPhoneme { ipa: "/r/", description: "voiced alveolar approximant", position: [0.3, 0.6, 0.1] },
+    SeedPhoneme { ipa: "/w/", description: "voiced labial-velar approximant", position: [0.1, 0.6, 0.1] },
+    SeedPhoneme { ipa: "/j/", description: "voiced palatal approximant", position: [0.4, 0.6, 0.1] },
+
+    // Vowels
+    SeedPhoneme { ipa: "/i/", description: "close front", position: [0.1, 0.1, 0.2] },
+    SeedPhoneme { ipa: "/ɪ/", description: "near_close front", position: [0.1, 0.2, 0.2] },
+    SeedPhoneme { ipa: "/e/", description: "close_mid front", position: [0.2, 0.1, 0.2] },
+    SeedPhoneme { ipa: "/ɛ/", description: "open_mid front", position: [0.2, 0.2, 0.2] },
+    SeedPhoneme { ipa: "/æ/", description: "near_open front", position: [0.3, 0.1, 0.2] },
+    SeedPhoneme { ipa: "/ɑ/", description: "open back", position: [0.3, 0.8, 0.2] },
+    SeedPhoneme { ipa: "/ɔ/", description: "open_mid back rounded", position: [0.2, 0.8, 0.2] },
+    SeedPhoneme { ipa: "/o/", description: "close_mid back rounded", position: [0.2, 0.9, 0.2] },
+    SeedPhoneme { ipa: "/ʊ/", description: "near_close back rounded", position: [0.1, 0.8, 0.2] },
+    SeedPhoneme { ipa: "/u/", description: "close back rounded", position: [0.1, 0.9, 0.2] },
+    SeedPhoneme { ipa: "/ʌ/", description: "open_mid central", position: [0.2, 0.5, 0.2] },
+    SeedPhoneme { ipa: "/ə/", description: "schwa", position: [0.2, 0.4, 0.2] },
+
+    // Diphthongs
+    SeedPhoneme { ipa: "/aɪ/", description: "diphthong", position: [0.4, 0.1, 0.2] },
+    SeedPhoneme { ipa: "/aʊ/", description: "diphthong", position: [0.4, 0.2, 0.2] },
+    SeedPhoneme { ipa: "/ɔɪ/", description: "diphthong", position: [0.4, 0.3, 0.2] },
+];
+
+/// Latin, Greek and Germanic prefixes
+pub const PREFIXES: &[SeedMorpheme] = &[
+    // Latin prefixes
+    SeedMorpheme { form: "pre", meaning: "before", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.85, position: [0.1, 0.1, 0.3], semantic_field: "temporal" },
+    SeedMorpheme { form: "post", meaning: "after", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.70, position: [0.9, 0.1, 0.3], semantic_field: "temporal" },
+    SeedMorpheme { form: "sub", meaning: "under", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.80, position: [0.5, 0.1, 0.3], semantic_field: "spatial" },
+    SeedMorpheme { form: "super", meaning: "above", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.75, position: [0.5, 0.9, 0.3], semantic_field: "spatial" },
+    SeedMorpheme { form: "inter", meaning: "between", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.85, position: [0.5, 0.5, 0.3], semantic_field: "spatial" },
+    SeedMorpheme { form: "intra", meaning: "within", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.65, position: [0.3, 0.5, 0.3], semantic_field: "spatial" },
+    SeedMorpheme { form: "trans", meaning: "across", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.80, position: [0.7, 0.5, 0.3], semantic_field: "spatial" },
+    SeedMorpheme { form: "de", meaning: "away, down", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.90, position: [0.2, 0.2, 0.3], semantic_field: "directional" },
+    SeedMorpheme { form: "re", meaning: "again, back", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.95, position: [0.8, 0.2, 0.3], semantic_field: "repetitive" },
+    SeedMorpheme { form: "ex", meaning: "out of", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.85, position: [0.1, 0.5, 0.3], semantic_field: "directional" },
+    SeedMorpheme { form: "in", meaning: "in, into", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.90, position: [0.9, 0.5, 0.3], semantic_field: "directional" },
+    SeedMorpheme { form: "con", meaning: "with, together", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.85, position: [0.5, 0.7, 0.3], semantic_field: "collective" },
+    SeedMorpheme { form: "dis", meaning: "apart, away", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.80, position: [0.2, 0.8, 0.3], semantic_field: "separative" },
+    SeedMorpheme { form: "ad", meaning: "to, toward", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.75, position: [0.8, 0.8, 0.3], semantic_field: "directional" },
+    SeedMorpheme { form: "ab", meaning: "away from", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Prefix, productivity: 0.60, position: [0.2, 0.1, 0.3], semantic_field: "directional" },
+
+    // Greek prefixes
+    SeedMorpheme { form: "anti", meaning: "against", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.85, position: [0.1, 0.1, 0.4], semantic_field: "oppositional" },
+    SeedMorpheme { form: "pro", meaning: "forward, in favor", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.80, position: [0.9, 0.9, 0.4], semantic_field: "supportive" },
+    SeedMorpheme { form: "syn", meaning: "together", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.75, position: [0.5, 0.8, 0.4], semantic_field: "collective" },
+    SeedMorpheme { form: "meta", meaning: "beyond, after", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.70, position: [0.8, 0.1, 0.4], semantic_field: "transcendent" },
+    SeedMorpheme { form: "hyper", meaning: "over, excessive", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.75, position: [0.1, 0.9, 0.4], semantic_field: "intensification" },
+    SeedMorpheme { form: "hypo", meaning: "under, insufficient", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.65, position: [0.1, 0.1, 0.4], semantic_field: "diminution" },
+    SeedMorpheme { form: "para", meaning: "beside, alongside", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.70, position: [0.5, 0.3, 0.4], semantic_field: "parallel" },
+    SeedMorpheme { form: "auto", meaning: "self", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.85, position: [0.5, 0.5, 0.4], semantic_field: "reflexive" },
+    SeedMorpheme { form: "pseudo", meaning: "false", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.60, position: [0.2, 0.2, 0.4], semantic_field: "deceptive" },
+    SeedMorpheme { form: "neo", meaning: "new", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.75, position: [0.8, 0.8, 0.4], semantic_field: "temporal" },
+    SeedMorpheme { form: "paleo", meaning: "old, ancient", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.55, position: [0.2, 0.8, 0.4], semantic_field: "temporal" },
+    SeedMorpheme { form: "micro", meaning: "small", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.80, position: [0.3, 0.1, 0.4], semantic_field: "scale" },
+    SeedMorpheme { form: "macro", meaning: "large", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.70, position: [0.7, 0.9, 0.4], semantic_field: "scale" },
+    SeedMorpheme { form: "mega", meaning: "great, large", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.65, position: [0.9, 0.7, 0.4], semantic_field: "scale" },
+    SeedMorpheme { form: "poly", meaning: "many", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Prefix, productivity: 0.75, position: [0.8, 0.3, 0.4], semantic_field: "quantity" },
+
+    // Germanic prefixes
+    SeedMorpheme { form: "un", meaning: "not, reverse", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Prefix, productivity: 0.95, position: [0.1, 0.1, 0.5], semantic_field: "negation" },
+    SeedMorpheme { form: "over", meaning: "excessive, above", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Prefix, productivity: 0.80, position: [0.1, 0.9, 0.5], semantic_field: "excess" },
+    SeedMorpheme { form: "under", meaning: "below, insufficient", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Prefix, productivity: 0.75, position: [0.1, 0.1, 0.5], semantic_field: "deficiency" },
+    SeedMorpheme { form: "out", meaning: "beyond, surpassing", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Prefix, productivity: 0.70, position: [0.9, 0.5, 0.5], semantic_field: "surpassing" },
+    SeedMorpheme { form: "up", meaning: "upward, increase", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Prefix, productivity: 0.65, position: [0.5, 0.9, 0.5], semantic_field: "elevation" },
+    SeedMorpheme { form: "down", meaning: "downward, decrease", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Prefix, productivity: 0.60, position: [0.5, 0.1, 0.5], semantic_field: "reduction" },
+    SeedMorpheme { form: "fore", meaning: "before, in front", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Prefix, productivity: 0.55, position: [0.1, 0.5, 0.5], semantic_field: "anterior" },
+    SeedMorpheme { form: "be", meaning: "around, thoroughly", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Prefix, productivity: 0.70, position: [0.5, 0.7, 0.5], semantic_field: "comprehensive" },
+    SeedMorpheme { form: "with", meaning: "together, against", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Prefix, productivity: 0.50, position: [0.7, 0.7, 0.5], semantic_field: "association" },
+    SeedMorpheme { form: "off", meaning: "away, from", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Prefix, productivity: 0.45, position: [0.3, 0.3, 0.5], semantic_field: "separation" },
+];
+
+/// Agent, action and quality suffixes
+pub const SUFFIXES: &[SeedMorpheme] = &[
+    // Agent suffixes
+    SeedMorpheme { form: "er", meaning: "one who does", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::AgentSuffix, productivity: 0.95, position: [0.8, 0.8, 0.6], semantic_field: "agency" },
+    SeedMorpheme { form: "or", meaning: "one who does", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::AgentSuffix, productivity: 0.85, position: [0.8, 0.7, 0.6], semantic_field: "agency" },
+    SeedMorpheme { form: "ant", meaning: "one who does", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::AgentSuffix, productivity: 0.70, position: [0.8, 0.6, 0.6], semantic_field: "agency" },
+    SeedMorpheme { form: "ist", meaning: "one who practices", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::AgentSuffix, productivity: 0.80, position: [0.8, 0.5, 0.6], semantic_field: "professional" },
+    SeedMorpheme { form: "ian", meaning: "one who specializes", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::AgentSuffix, productivity: 0.75, position: [0.8, 0.4, 0.6], semantic_field: "specialist" },
+    SeedMorpheme { form: "ite", meaning: "follower, member", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::AgentSuffix, productivity: 0.60, position: [0.8, 0.3, 0.6], semantic_field: "affiliation" },
+
+    // Action suffixes
+    SeedMorpheme { form: "ize", meaning: "to make, cause", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::ActionSuffix, productivity: 0.90, position: [0.6, 0.8, 0.6], semantic_field: "causation" },
+    SeedMorpheme { form: "fy", meaning: "to make, cause", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::ActionSuffix, productivity: 0.75, position: [0.6, 0.7, 0.6], semantic_field: "causation" },
+    SeedMorpheme { form: "ate", meaning: "to act upon", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::ActionSuffix, productivity: 0.80, position: [0.6, 0.6, 0.6], semantic_field: "action" },
+    SeedMorpheme { form: "en", meaning: "to make, become", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::ActionSuffix, productivity: 0.70, position: [0.6, 0.5, 0.6], semantic_field: "transformation" },
+    SeedMorpheme { form: "ish", meaning: "to make somewhat", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::ActionSuffix, productivity: 0.60, position: [0.6, 0.4, 0.6], semantic_field: "approximation" },
+
+    // Quality suffixes
+    SeedMorpheme { form: "able", meaning: "capable of", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::QualitySuffix, productivity: 0.85, position: [0.4, 0.8, 0.6], semantic_field: "capability" },
+    SeedMorpheme { form: "ible", meaning: "capable of", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::QualitySuffix, productivity: 0.70, position: [0.4, 0.7, 0.6], semantic_field: "capability" },
+    SeedMorpheme { form: "ous", meaning: "full of", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::QualitySuffix, productivity: 0.80, position: [0.4, 0.6, 0.6], semantic_field: "abundance" },
+    SeedMorpheme { form: "ful", meaning: "full of", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::QualitySuffix, productivity: 0.90, position: [0.4, 0.5, 0.6], semantic_field: "abundance" },
+    SeedMorpheme { form: "less", meaning: "without", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::QualitySuffix, productivity: 0.85, position: [0.4, 0.4, 0.6], semantic_field: "absence" },
+    SeedMorpheme { form: "ic", meaning: "relating to", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::QualitySuffix, productivity: 0.95, position: [0.4, 0.3, 0.6], semantic_field: "relation" },
+    SeedMorpheme { form: "al", meaning: "relating to", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::QualitySuffix, productivity: 0.90, position: [0.4, 0.2, 0.6], semantic_field: "relation" },
+    SeedMorpheme { form: "ive", meaning: "having nature of", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::QualitySuffix, productivity: 0.85, position: [0.4, 0.1, 0.6], semantic_field: "characteristic" },
+];
+
+/// Root morphemes
+pub const ROOTS: &[SeedMorpheme] = &[
+    // Latin roots
+    // Management/Agency roots
+    SeedMorpheme { form: "manage", meaning: "to handle, control", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.85, position: [0.7, 0.7, 0.7], semantic_field: "control" },
+    SeedMorpheme { form: "duc", meaning: "to lead", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.80, position: [0.8, 0.7, 0.7], semantic_field: "leadership" },
+    SeedMorpheme { form: "reg", meaning: "to rule", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.75, position: [0.9, 0.7, 0.7], semantic_field: "authority" },
+    SeedMorpheme { form: "ord", meaning: "order, rank", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.60, position: [0.3, 0.3, 0.7], semantic_field: "hierarchy" },
+    // Creation/Action roots
+    SeedMorpheme { form: "create", meaning: "to make, bring forth", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.90, position: [0.8, 0.9, 0.7], semantic_field: "creation" },
+    SeedMorpheme { form: "struct", meaning: "to build", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.85, position: [0.7, 0.9, 0.7], semantic_field: "construction" },
+    SeedMorpheme { form: "form", meaning: "to shape", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.80, position: [0.6, 0.9, 0.7], semantic_field: "shaping" },
+    SeedMorpheme { form: "fact", meaning: "to make, do", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.85, position: [0.5, 0.9, 0.7], semantic_field: "making" },
+    // Destruction/Opposite roots
+    SeedMorpheme { form: "destroy", meaning: "to unmake, ruin", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.75, position: [0.2, 0.1, 0.7], semantic_field: "destruction" },
+    SeedMorpheme { form: "demol", meaning: "to tear down", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.60, position: [0.3, 0.1, 0.7], semantic_field: "destruction" },
+    // Communication roots
+    SeedMorpheme { form: "spect", meaning: "to look, see", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.80, position: [0.3, 0.8, 0.7], semantic_field: "perception" },
+    SeedMorpheme { form: "dict", meaning: "to say, speak", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.75, position: [0.4, 0.8, 0.7], semantic_field: "communication" },
+    SeedMorpheme { form: "scrib", meaning: "to write", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.70, position: [0.5, 0.8, 0.7], semantic_field: "writing" },
+    // Movement roots
+    SeedMorpheme { form: "port", meaning: "to carry", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.85, position: [0.6, 0.5, 0.7], semantic_field: "transport" },
+    SeedMorpheme { form: "miss", meaning: "to send", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.80, position: [0.7, 0.5, 0.7], semantic_field: "transmission" },
+    SeedMorpheme { form: "vert", meaning: "to turn", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.75, position: [0.8, 0.5, 0.7], semantic_field: "rotation" },
+
+    // Greek roots
+    // Technology roots
+    SeedMorpheme { form: "tech", meaning: "skill, craft", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Root, productivity: 0.90, position: [0.9, 0.8, 0.8], semantic_field: "technology" },
+    SeedMorpheme { form: "log", meaning: "word, study", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Root, productivity: 0.95, position: [0.4, 0.9, 0.8], semantic_field: "knowledge" },
+    SeedMorpheme { form: "graph", meaning: "to write", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Root, productivity: 0.85, position: [0.5, 0.9, 0.8], semantic_field: "recording" },
+    SeedMorpheme { form: "morph", meaning: "form, shape", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Root, productivity: 0.80, position: [0.6, 0.8, 0.8], semantic_field: "transformation" },
+    // Organization roots
+    SeedMorpheme { form: "organ", meaning: "tool, instrument", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Root, productivity: 0.85, position: [0.7, 0.8, 0.8], semantic_field: "organization" },
+    SeedMorpheme { form: "system", meaning: "organized whole", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Root, productivity: 0.80, position: [0.8, 0.8, 0.8], semantic_field: "systematization" },
+    // Chaos/Opposite roots
+    SeedMorpheme { form: "chaos", meaning: "disorder, void", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Root, productivity: 0.60, position: [0.2, 0.2, 0.8], semantic_field: "disorder" },
+    // Measurement/Analysis roots
+    SeedMorpheme { form: "metr", meaning: "measure", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Root, productivity: 0.75, position: [0.3, 0.7, 0.8], semantic_field: "measurement" },
+    SeedMorpheme { form: "scope", meaning: "to see, examine", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Root, productivity: 0.70, position: [0.3, 0.8, 0.8], semantic_field: "observation" },
+    // Social roots
+    SeedMorpheme { form: "anthrop", meaning: "human", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Root, productivity: 0.65, position: [0.5, 0.6, 0.8], semantic_field: "humanity" },
+    SeedMorpheme { form: "soci", meaning: "companion", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.80, position: [0.6, 0.6, 0.8], semantic_field: "social" },
+
+    // Germanic roots
+    // Basic action roots
+    SeedMorpheme { form: "work", meaning: "to labor", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.90, position: [0.7, 0.6, 0.9], semantic_field: "labor" },
+    SeedMorpheme { form: "make", meaning: "to create", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.95, position: [0.8, 0.8, 0.9], semantic_field: "creation" },
+    SeedMorpheme { form: "build", meaning: "to construct", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.85, position: [0.7, 0.8, 0.9], semantic_field: "construction" },
+    SeedMorpheme { form: "break", meaning: "to destroy", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.80, position: [0.3, 0.2, 0.9], semantic_field: "destruction" },
+    // Cognitive roots
+    SeedMorpheme { form: "think", meaning: "to consider", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.85, position: [0.4, 0.7, 0.9], semantic_field: "cognition" },
+    SeedMorpheme { form: "know", meaning: "to understand", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.90, position: [0.5, 0.7, 0.9], semantic_field: "knowledge" },
+    SeedMorpheme { form: "learn", meaning: "to acquire knowledge", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.85, position: [0.6, 0.7, 0.9], semantic_field: "acquisition" },
+    // Growth/Change roots
+    SeedMorpheme { form: "grow", meaning: "to increase", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.80, position: [0.8, 0.6, 0.9], semantic_field: "growth" },
+    SeedMorpheme { form: "shrink", meaning: "to decrease", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.70, position: [0.2, 0.4, 0.9], semantic_field: "reduction" },
+    // Leadership roots
+    SeedMorpheme { form: "lead", meaning: "to guide", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.85, position: [0.8, 0.7, 0.9], semantic_field: "leadership" },
+    SeedMorpheme { form: "follow", meaning: "to come after", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.75, position: [0.2, 0.7, 0.9], semantic_field: "followership" },
+
+    // Business roots
+    SeedMorpheme { form: "business", meaning: "commercial activity", etymology: EtymologyOrigin::Germanic, morpheme_type: MorphemeType::Root, productivity: 0.80, position: [0.9, 0.5, 0.7], semantic_field: "commerce" },
+    SeedMorpheme { form: "market", meaning: "place of trade", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.85, position: [0.8, 0.4, 0.7], semantic_field: "trade" },
+    SeedMorpheme { form: "product", meaning: "thing produced", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.90, position: [0.7, 0.4, 0.7], semantic_field: "output" },
+    SeedMorpheme { form: "develop", meaning: "to expand", etymology: EtymologyOrigin::French, morpheme_type: MorphemeType::Root, productivity: 0.95, position: [0.9, 0.6, 0.7], semantic_field: "development" },
+    SeedMorpheme { form: "architect", meaning: "chief builder", etymology: EtymologyOrigin::Greek, morpheme_type: MorphemeType::Root, productivity: 0.75, position: [0.8, 0.9, 0.8], semantic_field: "design" },
+    SeedMorpheme { form: "design", meaning: "to mark out", etymology: EtymologyOrigin::French, morpheme_type: MorphemeType::Root, productivity: 0.70, position: [0.6, 0.8, 0.8], semantic_field: "planning" },
+    SeedMorpheme { form: "scale", meaning: "ladder, series of steps", etymology: EtymologyOrigin::Latin, morpheme_type: MorphemeType::Root, productivity: 0.65, position: [0.8, 0.6, 0.7], semantic_field: "measurement" },
+];
+
+/// Words composed from the seeded morphemes
+pub const WORDS: &[SeedWord] = &[
+    // Agent words
+    SeedWord { word: "manager", morphemes: &["manage", "er"], position: [0.7, 0.7, 0.7], semantic_field: "leadership" },
+    SeedWord { word: "developer", morphemes: &["develop", "er"], position: [0.9, 0.6, 0.7], semantic_field: "creation" },
+    SeedWord { word: "leader", morphemes: &["lead", "er"], position: [0.8, 0.7, 0.9], semantic_field: "leadership" },
+    SeedWord { word: "creator", morphemes: &["create", "or"], position: [0.8, 0.9, 0.7], semantic_field: "creation" },
+    SeedWord { word: "organizer", morphemes: &["organ", "ize", "er"], position: [0.7, 0.8, 0.8], semantic_field: "organization" },
+    SeedWord { word: "architect", morphemes: &["architect"], position: [0.8, 0.9, 0.8], semantic_field: "design" },
+
+    // Action words
+    SeedWord { word: "organize", morphemes: &["organ", "ize"], position: [0.7, 0.8, 0.8], semantic_field: "organization" },
+    SeedWord { word: "create", morphemes: &["create"], position: [0.8, 0.9, 0.7], semantic_field: "creation" },
+    SeedWord { word: "develop", morphemes: &["develop"], position: [0.9, 0.6, 0.7], semantic_field: "development" },
+    SeedWord { word: "build", morphemes: &["build"], position: [0.7, 0.8, 0.9], semantic_field: "construction" },
+    SeedWord { word: "design", morphemes: &["design"], position: [0.6, 0.8, 0.8], semantic_field: "planning" },
+
+    // Quality words
+    SeedWord { word: "technical", morphemes: &["tech", "ic", "al"], position: [0.9, 0.8, 0.8], semantic_field: "technology" },
+    SeedWord { word: "business", morphemes: &["business"], position: [0.9, 0.5, 0.7], semantic_field: "commerce" },
+    SeedWord { word: "manageable", morphemes: &["manage", "able"], position: [0.7, 0.7, 0.6], semantic_field: "capability" },
+    SeedWord { word: "scalable", morphemes: &["scale", "able"], position: [0.8, 0.6, 0.6], semantic_field: "capability" },
+
+    // Opposition words
+    SeedWord { word: "destroy", morphemes: &["destroy"], position: [0.2, 0.1, 0.7], semantic_field: "destruction" },
+    SeedWord { word: "disorganize", morphemes: &["dis", "organ", "ize"], position: [0.3, 0.2, 0.8], semantic_field: "disorder" },
+    SeedWord { word: "follower", morphemes: &["follow", "er"], position: [0.2, 0.7, 0.9], semantic_field: "followership" },
+    SeedWord { word: "subordinate", morphemes: &["sub", "ord", "ate"], position: [0.3, 0.3, 0.7], semantic_field: "hierarchy" },
+];
+
+/// Roots and the morphemes etymologically related to them
+pub const ETYMOLOGY_RELATIONS: &[(&str, &[&str])] = &[
+    // Latin
+    ("create", &["fact", "form", "struct"]),
+    ("destroy", &["demol", "rupt"]),
+    ("duc", &["duct", "duce"]),
+    ("spect", &["vis", "vid"]),
+    ("port", &["fer", "lat"]),
+
+    // Greek
+    ("tech", &["mechan", "art"]),
+    ("log", &["graph", "gram"]),
+    ("morph", &["form", "type"]),
+    ("organ", &["system", "struct"]),
+
+    // Germanic
+    ("work", &["labor", "toil"]),
+    ("make", &["build", "craft"]),
+    ("think", &["know", "learn"]),
+    ("lead", &["guide", "head"]),
+];
+
+/// Morphemes with opposite meanings
+pub const MORPHEME_OPPOSITIONS: &[(&str, &str)] = &[
+    // Latin
+    ("create", "destroy"),
+    ("construct", "destruct"),
+    ("produce", "reduce"),
+    ("attract", "repel"),
+    ("advance", "retreat"),
+
+    // Greek
+    ("organize", "chaos"),
+    ("synthesis", "analysis"),
+    ("macro", "micro"),
+    ("hyper", "hypo"),
+    ("pro", "anti"),
+
+    // Germanic
+    ("build", "break"),
+    ("grow", "shrink"),
+    ("rise", "fall"),
+    ("lead", "follow"),
+    ("know", "ignore"),
+];
+
+/// Words and their opposites
+pub const WORD_OPPOSITIONS: &[SeedOpposition] = &[
+    // Functional oppositions
+    SeedOpposition { original: "manager", opposites: &["subordinate", "employee", "follower", "team member"], confidence: 0.90 },
+    SeedOpposition { original: "leader", opposites: &["follower", "member", "subordinate"], confidence: 0.95 },
+    SeedOpposition { original: "teacher", opposites: &["student", "learner", "pupil"], confidence: 0.90 },
+    SeedOpposition { original: "creator", opposites: &["destroyer", "critic", "consumer"], confidence: 0.85 },
+    SeedOpposition { original: "developer", opposites: &["user", "client", "tester"], confidence: 0.80 },
+
+    // Action oppositions
+    SeedOpposition { original: "create", opposites: &["destroy", "demolish", "eliminate"], confidence: 0.95 },
+    SeedOpposition { original: "build", opposites: &["destroy", "demolish", "break", "dismantle"], confidence: 0.90 },
+    SeedOpposition { original: "organize", opposites: &["disorganize", "scatter", "chaos"], confidence: 0.85 },
+    SeedOpposition { original: "centralize", opposites: &["decentralize", "distribute", "scatter"], confidence: 0.80 },
+    SeedOpposition { original: "accelerate", opposites: &["decelerate", "slow", "brake"], confidence: 0.85 },
+
+    // State oppositions
+    SeedOpposition { original: "order", opposites: &["chaos", "disorder", "confusion"], confidence: 0.90 },
+    SeedOpposition { original: "growth", opposites: &["decline", "shrinkage", "reduction"], confidence: 0.85 },
+    SeedOpposition { original: "success", opposites: &["failure", "defeat", "loss"], confidence: 0.90 },
+
+    // Technical oppositions
+    SeedOpposition { original: "technical", opposites: &["business", "commercial", "non-technical", "managerial"], confidence: 0.75 },
+    SeedOpposition { original: "frontend", opposites: &["backend", "server-side", "infrastructure"], confidence: 0.80 },
+    SeedOpposition { original: "architect", opposites: &["implementer", "user", "operator"], confidence: 0.75 },
+];
+
+/// Morphological composition rules, by the kind of word they form
+pub const COMPOSITION_RULES: &[SeedCompositionRule] = &[
+    // Agent formation
+    SeedCompositionRule {
+        pattern: "{root} + er",
+        semantic_function: SemanticFunction::AgentFormation,
+        productivity: 0.95,
+        examples: &["manage + er = manager", "teach + er = teacher", "work + er = worker"],
+        constraints: &["root must be action or process"],
+    },
+    SeedCompositionRule {
+        pattern: "{root} + or",
+        semantic_function: SemanticFunction::AgentFormation,
+        productivity: 0.85,
+        examples: &["act + or = actor", "edit + or = editor", "invest + or = investor"],
+        constraints: &["typically with Latin roots"],
+    },
+    SeedCompositionRule {
+        pattern: "{root} + ist",
+        semantic_function: SemanticFunction::SpecialistFormation,
+        productivity: 0.80,
+        examples: &["art + ist = artist", "special + ist = specialist", "journal + ist = journalist"],
+        constraints: &["forms professional/practitioner roles"],
+    },
+
+    // Action formation
+    SeedCompositionRule {
+        pattern: "{root} + ize",
+        semantic_function: SemanticFunction::CausativeFormation,
+        productivity: 0.90,
+        examples: &["organ + ize = organize", "modern + ize = modernize", "central + ize = centralize"],
+        constraints: &["creates causative verbs"],
+    },
+    SeedCompositionRule {
+        pattern: "{root} + fy",
+        semantic_function: SemanticFunction::CausativeFormation,
+        productivity: 0.75,
+        examples: &["simple + fy = simplify", "class + fy = classify", "intense + fy = intensify"],
+        constraints: &["typically creates state-change verbs"],
+    },
+
+    // Quality formation
+    SeedCompositionRule {
+        pattern: "{root} + able",
+        semantic_function: SemanticFunction::CapabilityFormation,
+        productivity: 0.85,
+        examples: &["manage + able = manageable", "scale + able = scalable", "predict + able = predictable"],
+        constraints: &["indicates capability or possibility"],
+    },
+    SeedCompositionRule {
+        pattern: "{root} + ful",
+        semantic_function: SemanticFunction::AbundanceFormation,
+        productivity: 0.90,
+        examples: &["help + ful = helpful", "use + ful = useful", "success + ful = successful"],
+        constraints: &["indicates abundance or possession"],
+    },
+    SeedCompositionRule {
+        pattern: "{root} + less",
+        semantic_function: SemanticFunction::AbsenceFormation,
+        productivity: 0.85,
+        examples: &["help + less = helpless", "use + less = useless", "hope + less = hopeless"],
+        constraints: &["indicates absence or lack"],
+    },
+
+    // Negation
+    SeedCompositionRule {
+        pattern: "un + {root}",
+        semantic_function: SemanticFunction::NegationFormation,
+        productivity: 0.95,
+        examples: &["un + able = unable", "un + known = unknown", "un + organized = unorganized"],
+        constraints: &["most productive negation prefix"],
+    },
+    SeedCompositionRule {
+        pattern: "dis + {root}",
+        semantic_function: SemanticFunction::ReversalFormation,
+        productivity: 0.80,
+        examples: &["dis + organize = disorganize", "dis + connect = disconnect", "dis + agree = disagree"],
+        constraints: &["typically indicates reversal of action"],
+    },
+];
+
+/// Add the seed inventory to `builder`.
+///
+/// Adds every phoneme, morpheme and word in this module's tables, with each
+/// morpheme's productivity, and connects etymologically related morphemes,
+/// opposite morphemes and opposite words. Composed words get `Derivation`
+/// connections to their morphemes when the database is built. Relations
+/// naming a morpheme or word that isn't in the tables are skipped, and
+/// [`COMPOSITION_RULES`] aren't added at all.
+///
+/// Each semantic field becomes a Concepts-layer node named after it, and
+/// every morpheme and word in the field gets a Hypernymy connection to it,
+/// which is what [`dominant_semantic_field`](crate::discovery::dominant_semantic_field)
+/// reads.
+///
+/// # Errors
+///
+/// Fails if the builder rejects a node or connection.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lingo::data::seed_database;
+/// use lingo::storage::DatabaseBuilder;
+///
+/// # fn main() -> lingo::core::error::Result<()> {
+/// let mut builder = DatabaseBuilder::new();
+/// seed_database(&mut builder)?;
+/// builder.build("seeded.lingo")?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn seed_database(builder: &mut DatabaseBuilder) -> Result<()> {
+    for phoneme in PHONEMES {
+        let features = PhonemeFeatures::from_description(phoneme.description);
+        builder.add_phoneme(phoneme.ipa, place(Layer::Phonemes, phoneme.position), features)?;
+    }
+
+    let mut fields: HashMap<&str, NodeId> = HashMap::new();
+    let mut morphemes: HashMap<&str, NodeId> = HashMap::new();
+    for morpheme in PREFIXES.iter().chain(SUFFIXES).chain(ROOTS) {
+        let id = builder.add_node_full(
+            morpheme.form,
+            Layer::Morphemes,
+            place(Layer::Morphemes, morpheme.position),
+            morpheme.etymology,
+            morpheme.morpheme_type,
+            NodeFlags::empty(),
+        )?;
+        if let Some(node) = builder.node_mut(id) {
+            node.set_productivity(morpheme.productivity);
+        }
+        link_field(builder, &mut fields, id, morpheme.semantic_field)?;
+        morphemes.insert(morpheme.form, id);
+    }
+
+    let mut words: HashMap<&str, NodeId> = HashMap::new();
+    for word in WORDS {
+        let id = builder.add_composed_word(word.word, Layer::Words, place(Layer::Words, word.position), word.morphemes)?;
+        if let Some(node) = builder.node_mut(id) {
+            node.set_productivity(WORD_PRODUCTIVITY);
+        }
+        link_field(builder, &mut fields, id, word.semantic_field)?;
+        words.insert(word.word, id);
+    }
+
+    for &(root, related) in ETYMOLOGY_RELATIONS {
+        let Some(&root) = morphemes.get(root) else { continue };
+        for &id in related.iter().filter_map(|form| morphemes.get(form)) {
+            builder.add_connection(root, id, ConnectionType::Etymology, ETYMOLOGY_STRENGTH)?;
+        }
+    }
+
+    for &(first, second) in MORPHEME_OPPOSITIONS {
+        if let (Some(&first), Some(&second)) = (morphemes.get(first), morphemes.get(second)) {
+            builder.add_connection(first, second, ConnectionType::Antonymy, MORPHEME_OPPOSITION_STRENGTH)?;
+            builder.add_connection(second, first, ConnectionType::Antonymy, MORPHEME_OPPOSITION_STRENGTH)?;
+        }
+    }
+
+    for opposition in WORD_OPPOSITIONS {
+        let Some(&original) = words.get(opposition.original) else { continue };
+        for &opposite in opposition.opposites.iter().filter_map(|word| words.get(word)) {
+            builder.add_connection(original, opposite, ConnectionType::Antonymy, opposition.confidence)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Connect `id` to the Concepts node for `field`, adding the node beside
+/// `id` the first time the field is seen
+fn link_field(
+    builder: &mut DatabaseBuilder,
+    fields: &mut HashMap<&'static str, NodeId>,
+    id: NodeId,
+    field: &'static str,
+) -> Result<()> {
+    let concept = match fields.get(field) {
+        Some(&concept) => concept,
+        None => {
+            let position = builder.node(id).map_or(Coordinate3D::new(0.5, 0.5, 0.0), |node| node.position);
+            let position = Coordinate3D::new(position.x, position.y, Layer::Concepts.z_center());
+            let concept = builder.add_node(field, Layer::Concepts, position)?;
+            fields.insert(field, concept);
+            concept
+        }
+    };
+    builder.add_connection(id, concept, ConnectionType::Hypernymy, FIELD_STRENGTH)
+}
+
+/// A table position in `layer`: x and y as given, z scaled into the layer's band
+fn place(layer: Layer, [x, y, z]: [f32; 3]) -> Coordinate3D {
+    let (min, max) = layer.z_range();
+    Coordinate3D::new(x, y, min + (max - min) * z)
+}
@@ -0,0 +1,398 @@
+//! Database building with reviewable connection discovery
+
+use super::productivity::{attachment, Attachment};
+use crate::core::{
+    clamp01, ConnectionType, Coordinate3D, EtymologyOrigin, Layer, MorphemeType, NodeFlags, NodeId,
+    error::Result,
+};
+use crate::logging::debug;
+use crate::storage::{BuildReport, DatabaseBuilder};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Confidence of an affix match whose remainder isn't a known word
+const AFFIX_ONLY_CONFIDENCE: f32 = 0.5;
+
+/// Confidence of an affix match whose remainder is a known word
+const STEM_CONFIRMED_CONFIDENCE: f32 = 0.9;
+
+/// A connection proposed by [`AutoLinguisticBuilder::discover`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateConnection {
+    /// Node the connection starts from
+    pub source: NodeId,
+    /// Node the connection points to
+    pub target: NodeId,
+    /// Surface form of the source node
+    pub source_word: String,
+    /// Surface form of the target node
+    pub target_word: String,
+    /// Kind of relationship found
+    pub connection_type: ConnectionType,
+    /// Strength the connection is committed with when accepted
+    pub strength: f32,
+    /// How sure the discovery is, from 0.0 to 1.0
+    pub confidence: f32,
+}
+
+/// What to do with a [`CandidateConnection`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConnectionDecision {
+    /// Commit the connection with its proposed strength
+    Accept,
+    /// Drop the connection
+    Reject,
+    /// Commit the connection with this strength instead, clamped to 0.0-1.0
+    /// (NaN becomes 0.0)
+    AdjustStrength(f32),
+}
+
+/// Callback deciding the fate of each candidate connection
+type ReviewFn = Box<dyn FnMut(&CandidateConnection) -> ConnectionDecision>;
+
+/// A [`DatabaseBuilder`] that finds morphological and analogical
+/// connections between the words and morphemes added to it.
+///
+/// [`discover`](Self::discover) proposes connections and passes each one
+/// to the review callback set with [`with_review`](Self::with_review)
+/// before committing it. Without a callback every candidate is accepted.
+/// Everything proposed in the latest run stays available from
+/// [`discovered_connections`](Self::discovered_connections), whatever the
+/// decision, so a curator can inspect what was found.
+///
+/// Two kinds of connection are proposed:
+///
+/// - `Derivation`, from a word to a prefix or suffix morpheme it starts or
+///   ends with, and to the known word left once the affix is removed. The
+///   confidence is higher when that remainder is a known word.
+/// - `Analogy`, between two words formed from known words with the same
+///   affix ("teach : teacher :: work : worker"). The confidence is how
+///   closely the first pair's offset in coordinate space, applied to the
+///   second base word, lands on the second derived word.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lingo::discovery::{AutoLinguisticBuilder, ConnectionDecision};
+/// use lingo::core::{Coordinate3D, MorphemeType};
+///
+/// # fn main() -> lingo::core::error::Result<()> {
+/// let mut builder = AutoLinguisticBuilder::new().with_review(|candidate| {
+///     if candidate.confidence < 0.6 {
+///         ConnectionDecision::Reject
+///     } else {
+///         ConnectionDecision::Accept
+///     }
+/// });
+/// builder.add_morpheme("er", MorphemeType::AgentSuffix, Coordinate3D::new(0.6, 0.3, 0.35))?;
+/// builder.add_word("teach", Coordinate3D::new(0.4, 0.5, 0.52))?;
+/// builder.add_word("teacher", Coordinate3D::new(0.45, 0.55, 0.52))?;
+///
+/// let committed = builder.discover()?;
+/// println!("{} of {} proposals kept", committed, builder.discovered_connections().len());
+/// builder.build("curated.lingo")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct AutoLinguisticBuilder {
+    /// Nodes and committed connections
+    builder: DatabaseBuilder,
+    /// Decides which candidates are committed
+    review: Option<ReviewFn>,
+    /// Candidates proposed by the latest run
+    discovered: Vec<CandidateConnection>,
+}
+
+impl AutoLinguisticBuilder {
+    /// Create an empty builder that accepts every candidate
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Discover connections among the nodes of an existing builder
+    pub fn from_builder(builder: DatabaseBuilder) -> Self {
+        Self {
+            builder,
+            ..Self::default()
+        }
+    }
+
+    /// Review each candidate connection before it is committed.
+    ///
+    /// The callback runs once per candidate, in discovery order, and its
+    /// decision is final for that run.
+    pub fn with_review<F>(mut self, review: F) -> Self
+    where
+        F: FnMut(&CandidateConnection) -> ConnectionDecision + 'static,
+    {
+        self.review = Some(Box::new(review));
+        self
+    }
+
+    /// Add a Words-layer node
+    pub fn add_word(&mut self, word: &str, position: Coordinate3D) -> Result<NodeId> {
+        self.builder.add_node(word, Layer::Words, position)
+    }
+
+    /// Add a Morphemes-layer node of the given type
+    pub fn add_morpheme(
+        &mut self,
+        form: &str,
+        morpheme_type: MorphemeType,
+        position: Coordinate3D,
+    ) -> Result<NodeId> {
+        self.builder.add_node_full(
+            form,
+            Layer::Morphemes,
+            position,
+            EtymologyOrigin::Unknown,
+            morpheme_type,
+            NodeFlags::empty(),
+        )
+    }
+
+    /// The underlying builder
+    pub fn builder(&self) -> &DatabaseBuilder {
+        &self.builder
+    }
+
+    /// The underlying builder, for adding nodes and connections directly
+    pub fn builder_mut(&mut self) -> &mut DatabaseBuilder {
+        &mut self.builder
+    }
+
+    /// Everything proposed by the latest [`discover`](Self::discover) run,
+    /// accepted or not, with confidence scores
+    pub fn discovered_connections(&self) -> &[CandidateConnection] {
+        &self.discovered
+    }
+
+    /// Find candidate connections, review them and commit the accepted ones.
+    ///
+    /// Each run starts over and proposes everything it finds, including
+    /// connections committed by earlier runs. Use
+    /// [`SymmetricConnectionMode::DedupExact`](crate::storage::SymmetricConnectionMode::DedupExact)
+    /// on the underlying builder to fold repeats.
+    ///
+    /// Returns the number of connections committed.
+    pub fn discover(&mut self) -> Result<usize> {
+        self.discovered = self.find_candidates();
+
+        let mut committed = 0;
+        for candidate in &self.discovered {
+            let decision = match self.review.as_mut() {
+                Some(review) => review(candidate),
+                None => ConnectionDecision::Accept,
+            };
+            let strength = match decision {
+                ConnectionDecision::Accept => candidate.strength,
+                ConnectionDecision::Reject => continue,
+                ConnectionDecision::AdjustStrength(strength) => clamp01(strength),
+            };
+            self.builder.add_connection(
+                candidate.source,
+                candidate.target,
+                candidate.connection_type,
+                strength,
+            )?;
+            committed += 1;
+        }
+
+        debug!(
+            proposed = self.discovered.len(),
+            committed = committed,
+            "Reviewed discovered connections"
+        );
+        Ok(committed)
+    }
+
+    /// Write the database to `path`; see [`DatabaseBuilder::build`]
+    pub fn build<P: AsRef<Path>>(&self, path: P) -> Result<BuildReport> {
+        self.builder.build(path)
+    }
+
+    /// Propose derivation and analogy connections, in node order
+    fn find_candidates(&self) -> Vec<CandidateConnection> {
+        let mut words = Vec::new();
+        let mut affixes = Vec::new();
+        for i in 0..self.builder.node_count() {
+            let id = NodeId(i as u32 + 1);
+            let Some(node) = self.builder.node(id) else { continue };
+            let form = self.builder.node_word(node);
+            match node.layer {
+                Layer::Words => words.push((id, form, node.position)),
+                Layer::Morphemes => {
+                    if let Some(attachment) = attachment(node.morpheme_type) {
+                        affixes.push((id, form, attachment));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // First node wins when a form is spelled twice
+        let mut by_form: HashMap<&str, NodeId> = HashMap::new();
+        let mut positions: HashMap<NodeId, Coordinate3D> = HashMap::new();
+        for &(id, form, position) in &words {
+            by_form.entry(form).or_insert(id);
+            positions.insert(id, position);
+        }
+
+        let derivation_strength = self.builder.default_strength(ConnectionType::Derivation);
+        let mut candidates = Vec::new();
+        // (affix, base word, derived word) for each confirmed derivation
+        let mut derivations = Vec::new();
+
+        for &(word_id, word, _) in &words {
+            for &(affix_id, affix, attachment) in &affixes {
+                if word.len() <= affix.len() {
+                    continue;
+                }
+                let remainder = match attachment {
+                    Attachment::Prefix => word.strip_prefix(affix),
+                    Attachment::Suffix => word.strip_suffix(affix),
+                };
+                let Some(remainder) = remainder else { continue };
+                let base = by_form.get(remainder).copied();
+
+                let confidence = match base {
+                    Some(_) => STEM_CONFIRMED_CONFIDENCE,
+                    None => AFFIX_ONLY_CONFIDENCE,
+                };
+                candidates.push(self.candidate(word_id, affix_id, ConnectionType::Derivation, derivation_strength, confidence));
+                if let Some(base_id) = base {
+                    candidates.push(self.candidate(word_id, base_id, ConnectionType::Derivation, derivation_strength, confidence));
+                    derivations.push((affix_id, base_id, word_id));
+                }
+            }
+        }
+
+        let analogy_strength = self.builder.default_strength(ConnectionType::Analogy);
+        let position = |id: NodeId| positions.get(&id).copied();
+        for (i, &(affix, base_a, derived_a)) in derivations.iter().enumerate() {
+            for &(other_affix, base_b, derived_b) in &derivations[i + 1..] {
+                if affix != other_affix {
+                    continue;
+                }
+                let (Some(pa), Some(da), Some(pb), Some(db)) =
+                    (position(base_a), position(derived_a), position(base_b), position(derived_b))
+                else {
+                    continue;
+                };
+                let predicted: Coordinate3D = pb + (da - pa);
+                let confidence = (1.0 - predicted.distance(db)).max(0.0);
+                if confidence > 0.0 {
+                    candidates.push(self.candidate(derived_a, derived_b, ConnectionType::Analogy, analogy_strength, confidence));
+                }
+            }
+        }
+
+        candidates
+    }
+
+    fn candidate(
+        &self,
+        source: NodeId,
+        target: NodeId,
+        connection_type: ConnectionType,
+        strength: f32,
+        confidence: f32,
+    ) -> CandidateConnection {
+        let word = |id| self.builder.node(id).map_or_else(String::new, |node| self.builder.node_word(node).to_string());
+        CandidateConnection {
+            source,
+            target,
+            source_word: word(source),
+            target_word: word(target),
+            connection_type,
+            strength,
+            confidence,
+        }
+    }
+}
+
+// Implement Debug manually since the review callback is a boxed closure
+impl std::fmt::Debug for AutoLinguisticBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AutoLinguisticBuilder")
+            .field("builder", &self.builder)
+            .field("reviewed", &self.review.is_some())
+            .field("discovered", &self.discovered)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use crate::test_support::open_database;
+
+    fn seeded(builder: AutoLinguisticBuilder) -> AutoLinguisticBuilder {
+        let mut builder = builder;
+        builder.add_morpheme("er", MorphemeType::AgentSuffix, Coordinate3D::new(0.6, 0.3, 0.35)).unwrap();
+        builder.add_word("teach", Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        builder.add_word("teacher", Coordinate3D::new(0.25, 0.6, 0.52)).unwrap();
+        builder.add_word("work", Coordinate3D::new(0.6, 0.5, 0.52)).unwrap();
+        builder.add_word("worker", Coordinate3D::new(0.65, 0.6, 0.52)).unwrap();
+        builder.add_word("river", Coordinate3D::new(0.8, 0.2, 0.52)).unwrap();
+        builder
+    }
+
+    #[test]
+    fn test_rejecting_review_commits_nothing() {
+        let reviewed = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&reviewed);
+        let mut builder = seeded(AutoLinguisticBuilder::new().with_review(move |_| {
+            counter.set(counter.get() + 1);
+            ConnectionDecision::Reject
+        }));
+
+        assert_eq!(builder.discover().unwrap(), 0);
+        assert_eq!(builder.builder().connection_count(), 0);
+
+        // Everything was still proposed and shown to the reviewer
+        let proposed = builder.discovered_connections();
+        assert_eq!(reviewed.get(), proposed.len());
+        assert!(proposed.iter().any(|c| c.connection_type == ConnectionType::Analogy
+            && c.source_word == "teacher"
+            && c.target_word == "worker"
+            && (c.confidence - 1.0).abs() < 1e-5));
+        let river = proposed.iter().find(|c| c.source_word == "river").unwrap();
+        assert_eq!(river.confidence, AFFIX_ONLY_CONFIDENCE);
+
+        let (_dir, db) = open_database(builder.builder());
+        assert_eq!(db.connection_count(), 0);
+    }
+
+    #[test]
+    fn test_review_adjusts_strength() {
+        let mut builder = seeded(AutoLinguisticBuilder::new().with_review(|candidate| {
+            match candidate.connection_type {
+                ConnectionType::Analogy => ConnectionDecision::AdjustStrength(0.2),
+                _ if candidate.confidence < STEM_CONFIRMED_CONFIDENCE => ConnectionDecision::Reject,
+                _ => ConnectionDecision::Accept,
+            }
+        }));
+
+        // teacher and worker each get the suffix and their base, plus one analogy
+        assert_eq!(builder.discover().unwrap(), 5);
+        assert_eq!(builder.discovered_connections().len(), 6);
+
+        let mut unreviewed = seeded(AutoLinguisticBuilder::new());
+        assert_eq!(unreviewed.discover().unwrap(), 6);
+
+        // A NaN strength is clamped like any other out-of-range value
+        let mut builder = seeded(AutoLinguisticBuilder::new()
+            .with_review(|_| ConnectionDecision::AdjustStrength(f32::NAN)));
+        assert_eq!(builder.discover().unwrap(), 6);
+        let (_dir, db) = open_database(builder.builder());
+        for node in db.nodes() {
+            for connection in db.get_node_connections(node.id).unwrap() {
+                assert_eq!(connection.strength_normalized(), 0.0);
+            }
+        }
+    }
+}
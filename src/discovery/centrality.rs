@@ -0,0 +1,67 @@
+//! Degree centrality for finding hub nodes
+
+use crate::core::{Layer, NodeId};
+use crate::storage::MemoryMappedDatabase;
+
+/// Rank the nodes of `layer` by total connection degree (incoming plus outgoing).
+///
+/// Returns at most `n` `(node, degree)` pairs, highest degree first. Ties are
+/// broken by node ID so the ranking is stable across runs.
+pub fn central_nodes(db: &MemoryMappedDatabase, layer: Layer, n: usize) -> Vec<(NodeId, usize)> {
+    let mut ranked: Vec<(NodeId, usize)> = db
+        .nodes()
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.layer == layer)
+        .map(|(i, _)| {
+            let id = NodeId(i as u32 + 1);
+            let outgoing = db.get_node_connections(id).map(|c| c.len()).unwrap_or(0);
+            let incoming = db.get_incoming_connections(id).map(|c| c.len()).unwrap_or(0);
+            (id, outgoing + incoming)
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0 .0.cmp(&b.0 .0)));
+    ranked.truncate(n);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ConnectionType, Coordinate3D};
+    use crate::storage::DatabaseBuilder;
+    use crate::test_support::open_database;
+
+    #[test]
+    fn test_star_hub_ranks_first() {
+        let mut builder = DatabaseBuilder::new();
+        let leaves: Vec<NodeId> = ["cat", "dog", "fish", "bird"]
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                builder
+                    .add_node(w, Layer::Words, Coordinate3D::new(0.1 + i as f32 * 0.2, 0.5, 0.5))
+                    .unwrap()
+            })
+            .collect();
+        let hub = builder.add_node("animal", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.55)).unwrap();
+        builder.add_node("fauna", Layer::Concepts, Coordinate3D::new(0.5, 0.5, 0.8)).unwrap();
+
+        // Two leaves point in, two are pointed at, plus one leaf-to-leaf edge
+        builder.add_connection(leaves[0], hub, ConnectionType::Hypernymy, 0.9).unwrap();
+        builder.add_connection(leaves[1], hub, ConnectionType::Hypernymy, 0.9).unwrap();
+        builder.add_connection(hub, leaves[2], ConnectionType::Hyponymy, 0.9).unwrap();
+        builder.add_connection(hub, leaves[3], ConnectionType::Hyponymy, 0.9).unwrap();
+        builder.add_connection(leaves[0], leaves[1], ConnectionType::Analogy, 0.5).unwrap();
+
+        let (_dir, db) = open_database(&builder);
+        let ranked = central_nodes(&db, Layer::Words, 3);
+
+        assert_eq!(ranked.len(), 3);
+        assert_eq!(ranked[0], (hub, 4));
+        assert_eq!(ranked[1], (leaves[0], 2));
+        assert_eq!(ranked[2], (leaves[1], 2));
+        assert_eq!(db.get_incoming_connections(hub).unwrap(), &[leaves[0], leaves[1]]);
+    }
+}
@@ -1,4 +1,13 @@
 //! Connection discovery and learning
 
 pub mod analogy;
-pub mod morphology;
\ No newline at end of file
+pub mod auto_builder;
+pub mod centrality;
+pub mod morphology;
+pub mod productivity;
+pub mod semantic_field;
+
+pub use auto_builder::{AutoLinguisticBuilder, CandidateConnection, ConnectionDecision};
+pub use centrality::central_nodes;
+pub use productivity::recalculate_productivity;
+pub use semantic_field::dominant_semantic_field;
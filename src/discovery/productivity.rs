@@ -0,0 +1,118 @@
+//! Corpus-driven morpheme productivity
+
+use crate::core::{Layer, MorphemeType, NodeId};
+use crate::logging::debug;
+use crate::storage::MutableDatabase;
+use std::collections::BTreeSet;
+
+/// Where an affix attaches to the words it forms
+#[derive(Clone, Copy)]
+pub(super) enum Attachment {
+    Prefix,
+    Suffix,
+}
+
+pub(super) fn attachment(morpheme_type: MorphemeType) -> Option<Attachment> {
+    match morpheme_type {
+        MorphemeType::Prefix => Some(Attachment::Prefix),
+        MorphemeType::Suffix
+        | MorphemeType::AgentSuffix
+        | MorphemeType::VerbSuffix
+        | MorphemeType::TenseSuffix
+        | MorphemeType::ActionSuffix
+        | MorphemeType::QualitySuffix => Some(Attachment::Suffix),
+        _ => None,
+    }
+}
+
+/// Recompute affix productivity from how many distinct corpus words each
+/// affix forms.
+///
+/// A word counts for an affix if it starts (prefixes) or ends (suffixes)
+/// with the affix and is longer than it. Counts are scaled so the most
+/// productive affix gets 1.0, and stored in each morpheme node's
+/// `productivity_score`. Roots and other non-affix morphemes are left
+/// unchanged, as is everything when no affix occurs in the corpus.
+///
+/// Returns the number of affix nodes updated.
+pub fn recalculate_productivity(db: &mut MutableDatabase, corpus: &[&str]) -> usize {
+    // Split on anything that isn't a letter so punctuation doesn't hide suffixes
+    let words: BTreeSet<String> = corpus
+        .iter()
+        .flat_map(|text| text.split(|c: char| !c.is_alphabetic()))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut counts: Vec<(NodeId, usize)> = Vec::new();
+    for i in 0..db.node_count() {
+        let id = NodeId(i as u32 + 1);
+        let Some(node) = db.get_node(id) else { continue };
+        if node.layer != Layer::Morphemes {
+            continue;
+        }
+        let Some(attachment) = attachment(node.morpheme_type) else { continue };
+        let Some(affix) = db.get_node_word(id) else { continue };
+        if affix.is_empty() {
+            continue;
+        }
+
+        let formed = words
+            .iter()
+            .filter(|word| word.len() > affix.len())
+            .filter(|word| match attachment {
+                Attachment::Prefix => word.starts_with(affix),
+                Attachment::Suffix => word.ends_with(affix),
+            })
+            .count();
+        counts.push((id, formed));
+    }
+
+    let max = counts.iter().map(|&(_, formed)| formed).max().unwrap_or(0);
+    if max == 0 {
+        debug!(affixes = counts.len(), "No affix occurs in the corpus; productivity unchanged");
+        return 0;
+    }
+
+    for &(id, formed) in &counts {
+        if let Some(node) = db.node_mut(id) {
+            node.set_productivity(formed as f32 / max as f32);
+        }
+    }
+    debug!(affixes = counts.len(), words = words.len(), "Recalculated affix productivity");
+    counts.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Coordinate3D;
+
+    #[test]
+    fn test_frequent_affix_scores_higher() {
+        let mut db = MutableDatabase::new();
+        let (un, ness, ward) = db.transaction(|tx| {
+            let un = tx.insert_node("un", Layer::Morphemes, Coordinate3D::new(0.2, 0.1, 0.35))?;
+            let ness = tx.insert_node("ness", Layer::Morphemes, Coordinate3D::new(0.6, 0.1, 0.35))?;
+            let ward = tx.insert_node("ward", Layer::Morphemes, Coordinate3D::new(0.7, 0.1, 0.35))?;
+            tx.insert_node("kind", Layer::Words, Coordinate3D::new(0.5, 0.1, 0.52))?;
+            Ok((un, ness, ward))
+        }).unwrap();
+        db.node_mut(un).unwrap().morpheme_type = MorphemeType::Prefix;
+        db.node_mut(ness).unwrap().morpheme_type = MorphemeType::Suffix;
+        db.node_mut(ward).unwrap().morpheme_type = MorphemeType::Suffix;
+        // Hand-assigned guess that the corpus should overturn
+        db.node_mut(ward).unwrap().set_productivity(0.9);
+
+        let corpus = [
+            "Kindness and unkindness are unusual, unclear and unfair.",
+            "Darkness fell; they walked homeward. Kindness again.",
+        ];
+        assert_eq!(recalculate_productivity(&mut db, &corpus), 3);
+
+        let score = |db: &MutableDatabase, id| db.get_node(id).unwrap().productivity();
+        assert_eq!(score(&db, un), 1.0);
+        assert!(score(&db, ness) > score(&db, ward));
+        assert!(score(&db, ward) > 0.0);
+    }
+}
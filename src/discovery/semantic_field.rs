@@ -0,0 +1,124 @@
+//! Semantic field inference for free text
+
+use crate::core::{ConnectionType, Layer, NodeId};
+use crate::engine::LingoExecutor;
+use crate::morphology::{decompose_all, preprocess_text};
+use crate::storage::MemoryMappedDatabase;
+use std::collections::HashMap;
+
+/// Weight given to morphemes whose productivity hasn't been scored
+const DEFAULT_PRODUCTIVITY: f32 = 0.5;
+
+/// Strength of the Hypernymy connection from a node to its semantic field
+pub(crate) const FIELD_STRENGTH: f32 = 0.8;
+
+/// Semantic fields of `id`: the Concepts-layer nodes it points to with
+/// Hypernymy connections
+pub(crate) fn semantic_fields(db: &MemoryMappedDatabase, id: NodeId) -> Vec<&str> {
+    db.get_node_connections(id).unwrap_or(&[]).iter()
+        .filter(|connection| connection.connection_type == ConnectionType::Hypernymy)
+        .map(|connection| connection.target_node)
+        .filter(|&target| db.get_node(target).is_ok_and(|node| node.layer == Layer::Concepts))
+        .filter_map(|target| db.get_node_word(target).ok())
+        .collect()
+}
+
+/// Infer the semantic field that dominates `text`.
+///
+/// Each word is decomposed with [`decompose_all`] and the best analysis is
+/// kept. A morpheme's fields are the Concepts-layer nodes it points to with
+/// Hypernymy connections; each field gets the morpheme's productivity as its
+/// vote. Returns the field with the most weight and its share of the total
+/// weight, or `None` if no morpheme in the text has a field.
+pub fn dominant_semantic_field(
+    db: &MemoryMappedDatabase,
+    executor: &mut LingoExecutor,
+    text: &str,
+) -> Option<(String, f32)> {
+    let mut tally: HashMap<String, f32> = HashMap::new();
+
+    for word in preprocess_text(text).split_whitespace() {
+        let Some(best) = decompose_all(word, db, executor, 0.0).into_iter().next() else {
+            continue;
+        };
+
+        for morpheme in best.morphemes {
+            let Some(id) = morpheme.node_id else { continue };
+            let Ok(node) = db.get_node(id) else { continue };
+            let weight = match node.productivity() {
+                p if p > 0.0 => p,
+                _ => DEFAULT_PRODUCTIVITY,
+            };
+
+            for field in semantic_fields(db, id) {
+                *tally.entry(field.to_string()).or_insert(0.0) += weight;
+            }
+        }
+    }
+
+    let total: f32 = tally.values().sum();
+    // Sort by name first so equal weights resolve the same way every run
+    let mut fields: Vec<(String, f32)> = tally.into_iter().collect();
+    fields.sort_by(|a, b| a.0.cmp(&b.0));
+    fields
+        .into_iter()
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal).then(b.0.cmp(&a.0)))
+        .map(|(field, weight)| (field, weight / total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Coordinate3D, EtymologyOrigin, MorphemeType, NodeFlags};
+    use crate::storage::DatabaseBuilder;
+    use crate::test_support::database_and_executor;
+
+    #[test]
+    fn test_organize_the_system() {
+        let mut builder = DatabaseBuilder::new();
+        let morpheme = |builder: &mut DatabaseBuilder, form: &str, kind: MorphemeType, x: f32, productivity: f32| {
+            let id = builder.add_node_full(
+                form,
+                Layer::Morphemes,
+                Coordinate3D::new(x, 0.6, 0.37),
+                EtymologyOrigin::Greek,
+                kind,
+                NodeFlags::empty(),
+            ).unwrap();
+            builder.node_mut(id).unwrap().set_productivity(productivity);
+            id
+        };
+        let organ = morpheme(&mut builder, "organ", MorphemeType::Root, 0.4, 0.7);
+        let ize = morpheme(&mut builder, "ize", MorphemeType::VerbSuffix, 0.3, 0.9);
+        let system = morpheme(&mut builder, "system", MorphemeType::Root, 0.5, 0.6);
+
+        let organization = builder.add_node("organization", Layer::Concepts, Coordinate3D::new(0.45, 0.6, 0.8)).unwrap();
+        let causative = builder.add_node("causative", Layer::Concepts, Coordinate3D::new(0.3, 0.6, 0.8)).unwrap();
+        builder.add_connection(organ, organization, ConnectionType::Hypernymy, 0.8).unwrap();
+        builder.add_connection(system, organization, ConnectionType::Hypernymy, 0.8).unwrap();
+        builder.add_connection(ize, causative, ConnectionType::Hypernymy, 0.8).unwrap();
+
+        let (_dir, db, mut executor) = database_and_executor(&builder);
+
+        let (field, confidence) = dominant_semantic_field(&db, &mut executor, "organize the system").unwrap();
+        assert_eq!(field, "organization");
+        assert!((confidence - 1.3 / 2.2).abs() < 0.01, "confidence {}", confidence);
+
+        assert!(dominant_semantic_field(&db, &mut executor, "the").is_none());
+    }
+
+    #[test]
+    fn test_seeded_fields() {
+        let mut builder = DatabaseBuilder::new();
+        crate::data::seed_database(&mut builder).unwrap();
+        let (_dir, db, mut executor) = database_and_executor(&builder);
+
+        let (field, confidence) = dominant_semantic_field(&db, &mut executor, "the system").unwrap();
+        assert_eq!((field.as_str(), confidence), ("systematization", 1.0));
+
+        // "ize" (causation, 0.9) outweighs "organ" (organization, 0.85)
+        let (field, confidence) = dominant_semantic_field(&db, &mut executor, "organize").unwrap();
+        assert_eq!(field, "causation");
+        assert!((confidence - 0.9 / 1.75).abs() < 0.01, "confidence {}", confidence);
+    }
+}
@@ -15,16 +15,29 @@
 //! SLANG bytecode executor - the heart of query execution
 
 use crate::core::{
-    NodeId,
-    bytecode::{SlangOp, SlangInstruction},
+    NodeId, Coordinate3D, BoundingBox3D, ConnectionType, OrthogonalConnection, Layer, EtymologyOrigin, MorphemeType,
+    SimilarityMetric, LinguisticNode, is_stopword, phrase_tokens,
+    bytecode::{filter_kinds, sort_keys, SlangOp, SlangInstruction, ANY_CONNECTION_TYPE},
     error::{LingoError, Result},
 };
-use crate::query::CompiledQuery;
-use crate::storage::{Database, MemoryMappedDatabase};
-use crate::logging::{debug, trace, warn, info};
-use std::collections::HashSet;
+use crate::query::{CompiledQuery, CompiledQueryTemplate, QueryBuilder};
+use super::ResultStream;
+use crate::storage::{Database, FileFormatFlags, MemoryMappedDatabase, locate_database};
+use crate::index::{OctreeBuilder, SpatialIndex};
+use crate::security::{validate_limit, ExecutionLimits};
+use crate::logging::{debug, trace, warn, info, info_span};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// Distances within this of each other count as a tie for `prefer_frequent`
+const FREQUENCY_TIE_TOLERANCE: f32 = 0.01;
+
+/// Most queries [`LingoExecutor::similar_auto`] runs while tuning its threshold
+const AUTO_THRESHOLD_PROBES: usize = 8;
+
 /// A set of node IDs with efficient deduplication and operations.
 ///
 /// `NodeSet` maintains both a vector for ordered access and a HashSet
@@ -221,6 +234,88 @@ impl NodeSet {
         }
     }
     
+    /// Puts the nodes in ascending ID order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::engine::executor::NodeSet;
+    /// use lingo::core::NodeId;
+    ///
+    /// let mut set = NodeSet::new();
+    /// set.extend(vec![NodeId(3), NodeId(1), NodeId(2)]);
+    /// set.sort_by_id();
+    /// assert_eq!(set.as_slice(), &[NodeId(1), NodeId(2), NodeId(3)]);
+    /// ```
+    pub fn sort_by_id(&mut self) {
+        if !self.sorted {
+            self.nodes.sort_unstable();
+            self.sorted = true;
+        }
+    }
+    
+    /// Returns true if the set contains `node_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::engine::executor::NodeSet;
+    /// use lingo::core::NodeId;
+    ///
+    /// let set = NodeSet::single(NodeId(7));
+    /// assert!(set.contains(NodeId(7)));
+    /// assert!(!set.contains(NodeId(8)));
+    /// ```
+    pub fn contains(&self, node_id: NodeId) -> bool {
+        self.set.contains(&node_id)
+    }
+    
+    /// Drops the first `count` nodes, keeping the rest in order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::engine::executor::NodeSet;
+    /// use lingo::core::NodeId;
+    ///
+    /// let mut set = NodeSet::new();
+    /// set.extend(vec![NodeId(1), NodeId(2), NodeId(3)]);
+    /// set.skip(2);
+    /// assert_eq!(set.as_slice(), &[NodeId(3)]);
+    /// ```
+    pub fn skip(&mut self, count: usize) {
+        let count = count.min(self.nodes.len());
+        for id in self.nodes.drain(..count) {
+            self.set.remove(&id);
+        }
+    }
+    
+    /// Keeps only the nodes for which `keep` returns true.
+    ///
+    /// The remaining nodes stay in their current order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::engine::executor::NodeSet;
+    /// use lingo::core::NodeId;
+    ///
+    /// let mut set = NodeSet::new();
+    /// set.extend(vec![NodeId(1), NodeId(2), NodeId(3)]);
+    /// set.retain(|id| id != NodeId(2));
+    /// assert_eq!(set.as_slice(), &[NodeId(1), NodeId(3)]);
+    /// ```
+    pub fn retain<F: FnMut(NodeId) -> bool>(&mut self, mut keep: F) {
+        let set = &mut self.set;
+        self.nodes.retain(|&id| {
+            let kept = keep(id);
+            if !kept {
+                set.remove(&id);
+            }
+            kept
+        });
+    }
+    
     /// Returns the nodes as a slice.
     ///
     /// The order is the same as insertion order (not sorted).
@@ -279,6 +374,53 @@ pub struct ExecutionFlags {
     pub missing_index: bool,
 }
 
+/// Work charged against the execution budget of the running query.
+///
+/// Every instruction costs one unit plus one per node in the set it
+/// produces. Operations that can expand a set check [`Budget::check`] as
+/// they build it, so they stop before doing the work rather than after.
+#[derive(Debug, Clone, Copy)]
+struct Budget {
+    spent: u64,
+    limit: u64,
+}
+
+impl Budget {
+    fn new(limit: u64) -> Self {
+        Self { spent: 0, limit }
+    }
+    
+    /// Largest set the next instruction can produce within the budget
+    fn room(&self) -> usize {
+        let room = self.limit.saturating_sub(self.spent.saturating_add(1));
+        usize::try_from(room).unwrap_or(usize::MAX)
+    }
+    
+    /// Fails if the next instruction would go over by producing `produced` nodes
+    fn check(&self, produced: usize) -> Result<()> {
+        let spent = self.spent.saturating_add(1).saturating_add(produced as u64);
+        if spent > self.limit {
+            return Err(LingoError::BudgetExceeded { spent, budget: self.limit });
+        }
+        Ok(())
+    }
+    
+    /// Charges an instruction that produced `produced` nodes
+    fn charge(&mut self, produced: usize) -> Result<()> {
+        self.check(produced)?;
+        self.spent = self.spent.saturating_add(1).saturating_add(produced as u64);
+        Ok(())
+    }
+}
+
+/// Result limit applied to queries that don't set their own.
+///
+/// This keeps broad searches from returning a whole dense layer by
+/// accident. It is separate from the security cap in
+/// [`ExecutionLimits`]; change it with [`LingoExecutor::set_default_limit`]
+/// or opt a single query out with [`QueryBuilder::no_limit`](crate::query::QueryBuilder::no_limit).
+pub const DEFAULT_RESULT_LIMIT: usize = 1_000;
+
 /// Contains the results of executing a compiled query.
 ///
 /// This structure provides both the result nodes and metadata about
@@ -311,6 +453,154 @@ pub struct QueryResult {
     pub instructions_executed: usize,
     /// Cache hit?
     pub cache_hit: bool,
+    /// Similarity of each result node, aligned with `nodes`; read through
+    /// [`nodes_with_scores`](Self::nodes_with_scores)
+    scores: Vec<f32>,
+}
+
+impl QueryResult {
+    /// A result holding `nodes`, with no timing, instruction count or scores
+    pub fn new(nodes: NodeSet) -> Self {
+        Self {
+            nodes,
+            execution_time: Duration::ZERO,
+            instructions_executed: 0,
+            cache_hit: false,
+            scores: Vec::new(),
+        }
+    }
+    
+    /// Pairs each result node with its similarity score, if scores were computed.
+    ///
+    /// Scores are filled when the query ends with a similarity search
+    /// (optionally followed by `limit` or deduplication) and are `None`
+    /// otherwise. Similarity is `1 - distance` to the closest seed, so with
+    /// one seed the scores are in descending order (apart from near-ties
+    /// that `prefer_frequent` reorders).
+    pub fn nodes_with_scores(&self) -> impl Iterator<Item = (NodeId, Option<f32>)> + '_ {
+        self.nodes.as_slice()
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| (id, self.scores.get(i).copied()))
+    }
+}
+
+/// A result node with its properties looked up, ready to serialize.
+///
+/// Part of a [`ResolvedResult`].
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ResolvedNode {
+    /// Node identifier
+    pub id: NodeId,
+    /// Surface form, or `None` if it couldn't be read from the string table
+    pub word: Option<String>,
+    /// Linguistic layer
+    pub layer: Layer,
+    /// Position in linguistic space
+    pub position: Coordinate3D,
+    /// Etymology origin
+    pub etymology: EtymologyOrigin,
+    /// Type of morpheme
+    pub morpheme_type: MorphemeType,
+    /// Similarity score, when the query computed one
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub score: Option<f32>,
+}
+
+/// A [`QueryResult`] with every node resolved against the database.
+///
+/// Returned by [`LingoExecutor::resolve`]. Serializes with serde, e.g. to
+/// JSON for an API response.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ResolvedResult {
+    /// Result nodes, in result order
+    pub nodes: Vec<ResolvedNode>,
+    /// Execution time in microseconds
+    pub execution_time_us: u64,
+    /// Cache hit?
+    pub cache_hit: bool,
+}
+
+/// A word's neighbours one step down and up the layer hierarchy.
+///
+/// Returned by [`LingoExecutor::vertical_context`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerticalContext {
+    /// Parts the word is composed of (composition edges into lower layers)
+    pub below: Vec<NodeId>,
+    /// The word itself
+    pub self_node: NodeId,
+    /// More general nodes (hypernymy edges)
+    pub above: Vec<NodeId>,
+}
+
+/// What one instruction did, as recorded by [`LingoExecutor::explain`].
+///
+/// Not comparable with `==`: `duration` differs between otherwise
+/// identical runs.
+#[derive(Debug, Clone)]
+pub struct TraceStep {
+    /// Position of the instruction in the bytecode
+    pub index: usize,
+    /// The operation executed
+    pub op: SlangOp,
+    /// Node sets on the stack before the instruction ran
+    pub stack_before: usize,
+    /// Node sets on the stack after the instruction ran
+    pub stack_after: usize,
+    /// Nodes in the top set afterwards that weren't in it before
+    pub nodes_added: usize,
+    /// Nodes in the top set before that aren't in it afterwards
+    pub nodes_removed: usize,
+    /// Size of the top set after the instruction ran
+    pub nodes_after: usize,
+    /// Time the instruction took
+    pub duration: Duration,
+}
+
+/// Step-by-step record of a query run, returned by [`LingoExecutor::explain`].
+///
+/// The query planner's `EXPLAIN`: it shows what each SLANG instruction a
+/// [`QueryBuilder`](crate::query::QueryBuilder) chain compiled to actually
+/// did to the result set. Its `Display` form is a one-line-per-step table.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionTrace {
+    /// Executed instructions, in order, including a failing one
+    pub steps: Vec<TraceStep>,
+    /// Nodes `execute` would have returned, after default and security limits
+    pub result_count: usize,
+    /// Why execution stopped early, if it did
+    pub error: Option<String>,
+}
+
+impl ExecutionTrace {
+    /// The operations executed, in order
+    pub fn ops(&self) -> Vec<SlangOp> {
+        self.steps.iter().map(|step| step.op).collect()
+    }
+}
+
+impl fmt::Display for ExecutionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(
+                f,
+                "{:>3} {:<20} stack {}→{}  +{} -{} = {} nodes  ({:?})",
+                step.index,
+                format!("{:?}", step.op),
+                step.stack_before,
+                step.stack_after,
+                step.nodes_added,
+                step.nodes_removed,
+                step.nodes_after,
+                step.duration,
+            )?;
+        }
+        match &self.error {
+            Some(error) => write!(f, "failed: {}", error),
+            None => write!(f, "{} result nodes", self.result_count),
+        }
+    }
 }
 
 /// The SLANG bytecode executor - the query execution engine for Lingo.
@@ -370,8 +660,18 @@ pub struct QueryResult {
 /// # }
 /// ```
 pub struct LingoExecutor {
-    /// Database access
-    pub database: Option<Database>,
+    /// Database access, shared with executors cloned from this one
+    database: Option<Arc<Database>>,
+    /// Spatial index over the database, built on first spatial query
+    spatial_index: Option<Arc<SpatialIndex>>,
+    /// Spatial indices built over the executor's lifetime
+    spatial_index_builds: usize,
+    /// Caps applied by `execute`
+    limits: ExecutionLimits,
+    /// Result limit for queries without an explicit one
+    default_limit: Option<usize>,
+    /// Directory that relative database names resolve against
+    base_path: Option<std::path::PathBuf>,
     
     // Execution state
     /// Instruction pointer
@@ -385,9 +685,23 @@ pub struct LingoExecutor {
     
     // String cache for LoadNode operations
     string_cache: Vec<String>,
+    /// Connection weight profiles for FollowWeighted operations
+    connection_weights: Vec<HashMap<ConnectionType, f32>>,
+    /// Boxes for SpatialBox operations
+    regions: Vec<BoundingBox3D>,
+    /// ID lists for LoadNodeIds operations
+    node_lists: Vec<Vec<NodeId>>,
+    /// Metrics for FindSimilar operations
+    metrics: Vec<SimilarityMetric>,
+    /// Similarities from the latest similarity search, while still current
+    similarity_scores: HashMap<NodeId, f32>,
+    /// Budget of the running query, if it has one
+    budget: Option<Budget>,
     
     // Statistics
     instructions_executed: usize,
+    /// Queries executed over the executor's lifetime
+    queries_executed: usize,
 }
 
 impl LingoExecutor {
@@ -407,12 +721,24 @@ impl LingoExecutor {
     pub fn new() -> Self {
         Self {
             database: None,
+            spatial_index: None,
+            spatial_index_builds: 0,
+            limits: ExecutionLimits::default(),
+            default_limit: Some(DEFAULT_RESULT_LIMIT),
             instruction_pointer: 0,
             stack: Vec::new(),
             registers: Default::default(),
             flags: ExecutionFlags::default(),
             string_cache: Vec::new(),
+            connection_weights: Vec::new(),
+            regions: Vec::new(),
+            node_lists: Vec::new(),
+            metrics: Vec::new(),
+            similarity_scores: HashMap::new(),
+            budget: None,
             instructions_executed: 0,
+            queries_executed: 0,
+            base_path: None,
         }
     }
     
@@ -438,13 +764,119 @@ impl LingoExecutor {
     /// # }
     /// ```
     pub fn set_database(&mut self, database: Database) {
+        self.set_shared_database(Arc::new(database));
+    }
+    
+    /// The database queries run against, if one is loaded
+    pub fn database(&self) -> Option<&Database> {
+        self.database.as_deref()
+    }
+    
+    /// Sets a database that other executors may also be using.
+    ///
+    /// The memory map is shared rather than reopened, so this is cheap.
+    pub fn set_shared_database(&mut self, database: Arc<Database>) {
         self.database = Some(database);
+        self.spatial_index = None;
+    }
+    
+    /// Creates an executor over a database shared with other executors.
+    ///
+    /// Each executor keeps its own execution state, so give every thread
+    /// its own executor and share the database between them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use lingo::{LingoExecutor, QueryBuilder, storage::Database};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let db = Arc::new(Database::open("english.lingo")?);
+    ///
+    /// let handles: Vec<_> = ["happy", "sad"].into_iter().map(|word| {
+    ///     let mut executor = LingoExecutor::from_shared(Arc::clone(&db));
+    ///     std::thread::spawn(move || {
+    ///         executor.execute(&QueryBuilder::find(word).similar().compile())
+    ///     })
+    /// }).collect();
+    ///
+    /// for handle in handles {
+    ///     println!("{} results", handle.join().unwrap()?.nodes.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_shared(database: Arc<Database>) -> Self {
+        let mut executor = Self::new();
+        executor.set_shared_database(database);
+        executor
+    }
+    
+    /// Creates another executor over the same database, with fresh state.
+    ///
+    /// The database and spatial index are shared, not copied; limits, the
+    /// default result limit and the base path are carried over. The
+    /// execution stack, registers and statistics start empty, so the clone
+    /// can run on another thread independently of this executor.
+    ///
+    /// # Errors
+    ///
+    /// - `LingoError::Database` - No database is loaded, so there is
+    ///   nothing to share
+    pub fn try_clone(&self) -> Result<Self> {
+        if self.database.is_none() {
+            return Err(LingoError::Database("No database loaded".to_string()));
+        }
+        Ok(self.fresh_copy())
+    }
+    
+    /// A new executor sharing this one's database, spatial index and
+    /// settings, with empty execution state and statistics.
+    ///
+    /// Backs both [`try_clone`](Self::try_clone) and
+    /// [`explain`](Self::explain), so settings added later reach both.
+    fn fresh_copy(&self) -> Self {
+        let mut executor = Self::new();
+        executor.database = self.database.clone();
+        executor.spatial_index = self.spatial_index.clone();
+        executor.limits = self.limits;
+        executor.default_limit = self.default_limit;
+        executor.base_path = self.base_path.clone();
+        executor
+    }
+    
+    /// Sets the directory that relative database names are resolved against.
+    ///
+    /// Without a base path, relative names passed to `load_database` depend
+    /// on the process working directory. Absolute paths are unaffected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use lingo::LingoExecutor;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut executor = LingoExecutor::new();
+    /// executor.set_base_path("/var/lib/lingo");
+    /// executor.load_database("english.lingo")?; // opens /var/lib/lingo/english.lingo
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_base_path<P: Into<std::path::PathBuf>>(&mut self, dir: P) {
+        self.base_path = Some(dir.into());
+    }
+    
+    /// Returns the directory relative database names resolve against, if set.
+    pub fn base_path(&self) -> Option<&std::path::Path> {
+        self.base_path.as_deref()
     }
     
     /// Loads a database from a file path.
     ///
     /// This is a convenience method that opens the database file and
-    /// sets it for use by the executor.
+    /// sets it for use by the executor. Relative paths are resolved
+    /// against the base path when one is set (see `set_base_path`).
     ///
     /// # Arguments
     ///
@@ -467,8 +899,9 @@ impl LingoExecutor {
     /// # }
     /// ```
     pub fn load_database<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<()> {
+        let path = locate_database(path, self.base_path.as_deref());
         let database = MemoryMappedDatabase::open(path)?;
-        self.database = Some(database);
+        self.set_database(database);
         Ok(())
     }
     
@@ -511,8 +944,201 @@ impl LingoExecutor {
     /// # }
     /// ```
     pub fn execute(&mut self, query: &CompiledQuery) -> Result<QueryResult> {
+        let limits = self.limits;
+        self.execute_with_limits(query, &limits)
+    }
+    
+    /// Executes a query with caps that apply to this execution only.
+    ///
+    /// Use this for trusted internal callers that need larger results than
+    /// the untrusted default, or to tighten the caps for a single request.
+    /// Results beyond `limits.max_result_nodes()` are truncated.
+    pub fn execute_with_limits(&mut self, query: &CompiledQuery, limits: &ExecutionLimits) -> Result<QueryResult> {
+        self.load_query_tables(query);
+        self.run_loaded(query, limits)
+    }
+    
+    /// Executes a query, aborting once it has done more than `budget` units
+    /// of work.
+    ///
+    /// Every instruction costs one unit plus one per node in the set it
+    /// produces, so a chain of hops across densely connected nodes is
+    /// stopped in the hop that overspends instead of running to the end.
+    /// Traversals, similarity searches, `nearest_k` and `spatial_box` check
+    /// the budget while they build their set, so even a single huge step
+    /// stops as soon as its output would go over. Compare [`CompiledQuery::estimated_cost`] with the budget to
+    /// turn such queries away before running them at all. The executor's
+    /// other limits still apply, and so does a smaller budget set with
+    /// [`set_limits`](Self::set_limits).
+    ///
+    /// # Errors
+    ///
+    /// `BudgetExceeded` with the work spent so far, plus everything
+    /// [`execute`](Self::execute) reports. The executor is left ready for
+    /// the next query.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use lingo::{LingoExecutor, QueryBuilder};
+    /// use lingo::core::error::LingoError;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut executor = LingoExecutor::new();
+    /// executor.load_database("english.lingo")?;
+    ///
+    /// let query = QueryBuilder::find("the").follow_connection().follow_connection().compile();
+    /// match executor.execute_within(&query, 10_000) {
+    ///     Err(LingoError::BudgetExceeded { spent, .. }) => println!("aborted after {} units", spent),
+    ///     other => println!("{:?}", other?.nodes.len()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_within(&mut self, query: &CompiledQuery, budget: u64) -> Result<QueryResult> {
+        let limits = self.limits.with_budget(budget);
+        self.execute_with_limits(query, &limits)
+    }
+    
+    /// Runs one query for each word in `inputs`, compiling the chain once.
+    ///
+    /// Each word replaces the template's seed, as if the query had been
+    /// built with `find(word)`. The query's tables are loaded once and the
+    /// execution stack is reused between inputs, so this is cheaper than
+    /// compiling and executing each word separately. Results are in the
+    /// same order as `inputs`; a word that matches nothing gets an empty
+    /// result.
+    ///
+    /// # Errors
+    ///
+    /// Fails on the first input whose execution fails, for the same
+    /// reasons as [`execute`](Self::execute).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use lingo::{LingoExecutor, QueryBuilder};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut executor = LingoExecutor::new();
+    /// executor.load_database("english.lingo")?;
+    ///
+    /// let template = QueryBuilder::find("").similar_threshold(0.8).limit(10).compile_template()?;
+    /// let results = executor.execute_batch(&template, &["happy", "sad", "angry"])?;
+    /// assert_eq!(results.len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_batch(&mut self, template: &CompiledQueryTemplate, inputs: &[&str]) -> Result<Vec<QueryResult>> {
+        let query = template.query();
+        let limits = self.limits;
+        self.load_query_tables(query);
+        
+        let mut results = Vec::with_capacity(inputs.len());
+        for word in inputs {
+            self.string_cache[template.seed_slot()] = CompiledQueryTemplate::seed_word(word);
+            results.push(self.run_loaded(query, &limits)?);
+        }
+        Ok(results)
+    }
+    
+    /// Finds about `target_count` nodes similar to `word`, choosing the
+    /// similarity threshold itself.
+    ///
+    /// A fixed [`similar_threshold`](QueryBuilder::similar_threshold)
+    /// returns nothing in a sparse region and a flood in a dense one. This
+    /// binary-searches the threshold instead, running
+    /// `find(word).similar_threshold(t).no_limit()` at most 8 times, and
+    /// returns the result whose size came closest to `target_count` (the
+    /// earliest, on a tie) with the threshold that produced it. The count
+    /// is only approximate: nodes at the same distance from `word` come and
+    /// go together, and the search stops at the first exact hit.
+    ///
+    /// Like `similar`, the results include `word`'s own nodes.
+    ///
+    /// # Errors
+    ///
+    /// `SecurityError` if `target_count` is 0 or above the result cap,
+    /// plus everything [`execute`](Self::execute) reports.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use lingo::LingoExecutor;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut executor = LingoExecutor::new();
+    /// executor.load_database("english.lingo")?;
+    ///
+    /// let (result, threshold) = executor.similar_auto("happy", 10)?;
+    /// println!("{} words at threshold {:.3}", result.nodes.len(), threshold);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn similar_auto(&mut self, word: &str, target_count: usize) -> Result<(QueryResult, f32)> {
+        validate_limit(target_count)?;
+        
+        // A lower threshold searches a wider radius and finds more
+        let (mut loose, mut strict) = (0.0f32, 1.0f32);
+        let mut best: Option<(QueryResult, f32)> = None;
+        for _ in 0..AUTO_THRESHOLD_PROBES {
+            let threshold = (loose + strict) / 2.0;
+            let query = QueryBuilder::find(word).similar_threshold(threshold).no_limit().compile();
+            let result = self.execute(&query)?;
+            let count = result.nodes.len();
+            
+            if count == target_count {
+                return Ok((result, threshold));
+            }
+            if count > target_count {
+                loose = threshold;
+            } else {
+                strict = threshold;
+            }
+            let closer = best.as_ref().is_none_or(|(best, _)| {
+                count.abs_diff(target_count) < best.nodes.len().abs_diff(target_count)
+            });
+            if closer {
+                best = Some((result, threshold));
+            }
+        }
+        best.ok_or_else(|| LingoError::Execution("No threshold probes were run".to_string()))
+    }
+    
+    /// Copy the side tables `query`'s instructions index into
+    fn load_query_tables(&mut self, query: &CompiledQuery) {
+        self.string_cache.clone_from(&query.string_cache);
+        self.connection_weights.clone_from(&query.connection_weights);
+        self.regions.clone_from(&query.regions);
+        self.node_lists.clone_from(&query.node_lists);
+        self.metrics.clone_from(&query.metrics);
+    }
+    
+    /// Run `query`'s bytecode on a scratch copy and return the raw set,
+    /// before default and result limits are applied
+    pub(super) fn execute_buffered(&self, query: &CompiledQuery) -> Result<NodeSet> {
+        let mut scratch = self.fresh_copy();
+        scratch.load_query_tables(query);
+        scratch.reset();
+        scratch.execute_bytecode(&query.bytecode, &self.limits)
+    }
+    
+    /// Execute `query` against the tables already loaded
+    fn run_loaded(&mut self, query: &CompiledQuery, limits: &ExecutionLimits) -> Result<QueryResult> {
         let start_time = Instant::now();
         
+        // Outcome fields are recorded once execution finishes; while the
+        // span is disabled, creating and recording it costs nothing
+        let span = info_span!(
+            "execute_query",
+            ops = query.bytecode.len(),
+            estimated_cost = query.estimated_cost,
+            instructions_executed = tracing::field::Empty,
+            result_count = tracing::field::Empty,
+            execution_time_us = tracing::field::Empty,
+        );
+        let _entered = span.enter();
+        
         info!(
             bytecode_length = query.bytecode.len(),
             string_cache_size = query.string_cache.len(),
@@ -521,14 +1147,42 @@ impl LingoExecutor {
         
         // Reset execution state
         self.reset();
-        
-        // Set string cache from query
-        self.string_cache = query.string_cache.clone();
+        self.queries_executed += 1;
         
         // Execute bytecode
-        let result = self.execute_bytecode(&query.bytecode)?;
+        let mut result = self.execute_bytecode(&query.bytecode, limits)?;
+        if let Some(default_limit) = self.effective_default_limit(query) {
+            if result.len() > default_limit {
+                debug!(
+                    result_count = result.len(),
+                    default_limit = default_limit,
+                    "Result cut to default limit"
+                );
+                result.truncate(default_limit);
+            }
+        }
+        if result.len() > limits.max_result_nodes() {
+            warn!(
+                result_count = result.len(),
+                max = limits.max_result_nodes(),
+                "Result truncated to execution limit"
+            );
+            result.truncate(limits.max_result_nodes());
+        }
+        
+        let scores = if self.similarity_scores.is_empty() {
+            Vec::new()
+        } else {
+            result.as_slice()
+                .iter()
+                .map(|id| self.similarity_scores.get(id).copied().unwrap_or(0.0))
+                .collect()
+        };
         
         let execution_time = start_time.elapsed();
+        span.record("instructions_executed", self.instructions_executed);
+        span.record("result_count", result.len());
+        span.record("execution_time_us", execution_time.as_micros() as u64);
         
         info!(
             duration_ms = execution_time.as_millis() as u64,
@@ -542,34 +1196,637 @@ impl LingoExecutor {
             execution_time,
             instructions_executed: self.instructions_executed,
             cache_hit: false,
+            scores,
         })
     }
     
-    /// Reset execution state
-    fn reset(&mut self) {
-        self.instruction_pointer = 0;
-        self.stack.clear();
-        self.flags = ExecutionFlags::default();
-        self.instructions_executed = 0;
-    }
-    
-    /// Execute bytecode instructions
-    fn execute_bytecode(&mut self, bytecode: &[SlangInstruction]) -> Result<NodeSet> {
-        trace!("Starting bytecode execution with {} instructions", bytecode.len());
-        
-        while self.instruction_pointer < bytecode.len() {
-            let instruction = &bytecode[self.instruction_pointer];
-            
-            trace!(
-                ip = self.instruction_pointer,
-                op = ?instruction.opcode,
-                "Executing instruction"
-            );
-            
-            // Execute instruction
-            self.execute_instruction(instruction)?;
-            
-            // Check for halt
+    /// Runs `query` step by step and records what each instruction did.
+    ///
+    /// For each instruction the trace holds its operation, the stack depth
+    /// before and after, and how many nodes it added to or removed from the
+    /// top set. Execution happens on a scratch copy of the executor, so
+    /// statistics and similarity scores here are untouched, and
+    /// [`execute`](Self::execute) pays nothing for tracing. Failures end
+    /// the trace with [`ExecutionTrace::error`] set instead of returning
+    /// an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use lingo::{LingoExecutor, QueryBuilder};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut executor = LingoExecutor::new();
+    /// executor.load_database("english.lingo")?;
+    ///
+    /// let query = QueryBuilder::find("run").similar().limit(5).compile();
+    /// println!("{}", executor.explain(&query));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn explain(&self, query: &CompiledQuery) -> ExecutionTrace {
+        let mut scratch = self.fresh_copy();
+        scratch.load_query_tables(query);
+        scratch.reset();
+        scratch.budget = self.limits.budget().map(Budget::new);
+        
+        // Follows the instruction pointer like execute_bytecode, so skipped
+        // branches don't appear in the trace
+        let mut trace = ExecutionTrace::default();
+        while scratch.instruction_pointer < query.bytecode.len() {
+            if trace.steps.len() > self.limits.max_instructions() {
+                trace.error = Some("Instruction limit exceeded".to_string());
+                break;
+            }
+            let index = scratch.instruction_pointer;
+            let instruction = &query.bytecode[index];
+            
+            let before = scratch.stack.last().map(|top| top.set.clone()).unwrap_or_default();
+            let stack_before = scratch.stack.len();
+            let start = Instant::now();
+            let outcome = scratch.execute_instruction(instruction);
+            let duration = start.elapsed();
+            
+            let after = scratch.stack.last().cloned().unwrap_or_default();
+            trace.steps.push(TraceStep {
+                index,
+                op: instruction.opcode,
+                stack_before,
+                stack_after: scratch.stack.len(),
+                nodes_added: after.as_slice().iter().filter(|id| !before.contains(id)).count(),
+                nodes_removed: before.iter().filter(|id| !after.set.contains(id)).count(),
+                nodes_after: after.len(),
+                duration,
+            });
+            
+            if let Err(error) = outcome {
+                trace.error = Some(error.to_string());
+                break;
+            }
+            if instruction.opcode == SlangOp::Halt {
+                break;
+            }
+            let produced = scratch.stack.last().map_or(0, NodeSet::len);
+            if let Some(Err(error)) = scratch.budget.as_mut().map(|budget| budget.charge(produced)) {
+                trace.error = Some(error.to_string());
+                break;
+            }
+            scratch.instruction_pointer += 1;
+        }
+        
+        if trace.error.is_none() {
+            let count = scratch.stack.last().map_or(0, NodeSet::len);
+            let default_limit = scratch.effective_default_limit(query).unwrap_or(usize::MAX);
+            trace.result_count = count.min(default_limit).min(self.limits.max_result_nodes());
+        }
+        trace
+    }
+    
+    /// Executes a query lazily, yielding node IDs as they are produced.
+    ///
+    /// Use this when a query may match many nodes but only the first few
+    /// are needed. The stream borrows the memory-mapped database, so no
+    /// result set is built up front. Similarity searches rank all candidates
+    /// around each seed before emitting any, so they buffer per seed; word
+    /// lookups and `spatial_box` scans stop as soon as the caller does.
+    /// Queries using operations without a lazy stage, such as layer
+    /// navigation or connection traversal, run in full first and their
+    /// results are streamed from the buffer. See [`ResultStream`] for details.
+    ///
+    /// Results match [`execute`](Self::execute): the same order, the
+    /// default limit unless the query sets one, and at most
+    /// `max_result_nodes` items. Similarity scores are not reported.
+    ///
+    /// # Errors
+    ///
+    /// - `LingoError::Database` - No database is loaded
+    /// - `LingoError::Execution` - The query is invalid
+    /// - `LingoError::BudgetExceeded` - A query that runs in full went over
+    ///   the execution budget
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use lingo::{LingoExecutor, QueryBuilder};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut executor = LingoExecutor::new();
+    /// executor.load_database("english.lingo")?;
+    ///
+    /// let query = QueryBuilder::find("run").similar().no_limit().compile();
+    /// let first: Vec<_> = executor.execute_iter(&query)?.take(5).collect();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_iter(&self, query: &CompiledQuery) -> Result<ResultStream<'_>> {
+        let db = self.database.as_ref()
+            .ok_or_else(|| LingoError::Database("No database loaded".to_string()))?;
+        let cap = self.effective_default_limit(query)
+            .unwrap_or(usize::MAX)
+            .min(self.limits.max_result_nodes());
+        ResultStream::new(self, db, query, cap)
+    }
+    
+    /// Estimates how many nodes `query` would return, without running it.
+    ///
+    /// This is an approximation for warning users about large result sets,
+    /// not a count. Seed lookups are resolved, similarity searches are
+    /// sized from octree leaf counts around each seed (which can overcount),
+    /// and steps that map one node to at most one node keep the running
+    /// estimate. Limits in the query and the executor's `max_result_nodes`
+    /// are applied. Returns 0 when no database is loaded.
+    pub fn estimate_result_size(&mut self, query: &CompiledQuery) -> usize {
+        use crate::core::bytecode::instruction_flags::HAS_LIMIT;
+        
+        if self.database.is_none() {
+            return 0;
+        }
+        self.ensure_spatial_index();
+        let (Some(db), Some(index)) = (&self.database, &self.spatial_index) else {
+            return 0;
+        };
+        
+        let mut estimate = 0usize;
+        // Positions of the current nodes while they're still known exactly
+        let mut seeds: Option<Vec<Coordinate3D>> = Some(Vec::new());
+        // Estimates of sets waiting for a combined branch to finish
+        let mut below: Vec<usize> = Vec::new();
+        let mut loaded = false;
+        
+        for instruction in &query.bytecode {
+            // A load on top of a current set starts a union or intersect branch
+            if matches!(
+                instruction.opcode,
+                SlangOp::LoadNode | SlangOp::LoadNodeId | SlangOp::LoadNodeIds | SlangOp::LoadPhrase | SlangOp::SpatialBox
+            ) {
+                if loaded {
+                    below.push(estimate);
+                }
+                loaded = true;
+            }
+            
+            match instruction.opcode {
+                SlangOp::LoadNode | SlangOp::LoadPhrase => {
+                    let positions: Vec<Coordinate3D> = query.string_cache
+                        .get(instruction.operand1 as usize)
+                        .map(|word| match instruction.opcode {
+                            SlangOp::LoadPhrase => Self::phrase_nodes(db, word),
+                            _ => Self::lookup_word(db, word, instruction.flags),
+                        })
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter_map(|id| db.get_node(id).ok().map(|node| node.position))
+                        .collect();
+                    estimate = positions.len();
+                    seeds = Some(positions);
+                }
+                SlangOp::LoadNodeId => {
+                    let position = db.get_node(NodeId(instruction.operand2)).ok().map(|node| node.position);
+                    estimate = position.is_some() as usize;
+                    seeds = Some(position.into_iter().collect());
+                }
+                SlangOp::LoadNodeIds => {
+                    let mut ids = NodeSet::new();
+                    if let Some(list) = query.node_lists.get(instruction.operand1 as usize) {
+                        ids.extend(list.iter().copied());
+                    }
+                    let positions: Vec<Coordinate3D> = ids.as_slice()
+                        .iter()
+                        .filter_map(|&id| db.get_node(id).ok().map(|node| node.position))
+                        .collect();
+                    estimate = positions.len();
+                    seeds = Some(positions);
+                }
+                SlangOp::FindSimilar => {
+                    let radius = 1.0 - (instruction.operand1 as f32) / 65535.0;
+                    let per_seed_limit = if instruction.flags & HAS_LIMIT != 0 {
+                        instruction.operand2 as usize
+                    } else {
+                        usize::MAX
+                    };
+                    // The octree counts Euclidean balls; other metrics can reach further
+                    let euclidean = instruction.operand3 == 0;
+                    if let Some(positions) = seeds.take() {
+                        estimate = positions.iter()
+                            .map(|&p| {
+                                let within = if euclidean {
+                                    index.estimate_within_radius(p, radius)
+                                } else {
+                                    db.node_count()
+                                };
+                                within.min(per_seed_limit)
+                            })
+                            .sum::<usize>()
+                            .min(db.node_count());
+                    }
+                }
+                SlangOp::Limit => {
+                    let limit = instruction.operand1 as usize;
+                    estimate = estimate.min(limit);
+                    if let Some(positions) = seeds.as_mut() {
+                        positions.truncate(limit);
+                    }
+                }
+                SlangOp::Offset => {
+                    let skipped = instruction.operand2 as usize;
+                    estimate = estimate.saturating_sub(skipped);
+                    if let Some(positions) = seeds.as_mut() {
+                        positions.drain(..skipped.min(positions.len()));
+                    }
+                }
+                SlangOp::SpatialBox => {
+                    estimate = query.regions
+                        .get(instruction.operand1 as usize)
+                        .map_or(0, |&bounds| index.find_within_box(bounds).len());
+                    seeds = None;
+                }
+                SlangOp::SpatialNearest => {
+                    let k = instruction.operand2 as usize;
+                    let seed_count = seeds.take().map_or(estimate, |positions| positions.len());
+                    estimate = seed_count.saturating_mul(k).min(db.node_count());
+                }
+                SlangOp::Union | SlangOp::Intersect | SlangOp::Difference => {
+                    let lower = below.pop().unwrap_or(0);
+                    estimate = match instruction.opcode {
+                        SlangOp::Union => lower.saturating_add(estimate).min(db.node_count()),
+                        SlangOp::Intersect => lower.min(estimate),
+                        _ => lower,
+                    };
+                    seeds = None;
+                }
+                SlangOp::Sort | SlangOp::Branch | SlangOp::Deduplicate | SlangOp::Nop | SlangOp::SpatialNeighbors => {}
+                SlangOp::Halt => break,
+                // Remaining steps filter the set or map it to related nodes;
+                // keep the current size as the guess
+                _ => seeds = None,
+            }
+        }
+        
+        if let Some(default_limit) = self.effective_default_limit(query) {
+            estimate = estimate.min(default_limit);
+        }
+        estimate.min(self.limits.max_result_nodes())
+    }
+    
+    /// Builds lazily constructed indices now so the first query is fast.
+    ///
+    /// Without this, the spatial index and the database's incoming-edge,
+    /// folded-word and phonetic indices are built by the first query that
+    /// needs each one. Calling it is optional and does nothing without a
+    /// database.
+    pub fn warmup(&mut self) {
+        let start = Instant::now();
+        self.ensure_spatial_index();
+        if let Some(db) = &self.database {
+            db.warm_indices();
+            info!(
+                duration_ms = start.elapsed().as_millis() as u64,
+                "Warmed executor indices"
+            );
+        }
+    }
+    
+    /// Returns how many lazy indices have been built for this executor.
+    ///
+    /// Counts spatial index builds plus the loaded database's own lazy
+    /// indices. Useful for checking that `warmup` left nothing to build.
+    pub fn index_builds(&self) -> usize {
+        let database_builds = self.database.as_ref().map_or(0, |db| db.index_builds());
+        self.spatial_index_builds + database_builds
+    }
+    
+    /// Sets the result limit for queries that don't specify one.
+    ///
+    /// Defaults to [`DEFAULT_RESULT_LIMIT`]. `None` disables the default
+    /// for every query; the security cap in [`ExecutionLimits`] still
+    /// applies either way.
+    pub fn set_default_limit(&mut self, limit: Option<usize>) {
+        self.default_limit = limit;
+    }
+    
+    /// Returns the result limit for queries that don't specify one.
+    pub fn default_limit(&self) -> Option<usize> {
+        self.default_limit
+    }
+    
+    /// The default limit that applies to `query`, if any
+    fn effective_default_limit(&self, query: &CompiledQuery) -> Option<usize> {
+        if query.unbounded || query.has_explicit_limit() {
+            None
+        } else {
+            self.default_limit
+        }
+    }
+    
+    /// Sets the caps used by `execute`.
+    pub fn set_limits(&mut self, limits: ExecutionLimits) {
+        self.limits = limits;
+    }
+    
+    /// Returns the caps used by `execute`.
+    pub fn limits(&self) -> &ExecutionLimits {
+        &self.limits
+    }
+    
+    /// Returns how many queries this executor has run.
+    ///
+    /// The count is cumulative and is not cleared between queries, which
+    /// makes it useful for checking how many lookups a higher-level
+    /// operation (such as morphological decomposition) issued.
+    pub fn queries_executed(&self) -> usize {
+        self.queries_executed
+    }
+    
+    /// Returns what `word` is built from and what it is a kind of.
+    ///
+    /// `below` follows composition edges (Derivation connections into a
+    /// lower layer, as written by `DatabaseBuilder::add_composed_word`) and
+    /// `above` follows Hypernymy connections. When several nodes share the
+    /// surface form, the Words-layer node is preferred.
+    ///
+    /// # Errors
+    ///
+    /// - `LingoError::Database` - No database is loaded
+    /// - `LingoError::WordNotFound` - `word` is not in the database
+    pub fn vertical_context(&self, word: &str) -> Result<VerticalContext> {
+        let db = self.database.as_ref()
+            .ok_or_else(|| LingoError::Database("No database loaded".to_string()))?;
+        
+        let self_node = Self::word_node(db, word)
+            .ok_or_else(|| LingoError::WordNotFound(word.to_string()))?;
+        let layer = db.get_node(self_node)?.layer as u8;
+        
+        let mut below = Vec::new();
+        let mut above = Vec::new();
+        for conn in db.get_node_connections(self_node)? {
+            let target = conn.target_node;
+            match conn.connection_type {
+                ConnectionType::Derivation if (db.get_node(target)?.layer as u8) < layer => {
+                    below.push(target);
+                }
+                ConnectionType::Hypernymy => above.push(target),
+                _ => {}
+            }
+        }
+        
+        Ok(VerticalContext { below, self_node, above })
+    }
+    
+    /// Completes the analogy "`a` is to `b` as `c` is to ?".
+    ///
+    /// The offset from `a` to `b` in coordinate space is added to `c`'s
+    /// position, and the `k` nodes nearest that point are returned with
+    /// their distance to it, nearest first. Equal distances are ordered by
+    /// node ID. Only nodes on `c`'s layer are answers, and every node
+    /// spelled `a`, `b` or `c` is left out, so the answer is never one of
+    /// the prompts. When a word has nodes on several layers, its
+    /// Words-layer node is used. The search runs on the spatial index,
+    /// which is built on first use.
+    ///
+    /// Returns an empty list if no database is loaded or any of the three
+    /// words is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use lingo::LingoExecutor;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut executor = LingoExecutor::new();
+    /// executor.load_database("english.lingo")?;
+    ///
+    /// // king : queen :: man : ?
+    /// for (node, distance) in executor.solve_analogy("king", "queen", "man", 3) {
+    ///     println!("{:?} at {:.3}", node, distance);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn solve_analogy(&mut self, a: &str, b: &str, c: &str, k: usize) -> Vec<(NodeId, f32)> {
+        self.ensure_spatial_index();
+        let (Some(db), Some(index)) = (self.database.as_deref(), self.spatial_index.as_deref()) else {
+            return Vec::new();
+        };
+        let node = |word| Self::word_node(db, word).and_then(|id| db.get_node(id).ok());
+        let (Some(na), Some(nb), Some(nc)) = (node(a), node(b), node(c)) else {
+            return Vec::new();
+        };
+        let target = nc.position + (nb.position - na.position);
+        let layer = nc.layer;
+        
+        let prompts: HashSet<NodeId> = [a, b, c]
+            .into_iter()
+            .flat_map(|word| db.find_nodes_by_word(word))
+            .collect();
+        let is_answer = |id: &NodeId| {
+            !prompts.contains(id) && db.get_node(*id).is_ok_and(|node| node.layer == layer)
+        };
+        
+        // Widen the search until enough nodes survive the filter, or
+        // every node has been seen
+        let mut wanted = k.saturating_add(prompts.len());
+        loop {
+            let found = index.k_nearest(target, wanted);
+            let exhausted = found.len() < wanted;
+            let answers: Vec<(NodeId, f32)> = found.into_iter()
+                .filter(|(id, _)| is_answer(id))
+                .take(k)
+                .collect();
+            if answers.len() == k || exhausted {
+                return answers;
+            }
+            wanted = wanted.saturating_mul(2);
+        }
+    }
+    
+    /// Finds words whose pronunciation ends with the same sounds as `word`'s.
+    ///
+    /// Pronunciations are the phoneme sequences recorded with
+    /// [`DatabaseBuilder::add_pronunciation`](crate::storage::DatabaseBuilder::add_pronunciation).
+    /// Each Words-layer node sharing at least the final phoneme is returned
+    /// with the length of the shared trailing run, longest first, equal
+    /// lengths by node ID, up to `k` of them. Other nodes spelled `word` are
+    /// left out.
+    ///
+    /// Returns an empty list if no database is loaded, `word` is unknown or
+    /// it has no recorded pronunciation.
+    pub fn rhymes_of(&self, word: &str, k: usize) -> Vec<(NodeId, usize)> {
+        let Some(db) = self.database.as_deref() else {
+            return Vec::new();
+        };
+        let Some(sounds) = Self::word_node(db, word).and_then(|id| db.pronunciation(id).ok()) else {
+            return Vec::new();
+        };
+        let Some(&last) = sounds.last() else {
+            return Vec::new();
+        };
+        
+        // Every word ending in the final sound has an edge into it
+        let mut candidates = db.get_incoming_connections(last).map(<[NodeId]>::to_vec).unwrap_or_default();
+        candidates.sort();
+        candidates.dedup();
+        let spelled: HashSet<NodeId> = db.find_nodes_by_word(word).into_iter().collect();
+        
+        let mut rhymes: Vec<(NodeId, usize)> = candidates.into_iter()
+            .filter(|id| !spelled.contains(id))
+            .filter(|&id| db.get_node(id).is_ok_and(|node| node.layer == Layer::Words))
+            .filter_map(|id| {
+                let other = db.pronunciation(id).ok()?;
+                let shared = sounds.iter().rev()
+                    .zip(other.iter().rev())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                (shared > 0).then_some((id, shared))
+            })
+            .collect();
+        // Stable, so equal lengths stay in ID order
+        rhymes.sort_by_key(|&(_, shared)| std::cmp::Reverse(shared));
+        rhymes.truncate(k);
+        rhymes
+    }
+    
+    /// Finds the phonemes articulated most like `symbol`.
+    ///
+    /// Phonemes are ranked by [`PhonemeFeatures::distance`](crate::core::PhonemeFeatures::distance), the number of
+    /// articulatory features set in one but not the other, rather than by
+    /// position: /b/ is one feature from /p/ (voicing) wherever the two
+    /// nodes sit. Equal distances are ordered by node ID. `symbol` may be
+    /// given with or without slashes ("/p/" or "p"). Phonemes with no
+    /// recorded features are skipped.
+    ///
+    /// Returns up to `k` phonemes with their distance, or an empty list if
+    /// no database is loaded or `symbol` isn't a phoneme with features.
+    pub fn similar_phonemes(&self, symbol: &str, k: usize) -> Vec<(NodeId, u32)> {
+        let Some(db) = self.database.as_deref() else {
+            return Vec::new();
+        };
+        let bare = symbol.trim_matches('/');
+        let features = [symbol.to_string(), bare.to_string(), format!("/{}/", bare)]
+            .iter()
+            .flat_map(|form| db.find_nodes_by_word(form))
+            .filter_map(|id| db.get_node(id).ok().map(|node| (id, node.phoneme_features())))
+            .find(|(_, features)| !features.is_empty());
+        let Some((origin, features)) = features else {
+            return Vec::new();
+        };
+        
+        let mut ranked: Vec<(NodeId, u32)> = db.nodes()
+            .iter()
+            .filter(|node| { node.id } != origin)
+            .filter_map(|node| {
+                let other = node.phoneme_features();
+                (!other.is_empty()).then(|| (node.id, features.distance(other)))
+            })
+            .collect();
+        // Stable, so equal distances stay in ID order
+        ranked.sort_by_key(|&(_, distance)| distance);
+        ranked.truncate(k);
+        ranked
+    }
+    
+    /// Looks up the surface form and properties of each node in `result`.
+    ///
+    /// A node whose word can't be read from the string table gets
+    /// `word: None` (`null` in JSON) instead of failing the batch; node IDs
+    /// that aren't in the database are left out.
+    ///
+    /// # Errors
+    ///
+    /// - `LingoError::Database` - No database is loaded
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use lingo::{LingoExecutor, QueryBuilder};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut executor = LingoExecutor::new();
+    /// executor.load_database("english.lingo")?;
+    ///
+    /// let result = executor.execute(&QueryBuilder::find("run").similar().compile())?;
+    /// let body = serde_json::to_string(&executor.resolve(&result)?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resolve(&self, result: &QueryResult) -> Result<ResolvedResult> {
+        let db = self.database.as_ref()
+            .ok_or_else(|| LingoError::Database("No database loaded".to_string()))?;
+        
+        let nodes = result.nodes_with_scores()
+            .filter_map(|(id, score)| {
+                let node = db.get_node(id).ok()?;
+                Some(ResolvedNode {
+                    id,
+                    word: db.get_node_word(id).ok().map(str::to_string),
+                    layer: node.layer,
+                    position: node.position,
+                    etymology: node.etymology_origin,
+                    morpheme_type: node.morpheme_type,
+                    score,
+                })
+            })
+            .collect();
+        
+        Ok(ResolvedResult {
+            nodes,
+            execution_time_us: result.execution_time.as_micros() as u64,
+            cache_hit: result.cache_hit,
+        })
+    }
+    
+    /// Reset execution state
+    fn reset(&mut self) {
+        self.instruction_pointer = 0;
+        self.stack.clear();
+        self.flags = ExecutionFlags::default();
+        self.similarity_scores.clear();
+        self.budget = None;
+        self.instructions_executed = 0;
+    }
+    
+    /// Clears the stack after a query goes over its budget, so the next
+    /// query starts clean
+    fn abandon(&mut self, error: LingoError) -> LingoError {
+        if let LingoError::BudgetExceeded { spent, budget } = error {
+            warn!(spent, budget, "Query budget exceeded");
+            self.stack.clear();
+        }
+        error
+    }
+    
+    /// Fails once a set the current instruction is building outgrows the budget
+    fn within_budget(budget: Option<Budget>, produced: usize) -> Result<()> {
+        budget.map_or(Ok(()), |budget| budget.check(produced))
+    }
+    
+    /// Caps a per-seed result count just past what the budget allows, so an
+    /// overshoot is still detected without fetching everything
+    fn budget_cap(budget: Option<Budget>, count: usize) -> usize {
+        budget.map_or(count, |budget| count.min(budget.room().saturating_add(1)))
+    }
+    
+    /// Execute bytecode instructions
+    fn execute_bytecode(&mut self, bytecode: &[SlangInstruction], limits: &ExecutionLimits) -> Result<NodeSet> {
+        trace!("Starting bytecode execution with {} instructions", bytecode.len());
+        self.budget = limits.budget().map(Budget::new);
+        
+        while self.instruction_pointer < bytecode.len() {
+            let instruction = &bytecode[self.instruction_pointer];
+            
+            // Execute instruction
+            if let Err(error) = self.execute_instruction(instruction) {
+                return Err(self.abandon(error));
+            }
+            
+            // Fields are only evaluated when debug events are enabled
+            debug!(
+                ip = self.instruction_pointer,
+                op = ?instruction.opcode,
+                nodes = self.stack.last().map_or(0, |top| top.len()),
+                "Executed instruction"
+            );
+            
+            // Check for halt
             if matches!(instruction.opcode, SlangOp::Halt) {
                 debug!("Halt instruction encountered");
                 break;
@@ -580,10 +1837,15 @@ impl LingoExecutor {
             self.instructions_executed += 1;
             
             // Safety check
-            if self.instructions_executed > 10000 {
+            if self.instructions_executed > limits.max_instructions() {
                 warn!("Instruction limit exceeded: {}", self.instructions_executed);
                 return Err(LingoError::Execution("Instruction limit exceeded".to_string()));
             }
+            let produced = self.stack.last().map_or(0, |top| top.len());
+            let charged = self.budget.as_mut().map_or(Ok(()), |budget| budget.charge(produced));
+            if let Err(error) = charged {
+                return Err(self.abandon(error));
+            }
         }
         
         // Return top of stack or empty set
@@ -592,6 +1854,16 @@ impl LingoExecutor {
     
     /// Execute a single instruction
     fn execute_instruction(&mut self, instruction: &SlangInstruction) -> Result<()> {
+        // Scores only describe the current set until a step replaces it
+        if !matches!(
+            instruction.opcode,
+            SlangOp::FindSimilar | SlangOp::Limit | SlangOp::Offset | SlangOp::Sort | SlangOp::Filter
+                | SlangOp::Branch | SlangOp::Deduplicate
+                | SlangOp::Exclude | SlangOp::ExcludeNear | SlangOp::Nop | SlangOp::Halt
+        ) {
+            self.similarity_scores.clear();
+        }
+        
         match instruction.opcode {
             SlangOp::LoadNode => {
                 let string_id = instruction.operand1 as usize;
@@ -603,7 +1875,7 @@ impl LingoExecutor {
                 
                 // Lookup node by word
                 if let Some(db) = &self.database {
-                    let node_ids = db.find_nodes_by_word(word);
+                    let node_ids = Self::lookup_word(db, word, instruction.flags);
                     if node_ids.is_empty() {
                         // No nodes found, push empty set
                         self.stack.push(NodeSet::new());
@@ -620,6 +1892,21 @@ impl LingoExecutor {
                 }
             }
             
+            SlangOp::LoadPhrase => {
+                let phrase = self.string_cache
+                    .get(instruction.operand1 as usize)
+                    .ok_or_else(|| LingoError::Execution("Invalid string ID".to_string()))?;
+                
+                let mut node_set = NodeSet::new();
+                if let Some(db) = &self.database {
+                    node_set.extend(Self::phrase_nodes(db, phrase));
+                } else {
+                    // No database, create test node
+                    node_set.push(NodeId(instruction.operand1 as u32 + 1));
+                }
+                self.stack.push(node_set);
+            }
+            
             SlangOp::LoadNodeId => {
                 let node_id = NodeId(instruction.operand2);
                 
@@ -635,6 +1922,24 @@ impl LingoExecutor {
                 self.stack.push(NodeSet::single(node_id));
             }
             
+            SlangOp::LoadNodeIds => {
+                let ids = self.node_lists
+                    .get(instruction.operand1 as usize)
+                    .ok_or_else(|| LingoError::Execution("Invalid node list ID".to_string()))?;
+                
+                if let Some(db) = &self.database {
+                    if let Some(missing) = ids.iter().find(|&&id| db.get_node(id).is_err()) {
+                        return Err(LingoError::Execution(
+                            format!("Node {} not found", missing.0)
+                        ));
+                    }
+                }
+                
+                let mut loaded = NodeSet::new();
+                loaded.extend(ids.iter().copied());
+                self.stack.push(loaded);
+            }
+            
             SlangOp::FindSimilar => {
                 let threshold = (instruction.operand1 as f32) / 65535.0;
                 let limit = if instruction.flags & crate::core::bytecode::instruction_flags::HAS_LIMIT != 0 {
@@ -642,24 +1947,56 @@ impl LingoExecutor {
                 } else {
                     None
                 };
+                let prefer_frequent = instruction.flags & crate::core::bytecode::instruction_flags::PREFER_FREQUENT != 0;
+                let metric = Self::similarity_metric(&self.metrics, instruction)?;
                 
                 let current = self.stack.pop().ok_or_else(|| {
                     LingoError::Execution("Empty stack for FindSimilar".to_string())
                 })?;
                 
                 let mut similar = NodeSet::new();
+                let mut scores: HashMap<NodeId, f32> = HashMap::new();
+                let budget = self.budget;
+                // Never fetch more per seed than the budget leaves room for
+                let candidate_cap = budget.map(|_| Self::budget_cap(budget, usize::MAX));
+                let seed_limit = match (limit, candidate_cap) {
+                    (Some(limit), Some(cap)) => Some(limit.min(cap)),
+                    (limit, cap) => limit.or(cap),
+                };
                 
                 if let Some(db) = &self.database {
                     // Find similar nodes for each node in current set
                     for node_id in current.as_slice() {
                         if let Ok(node) = db.get_node(*node_id) {
                             let radius = 1.0 - threshold; // Convert similarity to distance
-                            let similar_ids = db.find_similar_nodes(
-                                node.position,
-                                radius,
-                                limit
-                            );
+                            let similar_ids = if prefer_frequent {
+                                // Rerank the full candidate list so ties at the cutoff aren't lost
+                                let similar_ids = db.find_similar_nodes_by(node.position, radius, candidate_cap, metric);
+                                let mut ranked = Self::prefer_frequent(db, node.position, similar_ids, metric);
+                                if let Some(limit) = limit {
+                                    ranked.truncate(limit);
+                                }
+                                ranked
+                            } else {
+                                db.find_similar_nodes_by(
+                                    node.position,
+                                    radius,
+                                    seed_limit,
+                                    metric
+                                )
+                            };
+                            
+                            // Keep each node's best similarity across seeds
+                            let seed_position = node.position;
+                            for &id in &similar_ids {
+                                if let Ok(found) = db.get_node(id) {
+                                    let similarity = 1.0 - metric.distance(seed_position, found.position);
+                                    let best = scores.entry(id).or_insert(similarity);
+                                    *best = best.max(similarity);
+                                }
+                            }
                             similar.extend(similar_ids);
+                            Self::within_budget(budget, similar.len().min(limit.unwrap_or(usize::MAX)))?;
                         }
                     }
                     
@@ -671,6 +2008,7 @@ impl LingoExecutor {
                     similar = current;
                 }
                 
+                self.similarity_scores = scores;
                 self.stack.push(similar);
             }
             
@@ -681,6 +2019,7 @@ impl LingoExecutor {
                 })?;
                 
                 let mut parents = NodeSet::new();
+                let budget = self.budget;
                 
                 if let Some(db) = &self.database {
                     // Find parent nodes using vertical index
@@ -695,6 +2034,7 @@ impl LingoExecutor {
                                 }
                             }
                         }
+                        Self::within_budget(budget, parents.len())?;
                     }
                 } else {
                     // Test implementation
@@ -725,6 +2065,28 @@ impl LingoExecutor {
                 self.stack.push(children);
             }
             
+            SlangOp::LayerSet => {
+                let layer = instruction.operand1 as u8;
+                let current = self.stack.pop().ok_or_else(|| {
+                    LingoError::Execution("Empty stack for LayerSet".to_string())
+                })?;
+                
+                if let Some(db) = &self.database {
+                    let mut in_layer = NodeSet::new();
+                    for node_id in current.as_slice() {
+                        if let Ok(node) = db.get_node(*node_id) {
+                            if node.layer as u8 == layer {
+                                in_layer.push(*node_id);
+                            }
+                        }
+                    }
+                    self.stack.push(in_layer);
+                } else {
+                    // No database, layer membership is unknown
+                    self.stack.push(current);
+                }
+            }
+            
             SlangOp::FollowConnection => {
                 let rank = instruction.operand1 as usize;
                 let current = self.stack.pop().ok_or_else(|| {
@@ -735,16 +2097,15 @@ impl LingoExecutor {
                 
                 if let Some(db) = &self.database {
                     for node_id in current.as_slice() {
-                        if let Ok(connections) = db.get_node_connections(*node_id) {
-                            // Sort by strength and get Nth connection
-                            let mut sorted_conns: Vec<_> = connections.iter()
-                                .map(|c| (c, c.strength))
-                                .collect();
-                            sorted_conns.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-                            
-                            if rank < sorted_conns.len() {
-                                connected.push(sorted_conns[rank].0.target_node);
-                            }
+                        let connections = Self::traversable_connections(db, *node_id);
+                        // Sort by strength and get Nth connection
+                        let mut sorted_conns: Vec<_> = connections.iter()
+                            .map(|c| (c, c.strength))
+                            .collect();
+                        sorted_conns.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+                        
+                        if rank < sorted_conns.len() {
+                            connected.push(sorted_conns[rank].0.target_node);
                         }
                     }
                 }
@@ -752,20 +2113,188 @@ impl LingoExecutor {
                 self.stack.push(connected);
             }
             
-            SlangOp::SpatialNeighbors => {
-                let radius = f32::from_bits(instruction.operand2);
-                let layer_mask = instruction.operand3 as u8;
-                
+            SlangOp::FollowConnectionType => {
+                let connection_type = ConnectionType::from_u8(instruction.operand1 as u8)
+                    .ok_or_else(|| LingoError::Execution("Invalid connection type".to_string()))?;
                 let current = self.stack.pop().ok_or_else(|| {
-                    LingoError::Execution("Empty stack for SpatialNeighbors".to_string())
+                    LingoError::Execution("Empty stack for FollowConnectionType".to_string())
                 })?;
                 
-                // In real implementation, use spatial index
-                // For now, return current nodes
-                self.stack.push(current);
+                let mut connected = NodeSet::new();
+                let budget = self.budget;
+                
+                if let Some(db) = &self.database {
+                    for node_id in current.as_slice() {
+                        // Every edge of the type, strongest first
+                        let mut matching: Vec<_> = Self::traversable_connections(db, *node_id).iter()
+                            .filter(|c| c.connection_type == connection_type)
+                            .map(|c| (c.target_node, c.strength))
+                            .collect();
+                        matching.sort_by_key(|&(_, strength)| std::cmp::Reverse(strength));
+                        connected.extend(matching.into_iter().map(|(target, _)| target));
+                        Self::within_budget(budget, connected.len())?;
+                    }
+                }
+                
+                self.stack.push(connected);
             }
             
-            SlangOp::Limit => {
+            SlangOp::FollowWhere => {
+                let connection_type = match instruction.operand1 {
+                    ANY_CONNECTION_TYPE => None,
+                    value => Some(ConnectionType::from_u8(value as u8)
+                        .ok_or_else(|| LingoError::Execution("Invalid connection type".to_string()))?),
+                };
+                let min_strength = instruction.operand2 as u16;
+                let max = instruction.operand3 as usize;
+                let current = self.stack.pop().ok_or_else(|| {
+                    LingoError::Execution("Empty stack for FollowWhere".to_string())
+                })?;
+                
+                let mut connected = NodeSet::new();
+                let budget = self.budget;
+                
+                if let Some(db) = &self.database {
+                    for node_id in current.as_slice() {
+                        // Qualifying edges, strongest first; NodeSet keeps
+                        // a target reached twice at its first position
+                        let mut matching: Vec<_> = Self::traversable_connections(db, *node_id).iter()
+                            .filter(|c| connection_type.is_none_or(|t| c.connection_type == t))
+                            .filter(|c| c.strength >= min_strength)
+                            .map(|c| (c.target_node, c.strength))
+                            .collect();
+                        matching.sort_by_key(|&(_, strength)| std::cmp::Reverse(strength));
+                        connected.extend(matching.into_iter().take(max).map(|(target, _)| target));
+                        Self::within_budget(budget, connected.len())?;
+                    }
+                }
+                
+                self.stack.push(connected);
+            }
+            
+            SlangOp::SpatialBox => {
+                let bounds = *self.regions
+                    .get(instruction.operand1 as usize)
+                    .ok_or_else(|| LingoError::Execution("Invalid region ID".to_string()))?;
+                
+                self.ensure_spatial_index();
+                let mut inside = NodeSet::new();
+                if let Some(index) = &self.spatial_index {
+                    let mut ids = index.find_within_box_limited(bounds, Self::budget_cap(self.budget, usize::MAX));
+                    Self::within_budget(self.budget, ids.len())?;
+                    ids.sort();
+                    inside.extend(ids);
+                }
+                
+                // A new set on top of the stack, independent of earlier loads
+                self.stack.push(inside);
+            }
+            
+            SlangOp::SpatialNearest => {
+                // k comes from the caller, so never ask for more nodes than exist
+                let node_count = self.database.as_ref().map_or(0, |db| db.node_count());
+                let budget = self.budget;
+                let k = Self::budget_cap(budget, (instruction.operand2 as usize).min(node_count));
+                let current = self.stack.pop().ok_or_else(|| {
+                    LingoError::Execution("Empty stack for SpatialNearest".to_string())
+                })?;
+                
+                self.ensure_spatial_index();
+                let mut nearest = NodeSet::new();
+                if let (Some(db), Some(index)) = (&self.database, &self.spatial_index) {
+                    let seeds: HashSet<NodeId> = current.as_slice().iter().copied().collect();
+                    for node_id in current.as_slice() {
+                        if let Ok(node) = db.get_node(*node_id) {
+                            // Ask for extra so the seeds themselves can be dropped
+                            let found = index.k_nearest(node.position, k.saturating_add(seeds.len()));
+                            nearest.extend(
+                                found.into_iter()
+                                    .map(|(id, _)| id)
+                                    .filter(|id| !seeds.contains(id))
+                                    .take(k)
+                            );
+                            Self::within_budget(budget, nearest.len())?;
+                        }
+                    }
+                }
+                
+                self.stack.push(nearest);
+            }
+            
+            SlangOp::FollowWeighted => {
+                let profile = self.connection_weights
+                    .get(instruction.operand1 as usize)
+                    .ok_or_else(|| LingoError::Execution("Invalid weight profile ID".to_string()))?;
+                let current = self.stack.pop().ok_or_else(|| {
+                    LingoError::Execution("Empty stack for FollowWeighted".to_string())
+                })?;
+                
+                let mut connected = NodeSet::new();
+                
+                if let Some(db) = &self.database {
+                    for node_id in current.as_slice() {
+                        // First edge wins ties, matching FollowConnection's stable sort
+                        let mut best: Option<(NodeId, f32)> = None;
+                        for conn in Self::traversable_connections(db, *node_id).iter() {
+                            let weight = profile.get(&conn.connection_type).copied().unwrap_or(1.0);
+                            let score = conn.strength_normalized() * weight;
+                            if best.is_none_or(|(_, s)| score > s) {
+                                best = Some((conn.target_node, score));
+                            }
+                        }
+                        if let Some((target, _)) = best {
+                            connected.push(target);
+                        }
+                    }
+                }
+                
+                self.stack.push(connected);
+            }
+            
+            SlangOp::SpatialNeighbors => {
+                let radius = f32::from_bits(instruction.operand2);
+                let layer_mask = instruction.operand3 as u8;
+                
+                let current = self.stack.pop().ok_or_else(|| {
+                    LingoError::Execution("Empty stack for SpatialNeighbors".to_string())
+                })?;
+                
+                // In real implementation, use spatial index
+                // For now, return current nodes
+                self.stack.push(current);
+            }
+            
+            SlangOp::SpatialReflect => {
+                let fixed = |v: u32| (v & 0xFFFF) as f32 / 65535.0;
+                let center = Coordinate3D::new(
+                    fixed(instruction.operand1 as u32),
+                    fixed(instruction.operand2),
+                    fixed(instruction.operand2 >> 16),
+                );
+                
+                let current = self.stack.pop().ok_or_else(|| {
+                    LingoError::Execution("Empty stack for SpatialReflect".to_string())
+                })?;
+                
+                self.ensure_spatial_index();
+                let mut reflected = NodeSet::new();
+                
+                if let (Some(db), Some(index)) = (&self.database, &self.spatial_index) {
+                    for node_id in current.as_slice() {
+                        if let Ok(node) = db.get_node(*node_id) {
+                            let position = node.position;
+                            let target = (center * 2.0 - position).clamp();
+                            if let Some(nearest) = Self::nearest_node(index, target, *node_id) {
+                                reflected.push(nearest);
+                            }
+                        }
+                    }
+                }
+                
+                self.stack.push(reflected);
+            }
+            
+            SlangOp::Limit => {
                 let limit = instruction.operand1 as usize;
                 if let Some(mut current) = self.stack.pop() {
                     current.truncate(limit);
@@ -773,6 +2302,50 @@ impl LingoExecutor {
                 }
             }
             
+            SlangOp::Filter => {
+                let keep = Self::node_filter(instruction)?;
+                if let (Some(db), Some(current)) = (&self.database, self.stack.last_mut()) {
+                    current.retain(|id| db.get_node(id).is_ok_and(&keep));
+                }
+            }
+            
+            SlangOp::Sort => {
+                let by_id = instruction.operand1 == sort_keys::NODE_ID
+                    && instruction.flags & crate::core::bytecode::instruction_flags::INVERSE == 0;
+                if let Some(current) = self.stack.last_mut() {
+                    if by_id {
+                        current.sort_by_id();
+                    } else {
+                        let mut ids = std::mem::take(current).into_vec();
+                        Self::sort_nodes(self.database.as_deref(), &mut ids, instruction, &self.similarity_scores)?;
+                        current.extend(ids);
+                    }
+                }
+            }
+            
+            SlangOp::Offset => {
+                if let Some(current) = self.stack.last_mut() {
+                    current.skip(instruction.operand2 as usize);
+                }
+            }
+            
+            SlangOp::Union | SlangOp::Intersect | SlangOp::Difference => {
+                let other = self.stack.pop().unwrap_or_default();
+                let mut current = self.stack.pop().unwrap_or_default();
+                match instruction.opcode {
+                    SlangOp::Union => current.extend(other.into_vec()),
+                    SlangOp::Intersect => current.retain(|id| other.contains(id)),
+                    _ => current.retain(|id| !other.contains(id)),
+                }
+                self.stack.push(current);
+            }
+            
+            SlangOp::Branch => {
+                if self.stack.last().is_none_or(NodeSet::is_empty) {
+                    self.instruction_pointer += instruction.operand2 as usize;
+                }
+            }
+            
             SlangOp::Deduplicate => {
                 // Already handled by NodeSet
                 if let Some(current) = self.stack.pop() {
@@ -780,6 +2353,19 @@ impl LingoExecutor {
                 }
             }
             
+            SlangOp::Exclude | SlangOp::ExcludeNear => {
+                let excluded = Self::exclusion(
+                    self.database.as_deref(),
+                    &self.string_cache,
+                    &self.node_lists,
+                    instruction,
+                )?;
+                // Nothing to remove from an empty stack
+                if let Some(current) = self.stack.last_mut() {
+                    current.retain(|id| !excluded(id));
+                }
+            }
+            
             SlangOp::Push => {
                 // Push current to register
                 let reg = (instruction.operand1 as usize).min(15);
@@ -814,6 +2400,334 @@ impl LingoExecutor {
         Ok(())
     }
     
+    /// Build the spatial index over the loaded database if it doesn't exist yet
+    fn ensure_spatial_index(&mut self) {
+        if self.spatial_index.is_some() {
+            return;
+        }
+        if let Some(db) = &self.database {
+            let mut builder = OctreeBuilder::new();
+            for node in db.nodes() {
+                builder.add_node(node.id, node.position);
+            }
+            self.spatial_index = Some(Arc::new(builder.build()));
+            self.spatial_index_builds += 1;
+            debug!(nodes = db.node_count(), "Built spatial index");
+        }
+    }
+    
+    /// Reorder distance-sorted candidates so near-ties favour frequent words.
+    ///
+    /// Candidates within `FREQUENCY_TIE_TOLERANCE` of the first distance in
+    /// their run form a tie group, sorted by ascending `frequency_rank`.
+    pub(super) fn prefer_frequent(
+        db: &Database,
+        origin: Coordinate3D,
+        ids: Vec<NodeId>,
+        metric: SimilarityMetric,
+    ) -> Vec<NodeId> {
+        let mut scored: Vec<(NodeId, f32, u32)> = ids.into_iter()
+            .filter_map(|id| {
+                let node = db.get_node(id).ok()?;
+                Some((id, metric.distance(origin, node.position), node.frequency_rank))
+            })
+            .collect();
+        
+        let mut start = 0;
+        while start < scored.len() {
+            let group_distance = scored[start].1;
+            let end = scored[start..].iter()
+                .position(|&(_, d, _)| d - group_distance > FREQUENCY_TIE_TOLERANCE)
+                .map_or(scored.len(), |offset| start + offset);
+            scored[start..end].sort_by_key(|&(_, _, rank)| rank);
+            start = end;
+        }
+        
+        scored.into_iter().map(|(id, _, _)| id).collect()
+    }
+    
+    /// Reorder `ids` by a `Sort` instruction's key, breaking ties by ID.
+    ///
+    /// `scores` holds the similarities of the last `FindSimilar`, used by
+    /// the distance key. Without a database only ID order is known, so the
+    /// other keys leave `ids` as they are.
+    pub(super) fn sort_nodes(
+        db: Option<&Database>,
+        ids: &mut [NodeId],
+        instruction: &SlangInstruction,
+        scores: &HashMap<NodeId, f32>,
+    ) -> Result<()> {
+        let key = instruction.operand1;
+        if key > sort_keys::CONNECTION_STRENGTH {
+            return Err(LingoError::Execution(format!("Unknown sort key {}", key)));
+        }
+        let descending = instruction.flags & crate::core::bytecode::instruction_flags::INVERSE != 0;
+        let direction = |ordering: std::cmp::Ordering| if descending { ordering.reverse() } else { ordering };
+        
+        if key == sort_keys::NODE_ID {
+            ids.sort_unstable_by(|a, b| direction(a.cmp(b)));
+            return Ok(());
+        }
+        let Some(db) = db else {
+            return Ok(());
+        };
+        
+        let mut keyed: Vec<(NodeId, f64, &str)> = Vec::with_capacity(ids.len());
+        for &id in ids.iter() {
+            let node = db.get_node(id)?;
+            let (value, text) = match key {
+                sort_keys::DISTANCE => (scores.get(&id).map_or(f64::INFINITY, |&s| 1.0 - s as f64), ""),
+                // Rank 1 is the most frequent word
+                sort_keys::FREQUENCY => (-({ node.frequency_rank } as f64), ""),
+                sort_keys::LAYER => (node.layer as u8 as f64, ""),
+                sort_keys::ETYMOLOGY => (node.etymology_origin as u8 as f64, ""),
+                sort_keys::ALPHABETICAL => (0.0, db.get_node_word(id)?),
+                _ => {
+                    let strongest = db.get_node_connections(id)?
+                        .iter()
+                        .map(|connection| connection.strength)
+                        .max()
+                        .unwrap_or(0);
+                    (strongest as f64, "")
+                }
+            };
+            keyed.push((id, value, text));
+        }
+        
+        keyed.sort_by(|a, b| {
+            direction(a.1.total_cmp(&b.1).then_with(|| a.2.cmp(b.2))).then(a.0.cmp(&b.0))
+        });
+        for (slot, (id, _, _)) in ids.iter_mut().zip(keyed) {
+            *slot = id;
+        }
+        Ok(())
+    }
+    
+    /// The metric a `FindSimilar` instruction measures distance with.
+    ///
+    /// operand3 is 0 for Euclidean, else 1 + an index into `metrics`.
+    pub(super) fn similarity_metric(
+        metrics: &[SimilarityMetric],
+        instruction: &SlangInstruction,
+    ) -> Result<SimilarityMetric> {
+        match instruction.operand3 {
+            0 => Ok(SimilarityMetric::Euclidean),
+            id => metrics
+                .get(id as usize - 1)
+                .copied()
+                .ok_or_else(|| LingoError::Execution("Invalid similarity metric ID".to_string())),
+        }
+    }
+    
+    /// The node predicate a `Filter` instruction keeps nodes by
+    pub(super) fn node_filter(instruction: &SlangInstruction) -> Result<impl Fn(&LinguisticNode) -> bool> {
+        let argument = instruction.operand2;
+        let keep: Box<dyn Fn(&LinguisticNode) -> bool> = match instruction.operand1 {
+            filter_kinds::LAYERS => Box::new(move |node| argument & (1 << node.layer as u8) != 0),
+            filter_kinds::FREQUENCY_RANK_BELOW => Box::new(move |node| { node.frequency_rank } < argument),
+            filter_kinds::PRODUCTIVITY_ABOVE => {
+                let threshold = f32::from_bits(argument);
+                Box::new(move |node| node.productivity() > threshold)
+            }
+            filter_kinds::ETYMOLOGY => Box::new(move |node| node.etymology_origin as u32 == argument),
+            other => return Err(LingoError::Execution(format!("Unknown filter {}", other))),
+        };
+        Ok(keep)
+    }
+    
+    /// Decide which nodes an `Exclude` or `ExcludeNear` instruction removes.
+    ///
+    /// The returned predicate is true for nodes to drop. `Exclude` matches
+    /// by identity, against a word's exact-match nodes or a node list;
+    /// `ExcludeNear` drops anything within the radius of the word's nodes.
+    /// Without a database, only node lists can exclude anything.
+    pub(super) fn exclusion<'a>(
+        db: Option<&'a Database>,
+        string_cache: &[String],
+        node_lists: &[Vec<NodeId>],
+        instruction: &SlangInstruction,
+    ) -> Result<Box<dyn Fn(NodeId) -> bool + 'a>> {
+        use crate::core::bytecode::instruction_flags::NODE_LIST;
+        
+        let index = instruction.operand1 as usize;
+        if instruction.flags & NODE_LIST != 0 {
+            let ids: HashSet<NodeId> = node_lists
+                .get(index)
+                .ok_or_else(|| LingoError::Execution("Invalid node list ID".to_string()))?
+                .iter()
+                .copied()
+                .collect();
+            return Ok(Box::new(move |id| ids.contains(&id)));
+        }
+        
+        let word = string_cache
+            .get(index)
+            .ok_or_else(|| LingoError::Execution("Invalid string ID".to_string()))?;
+        let Some(db) = db else {
+            return Ok(Box::new(|_| false));
+        };
+        let anchors = Self::lookup_word(db, word, 0);
+        
+        if instruction.opcode == SlangOp::ExcludeNear {
+            let radius = f32::from_bits(instruction.operand2);
+            let positions: Vec<Coordinate3D> = anchors.iter()
+                .filter_map(|&id| db.get_node(id).ok().map(|node| node.position))
+                .collect();
+            return Ok(Box::new(move |id| {
+                db.get_node(id).is_ok_and(|node| {
+                    let position = node.position;
+                    positions.iter().any(|anchor| anchor.distance_to(position) <= radius)
+                })
+            }));
+        }
+        
+        let anchors: HashSet<NodeId> = anchors.into_iter().collect();
+        Ok(Box::new(move |id| anchors.contains(&id)))
+    }
+    
+    /// The node for `word`, preferring its Words-layer node.
+    fn word_node(db: &Database, word: &str) -> Option<NodeId> {
+        let candidates = db.find_nodes_by_word(word);
+        candidates.iter()
+            .copied()
+            .find(|id| db.get_node(*id).is_ok_and(|n| n.layer as u8 == Layer::Words as u8))
+            .or_else(|| candidates.first().copied())
+    }
+    
+    /// The edges a traversal can take from `node_id`.
+    ///
+    /// The node's own edges come first. A bidirectional edge stored only on
+    /// the other end, as `SymmetricConnectionMode::StoreOnce` does, follows
+    /// them with its target pointing back at that source. An edge stored in
+    /// both directions is not repeated. The reversed edge's transformation
+    /// vector is negated so it points from `node_id` to the source.
+    ///
+    /// Only files built with `StoreOnce` are searched for such edges;
+    /// for every other file this borrows the node's own edges.
+    fn traversable_connections(db: &Database, node_id: NodeId) -> Cow<'_, [OrthogonalConnection]> {
+        let own = db.get_node_connections(node_id).unwrap_or_default();
+        // Only StoreOnce files leave edges on the other end
+        if !db.header().format_flags.contains(FileFormatFlags::SYMMETRIC_STORED_ONCE) {
+            return Cow::Borrowed(own);
+        }
+        
+        let mut connections = own.to_vec();
+        let stored: HashSet<(NodeId, ConnectionType)> = own.iter()
+            .map(|c| (c.target_node, c.connection_type))
+            .collect();
+        
+        let mut sources = db.get_incoming_connections(node_id).map(<[NodeId]>::to_vec).unwrap_or_default();
+        sources.sort();
+        sources.dedup();
+        for source in sources {
+            let Ok(theirs) = db.get_node_connections(source) else { continue };
+            for conn in theirs {
+                let connection_type = conn.connection_type;
+                if { conn.target_node } == node_id
+                    && connection_type.is_bidirectional()
+                    && !stored.contains(&(source, connection_type))
+                {
+                    let mut reverse = *conn;
+                    reverse.target_node = source;
+                    reverse.transformation_vector = { conn.transformation_vector }.reversed();
+                    connections.push(reverse);
+                }
+            }
+        }
+        Cow::Owned(connections)
+    }
+    
+    /// Resolve a LoadNode word according to its instruction flags.
+    pub(super) fn lookup_word(db: &MemoryMappedDatabase, word: &str, flags: u8) -> Vec<NodeId> {
+        use crate::core::bytecode::instruction_flags::{CASE_INSENSITIVE, FOLD_ACCENTS, PHONETIC};
+        
+        if flags & PHONETIC != 0 {
+            db.find_nodes_by_sound(word)
+        } else if flags & FOLD_ACCENTS != 0 && flags & CASE_INSENSITIVE != 0 {
+            db.find_normalized(word).into_iter().collect()
+        } else if flags & FOLD_ACCENTS != 0 {
+            db.find_nodes_by_folded_word(word)
+        } else {
+            db.find_nodes_by_word(word)
+        }
+    }
+    
+    /// Nodes for a phrase, as described on
+    /// [`QueryBuilder::find_phrase`](crate::query::QueryBuilder::find_phrase).
+    pub(super) fn phrase_nodes(db: &MemoryMappedDatabase, phrase: &str) -> Vec<NodeId> {
+        let tokens = phrase_tokens(phrase);
+        if tokens.is_empty() {
+            return Vec::new();
+        }
+        
+        let phrase_nodes: Vec<NodeId> = db.find_nodes_by_folded_word(&tokens.join(" "))
+            .into_iter()
+            .filter(|&id| db.get_node(id).is_ok_and(|node| node.layer as u8 == Layer::Phrases as u8))
+            .collect();
+        if !phrase_nodes.is_empty() {
+            return phrase_nodes;
+        }
+        
+        let content: Vec<&String> = tokens.iter().filter(|token| !is_stopword(token)).collect();
+        let words = if content.is_empty() { tokens.iter().collect() } else { content };
+        if let [word] = words.as_slice() {
+            return db.find_nodes_by_folded_word(word);
+        }
+        
+        // Keep the first word's neighborhood, in order, narrowed by the rest
+        let mut shared: Option<NodeSet> = None;
+        for word in words {
+            let mut neighborhood = NodeSet::new();
+            for id in db.find_nodes_by_folded_word(word) {
+                neighborhood.push(id);
+                if let Ok(connections) = db.get_node_connections(id) {
+                    neighborhood.extend(connections.iter().map(|c| c.target_node));
+                }
+                if let Ok(sources) = db.get_incoming_connections(id) {
+                    neighborhood.extend(sources.iter().copied());
+                }
+            }
+            shared = Some(match shared {
+                None => neighborhood,
+                Some(mut shared) => {
+                    shared.retain(|id| neighborhood.contains(id));
+                    shared
+                }
+            });
+            if shared.as_ref().is_some_and(NodeSet::is_empty) {
+                break;
+            }
+        }
+        shared.map(NodeSet::into_vec).unwrap_or_default()
+    }
+    
+    /// Find the node closest to `target`, ignoring `exclude`.
+    ///
+    /// The search radius doubles until a candidate is found.
+    fn nearest_node(
+        index: &SpatialIndex,
+        target: Coordinate3D,
+        exclude: NodeId,
+    ) -> Option<NodeId> {
+        let mut radius = 0.05;
+        loop {
+            let nearest = index.within_radius_with_pos(target, radius)
+                .into_iter()
+                .filter(|&(id, _)| id != exclude)
+                .map(|(id, position)| (id, target.distance_squared_to(position)))
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+            
+            if let Some((id, _)) = nearest {
+                return Some(id);
+            }
+            // The unit cube diagonal is ~1.73, so one more doubling covers everything
+            if radius > 1.8 {
+                return None;
+            }
+            radius *= 2.0;
+        }
+    }
+    
     /// Add string to cache (for testing)
     pub fn add_string(&mut self, s: String) -> u16 {
         let id = self.string_cache.len() as u16;
@@ -957,6 +2871,43 @@ mod tests {
         assert_eq!(executor.stack[0].as_slice()[0], NodeId(42));
     }
     
+    #[test]
+    fn test_execute_layer_set_without_database() {
+        use crate::core::Layer;
+        
+        let mut executor = LingoExecutor::new();
+        executor.stack.push(NodeSet::single(NodeId(7)));
+        
+        let inst = SlangInstruction::with_operand1(SlangOp::LayerSet, Layer::Letters as u16);
+        executor.execute_instruction(&inst).unwrap();
+        
+        // Without database, layer membership is unknown so the set passes through
+        assert_eq!(executor.stack[0].as_slice(), &[NodeId(7)]);
+    }
+    
+    #[test]
+    fn test_execute_layer_set_filters_by_layer() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use tempfile::TempDir;
+        
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("layers.lingo");
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("q", Layer::Letters, Coordinate3D::new(0.2, 0.4, Layer::Letters.z_center())).unwrap();
+        builder.add_node("re", Layer::Morphemes, Coordinate3D::new(0.4, 0.3, Layer::Morphemes.z_center())).unwrap();
+        builder.build(&path).unwrap();
+        
+        let mut executor = LingoExecutor::new();
+        executor.load_database(&path).unwrap();
+        
+        let letters = executor.execute(&QueryBuilder::find("q").layer(Layer::Letters).compile()).unwrap();
+        assert_eq!(letters.nodes.len(), 1);
+        
+        let morphemes = executor.execute(&QueryBuilder::find("q").layer(Layer::Morphemes).compile()).unwrap();
+        assert!(morphemes.nodes.is_empty());
+    }
+    
     #[test]
     fn test_execute_find_similar_empty_stack() {
         let mut executor = LingoExecutor::new();
@@ -1006,6 +2957,1513 @@ mod tests {
         assert_eq!(executor.stack[0].as_slice()[0], NodeId(50));
     }
     
+    #[test]
+    fn test_execute_reflect_through_layer_center() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let center = Coordinate3D::new(0.5, 0.5, Layer::Words.z_center());
+        
+        let mut builder = DatabaseBuilder::new();
+        let hot_pos = Coordinate3D::new(0.2, 0.3, 0.5);
+        builder.add_node("hot", Layer::Words, hot_pos).unwrap();
+        builder.add_node("warm", Layer::Words, Coordinate3D::new(0.25, 0.32, 0.5)).unwrap();
+        builder.add_node("tepid", Layer::Words, center).unwrap();
+        let cold = builder.add_node("cold", Layer::Words, center * 2.0 - hot_pos).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let query = QueryBuilder::find("hot").reflect_through(center).compile();
+        let result = executor.execute(&query).unwrap();
+        
+        assert_eq!(result.nodes.as_slice(), &[cold]);
+    }
+    
+    #[test]
+    fn test_execution_limits_cap_results() {
+        use crate::core::Layer;
+        use crate::security::MAX_RESULT_NODES;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let total = MAX_RESULT_NODES + 50;
+        
+        let mut builder = DatabaseBuilder::new();
+        for i in 0..total {
+            builder.add_node(&format!("w{}", i), Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5)).unwrap();
+        }
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        // Skip the default limit so only the security cap applies
+        let query = QueryBuilder::find("w0").similar_threshold(0.5).no_limit().compile();
+        
+        let untrusted = executor.execute(&query).unwrap();
+        assert_eq!(untrusted.nodes.len(), MAX_RESULT_NODES);
+        
+        let trusted_limits = ExecutionLimits::trusted().with_max_result_nodes(total);
+        let trusted = executor.execute_with_limits(&query, &trusted_limits).unwrap();
+        assert_eq!(trusted.nodes.len(), total);
+        
+        // The raised cap applied to that execution only
+        assert_eq!(executor.execute(&query).unwrap().nodes.len(), MAX_RESULT_NODES);
+    }
+    
+    #[test]
+    fn test_follow_connection_weighted_prefers_type() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use std::collections::HashMap;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let word = builder.add_node("democracy", Layer::Words, Coordinate3D::new(0.4, 0.7, 0.5)).unwrap();
+        let analog = builder.add_node("meritocracy", Layer::Words, Coordinate3D::new(0.45, 0.7, 0.5)).unwrap();
+        let root = builder.add_node("demos", Layer::Morphemes, Coordinate3D::new(0.4, 0.7, 0.35)).unwrap();
+        builder.add_connection(word, analog, ConnectionType::Analogy, 0.9).unwrap();
+        builder.add_connection(word, root, ConnectionType::Etymology, 0.6).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let raw = executor.execute(&QueryBuilder::find("democracy").follow_connection().compile()).unwrap();
+        assert_eq!(raw.nodes.as_slice(), &[analog]);
+        
+        let mut profile = HashMap::new();
+        profile.insert(ConnectionType::Etymology, 2.0);
+        profile.insert(ConnectionType::Analogy, 0.5);
+        let query = QueryBuilder::find("democracy").follow_connection_weighted(&profile).compile();
+        let weighted = executor.execute(&query).unwrap();
+        assert_eq!(weighted.nodes.as_slice(), &[root]);
+    }
+    
+    #[test]
+    fn test_vertical_context_morphemes_and_concept() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let teach = builder.add_node("teach", Layer::Morphemes, Coordinate3D::new(0.4, 0.2, 0.35)).unwrap();
+        let er = builder.add_node("er", Layer::Morphemes, Coordinate3D::new(0.6, 0.2, 0.35)).unwrap();
+        let word = builder.add_composed_word("teacher", Layer::Words, Coordinate3D::new(0.5, 0.2, 0.52), &["teach", "er"]).unwrap();
+        let learner = builder.add_node("learner", Layer::Words, Coordinate3D::new(0.5, 0.3, 0.52)).unwrap();
+        let profession = builder.add_node("profession", Layer::Concepts, Coordinate3D::new(0.5, 0.4, 0.82)).unwrap();
+        builder.add_connection(word, profession, ConnectionType::Hypernymy, 0.9).unwrap();
+        builder.add_connection(word, learner, ConnectionType::Antonymy, 0.5).unwrap();
+        
+        let (_dir, executor) = executor_from(&builder);
+        
+        let context = executor.vertical_context("teacher").unwrap();
+        assert_eq!(context.self_node, word);
+        assert_eq!(context.below, vec![teach, er]);
+        assert_eq!(context.above, vec![profession]);
+        
+        assert!(matches!(executor.vertical_context("missing"), Err(LingoError::WordNotFound(_))));
+    }
+    
+    #[test]
+    fn test_load_database_relative_to_base_path() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("anchor", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.52)).unwrap();
+        let (temp_dir, path) = build_database(&builder);
+        let file_name = path.file_name().unwrap();
+        
+        // The working directory doesn't contain the file, so only the base path can find it
+        let mut executor = LingoExecutor::new();
+        assert!(executor.load_database(file_name).is_err());
+        
+        executor.set_base_path(temp_dir.path());
+        executor.load_database(file_name).unwrap();
+        let result = executor.execute(&QueryBuilder::find("anchor").compile()).unwrap();
+        assert_eq!(result.nodes.len(), 1);
+    }
+    
+    #[test]
+    fn test_estimate_result_size_tracks_actual() {
+        use crate::core::Layer;
+        use crate::security::ExecutionLimits;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        for i in 0..2000 {
+            let position = Coordinate3D::new(
+                (i % 20) as f32 / 19.0,
+                ((i / 20) % 10) as f32 / 9.0,
+                0.45 + (i / 200) as f32 * 0.015,
+            );
+            builder.add_node(&format!("w{}", i), Layer::Words, position).unwrap();
+        }
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        executor.set_limits(ExecutionLimits::trusted());
+        
+        for threshold in [0.9, 0.8, 0.7] {
+            let query = QueryBuilder::find("w1010").similar_threshold(threshold).compile();
+            let actual = executor.execute(&query).unwrap().nodes.len();
+            let estimate = executor.estimate_result_size(&query);
+            assert!(actual > 0);
+            assert!(estimate >= actual / 2 && estimate <= actual * 4,
+                "threshold {}: estimate {} vs actual {}", threshold, estimate, actual);
+        }
+        
+        let limited = QueryBuilder::find("w1010").similar().limit(5).compile();
+        assert_eq!(executor.estimate_result_size(&limited), 5);
+        assert_eq!(executor.estimate_result_size(&QueryBuilder::find("missing").similar().compile()), 0);
+    }
+    
+    #[test]
+    fn test_fold_accents_finds_accented_word() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let cafe = builder.add_node("café", Layer::Words, Coordinate3D::new(0.5, 0.8, 0.52)).unwrap();
+        let naive = builder.add_node("Naïve", Layer::Words, Coordinate3D::new(0.6, 0.8, 0.52)).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let exact = executor.execute(&QueryBuilder::find("cafe").compile()).unwrap();
+        assert!(exact.nodes.is_empty());
+        
+        let folded = executor.execute(&QueryBuilder::find("cafe").fold_accents().compile()).unwrap();
+        assert_eq!(folded.nodes.as_slice(), &[cafe]);
+        let folded = executor.execute(&QueryBuilder::find("naive").fold_accents().compile()).unwrap();
+        assert_eq!(folded.nodes.as_slice(), &[naive]);
+    }
+    
+    #[test]
+    fn test_fuzzy_ascii_resolves_through_normalized_index() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let cafe = builder.add_node("Café", Layer::Words, Coordinate3D::new(0.5, 0.8, 0.52)).unwrap();
+        builder.add_node("cafe", Layer::Words, Coordinate3D::new(0.6, 0.8, 0.52)).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        // Folding returns every variant; fuzzy_ascii the first one added
+        let folded = executor.execute(&QueryBuilder::find("CAFE").fold_accents().compile()).unwrap();
+        assert_eq!(folded.nodes.len(), 2);
+        let fuzzy = executor.execute(&QueryBuilder::find("CAFE").fuzzy_ascii().compile()).unwrap();
+        assert_eq!(fuzzy.nodes.as_slice(), &[cafe]);
+        assert_eq!(executor.database().unwrap().find_normalized("cafe\u{301}"), Some(cafe));
+        
+        let missing = executor.execute(&QueryBuilder::find("tea").fuzzy_ascii().compile()).unwrap();
+        assert!(missing.nodes.is_empty());
+    }
+    
+    #[test]
+    fn test_sounds_like_finds_homophone() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let phone = builder.add_node("phone", Layer::Words, Coordinate3D::new(0.4, 0.5, 0.52)).unwrap();
+        builder.add_node("bone", Layer::Words, Coordinate3D::new(0.6, 0.5, 0.52)).unwrap();
+        builder.add_node("phon", Layer::Morphemes, Coordinate3D::new(0.4, 0.5, 0.35)).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let exact = executor.execute(&QueryBuilder::find("fone").compile()).unwrap();
+        assert!(exact.nodes.is_empty());
+        
+        // The "phon" morpheme shares the key but isn't on the Words layer
+        let query = QueryBuilder::sounds_like("fone").compile();
+        let result = executor.execute(&query).unwrap();
+        assert_eq!(result.nodes.as_slice(), &[phone]);
+        assert_eq!(executor.estimate_result_size(&query), 1);
+    }
+    
+    #[test]
+    fn test_similar_returns_descending_scores() {
+        use crate::core::Layer;
+        use crate::test_support::executor_with;
+        
+        let (_dir, mut executor) = executor_with(&[
+            ("warm", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.52)),
+            ("hot", Layer::Words, Coordinate3D::new(0.55, 0.5, 0.52)),
+            ("tepid", Layer::Words, Coordinate3D::new(0.4, 0.5, 0.52)),
+            ("mild", Layer::Words, Coordinate3D::new(0.5, 0.7, 0.52)),
+        ]);
+        
+        let result = executor.execute(&QueryBuilder::find("warm").similar().compile()).unwrap();
+        assert_eq!(result.scores.len(), result.nodes.len());
+        assert!(result.nodes.len() >= 3);
+        assert!((result.scores[0] - 1.0).abs() < 1e-6);
+        assert!(result.scores.windows(2).all(|w| w[0] >= w[1]));
+        assert!(result.nodes_with_scores().all(|(_, score)| score.is_some()));
+        
+        // Plain lookups carry no scores
+        let result = executor.execute(&QueryBuilder::find("warm").compile()).unwrap();
+        assert!(result.scores.is_empty());
+    }
+    
+    #[test]
+    fn test_default_limit_and_no_limit() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        for i in 0..30 {
+            let x = 0.4 + i as f32 * 0.005;
+            builder.add_node(&format!("w{}", i), Layer::Words, Coordinate3D::new(x, 0.5, 0.52)).unwrap();
+        }
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        assert_eq!(executor.default_limit(), Some(DEFAULT_RESULT_LIMIT));
+        executor.set_default_limit(Some(10));
+        
+        let query = QueryBuilder::find("w0").similar().compile();
+        assert_eq!(executor.execute(&query).unwrap().nodes.len(), 10);
+        assert_eq!(executor.estimate_result_size(&query), 10);
+        
+        let unbounded = QueryBuilder::find("w0").similar().no_limit().compile();
+        assert_eq!(executor.execute(&unbounded).unwrap().nodes.len(), 30);
+        
+        // An explicit limit replaces the default
+        let explicit = QueryBuilder::find("w0").similar().limit(20).compile();
+        assert_eq!(executor.execute(&explicit).unwrap().nodes.len(), 20);
+    }
+    
+    #[test]
+    fn test_warmup_builds_indices_up_front() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        let hot = builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        let cold = builder.add_node("cold", Layer::Words, Coordinate3D::new(0.8, 0.5, 0.52)).unwrap();
+        builder.add_connection(hot, cold, crate::core::ConnectionType::Antonymy, 0.9).unwrap();
+        let (_dir, path) = build_database(&builder);
+        
+        let queries = [
+            QueryBuilder::sounds_like("hott").compile(),
+            QueryBuilder::find("HOT").fold_accents().compile(),
+            QueryBuilder::find("hot").reflect_through(Coordinate3D::new(0.5, 0.5, 0.52)).compile(),
+        ];
+        
+        // Cold: the first queries build indices as they go
+        let mut executor = LingoExecutor::new();
+        executor.load_database(&path).unwrap();
+        assert_eq!(executor.index_builds(), 0);
+        executor.execute(&queries[0]).unwrap();
+        assert_eq!(executor.index_builds(), 1);
+        
+        let mut executor = LingoExecutor::new();
+        executor.load_database(&path).unwrap();
+        executor.warmup();
+        let built = executor.index_builds();
+        assert!(built > 0);
+        
+        for query in &queries {
+            executor.execute(query).unwrap();
+        }
+        executor.estimate_result_size(&queries[2]);
+        assert_eq!(executor.execute(&queries[0]).unwrap().nodes.as_slice(), &[hot]);
+        assert_eq!(executor.index_builds(), built);
+    }
+    
+    #[test]
+    fn test_spatial_box_selects_region() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let z = Layer::Morphemes.z_center();
+        let inside: Vec<NodeId> = [0.42, 0.5, 0.58].iter()
+            .enumerate()
+            .map(|(i, &x)| builder.add_node(&format!("m{}", i), Layer::Morphemes, Coordinate3D::new(x, 0.5, z)).unwrap())
+            .collect();
+        builder.add_node("far", Layer::Morphemes, Coordinate3D::new(0.9, 0.5, z)).unwrap();
+        builder.add_node("word", Layer::Words, Coordinate3D::new(0.5, 0.5, Layer::Words.z_center())).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let (z_min, z_max) = Layer::Morphemes.z_range();
+        let region = BoundingBox3D::new(
+            Coordinate3D::new(0.4, 0.0, z_min),
+            Coordinate3D::new(0.6, 1.0, z_max),
+        );
+        let result = executor.execute(&QueryBuilder::spatial_box(region).compile()).unwrap();
+        assert_eq!(result.nodes.as_slice(), inside.as_slice());
+        
+        let limited = executor.execute(&QueryBuilder::spatial_box(region).limit(2).compile()).unwrap();
+        assert_eq!(limited.nodes.len(), 2);
+        
+        // Loaded after a lookup, the box result replaces rather than merges
+        let query = QueryBuilder::spatial_box(region).with_find("word").compile();
+        assert_eq!(executor.execute(&query).unwrap().nodes.as_slice(), inside.as_slice());
+        
+        let inverted = BoundingBox3D::new(
+            Coordinate3D::new(0.6, 0.0, z_min),
+            Coordinate3D::new(0.4, 1.0, z_max),
+        );
+        let empty = executor.execute(&QueryBuilder::spatial_box(inverted).compile()).unwrap();
+        assert!(empty.nodes.is_empty());
+    }
+    
+    #[test]
+    fn test_execute_iter_stops_early() {
+        use crate::core::{BoundingBox3D, Layer};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        for i in 0..5000 {
+            let x = 0.2 + (i % 100) as f32 * 0.006;
+            let y = 0.2 + (i / 100) as f32 * 0.012;
+            builder.add_node(&format!("w{}", i), Layer::Words, Coordinate3D::new(x, y, 0.52)).unwrap();
+        }
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let region = BoundingBox3D::new(Coordinate3D::new(0.0, 0.0, 0.0), Coordinate3D::new(1.0, 1.0, 1.0));
+        let query = QueryBuilder::spatial_box(region).no_limit().compile();
+        
+        let mut stream = executor.execute_iter(&query).unwrap();
+        let first: Vec<NodeId> = stream.by_ref().take(5).collect();
+        assert_eq!(first.len(), 5);
+        assert!(stream.nodes_examined() < 50);
+        drop(stream);
+        
+        let mut full = executor.execute_iter(&query).unwrap();
+        assert_eq!(full.by_ref().count(), 5000);
+        assert_eq!(full.nodes_examined(), 5000);
+        drop(full);
+        
+        // Same nodes, in the same order, as the materialized result
+        let materialized = executor.execute(&query).unwrap();
+        assert_eq!(first.as_slice(), &materialized.nodes.as_slice()[..5]);
+        
+        let similar = QueryBuilder::find("w0").similar_threshold(0.9).limit(20).compile();
+        let streamed: Vec<NodeId> = executor.execute_iter(&similar).unwrap().collect();
+        assert_eq!(streamed.as_slice(), executor.execute(&similar).unwrap().nodes.as_slice());
+        
+        // Layer navigation has no lazy stage, so it streams from a buffered run
+        let buffered = QueryBuilder::find("w0").layer_up().compile();
+        let mut stream = executor.execute_iter(&buffered).unwrap();
+        let streamed: Vec<NodeId> = stream.by_ref().collect();
+        assert_eq!(stream.nodes_examined(), 0);
+        drop(stream);
+        assert_eq!(streamed.as_slice(), executor.execute(&buffered).unwrap().nodes.as_slice());
+    }
+    
+    #[test]
+    fn test_follow_connection_type_filters_edges() {
+        use crate::core::{ConnectionType, Layer};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let big = builder.add_node("big", Layer::Words, Coordinate3D::new(0.4, 0.5, 0.52)).unwrap();
+        let large = builder.add_node("large", Layer::Words, Coordinate3D::new(0.45, 0.5, 0.52)).unwrap();
+        let huge = builder.add_node("huge", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.52)).unwrap();
+        let small = builder.add_node("small", Layer::Words, Coordinate3D::new(0.6, 0.5, 0.52)).unwrap();
+        builder.add_connection(big, huge, ConnectionType::Synonymy, 0.7).unwrap();
+        builder.add_connection(big, small, ConnectionType::Antonymy, 0.9).unwrap();
+        builder.add_connection(big, large, ConnectionType::Synonymy, 0.95).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let synonyms = QueryBuilder::find("big")
+            .follow_connection_type(ConnectionType::Synonymy)
+            .compile();
+        assert_eq!(executor.execute(&synonyms).unwrap().nodes.as_slice(), &[large, huge]);
+        let streamed: Vec<NodeId> = executor.execute_iter(&synonyms).unwrap().collect();
+        assert_eq!(streamed, vec![large, huge]);
+        
+        let antonyms = QueryBuilder::find("big")
+            .follow_connection_type(ConnectionType::Antonymy)
+            .compile();
+        assert_eq!(executor.execute(&antonyms).unwrap().nodes.as_slice(), &[small]);
+        
+        let none = QueryBuilder::find("big")
+            .follow_connection_type(ConnectionType::Etymology)
+            .compile();
+        assert!(executor.execute(&none).unwrap().nodes.is_empty());
+    }
+    
+    #[test]
+    fn test_follow_connection_reverses_stored_once_edges() {
+        use crate::core::{ConnectionType, Layer};
+        use crate::storage::{DatabaseBuilder, SymmetricConnectionMode};
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let big = builder.add_node("big", Layer::Words, Coordinate3D::new(0.4, 0.5, 0.52)).unwrap();
+        let large = builder.add_node("large", Layer::Words, Coordinate3D::new(0.45, 0.5, 0.52)).unwrap();
+        let whole = builder.add_node("whole", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.52)).unwrap();
+        builder.add_connection(large, big, ConnectionType::Synonymy, 0.9).unwrap();
+        // Hypernymy is one-way, so its reverse is never followed
+        builder.add_connection(whole, big, ConnectionType::Hypernymy, 0.8).unwrap();
+        builder.add_connection(big, large, ConnectionType::Synonymy, 0.9).unwrap();
+        builder.set_symmetric_connections(SymmetricConnectionMode::StoreOnce);
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        let db = executor.database().unwrap();
+        assert!(db.get_node_connections(big).unwrap().is_empty());
+        
+        let synonyms = QueryBuilder::find("big")
+            .follow_connection_type(ConnectionType::Synonymy)
+            .compile();
+        assert_eq!(executor.execute(&synonyms).unwrap().nodes.as_slice(), &[large]);
+        let back = QueryBuilder::find("large")
+            .follow_connection_type(ConnectionType::Synonymy)
+            .compile();
+        assert_eq!(executor.execute(&back).unwrap().nodes.as_slice(), &[big]);
+        
+        let strongest = QueryBuilder::find("big").follow_connection().compile();
+        assert_eq!(executor.execute(&strongest).unwrap().nodes.as_slice(), &[large]);
+        let parents = QueryBuilder::find("big")
+            .follow_connection_type(ConnectionType::Hypernymy)
+            .compile();
+        assert!(executor.execute(&parents).unwrap().nodes.is_empty());
+    }
+    
+    #[test]
+    fn test_stored_once_reverse_edges_point_back() {
+        use crate::core::{ConnectionType, Layer, OrthogonalConnection, Vector3D};
+        use crate::storage::{DatabaseBuilder, DatabaseWriter, FileFormatFlags, SymmetricConnectionMode};
+        use crate::test_support::{build_database, open_database};
+        
+        let mut builder = DatabaseBuilder::new();
+        let big = builder.add_node("big", Layer::Words, Coordinate3D::new(0.4, 0.5, 0.52)).unwrap();
+        let large = builder.add_node("large", Layer::Words, Coordinate3D::new(0.45, 0.5, 0.52)).unwrap();
+        builder.add_connection(large, big, ConnectionType::Synonymy, 0.9).unwrap();
+        
+        // Without the flag only the node's own edges are traversable
+        let (_keep_dir, kept) = open_database(&builder);
+        assert!(!kept.header().format_flags.contains(FileFormatFlags::SYMMETRIC_STORED_ONCE));
+        assert!(LingoExecutor::traversable_connections(&kept, big).is_empty());
+        
+        builder.set_symmetric_connections(SymmetricConnectionMode::StoreOnce);
+        let (dir, path) = build_database(&builder);
+        let db = Database::open(&path).unwrap();
+        assert!(db.header().format_flags.contains(FileFormatFlags::SYMMETRIC_STORED_ONCE));
+        
+        // A copy keeps the flag, and the reversed edge points back
+        let mut writer = DatabaseWriter::from_database(&db).unwrap();
+        let mut edge = OrthogonalConnection::new(big, ConnectionType::Synonymy, 0.7);
+        edge.transformation_vector = Vector3D::new(-0.05, 0.0, 0.1);
+        writer.add_connection(large, edge).unwrap();
+        let copy_path = dir.path().join("copy.lingo");
+        writer.write(&copy_path).unwrap();
+        let copy = Database::open(&copy_path).unwrap();
+        
+        let reversed = LingoExecutor::traversable_connections(&copy, big);
+        assert_eq!(reversed.len(), 2);
+        assert!(reversed.iter().all(|c| { c.target_node } == large));
+        assert_eq!({ reversed[1].transformation_vector }, Vector3D::new(0.05, -0.0, -0.1));
+    }
+    
+    #[test]
+    fn test_follow_connections_where_filters_type_and_strength() {
+        use crate::core::{ConnectionType, Layer};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let mut word = |text: &str, x: f32| {
+            builder.add_node(text, Layer::Words, Coordinate3D::new(x, 0.5, 0.52)).unwrap()
+        };
+        let (hot, warm, cold, cool, chilly, icy) =
+            (word("hot", 0.1), word("warm", 0.2), word("cold", 0.3), word("cool", 0.4), word("chilly", 0.5), word("icy", 0.6));
+        let (big, small) = (word("big", 0.7), word("small", 0.8));
+        builder.add_connection(hot, chilly, ConnectionType::Antonymy, 0.5).unwrap();
+        builder.add_connection(hot, cool, ConnectionType::Antonymy, 0.75).unwrap();
+        builder.add_connection(hot, cold, ConnectionType::Antonymy, 0.95).unwrap();
+        builder.add_connection(hot, icy, ConnectionType::Antonymy, 0.7).unwrap();
+        builder.add_connection(hot, warm, ConnectionType::Synonymy, 0.8).unwrap();
+        builder.add_connection(big, small, ConnectionType::Antonymy, 0.9).unwrap();
+        builder.add_connection(big, cold, ConnectionType::Antonymy, 0.7).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        let mut run = |query: QueryBuilder| executor.execute(&query.compile()).unwrap().nodes.as_slice().to_vec();
+        
+        // Strongest first, weak and other-typed edges dropped, exactly 0.7 kept
+        let antonyms = QueryBuilder::find("hot").follow_connections_where(Some(ConnectionType::Antonymy), 0.7, 10);
+        assert_eq!(run(antonyms), vec![cold, cool, icy]);
+        
+        let top_two = QueryBuilder::find("hot").follow_connections_where(Some(ConnectionType::Antonymy), 0.7, 2);
+        assert_eq!(run(top_two), vec![cold, cool]);
+        
+        let any_type = QueryBuilder::find("hot").follow_connections_where(None, 0.76, 10);
+        assert_eq!(run(any_type), vec![cold, warm]);
+        
+        // The limit is per source; "cold" is reached from both but kept once
+        let both = QueryBuilder::from_nodes([hot, big]).unwrap()
+            .follow_connections_where(Some(ConnectionType::Antonymy), 0.7, 2);
+        assert_eq!(run(both), vec![cold, cool, small]);
+        
+        let none = QueryBuilder::find("hot").follow_connections_where(Some(ConnectionType::Antonymy), 0.99, 10);
+        assert!(run(none).is_empty());
+    }
+    
+    #[test]
+    fn test_find_phrase_prefers_phrase_node_then_shared_neighbors() {
+        use crate::core::{ConnectionType, Layer};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let mut word = |text: &str, x: f32| {
+            builder.add_node(text, Layer::Words, Coordinate3D::new(x, 0.5, 0.52)).unwrap()
+        };
+        let (machine, learning, algorithm, car) = (word("machine", 0.1), word("learning", 0.2), word("algorithm", 0.3), word("car", 0.4));
+        let (neural, network) = (word("neural", 0.5), word("network", 0.6));
+        let neural_network = builder.add_node("neural network", Layer::Phrases, Coordinate3D::new(0.55, 0.5, 0.67)).unwrap();
+        builder.add_connection(machine, learning, ConnectionType::Collocation, 0.9).unwrap();
+        builder.add_connection(machine, algorithm, ConnectionType::Hyponymy, 0.6).unwrap();
+        builder.add_connection(algorithm, learning, ConnectionType::Analogy, 0.5).unwrap();
+        builder.add_connection(machine, car, ConnectionType::Hyponymy, 0.4).unwrap();
+        builder.add_connection(neural, network, ConnectionType::Collocation, 0.9).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        let mut run = |text: &str| {
+            let mut nodes = executor.execute(&QueryBuilder::find_phrase(text).compile()).unwrap().nodes.into_vec();
+            nodes.sort();
+            nodes
+        };
+        
+        // The n-gram exists, so its words aren't consulted
+        assert_eq!(run("Neural network"), vec![neural_network]);
+        assert!(run("neural network").iter().all(|&id| id != neural && id != network));
+        
+        // Only separate words: what both are linked to, "car" being machine's alone
+        let shared = vec![machine, learning, algorithm];
+        assert_eq!(run("machine learning"), shared);
+        assert_eq!(run("the machine, and the learning!"), shared);
+        
+        assert_eq!(run("machine"), vec![machine]);
+        assert!(run("machine quantum").is_empty());
+        assert!(run("  ").is_empty());
+        
+        // Streaming matches, and a phrase can start the right side of a combination
+        let query = QueryBuilder::find("car").union(QueryBuilder::find_phrase("neural network")).compile();
+        let streamed: Vec<NodeId> = executor.execute_iter(&query).unwrap().collect();
+        assert_eq!(streamed, executor.execute(&query).unwrap().nodes.into_vec());
+        assert_eq!(streamed, vec![car, neural_network]);
+    }
+    
+    #[test]
+    fn test_similar_auto_tunes_threshold_to_target_count() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        // An 8x8 grid of words 0.01 apart, and one far away
+        let mut builder = DatabaseBuilder::new();
+        for i in 0..64 {
+            let position = Coordinate3D::new(0.4 + (i % 8) as f32 * 0.01, 0.4 + (i / 8) as f32 * 0.01, 0.52);
+            builder.add_node(&format!("w{}", i), Layer::Words, position).unwrap();
+        }
+        builder.add_node("remote", Layer::Words, Coordinate3D::new(0.95, 0.05, 0.52)).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let before = executor.queries_executed();
+        let (result, threshold) = executor.similar_auto("w27", 5).unwrap();
+        assert!((3..=8).contains(&result.nodes.len()), "{} nodes", result.nodes.len());
+        assert!(executor.queries_executed() - before <= AUTO_THRESHOLD_PROBES);
+        assert!(threshold > 0.0 && threshold < 1.0);
+        
+        // The reported threshold reproduces the result
+        let query = QueryBuilder::find("w27").similar_threshold(threshold).no_limit().compile();
+        assert_eq!(executor.execute(&query).unwrap().nodes.len(), result.nodes.len());
+        
+        // Asking for more loosens the threshold
+        let (wide, wide_threshold) = executor.similar_auto("w27", 30).unwrap();
+        assert!(wide.nodes.len() > result.nodes.len());
+        assert!(wide_threshold < threshold);
+        
+        assert!(executor.similar_auto("w27", 0).is_err());
+    }
+    
+    #[test]
+    fn test_from_nodes_continues_earlier_result() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let ids: Vec<NodeId> = (0..20)
+            .map(|i| {
+                let position = Coordinate3D::new(0.1 + i as f32 * 0.04, 0.5, 0.52);
+                builder.add_node(&format!("w{}", i), Layer::Words, position).unwrap()
+            })
+            .collect();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        // Second page of an earlier result, fed back in without word lookups
+        let page: Vec<NodeId> = ids[5..10].to_vec();
+        let query = QueryBuilder::from_nodes(page.iter().copied().chain([ids[5]])).unwrap().compile();
+        let result = executor.execute(&query).unwrap();
+        assert_eq!(result.nodes.as_slice(), page.as_slice());
+        
+        let streamed: Vec<NodeId> = executor.execute_iter(&query).unwrap().collect();
+        assert_eq!(streamed, page);
+        
+        let chained = QueryBuilder::from_nodes([ids[0]]).unwrap().similar_threshold(0.95).compile();
+        let by_word = QueryBuilder::find("w0").similar_threshold(0.95).compile();
+        assert_eq!(
+            executor.execute(&chained).unwrap().nodes.as_slice(),
+            executor.execute(&by_word).unwrap().nodes.as_slice(),
+        );
+        
+        let missing = QueryBuilder::from_nodes([ids[0], NodeId(500)]).unwrap().compile();
+        assert!(executor.execute(&missing).is_err());
+    }
+    
+    #[test]
+    fn test_execute_batch_matches_individual_queries() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        for i in 0..200 {
+            let position = Coordinate3D::new(0.1 + (i % 20) as f32 * 0.04, 0.1 + (i / 20) as f32 * 0.08, 0.52);
+            builder.add_node(&format!("w{}", i), Layer::Words, position).unwrap();
+        }
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let template = QueryBuilder::find("w0")
+            .similar_threshold(0.9)
+            .limit(5)
+            .compile_template()
+            .unwrap();
+        let inputs = ["w3", "w150", "missing", "w3"];
+        let batch = executor.execute_batch(&template, &inputs).unwrap();
+        
+        assert_eq!(batch.len(), inputs.len());
+        for (word, batched) in inputs.iter().zip(&batch) {
+            let single = QueryBuilder::find(word).similar_threshold(0.9).limit(5).compile();
+            let expected = executor.execute(&single).unwrap();
+            assert_eq!(batched.nodes.as_slice(), expected.nodes.as_slice(), "input {}", word);
+            assert_eq!(batched.scores, expected.scores);
+            assert_eq!(template.bind(word).string_cache.last(), Some(&word.to_string()));
+        }
+        assert!(batch[2].nodes.is_empty());
+        assert!(!batch[0].nodes.is_empty());
+        
+        // Only word lookups can be templated
+        assert!(QueryBuilder::find_by_id(1).similar().compile_template().is_err());
+    }
+    
+    #[test]
+    fn test_nearest_k_ignores_distance() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let lonely = builder.add_node("lonely", Layer::Words, Coordinate3D::new(0.05, 0.05, 0.52)).unwrap();
+        let far: Vec<NodeId> = [0.5f32, 0.6, 0.7]
+            .iter()
+            .map(|&x| builder.add_node(&format!("far{}", x), Layer::Words, Coordinate3D::new(x, 0.05, 0.52)).unwrap())
+            .collect();
+        let crowded = builder.add_node("crowded", Layer::Words, Coordinate3D::new(0.8, 0.8, 0.52)).unwrap();
+        for i in 0..50 {
+            let offset = 0.001 * (i + 1) as f32;
+            builder.add_node(&format!("crowd{}", i), Layer::Words, Coordinate3D::new(0.8 + offset, 0.8, 0.52)).unwrap();
+        }
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        // Nothing within a typical radius, but the three nearest are still found
+        let sparse = executor.execute(&QueryBuilder::find("lonely").nearest_k(3).compile()).unwrap();
+        assert_eq!(sparse.nodes.as_slice(), far.as_slice());
+        assert!(!sparse.nodes.as_slice().contains(&lonely));
+        
+        let dense = executor.execute(&QueryBuilder::find("crowded").nearest_k(5).compile()).unwrap();
+        assert_eq!(dense.nodes.len(), 5);
+        assert!(!dense.nodes.as_slice().contains(&crowded));
+        let crowd_word = executor.database.as_ref().unwrap().get_node_word(dense.nodes.as_slice()[0]).unwrap().to_string();
+        assert_eq!(crowd_word, "crowd0");
+        
+        // A k far past the node count returns every other node
+        let all = executor.execute(&QueryBuilder::find("lonely").nearest_k(u32::MAX as usize).compile()).unwrap();
+        assert_eq!(all.nodes.len(), 54);
+    }
+    
+    #[test]
+    fn test_exclude_by_identity_and_radius() {
+        use crate::core::{BoundingBox3D, Layer};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let hot = builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        let warm = builder.add_node("warm", Layer::Words, Coordinate3D::new(0.3, 0.5, 0.52)).unwrap();
+        let cold = builder.add_node("cold", Layer::Words, Coordinate3D::new(0.7, 0.5, 0.52)).unwrap();
+        let chilly = builder.add_node("chilly", Layer::Words, Coordinate3D::new(0.75, 0.5, 0.52)).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let region = BoundingBox3D::new(Coordinate3D::new(0.0, 0.0, 0.0), Coordinate3D::new(1.0, 1.0, 1.0));
+        let all = || QueryBuilder::spatial_box(region);
+        
+        // Identity only removes the word's own node, however close others are
+        let result = executor.execute(&all().exclude("cold").compile()).unwrap();
+        assert_eq!(result.nodes.as_slice(), &[hot, warm, chilly]);
+        
+        let result = executor.execute(&all().exclude_nodes([hot, chilly]).compile()).unwrap();
+        assert_eq!(result.nodes.as_slice(), &[warm, cold]);
+        
+        // The radius reaches "chilly" but not "warm"
+        let result = executor.execute(&all().exclude_near("cold", 0.1).compile()).unwrap();
+        assert_eq!(result.nodes.as_slice(), &[hot, warm]);
+        let streamed: Vec<NodeId> = executor
+            .execute_iter(&all().exclude_near("cold", 0.1).compile())
+            .unwrap()
+            .collect();
+        assert_eq!(streamed, vec![hot, warm]);
+        
+        // Unknown words and empty sets are no-ops
+        let result = executor.execute(&all().exclude("lukewarm").compile()).unwrap();
+        assert_eq!(result.nodes.len(), 4);
+        let result = executor.execute(&QueryBuilder::find("missing").exclude("hot").compile()).unwrap();
+        assert!(result.nodes.is_empty());
+        let result = executor.execute(&QueryBuilder::find("hot").exclude("hot").exclude_near("cold", 0.5).compile()).unwrap();
+        assert!(result.nodes.is_empty());
+    }
+    
+    #[test]
+    fn test_solve_analogy_follows_offset() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        let word = |builder: &mut DatabaseBuilder, w: &str, x: f32, y: f32| {
+            builder.add_node(w, Layer::Words, Coordinate3D::new(x, y, 0.52)).unwrap()
+        };
+        // Opposites sit 0.3 apart along y
+        let hot = word(&mut builder, "hot", 0.2, 0.3);
+        let cold = word(&mut builder, "cold", 0.2, 0.6);
+        let light = word(&mut builder, "light", 0.5, 0.3);
+        let dark = word(&mut builder, "dark", 0.5, 0.6);
+        let dim = word(&mut builder, "dim", 0.55, 0.55);
+        // Latin-derived adjectives sit 0.2 along x from their roots
+        let king = word(&mut builder, "king", 0.3, 0.8);
+        let regal = word(&mut builder, "regal", 0.5, 0.8);
+        let law = word(&mut builder, "law", 0.6, 0.1);
+        let legal = word(&mut builder, "legal", 0.8, 0.1);
+        // Nodes off the third word's layer are never answers, however close
+        let suffix = builder.add_node("-ness", Layer::Morphemes, Coordinate3D::new(0.5, 0.3, 0.4)).unwrap();
+        builder.add_default_connection(hot, cold, ConnectionType::Antonymy).unwrap();
+        builder.add_default_connection(light, dark, ConnectionType::Antonymy).unwrap();
+        builder.add_default_connection(regal, king, ConnectionType::Etymology).unwrap();
+        builder.add_default_connection(legal, law, ConnectionType::Etymology).unwrap();
+        let (_dir, path) = build_database(&builder);
+        
+        let mut executor = LingoExecutor::new();
+        assert!(executor.solve_analogy("hot", "cold", "light", 1).is_empty());
+        executor.load_database(&path).unwrap();
+        
+        let opposite = executor.solve_analogy("hot", "cold", "light", 2);
+        assert_eq!(opposite[0].0, dark);
+        assert!(opposite[0].1 < 1e-5);
+        assert_eq!(opposite[1].0, dim);
+        
+        let derived = executor.solve_analogy("king", "regal", "law", 1);
+        assert_eq!(derived[0].0, legal);
+        
+        // The prompts themselves are never answers
+        let answers = executor.solve_analogy("hot", "hot", "light", 9);
+        assert_eq!(answers.len(), 7);
+        assert!(answers.iter().all(|&(id, _)| id != hot && id != light));
+        assert!(answers.iter().all(|&(id, _)| id != suffix));
+        
+        assert!(executor.solve_analogy("hot", "cold", "missing", 3).is_empty());
+    }
+    
+    #[test]
+    fn test_pronunciations_and_rhymes() {
+        use crate::core::Layer;
+        use crate::storage::{DatabaseBuilder, SymmetricConnectionMode};
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.set_symmetric_connections(SymmetricConnectionMode::DedupExact);
+        let mut phoneme = HashMap::new();
+        for (i, symbol) in ["k", "æ", "t", "h", "b", "ɪ", "ɒ"].iter().enumerate() {
+            let position = Coordinate3D::new(0.1 + i as f32 * 0.1, 0.5, 0.22);
+            phoneme.insert(*symbol, builder.add_node(symbol, Layer::Phonemes, position).unwrap());
+        }
+        let mut word = |builder: &mut DatabaseBuilder, w: &str, sounds: &[&str]| {
+            let id = builder.add_node(w, Layer::Words, Coordinate3D::new(0.5, 0.5, 0.52)).unwrap();
+            let sounds: Vec<NodeId> = sounds.iter().map(|s| phoneme[s]).collect();
+            builder.add_pronunciation(id, &sounds).unwrap();
+            id
+        };
+        let cat = word(&mut builder, "cat", &["k", "æ", "t"]);
+        let hat = word(&mut builder, "hat", &["h", "æ", "t"]);
+        let bit = word(&mut builder, "bit", &["b", "ɪ", "t"]);
+        let tot = word(&mut builder, "tot", &["t", "ɒ", "t"]);
+        let bat = word(&mut builder, "bat", &["b", "æ", "t"]);
+        let kit = word(&mut builder, "kit", &["k", "ɪ"]);
+        // A homophone link between words isn't part of either pronunciation
+        builder.add_default_connection(cat, kit, ConnectionType::Phonetic).unwrap();
+        assert!(builder.add_pronunciation(cat, &[hat]).is_err());
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        let db = executor.database().unwrap();
+        
+        // Repeated sounds survive connection deduplication
+        assert_eq!(db.pronunciation(tot).unwrap(), vec![phoneme["t"], phoneme["ɒ"], phoneme["t"]]);
+        
+        let sounds = executor.execute(&QueryBuilder::find("tot").to_phonemes().compile()).unwrap();
+        assert_eq!(sounds.nodes.as_slice(), &[phoneme["t"], phoneme["ɒ"]]);
+        let sounds = executor.execute(&QueryBuilder::find("cat").to_phonemes().compile()).unwrap();
+        assert_eq!(sounds.nodes.as_slice(), &[phoneme["k"], phoneme["æ"], phoneme["t"]]);
+        
+        assert_eq!(executor.rhymes_of("cat", 3), vec![(hat, 2), (bat, 2), (bit, 1)]);
+        assert_eq!(executor.rhymes_of("cat", 10).len(), 4);
+        assert!(executor.rhymes_of("kit", 10).is_empty());
+        assert!(executor.rhymes_of("missing", 10).is_empty());
+    }
+    
+    #[test]
+    fn test_similar_phonemes_rank_by_features() {
+        use crate::core::{Layer, PhonemeFeatures};
+        use crate::data::english_base::ENGLISH_PHONEMES;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let mut ids = HashMap::new();
+        for (i, (symbol, description, _)) in ENGLISH_PHONEMES.iter().enumerate() {
+            // Spread positions so they say nothing about articulation
+            let position = Coordinate3D::new((i * 7 % 40) as f32 / 40.0, 0.5, 0.22);
+            let id = builder.add_phoneme(symbol, position, PhonemeFeatures::from_description(description)).unwrap();
+            ids.insert(*symbol, id);
+        }
+        builder.add_node("pat", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.52)).unwrap();
+        
+        let (_dir, executor) = executor_from(&builder);
+        let db = executor.database().unwrap();
+        
+        // Features survive the round trip through the file
+        let p = db.get_node(ids["/p/"]).unwrap().phoneme_features();
+        assert_eq!(p, PhonemeFeatures::BILABIAL | PhonemeFeatures::PLOSIVE);
+        
+        // Voicing, then place, then manner
+        let similar = executor.similar_phonemes("p", 3);
+        assert_eq!(similar[0], (ids["/b/"], 1));
+        assert_eq!(similar[1..].iter().map(|&(_, d)| d).collect::<Vec<_>>(), vec![2, 2]);
+        let near: Vec<NodeId> = similar[1..].iter().map(|&(id, _)| id).collect();
+        assert_eq!(near, vec![ids["/t/"], ids["/k/"]]);
+        
+        let vowels = executor.similar_phonemes("/i/", 1);
+        assert_eq!(vowels, vec![(ids["/ɪ/"], 2)]);
+        
+        assert!(executor.similar_phonemes("pat", 3).is_empty());
+        assert!(executor.similar_phonemes("/x/", 3).is_empty());
+    }
+    
+    #[test]
+    fn test_explain_traces_each_step() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        for i in 0..20 {
+            let x = 0.4 + i as f32 * 0.01;
+            builder.add_node(&format!("w{}", i), Layer::Words, Coordinate3D::new(x, 0.5, 0.52)).unwrap();
+        }
+        builder.add_node("x", Layer::Words, Coordinate3D::new(0.45, 0.5, 0.52)).unwrap();
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let query = QueryBuilder::find("x").similar().limit(5).compile();
+        let trace = executor.explain(&query);
+        assert!(trace.error.is_none());
+        assert_eq!(
+            trace.ops(),
+            vec![SlangOp::LoadNode, SlangOp::FindSimilar, SlangOp::Limit, SlangOp::Halt]
+        );
+        
+        let load = &trace.steps[0];
+        assert_eq!((load.stack_before, load.stack_after, load.nodes_added), (0, 1, 1));
+        let similar = &trace.steps[1];
+        assert!(similar.nodes_after > 5);
+        let limit = &trace.steps[2];
+        assert_eq!(limit.nodes_added, 0);
+        assert_eq!(limit.nodes_removed, similar.nodes_after - 5);
+        assert_eq!(limit.nodes_after, 5);
+        assert_eq!(trace.result_count, executor.execute(&query).unwrap().nodes.len());
+        assert!(trace.to_string().contains("FindSimilar"));
+        
+        // Tracing doesn't count as an executed query
+        assert_eq!(executor.queries_executed(), 1);
+        
+        let mut broken = QueryBuilder::find("x").similar().compile();
+        broken.bytecode[0].operand1 = 999;
+        let failing = executor.explain(&broken);
+        assert_eq!(failing.steps.len(), 1);
+        assert!(failing.error.is_some());
+        
+        // An empty left side jumps over the intersected branch
+        let skipped = QueryBuilder::find("missing").intersect(QueryBuilder::find("x")).compile();
+        let trace = executor.explain(&skipped);
+        assert!(trace.error.is_none());
+        assert_eq!(trace.ops(), vec![SlangOp::LoadNode, SlangOp::Branch, SlangOp::Halt]);
+        let indices: Vec<usize> = trace.steps.iter().map(|step| step.index).collect();
+        assert_eq!(indices, vec![0, 1, skipped.bytecode.len() - 1]);
+        let result = executor.execute(&skipped).unwrap();
+        assert_eq!(result.instructions_executed, trace.steps.len() - 1);
+        assert_eq!(trace.result_count, 0);
+    }
+    
+    #[test]
+    fn test_semantic_metric_ignores_etymology() {
+        use crate::core::{Layer, SimilarityMetric};
+        use crate::test_support::executor_with;
+        
+        // "commence" differs from "begin" only in etymology
+        let (_dir, mut executor) = executor_with(&[
+            ("begin", Layer::Words, Coordinate3D::new(0.5, 0.1, 0.5)),
+            ("commence", Layer::Words, Coordinate3D::new(0.5, 0.9, 0.5)),
+            ("start", Layer::Words, Coordinate3D::new(0.52, 0.15, 0.5)),
+        ]);
+        let words = |executor: &mut LingoExecutor, query: &CompiledQuery| -> HashSet<String> {
+            let result = executor.execute(query).unwrap();
+            let db = executor.database.as_ref().unwrap();
+            result.nodes.as_slice().iter()
+                .map(|&id| db.get_node_word(id).unwrap().to_string())
+                .collect()
+        };
+        let set = |list: &[&str]| list.iter().map(|w| w.to_string()).collect::<HashSet<_>>();
+        
+        let euclidean = QueryBuilder::find("begin").similar_threshold(0.9).compile();
+        assert_eq!(words(&mut executor, &euclidean), set(&["begin", "start"]));
+        
+        let semantic = QueryBuilder::find("begin")
+            .similar_with_metric(0.9, SimilarityMetric::SemanticOnly)
+            .compile();
+        assert_eq!(semantic.metrics, vec![SimilarityMetric::SemanticOnly]);
+        assert_eq!(words(&mut executor, &semantic), set(&["begin", "commence", "start"]));
+        
+        // Streaming measures distance the same way
+        let streamed: Vec<NodeId> = executor.execute_iter(&semantic).unwrap().collect();
+        assert_eq!(streamed.len(), 3);
+        
+        // Zero weight on y behaves like ignoring etymology
+        let weighted = QueryBuilder::find("begin")
+            .similar_with_metric(0.9, SimilarityMetric::WeightedAxes { wx: 1.0, wy: 0.0, wz: 1.0 })
+            .compile();
+        assert_eq!(words(&mut executor, &weighted), set(&["begin", "commence", "start"]));
+    }
+    
+    #[test]
+    fn test_offset_pages_without_overlap() {
+        use crate::core::{BoundingBox3D, Layer};
+        use crate::query::SortCriteria;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        // Added in reverse x order, so discovery order isn't ID order
+        let mut builder = DatabaseBuilder::new();
+        for i in 0..30 {
+            let x = 0.9 - i as f32 * 0.02;
+            builder.add_node(&format!("word{}", i), Layer::Words, Coordinate3D::new(x, 0.5, 0.52)).unwrap();
+        }
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        let bounds = BoundingBox3D::new(Coordinate3D::new(0.0, 0.0, 0.0), Coordinate3D::new(1.0, 1.0, 1.0));
+        let page = |executor: &mut LingoExecutor, offset: usize| -> Vec<NodeId> {
+            let query = QueryBuilder::spatial_box(bounds).offset(offset).limit(10).compile();
+            executor.execute(&query).unwrap().nodes.into_vec()
+        };
+        
+        let first = page(&mut executor, 0);
+        let second = page(&mut executor, 10);
+        let third = page(&mut executor, 20);
+        assert_eq!(first, (1..=10).map(NodeId).collect::<Vec<_>>());
+        assert_eq!(second, (11..=20).map(NodeId).collect::<Vec<_>>());
+        assert!(second.iter().all(|id| !first.contains(id)));
+        assert_eq!(third.len(), 10);
+        assert!(page(&mut executor, 30).is_empty());
+        
+        // Streaming pages the same way
+        let query = QueryBuilder::spatial_box(bounds).offset(10).limit(10).compile();
+        let opcodes: Vec<SlangOp> = query.bytecode.iter().map(|instruction| instruction.opcode).collect();
+        assert_eq!(
+            opcodes,
+            vec![SlangOp::SpatialBox, SlangOp::Sort, SlangOp::Offset, SlangOp::Limit, SlangOp::Halt]
+        );
+        assert_eq!(executor.execute_iter(&query).unwrap().collect::<Vec<_>>(), second);
+        
+        // Similarity results page in relevance order rather than by ID
+        let similar = || QueryBuilder::find("word15").similar_threshold(0.5).no_limit();
+        let ranked = executor.execute(&similar().compile()).unwrap().nodes.into_vec();
+        let paged = executor.execute(&similar().offset(10).limit(10).compile()).unwrap();
+        assert_eq!(paged.nodes.as_slice(), &ranked[10..20]);
+        
+        // A sort inside a merged branch doesn't order the merged set
+        let merged = QueryBuilder::find("word0")
+            .union(QueryBuilder::find("word1").sort(SortCriteria::NodeId))
+            .offset(1)
+            .compile();
+        let opcodes: Vec<SlangOp> = merged.bytecode.iter().map(|instruction| instruction.opcode).collect();
+        assert_eq!(&opcodes[opcodes.len() - 4..], &[SlangOp::Union, SlangOp::Sort, SlangOp::Offset, SlangOp::Halt]);
+    }
+    
+    #[test]
+    fn test_sort_orders_are_deterministic() {
+        use crate::core::Layer;
+        use crate::query::{SortCriteria, SortOrder};
+        use crate::test_support::executor_with;
+        
+        // "far" and "near" are added before the nodes they rank behind or
+        // level with, and "left" and "right" tie on distance
+        let (_dir, mut executor) = executor_with(&[
+            ("far", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.9)),
+            ("right", Layer::Words, Coordinate3D::new(0.75, 0.5, 0.5)),
+            ("center", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5)),
+            ("left", Layer::Words, Coordinate3D::new(0.25, 0.5, 0.5)),
+            ("near", Layer::Morphemes, Coordinate3D::new(0.5, 0.55, 0.5)),
+        ]);
+        let words = |executor: &mut LingoExecutor, query: QueryBuilder| -> Vec<String> {
+            let result = executor.execute(&query.compile()).unwrap();
+            let db = executor.database.as_ref().unwrap();
+            result.nodes.as_slice().iter()
+                .map(|&id| db.get_node_word(id).unwrap().to_string())
+                .collect()
+        };
+        
+        // Most similar first by default, with the tie in ID order
+        let similar = || QueryBuilder::find("center").similar_threshold(0.5);
+        let query = similar().compile();
+        let result = executor.execute(&query).unwrap();
+        assert!(result.scores.windows(2).all(|pair| pair[0] >= pair[1]), "{:?}", result.scores);
+        assert_eq!(words(&mut executor, similar()), ["center", "near", "right", "left", "far"]);
+        let again = executor.execute(&query).unwrap();
+        assert_eq!(again.nodes.as_slice(), result.nodes.as_slice());
+        
+        assert_eq!(
+            words(&mut executor, similar().sort_by(SortCriteria::Distance, SortOrder::Descending)),
+            ["far", "right", "left", "near", "center"]
+        );
+        assert_eq!(
+            words(&mut executor, similar().sort(SortCriteria::Alphabetical)),
+            ["center", "far", "left", "near", "right"]
+        );
+        assert_eq!(
+            words(&mut executor, similar().sort(SortCriteria::Layer)),
+            ["near", "far", "right", "center", "left"]
+        );
+        assert_eq!(
+            words(&mut executor, similar().sort_by(SortCriteria::NodeId, SortOrder::Descending)),
+            ["near", "left", "center", "right", "far"]
+        );
+        
+        // Streams sort the same way, distance included
+        for criteria in [SortCriteria::Layer, SortCriteria::Distance] {
+            let query = similar().sort_by(criteria, SortOrder::Descending).compile();
+            let streamed: Vec<NodeId> = executor.execute_iter(&query).unwrap().collect();
+            assert_eq!(streamed, executor.execute(&query).unwrap().nodes.into_vec());
+        }
+    }
+    
+    #[test]
+    fn test_node_property_filters() {
+        use crate::core::Layer;
+        use crate::query::FilterCriteria;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let mut add = |word: &str, layer: Layer, z: f32, rank: u32, productivity: f32| {
+            let id = builder.add_node(word, layer, Coordinate3D::new(0.5, 0.5, z)).unwrap();
+            let node = builder.node_mut(id).unwrap();
+            node.frequency_rank = rank;
+            node.set_productivity(productivity);
+        };
+        add("the", Layer::Words, 0.52, 1, 0.0);
+        add("sesquipedalian", Layer::Words, 0.53, 90_000, 0.0);
+        add("un", Layer::Morphemes, 0.37, u32::MAX, 0.9);
+        add("pseudo", Layer::Morphemes, 0.38, u32::MAX, 0.2);
+        add("t", Layer::Letters, 0.05, u32::MAX, 0.0);
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        let bounds = BoundingBox3D::new(Coordinate3D::new(0.0, 0.0, 0.0), Coordinate3D::new(1.0, 1.0, 1.0));
+        let words = |executor: &mut LingoExecutor, criteria: FilterCriteria| -> Vec<String> {
+            let query = QueryBuilder::spatial_box(bounds).filter(criteria).compile();
+            let result = executor.execute(&query).unwrap();
+            let streamed: Vec<NodeId> = executor.execute_iter(&query).unwrap().collect();
+            assert_eq!(streamed, result.nodes.as_slice());
+            let db = executor.database.as_ref().unwrap();
+            result.nodes.as_slice().iter()
+                .map(|&id| db.get_node_word(id).unwrap().to_string())
+                .collect()
+        };
+        
+        assert_eq!(words(&mut executor, FilterCriteria::FrequencyRankBelow(1000)), ["the"]);
+        assert_eq!(words(&mut executor, FilterCriteria::ProductivityAbove(0.5)), ["un"]);
+        assert_eq!(words(&mut executor, FilterCriteria::ProductivityAbove(0.1)), ["un", "pseudo"]);
+        assert_eq!(
+            words(&mut executor, FilterCriteria::LayerIn(vec![Layer::Letters, Layer::Morphemes])),
+            ["un", "pseudo", "t"]
+        );
+        assert_eq!(words(&mut executor, FilterCriteria::Layer(Layer::Words)), ["the", "sesquipedalian"]);
+        assert!(words(&mut executor, FilterCriteria::LayerIn(Vec::new())).is_empty());
+    }
+    
+    #[test]
+    fn test_set_combinators() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        let manager = builder.add_node("manager", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.52)).unwrap();
+        let director = builder.add_node("director", Layer::Words, Coordinate3D::new(0.52, 0.5, 0.52)).unwrap();
+        let supervisor = builder.add_node("supervisor", Layer::Words, Coordinate3D::new(0.5, 0.53, 0.52)).unwrap();
+        let vision = builder.add_node("vision", Layer::Words, Coordinate3D::new(0.1, 0.5, 0.52)).unwrap();
+        let leadership = builder.add_node("leadership", Layer::Concepts, Coordinate3D::new(0.3, 0.5, 0.82)).unwrap();
+        for target in [director, vision, supervisor] {
+            builder.add_connection(leadership, target, ConnectionType::Hypernymy, 0.8).unwrap();
+        }
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        let similar = || QueryBuilder::find("manager").similar_threshold(0.9);
+        let connected = || QueryBuilder::find("leadership").follow_connection_type(ConnectionType::Hypernymy);
+        let run = |executor: &mut LingoExecutor, query: QueryBuilder| -> Vec<NodeId> {
+            executor.execute(&query.compile()).unwrap().nodes.into_vec()
+        };
+        
+        let near = run(&mut executor, similar());
+        let linked = run(&mut executor, connected());
+        assert_eq!(near, vec![manager, director, supervisor]);
+        
+        let both = run(&mut executor, similar().intersect(connected()));
+        assert_eq!(both, vec![director, supervisor]);
+        assert!(both.iter().all(|id| near.contains(id) && linked.contains(id)));
+        
+        let either = run(&mut executor, similar().union(connected()));
+        assert_eq!(either, vec![manager, director, supervisor, vision]);
+        
+        let only_near = run(&mut executor, similar().difference(connected()));
+        assert_eq!(only_near, vec![manager]);
+        
+        // An empty left side skips the right branch entirely
+        let query = QueryBuilder::find("nobody").intersect(connected()).compile();
+        let result = executor.execute(&query).unwrap();
+        assert!(result.nodes.is_empty());
+        assert_eq!(result.instructions_executed, 2);
+        let estimate = |executor: &mut LingoExecutor, query: QueryBuilder| executor.estimate_result_size(&query.compile());
+        let branches = (estimate(&mut executor, similar()), estimate(&mut executor, connected()));
+        assert_eq!(estimate(&mut executor, similar().intersect(connected())), branches.0.min(branches.1));
+        assert_eq!(estimate(&mut executor, similar().union(connected())), (branches.0 + branches.1).min(5));
+        
+        // Streams combine the same way
+        let query = similar()
+            .union(QueryBuilder::find("vision"))
+            .difference(QueryBuilder::find("director"))
+            .compile();
+        let streamed: Vec<NodeId> = executor.execute_iter(&query).unwrap().collect();
+        assert_eq!(streamed, vec![manager, supervisor, vision]);
+        assert_eq!(streamed, executor.execute(&query).unwrap().nodes.into_vec());
+    }
+    
+    #[test]
+    fn test_budget_aborts_expensive_query() {
+        use crate::core::{ConnectionType, Layer};
+        use crate::core::error::LingoError;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        // A hub tied to every spoke, and every spoke tied back to it
+        let mut builder = DatabaseBuilder::new();
+        let hub = builder.add_node("hub", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.52)).unwrap();
+        for i in 0..300 {
+            let position = Coordinate3D::new(i as f32 / 300.0, 0.2, 0.52);
+            let spoke = builder.add_node(&format!("spoke{}", i), Layer::Words, position).unwrap();
+            builder.add_connection(hub, spoke, ConnectionType::Collocation, 0.5).unwrap();
+            builder.add_connection(spoke, hub, ConnectionType::Collocation, 0.5).unwrap();
+        }
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let one_hop = QueryBuilder::find("hub").follow_connection_type(ConnectionType::Collocation).compile();
+        let deep = QueryBuilder::find("hub")
+            .follow_connection_type(ConnectionType::Collocation)
+            .follow_connection_type(ConnectionType::Collocation)
+            .follow_connection_type(ConnectionType::Collocation)
+            .compile();
+        // Each hop multiplies the estimate by the assumed fan-out
+        assert!(deep.estimated_cost > 100 * one_hop.estimated_cost);
+        
+        match executor.execute_within(&deep, 100) {
+            Err(LingoError::BudgetExceeded { spent, budget }) => {
+                assert_eq!(budget, 100);
+                assert!(spent > 100);
+            }
+            other => panic!("expected BudgetExceeded, got {:?}", other.map(|r| r.nodes.len())),
+        }
+        
+        // The executor is ready for the next query, and cheap ones fit
+        let result = executor.execute_within(&QueryBuilder::find("hub").compile(), 100).unwrap();
+        assert_eq!(result.nodes.as_slice(), &[hub]);
+        assert_eq!(executor.execute(&deep).unwrap().nodes.len(), 300);
+    }
+    
+    #[test]
+    fn test_budget_stops_expanding_ops_early() {
+        use crate::core::{BoundingBox3D, Layer};
+        use crate::core::error::LingoError;
+        use crate::security::ExecutionLimits;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("hub", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.52)).unwrap();
+        for i in 0..300 {
+            let position = Coordinate3D::new(i as f32 / 300.0, 0.2, 0.52);
+            builder.add_node(&format!("spoke{}", i), Layer::Words, position).unwrap();
+        }
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        // Each op stops one node past the budget instead of producing all 300
+        let whole_space = BoundingBox3D::new(Coordinate3D::new(0.0, 0.0, 0.0), Coordinate3D::new(1.0, 1.0, 1.0));
+        let queries = [
+            QueryBuilder::find("hub").nearest_k(10_000).no_limit().compile(),
+            QueryBuilder::spatial_box(whole_space).no_limit().compile(),
+        ];
+        for query in &queries {
+            match executor.execute_within(query, 50) {
+                Err(LingoError::BudgetExceeded { spent, budget }) => assert_eq!(spent, budget + 1),
+                other => panic!("expected BudgetExceeded, got {:?}", other.map(|r| r.nodes.len())),
+            }
+        }
+        
+        // explain applies the executor's budget as well
+        assert!(executor.explain(&queries[0]).error.is_none());
+        executor.set_limits(ExecutionLimits::default().with_budget(50));
+        let trace = executor.explain(&queries[0]);
+        let error = trace.error.expect("explain should stop at the budget");
+        assert!(error.contains("budget"), "{}", error);
+        assert_eq!(trace.steps.last().unwrap().op, SlangOp::SpatialNearest);
+    }
+    
+    #[test]
+    fn test_execution_emits_span_with_fields() {
+        use crate::core::Layer;
+        use crate::test_support::executor_with;
+        use std::sync::Mutex;
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing_subscriber::layer::{self, Context, SubscriberExt};
+        
+        /// Spans and events by name, with their fields as debug strings
+        #[derive(Clone, Default)]
+        struct Capture {
+            spans: Arc<Mutex<HashMap<u64, (String, HashMap<String, String>)>>>,
+            events: Arc<Mutex<Vec<HashMap<String, String>>>>,
+        }
+        
+        struct Fields<'a>(&'a mut HashMap<String, String>);
+        
+        impl Visit for Fields<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0.insert(field.name().to_string(), format!("{:?}", value));
+            }
+        }
+        
+        impl<S: tracing::Subscriber> layer::Layer<S> for Capture {
+            fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _: Context<'_, S>) {
+                let mut fields = HashMap::new();
+                attrs.record(&mut Fields(&mut fields));
+                self.spans.lock().unwrap().insert(id.into_u64(), (attrs.metadata().name().to_string(), fields));
+            }
+            
+            fn on_record(&self, id: &Id, values: &Record<'_>, _: Context<'_, S>) {
+                if let Some((_, fields)) = self.spans.lock().unwrap().get_mut(&id.into_u64()) {
+                    values.record(&mut Fields(fields));
+                }
+            }
+            
+            fn on_event(&self, event: &tracing::Event<'_>, _: Context<'_, S>) {
+                let mut fields = HashMap::new();
+                event.record(&mut Fields(&mut fields));
+                self.events.lock().unwrap().push(fields);
+            }
+        }
+        
+        let (_dir, mut executor) = executor_with(&[
+            ("happy", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.52)),
+            ("glad", Layer::Words, Coordinate3D::new(0.52, 0.5, 0.52)),
+        ]);
+        let query = QueryBuilder::find("happy").similar().compile();
+        
+        let capture = Capture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let result = tracing::subscriber::with_default(subscriber, || executor.execute(&query).unwrap());
+        
+        let spans = capture.spans.lock().unwrap();
+        let (_, fields) = spans.values()
+            .find(|(name, _)| name == "execute_query")
+            .expect("execute_query span");
+        assert_eq!(fields["ops"], query.bytecode.len().to_string());
+        assert_eq!(fields["estimated_cost"], query.estimated_cost.to_string());
+        assert_eq!(fields["result_count"], result.nodes.len().to_string());
+        assert_eq!(fields["instructions_executed"], result.instructions_executed.to_string());
+        assert!(fields.contains_key("execution_time_us"));
+        
+        // One debug event per instruction, with the size of the set it left
+        let events = capture.events.lock().unwrap();
+        let ops: Vec<(&str, &str)> = events.iter()
+            .filter(|fields| fields.get("message").is_some_and(|m| m == "Executed instruction"))
+            .map(|fields| (fields["op"].as_str(), fields["nodes"].as_str()))
+            .collect();
+        assert_eq!(ops, [("LoadNode", "1"), ("FindSimilar", "2"), ("Halt", "2")]);
+    }
+    
+    #[test]
+    fn test_shared_database_across_threads() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        fn assert_send<T: Send>() {}
+        assert_send::<LingoExecutor>();
+        
+        let mut builder = DatabaseBuilder::new();
+        for i in 0..400 {
+            let position = Coordinate3D::new(0.05 + (i % 20) as f32 * 0.045, 0.05 + (i / 20) as f32 * 0.045, 0.52);
+            builder.add_node(&format!("w{}", i), Layer::Words, position).unwrap();
+        }
+        let (_dir, path) = build_database(&builder);
+        
+        let mut executor = LingoExecutor::new();
+        assert!(executor.try_clone().is_err());
+        executor.load_database(&path).unwrap();
+        executor.set_default_limit(Some(7));
+        executor.warmup();
+        
+        let query = |i: usize| QueryBuilder::find(&format!("w{}", i)).similar_threshold(0.9).compile();
+        let expected: Vec<Vec<NodeId>> = (0..40)
+            .map(|i| executor.execute(&query(i)).unwrap().nodes.as_slice().to_vec())
+            .collect();
+        
+        let shared = Arc::clone(executor.database.as_ref().unwrap());
+        std::thread::scope(|scope| {
+            for t in 0..4 {
+                let mut worker = if t % 2 == 0 {
+                    executor.try_clone().unwrap()
+                } else {
+                    let mut worker = LingoExecutor::from_shared(Arc::clone(&shared));
+                    worker.set_default_limit(Some(7));
+                    worker
+                };
+                let expected = &expected;
+                scope.spawn(move || {
+                    for round in 0..5 {
+                        for (i, nodes) in expected.iter().enumerate() {
+                            if (i + round + t) % 3 == 0 {
+                                let result = worker.execute(&query(i)).unwrap();
+                                assert_eq!(result.nodes.as_slice(), nodes.as_slice());
+                            }
+                        }
+                    }
+                    assert!(worker.queries_executed() > 0);
+                });
+            }
+        });
+        
+        // Clones share the map rather than reopening it
+        assert!(Arc::strong_count(&shared) >= 2);
+        assert_eq!(executor.try_clone().unwrap().queries_executed(), 0);
+    }
+    
+    #[test]
+    fn test_resolve_serializes_to_json() {
+        use crate::core::{EtymologyOrigin, Layer, MorphemeType, NodeFlags};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        let tele = builder.add_node_full(
+            "tele",
+            Layer::Morphemes,
+            Coordinate3D::new(0.3, 0.7, 0.3),
+            EtymologyOrigin::Greek,
+            MorphemeType::Prefix,
+            NodeFlags::empty(),
+        ).unwrap();
+        let (_dir, path) = build_database(&builder);
+        
+        let mut executor = LingoExecutor::new();
+        assert!(executor.resolve(&QueryResult::new(NodeSet::new())).is_err());
+        executor.load_database(&path).unwrap();
+        
+        let mut result = executor.execute(&QueryBuilder::find("tele").compile()).unwrap();
+        // IDs missing from the database are skipped rather than failing the batch
+        result.nodes.push(NodeId(999));
+        
+        let resolved = executor.resolve(&result).unwrap();
+        assert_eq!(resolved.nodes.len(), 1);
+        
+        let json = serde_json::to_value(&resolved).unwrap();
+        let node = &json["nodes"][0];
+        assert_eq!(node["id"], tele.0);
+        assert_eq!(node["word"], "tele");
+        assert_eq!(node["layer"], "Morphemes");
+        assert_eq!(node["etymology"], "Greek");
+        assert_eq!(node["morpheme_type"], "Prefix");
+        assert!((node["position"]["y"].as_f64().unwrap() - 0.7).abs() < 1e-6);
+        assert!(node.get("score").is_none());
+        
+        let round_trip: ResolvedResult = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip, resolved);
+    }
+    
+    #[test]
+    fn test_prefer_frequent_breaks_ties() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::executor_from;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("big", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5)).unwrap();
+        let rare = builder.add_node("sizeable", Layer::Words, Coordinate3D::new(0.4, 0.5, 0.5)).unwrap();
+        let common = builder.add_node("large", Layer::Words, Coordinate3D::new(0.6, 0.5, 0.5)).unwrap();
+        builder.node_mut(rare).unwrap().frequency_rank = 12_000;
+        builder.node_mut(common).unwrap().frequency_rank = 300;
+        
+        let (_dir, mut executor) = executor_from(&builder);
+        
+        let plain = executor.execute(&QueryBuilder::find("big").similar().compile()).unwrap();
+        let plain = plain.nodes.as_slice();
+        let rare_pos = plain.iter().position(|&id| id == rare).unwrap();
+        let common_pos = plain.iter().position(|&id| id == common).unwrap();
+        assert!(rare_pos < common_pos);
+        
+        let query = QueryBuilder::find("big").similar().prefer_frequent().compile();
+        let ranked = executor.execute(&query).unwrap();
+        let ranked = ranked.nodes.as_slice();
+        let rare_pos = ranked.iter().position(|&id| id == rare).unwrap();
+        let common_pos = ranked.iter().position(|&id| id == common).unwrap();
+        assert!(common_pos < rare_pos);
+    }
+    
     #[test]
     fn test_execute_limit() {
         let mut executor = LingoExecutor::new();
@@ -1084,7 +4542,7 @@ mod tests {
             SlangInstruction::with_operand1(SlangOp::LoadNode, 0), // Should not execute
         ];
         
-        let result = executor.execute_bytecode(&bytecode).unwrap();
+        let result = executor.execute_bytecode(&bytecode, &ExecutionLimits::default()).unwrap();
         assert_eq!(executor.instructions_executed, 1); // Only LoadNode, Halt doesn't increment
     }
     
@@ -1096,7 +4554,7 @@ mod tests {
         // We need more than 10000 instructions since we check > 10000
         let bytecode: Vec<_> = (0..10002).map(|_| SlangInstruction::new(SlangOp::Nop)).collect();
         
-        let result = executor.execute_bytecode(&bytecode);
+        let result = executor.execute_bytecode(&bytecode, &ExecutionLimits::default());
         assert!(result.is_err(), "Expected error from instruction limit");
         let err_str = result.unwrap_err().to_string();
         assert!(err_str.contains("Instruction limit exceeded"), "Error was: {}", err_str);
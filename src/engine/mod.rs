@@ -69,5 +69,7 @@
 //! ```
 
 pub mod executor;
+mod stream;
 
-pub use executor::{LingoExecutor, QueryResult, NodeSet, ExecutionStats};
\ No newline at end of file
+pub use executor::{LingoExecutor, QueryResult, ExecutionTrace, TraceStep, ResolvedNode, ResolvedResult, NodeSet, ExecutionStats, VerticalContext, DEFAULT_RESULT_LIMIT};
+pub use stream::ResultStream;
\ No newline at end of file
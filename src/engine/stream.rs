@@ -0,0 +1,311 @@
+// Copyright 2025 Roberto Antunes
+//
+// Licensed under the Functional Source License, Version 1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://github.com/RobAntunes/lingodb/blob/main/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Lazy query execution that yields nodes as they are produced
+
+use crate::core::{
+    NodeId, SimilarityMetric,
+    bytecode::{instruction_flags, sort_keys, SlangInstruction, SlangOp},
+    error::{LingoError, Result},
+};
+use crate::query::CompiledQuery;
+use crate::storage::Database;
+use super::LingoExecutor;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+type NodeStream<'a> = Box<dyn Iterator<Item = NodeId> + 'a>;
+
+/// Node IDs from a query, produced on demand.
+///
+/// Returned by [`LingoExecutor::execute_iter`]. Each instruction becomes an
+/// iterator stage over the memory-mapped database, so stopping early skips
+/// the work for the nodes never asked for.
+///
+/// Word lookups and `spatial_box` scan lazily. Similarity searches have to
+/// rank every candidate around a seed before the nearest can be emitted, so
+/// they buffer one seed's candidates at a time; a `sort` buffers the whole
+/// set. Queries with a step that has no lazy stage (layer navigation,
+/// connection traversal, nearest-neighbour searches and the like) run in
+/// full through [`LingoExecutor::execute`]'s interpreter, and the stream
+/// yields from that buffered result. Nodes come out in the same order as
+/// [`LingoExecutor::execute`], without duplicates.
+pub struct ResultStream<'a> {
+    nodes: NodeStream<'a>,
+    examined: Rc<Cell<usize>>,
+}
+
+impl<'a> ResultStream<'a> {
+    /// Build the stage pipeline for `query`, yielding at most `cap` nodes
+    pub(super) fn new(
+        executor: &LingoExecutor,
+        db: &'a Database,
+        query: &CompiledQuery,
+        cap: usize,
+    ) -> Result<Self> {
+        let examined = Rc::new(Cell::new(0));
+        if !query.bytecode.iter().all(streamable) {
+            let buffered = executor.execute_buffered(query)?.into_vec();
+            return Ok(Self {
+                nodes: Box::new(buffered.into_iter().take(cap)),
+                examined,
+            });
+        }
+
+        let mut current: NodeStream<'a> = Box::new(std::iter::empty());
+        // Left sides of set operations whose right branch is being built
+        let mut pending: Vec<NodeStream<'a>> = Vec::new();
+
+        for instruction in &query.bytecode {
+            if matches!(
+                instruction.opcode,
+                SlangOp::LoadNode | SlangOp::LoadNodeId | SlangOp::LoadNodeIds | SlangOp::LoadPhrase | SlangOp::SpatialBox
+            ) {
+                pending.push(current);
+                current = Box::new(std::iter::empty());
+            }
+
+            current = match instruction.opcode {
+                SlangOp::LoadNode => load_node(db, query, instruction, &examined)?,
+                SlangOp::LoadPhrase => {
+                    let phrase = query.string_cache
+                        .get(instruction.operand1 as usize)
+                        .ok_or_else(|| LingoError::Execution("Invalid string ID".to_string()))?;
+                    // Neighborhoods have to be intersected before anything is known
+                    Box::new(LingoExecutor::phrase_nodes(db, phrase).into_iter())
+                }
+                SlangOp::LoadNodeId => {
+                    let node_id = NodeId(instruction.operand2);
+                    if db.get_node(node_id).is_err() {
+                        return Err(LingoError::Execution(format!("Node {} not found", node_id.0)));
+                    }
+                    Box::new(std::iter::once(node_id))
+                }
+                SlangOp::LoadNodeIds => {
+                    let ids = query.node_lists
+                        .get(instruction.operand1 as usize)
+                        .ok_or_else(|| LingoError::Execution("Invalid node list ID".to_string()))?;
+                    if let Some(missing) = ids.iter().find(|&&id| db.get_node(id).is_err()) {
+                        return Err(LingoError::Execution(format!("Node {} not found", missing.0)));
+                    }
+                    dedup(Box::new(ids.clone().into_iter()))
+                }
+                SlangOp::SpatialBox => {
+                    let bounds = *query.regions
+                        .get(instruction.operand1 as usize)
+                        .ok_or_else(|| LingoError::Execution("Invalid region ID".to_string()))?;
+                    let examined = Rc::clone(&examined);
+                    Box::new(db.nodes().iter().filter_map(move |node| {
+                        examined.set(examined.get() + 1);
+                        bounds.contains(node.position).then_some(node.id)
+                    }))
+                }
+                SlangOp::FindSimilar => {
+                    let metric = LingoExecutor::similarity_metric(&query.metrics, instruction)?;
+                    find_similar(db, current, instruction, metric, &examined)
+                }
+                SlangOp::LayerSet => {
+                    let layer = instruction.operand1 as u8;
+                    Box::new(current.filter(move |&id| {
+                        db.get_node(id).is_ok_and(|node| node.layer as u8 == layer)
+                    }))
+                }
+                SlangOp::Filter => {
+                    let keep = LingoExecutor::node_filter(instruction)?;
+                    Box::new(current.filter(move |&id| db.get_node(id).is_ok_and(&keep)))
+                }
+                SlangOp::Limit => Box::new(current.take(instruction.operand1 as usize)),
+                SlangOp::Offset => Box::new(dedup(current).skip(instruction.operand2 as usize)),
+                SlangOp::Sort => {
+                    // Ordering needs every node, so this stage buffers the set
+                    let mut ids: Vec<NodeId> = dedup(current).collect();
+                    LingoExecutor::sort_nodes(Some(db), &mut ids, instruction, &HashMap::new())?;
+                    Box::new(ids.into_iter())
+                }
+                SlangOp::Exclude | SlangOp::ExcludeNear => {
+                    let excluded = LingoExecutor::exclusion(
+                        Some(db),
+                        &query.string_cache,
+                        &query.node_lists,
+                        instruction,
+                    )?;
+                    Box::new(current.filter(move |&id| !excluded(id)))
+                }
+                SlangOp::Union => {
+                    let lower = pending.pop().unwrap_or_else(|| Box::new(std::iter::empty()));
+                    dedup(Box::new(lower.chain(current)))
+                }
+                SlangOp::Intersect | SlangOp::Difference => {
+                    let lower = pending.pop().unwrap_or_else(|| Box::new(std::iter::empty()));
+                    combine(lower, current, instruction.opcode == SlangOp::Intersect)
+                }
+                // Duplicates are always removed below; SpatialNeighbors passes
+                // its input through, as in `execute`
+                // An empty left side never pulls its right branch, so streams
+                // short-circuit without jumping
+                SlangOp::Deduplicate | SlangOp::SpatialNeighbors | SlangOp::Branch | SlangOp::Nop => current,
+                SlangOp::Halt => break,
+                // `streamable` sent everything else to the buffered path
+                _ => unreachable!("{:?} has no stream stage", instruction.opcode),
+            };
+        }
+
+        Ok(Self {
+            nodes: Box::new(dedup(current).take(cap)),
+            examined,
+        })
+    }
+
+    /// Number of nodes the stream has inspected so far.
+    ///
+    /// Counts candidates looked at by word scans, box scans and similarity
+    /// searches, whether or not they were yielded. Queries that ran in full
+    /// through the buffered path report 0.
+    pub fn nodes_examined(&self) -> usize {
+        self.examined.get()
+    }
+}
+
+// Implement Debug manually since the stages are boxed closures
+impl std::fmt::Debug for ResultStream<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResultStream")
+            .field("nodes_examined", &self.nodes_examined())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Iterator for ResultStream<'_> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        self.nodes.next()
+    }
+}
+
+/// Whether `instruction` has a lazy stage in [`ResultStream::new`]
+fn streamable(instruction: &SlangInstruction) -> bool {
+    matches!(
+        instruction.opcode,
+        SlangOp::LoadNode
+            | SlangOp::LoadPhrase
+            | SlangOp::LoadNodeId
+            | SlangOp::LoadNodeIds
+            | SlangOp::SpatialBox
+            | SlangOp::FindSimilar
+            | SlangOp::LayerSet
+            | SlangOp::Filter
+            | SlangOp::Limit
+            | SlangOp::Offset
+            | SlangOp::Exclude
+            | SlangOp::ExcludeNear
+            | SlangOp::Union
+            | SlangOp::Intersect
+            | SlangOp::Difference
+            | SlangOp::Deduplicate
+            | SlangOp::SpatialNeighbors
+            | SlangOp::Branch
+            | SlangOp::Nop
+            | SlangOp::Halt
+    ) || (
+        // Streams don't keep the similarity scores a distance sort ranks by
+        instruction.opcode == SlangOp::Sort && instruction.operand1 != sort_keys::DISTANCE
+    )
+}
+
+/// Word lookup; exact matches scan lazily, folded and phonetic ones use their index
+fn load_node<'a>(
+    db: &'a Database,
+    query: &CompiledQuery,
+    instruction: &SlangInstruction,
+    examined: &Rc<Cell<usize>>,
+) -> Result<NodeStream<'a>> {
+    let word = query.string_cache
+        .get(instruction.operand1 as usize)
+        .ok_or_else(|| LingoError::Execution("Invalid string ID".to_string()))?
+        .clone();
+
+    let indexed = instruction_flags::FOLD_ACCENTS | instruction_flags::PHONETIC;
+    if instruction.flags & indexed != 0 {
+        return Ok(Box::new(LingoExecutor::lookup_word(db, &word, instruction.flags).into_iter()));
+    }
+
+    let examined = Rc::clone(examined);
+    Ok(Box::new(db.nodes().iter().filter_map(move |node| {
+        examined.set(examined.get() + 1);
+        let matches = db.get_string(node.word_offset, node.word_length)
+            .is_ok_and(|node_word| node_word == word);
+        matches.then_some(node.id)
+    })))
+}
+
+/// Similarity search, buffering one seed's ranked candidates at a time
+fn find_similar<'a>(
+    db: &'a Database,
+    seeds: NodeStream<'a>,
+    instruction: &SlangInstruction,
+    metric: SimilarityMetric,
+    examined: &Rc<Cell<usize>>,
+) -> NodeStream<'a> {
+    let radius = 1.0 - (instruction.operand1 as f32) / 65535.0;
+    let limit = (instruction.flags & instruction_flags::HAS_LIMIT != 0)
+        .then_some(instruction.operand2 as usize);
+    let prefer_frequent = instruction.flags & instruction_flags::PREFER_FREQUENT != 0;
+
+    let examined = Rc::clone(examined);
+    let similar = seeds.flat_map(move |seed| {
+        let Ok(node) = db.get_node(seed) else {
+            return Vec::new();
+        };
+        let scan_limit = if prefer_frequent { None } else { limit };
+        let (similar, compared) = db.scan_similar(node.position, radius, scan_limit, metric);
+        examined.set(examined.get() + compared);
+        if prefer_frequent {
+            let mut ranked = LingoExecutor::prefer_frequent(db, node.position, similar, metric);
+            if let Some(limit) = limit {
+                ranked.truncate(limit);
+            }
+            ranked
+        } else {
+            similar
+        }
+    });
+
+    // `execute` applies the limit again across all seeds after merging
+    let similar = dedup(Box::new(similar));
+    match limit {
+        Some(limit) => Box::new(similar.take(limit)),
+        None => similar,
+    }
+}
+
+/// Nodes of `lower` that are (or with `keep` false, aren't) in `other`.
+///
+/// `other` is collected on the first pull, so it never runs when `lower`
+/// is empty.
+fn combine<'a>(lower: NodeStream<'a>, other: NodeStream<'a>, keep: bool) -> NodeStream<'a> {
+    let mut other = Some(other);
+    let mut members: Option<HashSet<NodeId>> = None;
+    Box::new(lower.filter(move |id| {
+        let members = members.get_or_insert_with(|| other.take().into_iter().flatten().collect());
+        members.contains(id) == keep
+    }))
+}
+
+/// Drop nodes that were already yielded
+fn dedup(nodes: NodeStream<'_>) -> NodeStream<'_> {
+    let mut seen = HashSet::new();
+    Box::new(nodes.filter(move |&id| seen.insert(id)))
+}
@@ -24,6 +24,36 @@
 //! 
 //! ## Usage
 //! 
+//! There are two ways in. The minimal API opens a database and looks up
+//! similar words in one call each. The handle API builds a query step by
+//! step and hands back a result handle to read nodes and statistics from.
+//! Every handle a function returns must be released with its matching
+//! `_free` or `lingo_close` call.
+//!
+//! ## Minimal API
+//!
+//! For bindings that only need word lookups, `lingo_open`, `lingo_find`
+//! and `lingo_close` cover the whole lifecycle:
+//!
+//! ```c
+//! LingoExecutor* db = lingo_open("english.lingo");
+//! if (!db) { fprintf(stderr, "%s\n", lingo_last_error()); }
+//!
+//! // First call asks for the count, second fills the buffer
+//! size_t len = 0;
+//! lingo_find(db, "happy", 0.7f, 10, NULL, &len);
+//! uint32_t* ids = malloc(len * sizeof(uint32_t));
+//! lingo_find(db, "happy", 0.7f, 10, ids, &len);
+//!
+//! free(ids);
+//! lingo_close(db);
+//! ```
+//!
+//! The one-shot search is `lingo_find` rather than `lingo_query_find`,
+//! which already names the query-handle constructor below.
+//!
+//! ## Handle API
+//!
 //! ```c
 //! // Create an executor
 //! LingoExecutor* executor = lingo_executor_new();
@@ -41,10 +71,11 @@
 //! lingo_executor_free(executor);
 //! ```
 
+use std::cell::RefCell;
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_int, c_uint, c_float};
 use std::ptr;
-use std::panic;
+use std::panic::{self, AssertUnwindSafe};
 
 use crate::engine::{LingoExecutor, QueryResult};
 use crate::query::{QueryBuilder, CompiledQuery};
@@ -67,6 +98,8 @@ pub enum LingoError {
     ExecutionError = 5,
     /// Out of memory
     OutOfMemory = 6,
+    /// Caller's buffer is too small; the required length was written back
+    BufferTooSmall = 7,
     /// Unknown error
     Unknown = 999,
 }
@@ -125,6 +158,164 @@ pub struct LingoStats {
     pub cache_hit: bool,
 }
 
+thread_local! {
+    /// Message for the latest failure of the minimal API on this thread
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<String>) {
+    let message = message.into().replace('\0', " ");
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run `f` for the minimal API: clear the last error, record any failure
+/// or panic as the new one, and never unwind into the caller
+fn guarded<T>(fallback: T, f: impl FnOnce() -> std::result::Result<T, (T, String)>) -> T {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => value,
+        Ok(Err((value, message))) => {
+            set_last_error(message);
+            value
+        }
+        Err(payload) => {
+            let detail = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            set_last_error(format!("panic in Lingo: {}", detail));
+            fallback
+        }
+    }
+}
+
+/// Read a C string argument, failing with a message naming it
+///
+/// # Safety
+///
+/// `value` must be null or point to a NUL-terminated string.
+unsafe fn c_str_arg<'a>(value: *const c_char, name: &str) -> std::result::Result<&'a str, (LingoError, String)> {
+    if value.is_null() {
+        return Err((LingoError::NullPointer, format!("{} is null", name)));
+    }
+    CStr::from_ptr(value)
+        .to_str()
+        .map_err(|_| (LingoError::InvalidString, format!("{} is not valid UTF-8", name)))
+}
+
+/// Opens a database, returning an executor handle for it.
+///
+/// The handle is owned by the caller and must be released with
+/// [`lingo_close`]. It also works with the `lingo_executor_*` functions.
+///
+/// # Returns
+///
+/// Executor handle, or null on failure; see [`lingo_last_error`].
+///
+/// # Safety
+///
+/// `path` must be null or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn lingo_open(path: *const c_char) -> *mut LingoExecutorHandle {
+    guarded(ptr::null_mut(), || {
+        let path = c_str_arg(path, "path").map_err(|(_, message)| (ptr::null_mut(), message))?;
+        let mut executor = LingoExecutor::new();
+        executor.load_database(path)
+            .map_err(|e| (ptr::null_mut(), format!("can't open {}: {}", path, e)))?;
+        Ok(Box::into_raw(Box::new(ExecutorWrapper { executor })) as *mut LingoExecutorHandle)
+    })
+}
+
+/// Finds nodes similar to `word`, writing their IDs to a caller buffer.
+///
+/// Uses the two-call pattern. On entry `*out_len` is the capacity of
+/// `out_ids` in elements; on return it is the number of results. Pass a
+/// null `out_ids` to ask for the count alone. When the capacity is too
+/// small nothing is written to `out_ids` and `BufferTooSmall` is returned.
+///
+/// `threshold` is the similarity threshold (0.0 to 1.0); a `limit` of 0
+/// applies the executor's default result limit. The buffer stays owned by
+/// the caller.
+///
+/// # Safety
+///
+/// `handle` must come from [`lingo_open`] (or `lingo_executor_new`) and not
+/// be closed, `word` must be a NUL-terminated string, `out_len` must be
+/// valid for reads and writes, and a non-null `out_ids` must have room for
+/// `*out_len` `u32`s.
+#[no_mangle]
+pub unsafe extern "C" fn lingo_find(
+    handle: *mut LingoExecutorHandle,
+    word: *const c_char,
+    threshold: c_float,
+    limit: c_uint,
+    out_ids: *mut u32,
+    out_len: *mut usize,
+) -> LingoError {
+    guarded(LingoError::Unknown, || {
+        if handle.is_null() || out_len.is_null() {
+            return Err((LingoError::NullPointer, "handle and out_len must not be null".to_string()));
+        }
+        let word = c_str_arg(word, "word")?;
+        let wrapper = &mut *(handle as *mut ExecutorWrapper);
+
+        let mut builder = QueryBuilder::find(word)
+            .try_similar_threshold(threshold)
+            .map_err(|e| (LingoError::QueryError, e.to_string()))?;
+        if limit > 0 {
+            builder = builder.limit(limit as usize);
+        }
+        let result = wrapper.executor.execute(&builder.compile())
+            .map_err(|e| (LingoError::ExecutionError, e.to_string()))?;
+
+        let ids = result.nodes.as_slice();
+        let capacity = *out_len;
+        *out_len = ids.len();
+        if out_ids.is_null() {
+            return Ok(LingoError::Success);
+        }
+        if capacity < ids.len() {
+            return Err((
+                LingoError::BufferTooSmall,
+                format!("{} results don't fit in a buffer of {}", ids.len(), capacity),
+            ));
+        }
+        for (i, id) in ids.iter().enumerate() {
+            *out_ids.add(i) = id.0;
+        }
+        Ok(LingoError::Success)
+    })
+}
+
+/// Closes a handle from [`lingo_open`], releasing the executor and its
+/// database. Null is ignored.
+///
+/// # Safety
+///
+/// `handle` must be null or a handle that hasn't been closed or freed.
+#[no_mangle]
+pub unsafe extern "C" fn lingo_close(handle: *mut LingoExecutorHandle) {
+    guarded((), || {
+        if !handle.is_null() {
+            drop(Box::from_raw(handle as *mut ExecutorWrapper));
+        }
+        Ok(())
+    })
+}
+
+/// Describes the latest failure of `lingo_open`, `lingo_find` or
+/// `lingo_close` on the calling thread.
+///
+/// # Returns
+///
+/// Error message, or null if the last of those calls succeeded. The string
+/// is owned by Lingo (do not free) and stays valid until the next of those
+/// calls on the same thread.
+#[no_mangle]
+pub extern "C" fn lingo_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
 // Internal storage for FFI objects
 struct ExecutorWrapper {
     executor: LingoExecutor,
@@ -468,7 +659,7 @@ pub extern "C" fn lingo_version() -> *const c_char {
 fn convert_result_to_ffi(executor: &LingoExecutor, result: &QueryResult) -> Vec<LingoNode> {
     let mut nodes = Vec::new();
     
-    if let Some(db) = &executor.database {
+    if let Some(db) = executor.database() {
         for &node_id in result.nodes.as_slice() {
             if let (Ok(word), Ok(node)) = (db.get_node_word(node_id), db.get_node(node_id)) {
                 let word_cstring = match CString::new(word) {
@@ -515,6 +706,53 @@ mod tests {
         lingo_query_free(query);
     }
     
+    #[test]
+    fn test_minimal_api_two_call_find() {
+        use crate::core::{Coordinate3D, Layer};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("happy", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5)).unwrap();
+        builder.add_node("glad", Layer::Words, Coordinate3D::new(0.52, 0.5, 0.5)).unwrap();
+        builder.add_node("far", Layer::Words, Coordinate3D::new(0.0, 0.0, 0.5)).unwrap();
+        let (temp_dir, path) = build_database(&builder);
+        
+        unsafe {
+            let missing = CString::new(temp_dir.path().join("missing.lingo").to_str().unwrap()).unwrap();
+            assert!(lingo_open(missing.as_ptr()).is_null());
+            let message = CStr::from_ptr(lingo_last_error()).to_str().unwrap();
+            assert!(message.contains("missing.lingo"), "{}", message);
+            
+            let path = CString::new(path.to_str().unwrap()).unwrap();
+            let handle = lingo_open(path.as_ptr());
+            assert!(!handle.is_null());
+            assert!(lingo_last_error().is_null());
+            
+            let word = CString::new("happy").unwrap();
+            let mut len = 0;
+            assert_eq!(lingo_find(handle, word.as_ptr(), 0.9, 0, ptr::null_mut(), &mut len), LingoError::Success);
+            assert_eq!(len, 2);
+            
+            let mut ids = vec![0u32; 1];
+            let mut capacity = ids.len();
+            assert_eq!(
+                lingo_find(handle, word.as_ptr(), 0.9, 0, ids.as_mut_ptr(), &mut capacity),
+                LingoError::BufferTooSmall
+            );
+            assert_eq!((capacity, ids[0]), (2, 0));
+            
+            ids.resize(len, 0);
+            assert_eq!(lingo_find(handle, word.as_ptr(), 0.9, 0, ids.as_mut_ptr(), &mut len), LingoError::Success);
+            assert_eq!(ids, vec![1, 2]);
+            
+            assert_eq!(lingo_find(handle, word.as_ptr(), 2.0, 0, ptr::null_mut(), &mut len), LingoError::QueryError);
+            assert!(!lingo_last_error().is_null());
+            
+            lingo_close(handle);
+        }
+    }
+    
     #[test]
     fn test_version() {
         let version = lingo_version();
@@ -3,5 +3,5 @@
 pub mod octree;
 pub mod vertical;
 
-pub use octree::{SpatialIndex, OctreeBuilder, OctreeNode};
+pub use octree::{SpatialIndex, OctreeBuilder, OctreeNode, OctreeStats};
 pub use vertical::VerticalIndex;
\ No newline at end of file
@@ -5,12 +5,14 @@
 //! O(log n) spatial queries for finding linguistic neighbors.
 
 use crate::core::{Coordinate3D, BoundingBox3D, NodeId};
-use std::collections::HashMap;
+use crate::core::error::{BuildError, Result};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
 
-/// Maximum depth of the octree (limits subdivision)
+/// Default maximum depth of the octree (limits subdivision)
 const MAX_OCTREE_DEPTH: u8 = 10;
 
-/// Maximum nodes per octree leaf before subdivision
+/// Default maximum nodes per octree leaf before subdivision
 const MAX_NODES_PER_LEAF: usize = 16;
 
 /// Octree node for spatial partitioning (64 bytes)
@@ -50,10 +52,14 @@ bitflags::bitflags! {
 pub struct OctreeBuilder {
     /// All octree nodes
     nodes: Vec<OctreeNode>,
-    /// Node buckets (leaf node -> contained node IDs)
-    node_buckets: HashMap<u32, Vec<NodeId>>,
+    /// Node buckets (leaf node -> contained nodes and their positions)
+    node_buckets: HashMap<u32, Vec<(NodeId, Coordinate3D)>>,
     /// Node positions for building
     positions: HashMap<NodeId, Coordinate3D>,
+    /// Depth at which octants stop subdividing
+    max_depth: u8,
+    /// Octants with more nodes than this are subdivided
+    max_leaf_nodes: usize,
 }
 
 impl OctreeBuilder {
@@ -63,9 +69,59 @@ impl OctreeBuilder {
             nodes: Vec::new(),
             node_buckets: HashMap::new(),
             positions: HashMap::new(),
+            max_depth: MAX_OCTREE_DEPTH,
+            max_leaf_nodes: MAX_NODES_PER_LEAF,
         }
     }
     
+    /// Limit how deep the tree may subdivide.
+    ///
+    /// Tightly clustered positions otherwise produce deep chains of octants
+    /// around the cluster, which slows neighbour searches. Octants at this
+    /// depth become leaves however many nodes they hold. Defaults to 10.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BuildError::InvalidOctreeConfig` if `depth` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::index::OctreeBuilder;
+    ///
+    /// let builder = OctreeBuilder::new().max_depth(6)?.max_leaf_nodes(32)?;
+    /// assert!(OctreeBuilder::new().max_depth(0).is_err());
+    /// # Ok::<(), lingo::core::error::LingoError>(())
+    /// ```
+    pub fn max_depth(mut self, depth: u8) -> Result<Self> {
+        if depth == 0 {
+            return Err(BuildError::InvalidOctreeConfig {
+                reason: "max_depth must be at least 1".to_string(),
+            }.into());
+        }
+        self.max_depth = depth;
+        Ok(self)
+    }
+    
+    /// Set how many nodes a leaf holds before it is subdivided.
+    ///
+    /// Larger leaves give a shallower tree with more distance checks per
+    /// leaf. Defaults to 16.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BuildError::InvalidOctreeConfig` if `count` is 0 or doesn't
+    /// fit the 16-bit per-octant node count.
+    pub fn max_leaf_nodes(mut self, count: usize) -> Result<Self> {
+        if count == 0 || count > u16::MAX as usize {
+            return Err(BuildError::InvalidOctreeConfig {
+                reason: format!("max_leaf_nodes must be between 1 and {}", u16::MAX),
+            }.into());
+        }
+        self.max_leaf_nodes = count;
+        Ok(self)
+    }
+    
     /// Add a node to be indexed
     pub fn add_node(&mut self, node_id: NodeId, position: Coordinate3D) {
         self.positions.insert(node_id, position);
@@ -103,7 +159,7 @@ impl OctreeBuilder {
         let node_index = self.nodes.len() as u32;
         
         // Check if we should create a leaf node
-        if nodes.len() <= MAX_NODES_PER_LEAF || depth >= MAX_OCTREE_DEPTH {
+        if nodes.len() <= self.max_leaf_nodes || depth >= self.max_depth {
             // Create leaf node
             let octree_node = OctreeNode {
                 bounds,
@@ -114,9 +170,8 @@ impl OctreeBuilder {
                 flags: OctreeFlags::IS_LEAF,
             };
             
-            // Store node IDs in bucket
-            let node_ids: Vec<NodeId> = nodes.iter().map(|(id, _)| *id).collect();
-            self.node_buckets.insert(node_index, node_ids);
+            // Store nodes in bucket, keeping positions for distance checks
+            self.node_buckets.insert(node_index, nodes.to_vec());
             
             self.nodes.push(octree_node);
             return node_index;
@@ -195,7 +250,7 @@ pub struct SpatialIndex {
     /// All octree nodes
     nodes: Vec<OctreeNode>,
     /// Node buckets for leaf nodes
-    node_buckets: HashMap<u32, Vec<NodeId>>,
+    node_buckets: HashMap<u32, Vec<(NodeId, Coordinate3D)>>,
 }
 
 impl SpatialIndex {
@@ -218,8 +273,94 @@ impl SpatialIndex {
         self.root_index
     }
     
+    /// Number of nodes stored in the index
+    pub fn len(&self) -> usize {
+        self.node_buckets.values().map(Vec::len).sum()
+    }
+    
+    /// Returns true if the index holds no nodes
+    pub fn is_empty(&self) -> bool {
+        self.node_buckets.values().all(Vec::is_empty)
+    }
+    
+    /// Assemble an index from previously built octree nodes and leaf buckets
+    pub(crate) fn from_parts(
+        root_index: u32,
+        nodes: Vec<OctreeNode>,
+        node_buckets: HashMap<u32, Vec<(NodeId, Coordinate3D)>>,
+    ) -> Self {
+        Self { root_index, nodes, node_buckets }
+    }
+    
+    /// Add a node without rebuilding the tree.
+    ///
+    /// The node goes into the leaf covering its position, and a new leaf is
+    /// created if that octant was empty. Leaves are never split here, so after
+    /// many inserts a fresh [`OctreeBuilder`] build gives faster queries.
+    pub fn insert(&mut self, node_id: NodeId, position: Coordinate3D) {
+        if self.nodes.is_empty() {
+            self.root_index = 0;
+            self.nodes.push(OctreeNode {
+                bounds: BoundingBox3D::new(Coordinate3D::zero(), Coordinate3D::new(1.0, 1.0, 1.0)),
+                children: [0; 8],
+                node_count: 0,
+                node_offset: 0,
+                depth: 0,
+                flags: OctreeFlags::IS_LEAF,
+            });
+        }
+        
+        // Index 0 is the root, so new children never collide with "no child"
+        let child_index = self.nodes.len() as u32;
+        let mut index = self.root_index;
+        loop {
+            let node = &mut self.nodes[index as usize];
+            node.node_count = node.node_count.saturating_add(1);
+            if node.flags.contains(OctreeFlags::IS_LEAF) {
+                let key = node.node_offset;
+                self.node_buckets.entry(key).or_default().push((node_id, position));
+                return;
+            }
+            
+            let octant = OctreeBuilder::get_octant(node.bounds.center(), position);
+            if node.children[octant] != 0 {
+                index = node.children[octant];
+                continue;
+            }
+            
+            let child = OctreeNode {
+                bounds: OctreeBuilder::get_octant_bounds(node.bounds, octant),
+                children: [0; 8],
+                node_count: 1,
+                node_offset: child_index,
+                depth: node.depth + 1,
+                flags: OctreeFlags::IS_LEAF,
+            };
+            node.children[octant] = child_index;
+            self.nodes.push(child);
+            self.node_buckets.insert(child_index, vec![(node_id, position)]);
+            return;
+        }
+    }
+    
+    /// Nodes stored in a leaf, keyed by the leaf's `node_offset`
+    pub(crate) fn leaf_entries(&self, key: u32) -> &[(NodeId, Coordinate3D)] {
+        self.node_buckets.get(&key).map_or(&[], Vec::as_slice)
+    }
+    
     /// Find all nodes within a given radius of a center point
     pub fn find_within_radius(&self, center: Coordinate3D, radius: f32) -> Vec<NodeId> {
+        self.within_radius_with_pos(center, radius)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect()
+    }
+    
+    /// Find all nodes within a given radius, along with their positions.
+    ///
+    /// The positions come from the index itself, so callers that only need
+    /// coordinates (visualization, clustering) don't have to fetch each node.
+    pub fn within_radius_with_pos(&self, center: Coordinate3D, radius: f32) -> Vec<(NodeId, Coordinate3D)> {
         let mut results = Vec::new();
         
         if self.nodes.is_empty() {
@@ -234,31 +375,152 @@ impl SpatialIndex {
     
     /// Find K nearest neighbors to a point
     pub fn find_k_nearest(&self, center: Coordinate3D, k: usize) -> Vec<(NodeId, f32)> {
-        // Start with a small radius and expand as needed
-        let mut radius = 0.1;
-        let mut results = Vec::new();
+        self.k_nearest(center, k)
+    }
+    
+    /// Find the `k` nodes nearest to `point` with their distances.
+    ///
+    /// Octants are visited closest first, keeping the best `k` candidates in
+    /// a bounded heap, and the search stops once no unvisited octant can
+    /// beat the current `k`th candidate. Sparse and dense regions therefore
+    /// cost about the same, with no radius to guess. Results are ordered
+    /// nearest first; nodes at equal distance are ordered by ID, so results
+    /// are stable across runs.
+    pub fn k_nearest(&self, point: Coordinate3D, k: usize) -> Vec<(NodeId, f32)> {
+        self.k_nearest_with_pos(point, k)
+            .into_iter()
+            .map(|(id, position)| (id, point.distance(position)))
+            .collect()
+    }
+    
+    /// Find K nearest neighbors to a point, along with their positions.
+    ///
+    /// Results are ordered as in [`k_nearest`](Self::k_nearest).
+    pub fn k_nearest_with_pos(&self, center: Coordinate3D, k: usize) -> Vec<(NodeId, Coordinate3D)> {
+        // A caller-supplied k can be far larger than the index
+        let k = k.min(self.len());
+        if k == 0 {
+            return Vec::new();
+        }
         
-        while results.len() < k && radius <= 2.0 {
-            results.clear();
-            let candidates = self.find_within_radius(center, radius);
-            
-            // Calculate distances and sort
-            for node_id in candidates {
-                // Note: In real implementation, we'd need access to actual positions
-                // This is a placeholder
-                results.push((node_id, 0.0));
+        // Worst candidate on top, so it's the one evicted
+        let mut best: BinaryHeap<Ranked<Coordinate3D>> = BinaryHeap::with_capacity(k + 1);
+        // Closest octant on top
+        let mut octants = BinaryHeap::new();
+        octants.push(Reverse(Ranked {
+            distance_sq: box_distance_squared(&self.nodes[self.root_index as usize].bounds, center),
+            key: self.root_index,
+            item: (),
+        }));
+        
+        while let Some(Reverse(octant)) = octants.pop() {
+            // Equal distances are still visited, since a lower ID may win the tie
+            if best.len() == k && best.peek().is_some_and(|worst| octant.distance_sq > worst.distance_sq) {
+                break;
             }
             
-            radius *= 2.0;
+            let node = &self.nodes[octant.key as usize];
+            if node.flags.contains(OctreeFlags::IS_LEAF) {
+                for &(id, position) in self.node_buckets.get(&octant.key).into_iter().flatten() {
+                    let candidate = Ranked {
+                        distance_sq: center.distance_squared(position),
+                        key: id.0,
+                        item: position,
+                    };
+                    if best.len() < k {
+                        best.push(candidate);
+                    } else if best.peek().is_some_and(|worst| candidate < *worst) {
+                        best.pop();
+                        best.push(candidate);
+                    }
+                }
+            } else {
+                for &child_index in &node.children {
+                    if child_index != 0 {
+                        octants.push(Reverse(Ranked {
+                            distance_sq: box_distance_squared(&self.nodes[child_index as usize].bounds, center),
+                            key: child_index,
+                            item: (),
+                        }));
+                    }
+                }
+            }
         }
         
-        // Sort by distance and take top K
-        results.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-        results.truncate(k);
-        
+        best.into_sorted_vec()
+            .into_iter()
+            .map(|candidate| (NodeId(candidate.key), candidate.item))
+            .collect()
+    }
+    
+    /// Estimate how many nodes lie within `radius` of `center` without
+    /// visiting them.
+    ///
+    /// Sums the item counts of every leaf whose bounds touch the search
+    /// sphere, so it never undercounts but can overcount by whole leaves.
+    pub fn estimate_within_radius(&self, center: Coordinate3D, radius: f32) -> usize {
+        if self.nodes.is_empty() {
+            return 0;
+        }
+        self.estimate_recursive(self.root_index, center, radius)
+    }
+    
+    fn estimate_recursive(&self, node_index: u32, center: Coordinate3D, radius: f32) -> usize {
+        let node = &self.nodes[node_index as usize];
+        if !node.bounds.intersects_sphere(center, radius) {
+            return 0;
+        }
+        if node.flags.contains(OctreeFlags::IS_LEAF) {
+            return node.node_count as usize;
+        }
+        node.children.iter()
+            .filter(|&&child| child != 0)
+            .map(|&child| self.estimate_recursive(child, center, radius))
+            .sum()
+    }
+    
+    /// Find all nodes whose position lies inside `bounds` (edges included).
+    ///
+    /// Returns nothing for an empty box, where `min` exceeds `max` on some axis.
+    pub fn find_within_box(&self, bounds: BoundingBox3D) -> Vec<NodeId> {
+        self.find_within_box_limited(bounds, usize::MAX)
+    }
+    
+    /// Like [`find_within_box`](Self::find_within_box), but stops once
+    /// `max` nodes have been found.
+    pub fn find_within_box_limited(&self, bounds: BoundingBox3D, max: usize) -> Vec<NodeId> {
+        let mut results = Vec::new();
+        if self.nodes.is_empty() || bounds.is_empty() {
+            return results;
+        }
+        self.box_search_recursive(self.root_index, bounds, max, &mut results);
         results
     }
     
+    fn box_search_recursive(&self, node_index: u32, bounds: BoundingBox3D, max: usize, results: &mut Vec<NodeId>) {
+        let node = &self.nodes[node_index as usize];
+        if results.len() >= max || !node.bounds.intersects(&bounds) {
+            return;
+        }
+        if node.flags.contains(OctreeFlags::IS_LEAF) {
+            if let Some(bucket) = self.node_buckets.get(&node_index) {
+                let room = max - results.len();
+                results.extend(
+                    bucket.iter()
+                        .filter(|(_, position)| bounds.contains(*position))
+                        .map(|(id, _)| *id)
+                        .take(room)
+                );
+            }
+            return;
+        }
+        for &child_index in &node.children {
+            if child_index != 0 {
+                self.box_search_recursive(child_index, bounds, max, results);
+            }
+        }
+    }
+    
     /// Find neighbors within threshold distance
     pub fn find_neighbors(&self, center: Coordinate3D, threshold: f32) -> Vec<NodeId> {
         self.find_within_radius(center, threshold)
@@ -271,7 +533,7 @@ impl SpatialIndex {
         center: Coordinate3D,
         radius: f32,
         search_bounds: BoundingBox3D,
-        results: &mut Vec<NodeId>,
+        results: &mut Vec<(NodeId, Coordinate3D)>,
     ) {
         let node = &self.nodes[node_index as usize];
         
@@ -282,9 +544,11 @@ impl SpatialIndex {
         
         // If this is a leaf node, check all contained nodes
         if node.flags.contains(OctreeFlags::IS_LEAF) {
-            if let Some(node_ids) = self.node_buckets.get(&node_index) {
-                // In real implementation, we'd check actual distances here
-                results.extend(node_ids);
+            if let Some(bucket) = self.node_buckets.get(&node_index) {
+                let radius_sq = radius * radius;
+                results.extend(
+                    bucket.iter().filter(|(_, position)| center.distance_squared(*position) <= radius_sq)
+                );
             }
             return;
         }
@@ -329,8 +593,10 @@ impl SpatialIndex {
     }
 }
 
-/// Statistics about the octree structure
-#[derive(Debug, Default)]
+/// Statistics about the octree structure.
+///
+/// Returned by [`SpatialIndex::stats`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct OctreeStats {
     /// Total number of octree nodes
     pub total_nodes: usize,
@@ -346,6 +612,56 @@ pub struct OctreeStats {
     pub max_items_per_leaf: usize,
 }
 
+impl OctreeStats {
+    /// Mean number of items per leaf, or 0.0 for an empty tree
+    pub fn average_leaf_occupancy(&self) -> f32 {
+        if self.leaf_nodes == 0 {
+            return 0.0;
+        }
+        self.total_items as f32 / self.leaf_nodes as f32
+    }
+}
+
+/// Entry in a nearest-neighbour search, ordered by distance then key
+struct Ranked<T> {
+    /// Squared distance from the query point
+    distance_sq: f32,
+    /// Node ID or octree node index, used to break ties
+    key: u32,
+    /// Payload carried along with the entry
+    item: T,
+}
+
+impl<T> Ord for Ranked<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance_sq.total_cmp(&other.distance_sq).then(self.key.cmp(&other.key))
+    }
+}
+
+impl<T> PartialOrd for Ranked<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> PartialEq for Ranked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Ranked<T> {}
+
+/// Squared distance from `point` to the nearest point of `bounds`
+fn box_distance_squared(bounds: &BoundingBox3D, point: Coordinate3D) -> f32 {
+    let nearest = Coordinate3D::new(
+        point.x.clamp(bounds.min.x, bounds.max.x),
+        point.y.clamp(bounds.min.y, bounds.max.y),
+        point.z.clamp(bounds.min.z, bounds.max.z),
+    );
+    point.distance_squared(nearest)
+}
+
 impl Default for SpatialIndex {
     fn default() -> Self {
         Self::new()
@@ -372,6 +688,93 @@ mod tests {
         assert_eq!(stats.total_items, 3);
     }
     
+    #[test]
+    fn test_octree_depth_and_leaf_limits() {
+        // A tight cluster in the Words band, plus a few outliers
+        let build = |builder: OctreeBuilder| {
+            let mut builder = builder;
+            for i in 0..500 {
+                let offset = (i % 50) as f32 * 0.0001;
+                builder.add_node(NodeId(i + 1), Coordinate3D::new(0.3 + offset, 0.3, 0.52 + offset));
+            }
+            for i in 0..8 {
+                builder.add_node(NodeId(1000 + i), Coordinate3D::new(0.9, 0.1 * i as f32, 0.1));
+            }
+            builder.build().stats()
+        };
+        
+        let default = build(OctreeBuilder::new());
+        let explicit = build(OctreeBuilder::new().max_depth(10).unwrap().max_leaf_nodes(16).unwrap());
+        assert_eq!(default, explicit);
+        assert_eq!(default.max_depth, 10);
+        
+        let shallow = build(OctreeBuilder::new().max_depth(4).unwrap());
+        assert!(shallow.max_depth <= 4);
+        assert!(shallow.total_nodes < default.total_nodes);
+        assert_eq!(shallow.total_items, 508);
+        
+        let wide = build(OctreeBuilder::new().max_leaf_nodes(1000).unwrap());
+        assert_eq!(wide.leaf_nodes, 1);
+        assert_eq!(wide.average_leaf_occupancy(), 508.0);
+        
+        assert!(OctreeBuilder::new().max_depth(0).is_err());
+        assert!(OctreeBuilder::new().max_leaf_nodes(0).is_err());
+        assert_eq!(OctreeStats::default().average_leaf_occupancy(), 0.0);
+    }
+    
+    #[test]
+    fn test_k_nearest_matches_brute_force() {
+        let mut builder = OctreeBuilder::new();
+        let mut points = Vec::new();
+        for i in 0..300u32 {
+            // Dense cluster plus a sparse scatter
+            let position = if i < 200 {
+                Coordinate3D::new(0.3 + (i % 10) as f32 * 0.002, 0.3 + (i / 10 % 10) as f32 * 0.002, 0.52)
+            } else {
+                let t = (i - 200) as f32 / 100.0;
+                Coordinate3D::new(t, (t * 7.0).fract(), (t * 13.0).fract())
+            };
+            builder.add_node(NodeId(i + 1), position);
+            points.push((NodeId(i + 1), position));
+        }
+        let index = builder.build();
+        
+        for center in [Coordinate3D::new(0.31, 0.31, 0.52), Coordinate3D::new(0.9, 0.1, 0.9)] {
+            let mut expected = points.clone();
+            expected.sort_by(|a, b| {
+                center.distance_squared(a.1).total_cmp(&center.distance_squared(b.1)).then(a.0.cmp(&b.0))
+            });
+            let expected: Vec<NodeId> = expected.iter().take(12).map(|&(id, _)| id).collect();
+            
+            let found: Vec<NodeId> = index.k_nearest(center, 12).into_iter().map(|(id, _)| id).collect();
+            assert_eq!(found, expected);
+        }
+        
+        assert_eq!(index.k_nearest(Coordinate3D::new(0.5, 0.5, 0.5), 1000).len(), 300);
+        assert!(index.k_nearest(Coordinate3D::new(0.5, 0.5, 0.5), 0).is_empty());
+        assert_eq!(index.k_nearest(Coordinate3D::new(0.5, 0.5, 0.5), usize::MAX).len(), 300);
+        assert_eq!(index.len(), 300);
+    }
+    
+    #[test]
+    fn test_k_nearest_breaks_ties_by_id() {
+        let mut builder = OctreeBuilder::new();
+        let center = Coordinate3D::new(0.5, 0.5, 0.5);
+        // Four nodes at the same distance, added out of order
+        for (id, position) in [
+            (7, Coordinate3D::new(0.75, 0.5, 0.5)),
+            (3, Coordinate3D::new(0.25, 0.5, 0.5)),
+            (9, Coordinate3D::new(0.5, 0.75, 0.5)),
+            (5, Coordinate3D::new(0.5, 0.25, 0.5)),
+        ] {
+            builder.add_node(NodeId(id), position);
+        }
+        let index = builder.build();
+        
+        let ids: Vec<NodeId> = index.k_nearest(center, 3).into_iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![NodeId(3), NodeId(5), NodeId(7)]);
+    }
+    
     #[test]
     fn test_octant_calculation() {
         let center = Coordinate3D::new(0.5, 0.5, 0.5);
@@ -381,4 +784,44 @@ mod tests {
         assert_eq!(OctreeBuilder::get_octant(center, Coordinate3D::new(0.0, 1.0, 0.0)), 2);
         assert_eq!(OctreeBuilder::get_octant(center, Coordinate3D::new(1.0, 1.0, 1.0)), 7);
     }
+    
+    #[test]
+    fn test_query_positions_match_database() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::open_database;
+        
+        let mut db_builder = DatabaseBuilder::new();
+        for i in 0..40 {
+            let t = i as f32 / 40.0;
+            db_builder.add_node(&format!("word{}", i), Layer::Words, Coordinate3D::new(t, 1.0 - t, 0.52)).unwrap();
+        }
+        let (_dir, db) = open_database(&db_builder);
+        
+        let mut builder = OctreeBuilder::new();
+        for (i, node) in db.nodes().iter().enumerate() {
+            builder.add_node(NodeId(i as u32 + 1), node.position);
+        }
+        let index = builder.build();
+        
+        let center = Coordinate3D::new(0.5, 0.5, 0.52);
+        let within = index.within_radius_with_pos(center, 0.2);
+        assert!(!within.is_empty());
+        for (id, position) in &within {
+            let expected = db.get_node(*id).unwrap().position;
+            assert_eq!(*position, expected);
+            assert!(center.distance(*position) <= 0.2);
+        }
+        
+        let nearest = index.k_nearest_with_pos(center, 5);
+        assert_eq!(nearest.len(), 5);
+        for (id, position) in &nearest {
+            let expected = db.get_node(*id).unwrap().position;
+            assert_eq!(*position, expected);
+        }
+        assert_eq!(nearest[0].0, NodeId(21));
+        for pair in nearest.windows(2) {
+            assert!(center.distance(pair[0].1) <= center.distance(pair[1].1));
+        }
+    }
 }
\ No newline at end of file
@@ -155,7 +155,10 @@ pub mod plugins;
 pub mod morphology;
 pub mod security;
 pub mod logging;
-// pub mod mirroring; // Temporarily disabled due to compilation issues
+pub mod mirroring;
+
+#[cfg(test)]
+mod test_support;
 
 // WebAssembly bindings (only compiled for WASM target)
 #[cfg(target_arch = "wasm32")]
@@ -171,4 +174,4 @@ pub use crate::plugins::{PluginPipeline, Plugin, FunctionExtractor};
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Maximum supported file format version
-pub const MAX_FILE_VERSION: (u16, u16) = (1, 0);
\ No newline at end of file
+pub const MAX_FILE_VERSION: (u16, u16) = (1, 1);
\ No newline at end of file
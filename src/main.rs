@@ -1,63 +1,246 @@
-//! Lingo database example
-
-use lingo::{
-    QueryBuilder,
-    LingoExecutor,
-    logging,
-};
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize logging
-    logging::init_logging()?;
-    
-    println!("🚀 Lingo database query example");
-    
-    // Create an executor and load a pre-built database
-    let mut executor = LingoExecutor::new();
-    
-    // Try to load the English database
-    match executor.load_database("english.lingo") {
-        Ok(_) => println!("✅ Loaded English database"),
-        Err(e) => {
-            println!("❌ Failed to load database: {}", e);
-            println!("Please ensure 'english.lingo' exists in the current directory");
-            return Ok(());
+//! `lingo` command-line interface
+//!
+//! Built with `cargo build --features cli`. Examples:
+//!
+//! ```text
+//! lingo english.lingo query 'find "technical" | similar 0.8 | limit 10'
+//! lingo english.lingo --format json decompose unhappiness
+//! lingo english.lingo mirrors visible
+//! lingo english.lingo info
+//! ```
+
+use clap::{Parser, Subcommand, ValueEnum};
+use lingo::core::{Coordinate3D, Layer};
+use lingo::mirroring::MirroringDecomposer;
+use lingo::storage::Database;
+use lingo::LingoExecutor;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+/// Query a Lingo database from the command line
+#[derive(Debug, Parser)]
+#[command(name = "lingo", version)]
+struct Cli {
+    /// Path to a `.lingo` database file
+    database: PathBuf,
+
+    /// How to print results
+    #[arg(long, value_enum, default_value_t = Format::Table, global = true)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run a text query, e.g. 'find "happy" | similar 0.8 | limit 10'
+    Query {
+        /// Query pipeline; see `lingo::query::parse` for the stages
+        text: String,
+    },
+    /// Split a word into its morphemes
+    Decompose {
+        /// Word to split
+        word: String,
+    },
+    /// Find the morphological opposites of a word
+    Mirrors {
+        /// Word to mirror
+        word: String,
+    },
+    /// Print node and connection counts, per layer
+    Info,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Aligned columns
+    Table,
+    /// One JSON document
+    Json,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
         }
     }
-    
-    // Example queries
-    println!("\n📝 Running example queries...\n");
-    
-    // Query 1: Find similar words to "technical"
-    let query = QueryBuilder::find("technical")
-        .similar_threshold(0.8)
-        .limit(10)
-        .compile();
-    
-    match executor.execute(&query) {
-        Ok(result) => {
-            println!("Query: Find words similar to 'technical'");
-            println!("Found {} node IDs", result.nodes.len());
-            println!("Execution time: {:?}", result.execution_time);
+}
+
+fn run(cli: &Cli) -> Result<(), String> {
+    let db = Database::open(&cli.database)
+        .map_err(|e| format!("can't open {}: {}", cli.database.display(), e))?;
+    let db = Arc::new(db);
+
+    match &cli.command {
+        Command::Query { text } => query(&db, text, cli.format),
+        Command::Decompose { word } => decompose(&db, word, cli.format),
+        Command::Mirrors { word } => mirrors(&db, word, cli.format),
+        Command::Info => {
+            info(&db, cli.format);
+            Ok(())
         }
-        Err(e) => println!("Query failed: {}", e),
-    }
-    
-    // Query 2: Explore morphemes of a word
-    println!("\n");
-    let query = QueryBuilder::find("technology")
-        .layer_down()  // Go to morphemes
-        .limit(10)
-        .compile();
-    
-    match executor.execute(&query) {
-        Ok(result) => {
-            println!("Query: Find morphemes in 'technology'");
-            println!("Found {} node IDs", result.nodes.len());
-            println!("Execution time: {:?}", result.execution_time);
+    }
+}
+
+fn query(db: &Arc<Database>, text: &str, format: Format) -> Result<(), String> {
+    let query = lingo::query::parse(text).map_err(|e| format!("{}\n{}", e, e.highlight(text)))?;
+    let mut executor = LingoExecutor::from_shared(Arc::clone(db));
+    let result = executor.execute(&query).map_err(|e| e.to_string())?;
+
+    let mut rows = Vec::new();
+    for &id in result.nodes.as_slice() {
+        let node = db.get_node(id).map_err(|e| e.to_string())?;
+        let position = node.position;
+        rows.push(json!({
+            "id": id.0,
+            "word": db.get_node_word(id).unwrap_or(""),
+            "layer": format!("{:?}", { node.layer }),
+            "etymology": format!("{:?}", { node.etymology_origin }),
+            "position": coordinates(position),
+        }));
+    }
+
+    let elapsed_us = result.execution_time.as_micros() as u64;
+    match format {
+        Format::Json => print_json(&json!({ "nodes": rows, "execution_time_us": elapsed_us })),
+        Format::Table => {
+            print_table(
+                &["ID", "WORD", "LAYER", "ETYMOLOGY", "POSITION"],
+                &rows,
+                &["id", "word", "layer", "etymology", "position"],
+            );
+            println!("{} result(s) in {} µs", rows.len(), elapsed_us);
         }
-        Err(e) => println!("Query failed: {}", e),
     }
-    
     Ok(())
-}
\ No newline at end of file
+}
+
+fn decompose(db: &Arc<Database>, word: &str, format: Format) -> Result<(), String> {
+    let mut decomposer = MirroringDecomposer::new(Arc::clone(db)).map_err(|e| e.to_string())?;
+    let rows: Vec<Value> = decomposer.decompose(word)
+        .into_iter()
+        .map(|morpheme| json!({
+            "morpheme": morpheme.surface_form,
+            "type": format!("{:?}", morpheme.morpheme_type),
+            "node": morpheme.node_id.map(|id| id.0),
+            "position": morpheme.position.map(coordinates),
+        }))
+        .collect();
+
+    match format {
+        Format::Json => print_json(&json!({ "word": word, "morphemes": rows })),
+        Format::Table => print_table(
+            &["MORPHEME", "TYPE", "NODE", "POSITION"],
+            &rows,
+            &["morpheme", "type", "node", "position"],
+        ),
+    }
+    Ok(())
+}
+
+fn mirrors(db: &Arc<Database>, word: &str, format: Format) -> Result<(), String> {
+    let mut decomposer = MirroringDecomposer::new(Arc::clone(db)).map_err(|e| e.to_string())?;
+    let rows: Vec<Value> = decomposer.find_mirrors(word)
+        .into_iter()
+        .map(|pair| json!({
+            "mirror": pair.mirror,
+            "type": format!("{:?}", pair.mirror_type),
+            "confidence": shortest(pair.confidence),
+        }))
+        .collect();
+
+    match format {
+        Format::Json => print_json(&json!({ "word": word, "mirrors": rows })),
+        Format::Table => print_table(&["MIRROR", "TYPE", "CONFIDENCE"], &rows, &["mirror", "type", "confidence"]),
+    }
+    Ok(())
+}
+
+fn info(db: &Database, format: Format) {
+    let layers: Vec<Value> = Layer::ALL.iter()
+        .map(|&layer| json!({ "layer": format!("{:?}", layer), "nodes": db.layer_count(layer) }))
+        .collect();
+    let connection_types: Vec<Value> = db.connection_stats()
+        .into_iter()
+        .map(|(connection_type, count)| json!({ "type": format!("{:?}", connection_type), "connections": count }))
+        .collect();
+
+    match format {
+        Format::Json => print_json(&json!({
+            "nodes": db.node_count(),
+            "connections": db.connection_count(),
+            "layers": layers,
+            "connection_types": connection_types,
+        })),
+        Format::Table => {
+            println!("{} nodes, {} connections", db.node_count(), db.connection_count());
+            print_table(&["LAYER", "NODES"], &layers, &["layer", "nodes"]);
+            if !connection_types.is_empty() {
+                println!();
+                print_table(&["CONNECTION TYPE", "CONNECTIONS"], &connection_types, &["type", "connections"]);
+            }
+        }
+    }
+}
+
+/// `[x, y, z]`, each at its shortest `f32` spelling
+fn coordinates(position: Coordinate3D) -> Value {
+    json!([shortest(position.x), shortest(position.y), shortest(position.z)])
+}
+
+/// An `f32` as JSON without the digits widening to `f64` would add
+fn shortest(value: f32) -> Value {
+    value.to_string().parse::<f64>().map_or(Value::Null, Value::from)
+}
+
+fn print_json(value: &Value) {
+    // Serializing a `Value` can't fail
+    println!("{}", serde_json::to_string_pretty(value).unwrap_or_default());
+}
+
+/// Print `rows` as left-aligned columns, one per key
+fn print_table(headers: &[&str], rows: &[Value], keys: &[&str]) {
+    let cells: Vec<Vec<String>> = rows.iter()
+        .map(|row| keys.iter().map(|&key| cell(&row[key])).collect())
+        .collect();
+    let widths: Vec<usize> = headers.iter()
+        .enumerate()
+        .map(|(i, header)| {
+            cells.iter().map(|row| row[i].chars().count()).fold(header.len(), usize::max)
+        })
+        .collect();
+
+    let line = |values: Vec<&str>| {
+        let padded: Vec<String> = values.iter()
+            .zip(&widths)
+            .map(|(value, &width)| format!("{:<width$}", value, width = width))
+            .collect();
+        println!("{}", padded.join("  ").trim_end());
+    };
+    line(headers.to_vec());
+    for row in &cells {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
+
+/// Text of one table cell: strings unquoted, positions to three places
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Number(n) if n.is_f64() => format!("{:.3}", n.as_f64().unwrap_or_default()),
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(cell).collect();
+            format!("({})", items.join(", "))
+        }
+        other => other.to_string(),
+    }
+}
@@ -16,23 +16,22 @@
 //! 
 //! Handles the reverse process of decomposition - building words from morphemes
 
-use std::collections::HashSet;
-use crate::core::{Layer, MorphemeType, Coordinate3D};
-use crate::storage::LingoDatabase;
+use crate::core::{MorphemeType, clamp01};
 use crate::engine::LingoExecutor;
 use crate::query::QueryBuilder;
-use crate::morphology::MorphemeAnalysis;
-use super::CompositionWeights;
 
-/// Handles composition of morphemes into words
-pub struct Composer<'a> {
-    db: &'a LingoDatabase,
-    weights: &'a CompositionWeights,
-}
+/// Handles composition of morphemes into words.
+///
+/// Composition only uses morphophonological rules; candidates are checked
+/// against the database through the executor passed to
+/// [`compose_from_morphemes`](Self::compose_from_morphemes).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Composer;
 
-impl<'a> Composer<'a> {
-    pub fn new(db: &'a LingoDatabase, weights: &'a CompositionWeights) -> Self {
-        Self { db, weights }
+impl Composer {
+    /// Create a composer
+    pub fn new() -> Self {
+        Self
     }
     
     /// Compose morphemes into possible words
@@ -178,12 +177,11 @@ impl<'a> Composer<'a> {
         // Rule 3: y → i before certain suffixes
         if stem.ends_with('y') && stem.len() > 1 {
             let chars: Vec<char> = stem.chars().collect();
-            if chars.len() >= 2 && !self.is_vowel(chars[chars.len()-2]) {
-                if morpheme == "es" || morpheme == "ed" || morpheme == "er" || morpheme == "est" {
-                    let mut adjusted = String::from("i");
-                    adjusted.push_str(morpheme);
-                    return (1, adjusted); // Remove 'y' from stem, add 'i' to morpheme
-                }
+            if chars.len() >= 2 && !self.is_vowel(chars[chars.len()-2])
+                && (morpheme == "es" || morpheme == "ed" || morpheme == "er" || morpheme == "est") {
+                let mut adjusted = String::from("i");
+                adjusted.push_str(morpheme);
+                return (1, adjusted); // Remove 'y' from stem, add 'i' to morpheme
             }
         }
         
@@ -282,7 +280,7 @@ impl<'a> Composer<'a> {
             .count() as f32 / original_morphemes.len() as f32;
         confidence += morpheme_coverage * 0.2;
         
-        confidence.min(1.0)
+        clamp01(confidence)
     }
     
     /// Classify a morpheme by its form
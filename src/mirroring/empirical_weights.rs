@@ -18,7 +18,7 @@
 //! for morpheme composition based on spatial distributions and co-occurrence patterns.
 
 use std::collections::HashMap;
-use crate::core::{Layer, MorphemeType, NodeId, Coordinate3D};
+use crate::core::{Layer, MorphemeType, Coordinate3D};
 use crate::core::error::LingoError;
 use crate::storage::LingoDatabase;
 use crate::engine::LingoExecutor;
@@ -30,7 +30,15 @@ pub struct EmpiricalWeightCalculator<'a> {
     db: &'a LingoDatabase,
 }
 
+// Implement Debug manually; the calculator borrows the whole database
+impl std::fmt::Debug for EmpiricalWeightCalculator<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmpiricalWeightCalculator").finish_non_exhaustive()
+    }
+}
+
 impl<'a> EmpiricalWeightCalculator<'a> {
+    /// Create a calculator over `db`
     pub fn new(db: &'a LingoDatabase) -> Self {
         Self { db }
     }
@@ -85,11 +93,11 @@ impl<'a> EmpiricalWeightCalculator<'a> {
                             if let Ok(morpheme_node) = self.db.get_node(*morpheme_id) {
                                 // Infer morpheme type from the morpheme text
                                 if let Ok(morpheme_text) = self.db.get_node_word(*morpheme_id) {
-                                    let morph_type = self.infer_morpheme_type(&morpheme_text, word);
+                                    let morph_type = self.infer_morpheme_type(morpheme_text, word);
                                     
                                     *type_counts.entry(morph_type).or_insert(0) += 1;
                                     type_positions.entry(morph_type)
-                                        .or_insert_with(Vec::new)
+                                        .or_default()
                                         .push(morpheme_node.position);
                                 }
                             }
@@ -167,7 +175,7 @@ impl<'a> EmpiricalWeightCalculator<'a> {
                             let composed = self.weighted_average_position(&morpheme_positions);
                             
                             // Calculate distance from actual word position
-                            let distance = euclidean_distance(word_node.position, composed);
+                            let distance = { word_node.position }.distance_to(composed);
                             let coherence = 1.0 / (1.0 + distance);
                             coherence_scores.push(coherence);
                         }
@@ -236,7 +244,7 @@ impl<'a> EmpiricalWeightCalculator<'a> {
         
         // Calculate variance as average squared distance from centroid
         let variance: f32 = positions.iter()
-            .map(|pos| euclidean_distance(*pos, centroid).powi(2))
+            .map(|pos| pos.distance_squared_to(centroid))
             .sum::<f32>() / positions.len() as f32;
         
         variance
@@ -255,9 +263,4 @@ impl<'a> EmpiricalWeightCalculator<'a> {
             z: sum_z / count,
         }
     }
-}
-
-/// Calculate Euclidean distance between two 3D points
-fn euclidean_distance(a: Coordinate3D, b: Coordinate3D) -> f32 {
-    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
 }
\ No newline at end of file
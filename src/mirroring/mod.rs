@@ -23,84 +23,142 @@ use std::sync::Arc;
 use lru::LruCache;
 use serde::{Serialize, Deserialize};
 
-use crate::core::{LinguisticNode, NodeId, Layer, Coordinate3D, MorphemeType};
+use crate::core::{Coordinate3D, MorphemeType, ConnectionType, Layer, NodeId, OrthogonalConnection};
 use crate::core::error::LingoError;
 use crate::storage::LingoDatabase;
 use crate::engine::LingoExecutor;
 use crate::query::QueryBuilder;
-use crate::morphology::{MorphemeAnalysis, decompose_word_to_morphemes, 
-                        calculate_composed_position, preprocess_text};
+use crate::morphology::{MorphemeAnalysis, PhonotacticValidator, decompose_word_to_morphemes, calculate_composed_position};
 
 mod empirical_weights;
 mod composition;
 mod opposition;
 mod synthesis;
+mod thread_safe;
+
+/// Default minimum opposition score for composing a legacy mirror candidate
+pub const DEFAULT_MIRROR_CONFIDENCE_FLOOR: f32 = 0.5;
 
 pub use empirical_weights::EmpiricalWeightCalculator;
 pub use composition::Composer;
 pub use opposition::{OppositionEngine, MirrorType, EtymologicalMirrorEngine, EtymologicalMirror, EtymologyProfile, 
                       EtymologyFamily, RoleType, NegationType, BorrowingType};
 pub use synthesis::{SynthesisEngine, SynthesisResult};
+pub use thread_safe::ThreadSafeMirroringDecomposer;
 
 /// Main mirroring decomposer that handles bidirectional morphological analysis
+///
+/// Every method takes `&mut self` and the synthesis cache is unsynchronized,
+/// so a decomposer serves one thread at a time. Use
+/// [`ThreadSafeMirroringDecomposer`] to share one across threads.
 pub struct MirroringDecomposer {
     db: Arc<LingoDatabase>,
     executor: LingoExecutor,
-    morpheme_patterns: HashMap<String, MorphemePattern>,
-    opposition_vectors: HashMap<String, Vec<String>>,
     synthesis_cache: LruCache<String, Vec<SynthesisResult>>,
+    /// Morpheme splits by word, shared by decomposition, mirroring and validation
+    decomposition_cache: LruCache<String, Vec<MorphemeAnalysis>>,
+    /// Number of decompositions computed rather than served from the cache
+    decompositions_computed: usize,
     /// Empirically calculated weights for morpheme composition
     composition_weights: CompositionWeights,
+    /// Phonotactics of the database's phonemes, read once for every synthesis
+    phonotactics: PhonotacticValidator,
+    /// Number of etymological mirror engines constructed so far
+    engines_built: usize,
+    /// Opposite morpheme sets scoring below this aren't composed
+    mirror_confidence_floor: f32,
+}
+
+// Implement Debug manually; the caches hold full analyses per word
+impl std::fmt::Debug for MirroringDecomposer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MirroringDecomposer")
+            .field("composition_weights", &self.composition_weights)
+            .field("decompositions_computed", &self.decompositions_computed)
+            .field("engines_built", &self.engines_built)
+            .finish_non_exhaustive()
+    }
 }
 
+/// A productive morphological pattern and the patterns that mirror it
 #[derive(Debug, Clone)]
 pub struct MorphemePattern {
+    /// Kind of word the pattern builds
     pub pattern_type: PatternType,
+    /// Morphemes making up the pattern, in surface order
     pub components: Vec<MorphemeComponent>,
+    /// Affixes or roots that form the pattern's opposite
     pub mirror_patterns: Vec<String>,
+    /// Open positions new morphemes can fill
     pub generative_slots: Vec<GenerativeSlot>,
 }
 
+/// One morpheme within a [`MorphemePattern`]
 #[derive(Debug, Clone)]
 pub struct MorphemeComponent {
+    /// Surface form of the morpheme
     pub morpheme: String,
+    /// Role of the morpheme in the word
     pub morpheme_type: MorphemeType,
+    /// Position in semantic space, if the morpheme is in the database
     pub position: Option<Coordinate3D>,
+    /// Weight of the morpheme when composing positions
     pub empirical_weight: f32,
 }
 
+/// An open position in a [`MorphemePattern`]
 #[derive(Debug, Clone)]
 pub struct GenerativeSlot {
+    /// Where in the word the slot sits
     pub slot_type: SlotType,
+    /// Morphemes known to fit the slot
     pub compatible_morphemes: Vec<String>,
+    /// Region of semantic space a filler must fall in
     pub position_constraints: PositionConstraints,
 }
 
+/// Kind of word a morphological pattern builds
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PatternType {
-    Agent,      // -er, -ant, -ist patterns
-    Action,     // -ize, -fy, -ate patterns  
-    Negation,   // un-, dis-, de- patterns
-    Intensifier, // super-, ultra-, hyper- patterns
-    Temporal,   // pre-, post-, re- patterns
-    Relational, // inter-, intra-, trans- patterns
+    /// Doers of an action: -er, -ant, -ist
+    Agent,
+    /// Verbs from other words: -ize, -fy, -ate
+    Action,
+    /// Negations and reversals: un-, dis-, de-
+    Negation,
+    /// Degree markers: super-, ultra-, hyper-
+    Intensifier,
+    /// Time relations: pre-, post-, re-
+    Temporal,
+    /// Relations between things: inter-, intra-, trans-
+    Relational,
 }
 
+/// Where a [`GenerativeSlot`] sits in a word
 #[derive(Debug, Clone)]
 pub enum SlotType {
+    /// Before the root
     Prefix,
+    /// The root itself
     Root,
+    /// After the root
     Suffix,
+    /// Inside the root
     Infix,
 }
 
+/// Region of semantic space a slot filler must fall in
 #[derive(Debug, Clone)]
 pub struct PositionConstraints {
+    /// Lowest corner of the allowed box
     pub min_position: Coordinate3D,
+    /// Highest corner of the allowed box
     pub max_position: Coordinate3D,
+    /// Point fillers are preferred to sit near
     pub preferred_region: Coordinate3D,
 }
 
+/// Weights for composing morpheme positions and scoring compositions
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompositionWeights {
     /// Weights learned from the database distribution
@@ -133,38 +191,87 @@ impl Default for CompositionWeights {
     }
 }
 
+/// Keep the best-scoring pair per mirror word, drop those under
+/// `min_confidence`, and return at most `max_results`, most confident first.
+///
+/// Ties keep the order the pairs were found in.
+fn select_mirrors(mirrors: Vec<MirrorPair>, min_confidence: f32, max_results: usize) -> Vec<MirrorPair> {
+    let mut best: Vec<MirrorPair> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for pair in mirrors.into_iter().filter(|pair| pair.confidence >= min_confidence) {
+        match index.get(&pair.mirror) {
+            Some(&i) if best[i].confidence >= pair.confidence => {}
+            Some(&i) => best[i] = pair,
+            None => {
+                index.insert(pair.mirror.clone(), best.len());
+                best.push(pair);
+            }
+        }
+    }
+    
+    best.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    best.truncate(max_results);
+    best
+}
+
+/// A word and a mirror found for it
 #[derive(Debug, Clone)]
 pub struct MirrorPair {
+    /// The word mirrors were requested for
     pub original: String,
+    /// The mirrored word
     pub mirror: String,
+    /// How the mirror relates to the original
     pub mirror_type: MirrorType,
+    /// Confidence in the mirror, from 0.0 to 1.0
     pub confidence: f32,
 }
 
+impl MirrorPair {
+    /// Connection type to record this pair under, if it maps onto one.
+    ///
+    /// Gradable opposites (hot/cold) and the lexical opposites the
+    /// etymological engine finds (build/break) are antonyms. The other
+    /// mirror types describe morphological or spatial relations with no
+    /// lexical equivalent, so they return `None`.
+    pub fn connection_type(&self) -> Option<ConnectionType> {
+        match self.mirror_type {
+            MirrorType::Gradable | MirrorType::EtymologicalOpposite { .. } => Some(ConnectionType::Antonymy),
+            _ => None,
+        }
+    }
+}
+
+/// How well a word survives decomposition and recomposition
 #[derive(Debug, Clone)]
 pub struct ValidationResult {
+    /// Whether composing the word's morphemes gives the word back
     pub round_trip_success: bool,
+    /// How close the composed position is to the word's own, from 0.0 to 1.0
     pub spatial_consistency_score: f32,
+    /// How well the morphemes fit together, from 0.0 to 1.0
     pub morpheme_coherence: f32,
+    /// Other words the morphemes compose to
     pub alternative_compositions: Vec<String>,
 }
 
 impl MirroringDecomposer {
     /// Create a new mirroring decomposer
     pub fn new(database: Arc<LingoDatabase>) -> Result<Self, LingoError> {
-        let mut executor = LingoExecutor::new();
-        
-        // Clone the Arc to get a reference for the executor
-        let db_path = "english.lingo"; // This should be configurable
-        executor.load_database(db_path)?;
+        // Share the caller's database rather than reopening a file
+        let executor = LingoExecutor::from_shared(Arc::clone(&database));
+        let phonotactics = PhonotacticValidator::from_database(&database);
         
         Ok(Self {
             db: database,
             executor,
-            morpheme_patterns: HashMap::new(),
-            opposition_vectors: HashMap::new(),
             synthesis_cache: LruCache::new(std::num::NonZeroUsize::new(1000).unwrap()),
+            decomposition_cache: LruCache::new(std::num::NonZeroUsize::new(1000).unwrap()),
+            decompositions_computed: 0,
             composition_weights: CompositionWeights::default(),
+            phonotactics,
+            engines_built: 0,
+            mirror_confidence_floor: DEFAULT_MIRROR_CONFIDENCE_FLOOR,
         })
     }
     
@@ -177,21 +284,129 @@ impl MirroringDecomposer {
     
     /// Forward decomposition - break word into morphemes
     pub fn decompose(&mut self, word: &str) -> Vec<MorphemeAnalysis> {
-        decompose_word_to_morphemes(word, &self.db, &mut self.executor)
+        self.cached_decompose(word)
+    }
+    
+    /// Number of decompositions computed so far, excluding cache hits
+    #[cfg(test)]
+    fn decompositions_computed(&self) -> usize {
+        self.decompositions_computed
+    }
+    
+    /// Decompose `word`, reusing an earlier split of the same word
+    fn cached_decompose(&mut self, word: &str) -> Vec<MorphemeAnalysis> {
+        if let Some(cached) = self.decomposition_cache.get(word) {
+            return cached.clone();
+        }
+        
+        self.decompositions_computed += 1;
+        let morphemes = decompose_word_to_morphemes(word, &self.db, &mut self.executor);
+        self.decomposition_cache.put(word.to_string(), morphemes.clone());
+        morphemes
     }
     
     /// Reverse composition - build words from morphemes
     pub fn compose(&mut self, morphemes: &[String]) -> Vec<String> {
-        let composer = Composer::new(&self.db, &self.composition_weights);
+        let composer = Composer::new();
         composer.compose_from_morphemes(morphemes, &mut self.executor)
     }
     
-    /// Find mirror patterns (opposites) using the new etymological engine
+    /// Find mirror patterns (opposites) using the new etymological engine.
+    ///
+    /// Returns every distinct mirror, most confident first. Use
+    /// [`find_mirrors_with_options`](Self::find_mirrors_with_options) to
+    /// filter and cap the results.
     pub fn find_mirrors(&mut self, word: &str) -> Vec<MirrorPair> {
-        // Use the new EtymologicalMirrorEngine for advanced mirror discovery
-        let etymological_engine = EtymologicalMirrorEngine::new(self.db.clone());
-        
-        match etymological_engine.discover_etymological_mirrors(word) {
+        self.find_mirrors_with_options(word, 0.0, usize::MAX)
+    }
+    
+    /// Find at most `max_results` mirrors with confidence of at least
+    /// `min_confidence`, most confident first.
+    ///
+    /// A mirror word reached through several morpheme paths is returned
+    /// once, with its highest confidence. On the legacy path, candidates that
+    /// can't reach `min_confidence` are dropped before they are composed or
+    /// looked up.
+    pub fn find_mirrors_with_options(
+        &mut self,
+        word: &str,
+        min_confidence: f32,
+        max_results: usize,
+    ) -> Vec<MirrorPair> {
+        let etymological_engine = self.build_mirror_engine();
+        self.find_mirrors_with(&etymological_engine, word, min_confidence, max_results)
+    }
+    
+    /// Find the mirrors of `word` that map onto a connection type, as
+    /// connections ready to write.
+    ///
+    /// Each is `(source, connection)` from `word`'s node to the mirror's,
+    /// typed by [`MirrorPair::connection_type`] (so gradable opposites
+    /// become `Antonymy`) with the mirror's confidence as its strength.
+    /// Pass them to [`DatabaseWriter::add_connection`](crate::storage::DatabaseWriter::add_connection)
+    /// or stage them in a [`MutableDatabase`](crate::storage::MutableDatabase)
+    /// transaction. Mirrors with no connection type, or whose words aren't
+    /// in the database, are skipped.
+    pub fn find_mirror_connections(&mut self, word: &str) -> Vec<(NodeId, OrthogonalConnection)> {
+        let Some(source) = word_node(&self.db, word) else {
+            return Vec::new();
+        };
+        self.find_mirrors(word)
+            .into_iter()
+            .filter_map(|pair| {
+                let connection_type = pair.connection_type()?;
+                let target = word_node(&self.db, &pair.mirror)?;
+                Some((source, OrthogonalConnection::new(target, connection_type, pair.confidence)))
+            })
+            .collect()
+    }
+    
+    /// Find mirrors for several words, building the etymological engine once.
+    ///
+    /// Results are in the same order as `words` and match calling
+    /// [`find_mirrors`](Self::find_mirrors) on each word. Prefer this over a
+    /// per-word loop, since engine setup rebuilds the etymology graphs.
+    pub fn find_mirrors_batch(&mut self, words: &[&str]) -> Vec<Vec<MirrorPair>> {
+        let etymological_engine = self.build_mirror_engine();
+        words.iter()
+            .map(|word| self.find_mirrors_with(&etymological_engine, word, 0.0, usize::MAX))
+            .collect()
+    }
+    
+    /// Set the minimum opposition score a morpheme set needs before the
+    /// legacy mirror path composes it, clamped to [0, 1].
+    ///
+    /// Defaults to [`DEFAULT_MIRROR_CONFIDENCE_FLOOR`]. Lower it to see more
+    /// (noisier) candidates.
+    pub fn set_mirror_confidence_floor(&mut self, floor: f32) {
+        self.mirror_confidence_floor = crate::core::clamp01(floor);
+    }
+    
+    /// Minimum opposition score for composing a mirror candidate
+    pub fn mirror_confidence_floor(&self) -> f32 {
+        self.mirror_confidence_floor
+    }
+    
+    /// Number of etymological mirror engines this decomposer has built
+    #[cfg(test)]
+    fn engines_built(&self) -> usize {
+        self.engines_built
+    }
+    
+    fn build_mirror_engine(&mut self) -> EtymologicalMirrorEngine {
+        self.engines_built += 1;
+        EtymologicalMirrorEngine::new(self.db.clone())
+    }
+    
+    /// Find mirrors for one word using an already-built engine
+    fn find_mirrors_with(
+        &mut self,
+        etymological_engine: &EtymologicalMirrorEngine,
+        word: &str,
+        min_confidence: f32,
+        max_results: usize,
+    ) -> Vec<MirrorPair> {
+        let mirrors = match etymological_engine.discover_etymological_mirrors(word) {
             Ok(etymological_mirrors) => {
                 etymological_mirrors.into_iter()
                     .map(|etym_mirror| MirrorPair {
@@ -204,24 +419,34 @@ impl MirroringDecomposer {
             },
             Err(_) => {
                 // Fallback to legacy method if new engine fails
-                self.find_mirrors_legacy(word)
+                self.find_mirrors_legacy(word, min_confidence)
             }
-        }
+        };
+        select_mirrors(mirrors, min_confidence, max_results)
     }
     
-    /// Legacy mirror finding method for backward compatibility
-    fn find_mirrors_legacy(&mut self, word: &str) -> Vec<MirrorPair> {
-        let decomposition = self.decompose(word);
+    /// Legacy mirror finding method for backward compatibility.
+    ///
+    /// Sets whose best possible confidence is below `min_confidence` are
+    /// skipped without composing them or checking the database.
+    fn find_mirrors_legacy(&mut self, word: &str, min_confidence: f32) -> Vec<MirrorPair> {
+        let decomposition = self.cached_decompose(word);
         
         let mut mirrors = Vec::new();
         
         for morpheme_analysis in &decomposition {
             let opposite_morphemes = {
                 let opposition_engine = OppositionEngine::new(&self.db);
-                opposition_engine.find_opposite_morphemes(morpheme_analysis)
+                opposition_engine.find_opposite_morphemes_ranked(morpheme_analysis)
             };
             
-            for opposite_set in opposite_morphemes {
+            // Ranked best first, so everything after the first miss is below the floor too
+            let floor = self.mirror_confidence_floor;
+            for (opposite_set, _) in opposite_morphemes.into_iter().take_while(|&(_, score)| score >= floor) {
+                let mirror_ratio = opposite_set.len() as f32 / decomposition.len() as f32;
+                if (mirror_ratio * 0.7 + 0.3).min(1.0) < min_confidence {
+                    continue;
+                }
                 let mirror_candidates = self.compose(&opposite_set);
                 
                 for mirror_word in mirror_candidates {
@@ -233,17 +458,7 @@ impl MirroringDecomposer {
                         )
                     };
                     
-                    let confidence = {
-                        let mirror_ratio = opposite_set.len() as f32 / decomposition.len() as f32;
-                        let exists_bonus = if let Ok(result) = self.executor.execute(
-                            &QueryBuilder::find(&mirror_word).compile()
-                        ) {
-                            if !result.nodes.is_empty() { 0.3 } else { 0.0 }
-                        } else {
-                            0.0
-                        };
-                        (mirror_ratio * 0.7 + exists_bonus).min(1.0)
-                    };
+                    let confidence = self.calculate_mirror_confidence(&decomposition, &opposite_set, &mirror_word);
                     
                     mirrors.push(MirrorPair {
                         original: word.to_string(),
@@ -265,13 +480,12 @@ impl MirroringDecomposer {
         base_morphemes: &[String]
     ) -> Vec<SynthesisResult> {
         // Check cache first
-        let cache_key = format!("{:?}:{}", pattern_type, base_morphemes.join(","));
+        let cache_key = synthesis_cache_key(pattern_type, base_morphemes);
         if let Some(cached) = self.synthesis_cache.get(&cache_key) {
             return cached.clone();
         }
         
-        let synthesis_engine = SynthesisEngine::new(&self.db, &self.composition_weights);
-        let results = synthesis_engine.synthesize(pattern_type, base_morphemes, &mut self.executor);
+        let results = self.synthesize_uncached(pattern_type, base_morphemes);
         
         // Cache results
         self.synthesis_cache.put(cache_key, results.clone());
@@ -279,17 +493,27 @@ impl MirroringDecomposer {
         results
     }
     
+    /// Run synthesis without consulting or filling the cache
+    fn synthesize_uncached(
+        &mut self,
+        pattern_type: PatternType,
+        base_morphemes: &[String]
+    ) -> Vec<SynthesisResult> {
+        let synthesis_engine = SynthesisEngine::new(&self.db, &self.composition_weights, &self.phonotactics);
+        synthesis_engine.synthesize(pattern_type, base_morphemes, &mut self.executor)
+    }
+    
     /// Validate decomposition through round-trip testing
     pub fn validate_decomposition_quality(&mut self, word: &str) -> ValidationResult {
         // Step 1: Decompose the word
-        let morphemes = self.decompose(word);
+        let morphemes = self.cached_decompose(word);
         
         // Step 2: Recompose from morphemes
         let morpheme_strings: Vec<String> = morphemes.iter()
             .map(|m| m.surface_form.clone())
             .collect();
         let recomposed_candidates = {
-            let composer = Composer::new(&self.db, &self.composition_weights);
+            let composer = Composer::new();
             composer.compose_from_morphemes(&morpheme_strings, &mut self.executor)
         };
         
@@ -302,8 +526,9 @@ impl MirroringDecomposer {
         ) {
             if let Some(word_node_id) = word_result.nodes.as_slice().first() {
                 if let Ok(word_node) = self.db.get_node(*word_node_id) {
-                    let composed_position = calculate_composed_position(&morphemes);
-                    let distance = euclidean_distance(word_node.position, composed_position);
+                    // Compare on the word's own layer, not just its x and y
+                    let composed_position = calculate_composed_position(&morphemes, word_node.layer);
+                    let distance = { word_node.position }.distance_to(composed_position);
                     1.0 / (1.0 + distance)
                 } else {
                     0.0
@@ -388,12 +613,14 @@ impl MirroringDecomposer {
             (MorphemeType::Root, MorphemeType::VerbSuffix) => 0.9,
             (MorphemeType::Prefix, MorphemeType::Root) => 0.8,
             (MorphemeType::Root, MorphemeType::Suffix) => 0.8,
+            // Infixed analyses list the root before its infix
+            (MorphemeType::Root, MorphemeType::Infix) => 0.75,
             _ => 0.5,
         };
         
         // Check spatial coherence if positions available
         let spatial_coherence = if let (Some(pos1), Some(pos2)) = (morph1.position, morph2.position) {
-            let distance = euclidean_distance(pos1, pos2);
+            let distance = pos1.distance_to(pos2);
             1.0 / (1.0 + distance)
         } else {
             0.5
@@ -403,18 +630,144 @@ impl MirroringDecomposer {
     }
 }
 
-/// Calculate Euclidean distance between two 3D points
-fn euclidean_distance(a: Coordinate3D, b: Coordinate3D) -> f32 {
-    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
+/// Node for `word`, preferring its Words-layer node
+fn word_node(db: &LingoDatabase, word: &str) -> Option<NodeId> {
+    let candidates = db.find_nodes_by_word(word);
+    candidates.iter()
+        .copied()
+        .find(|&id| db.get_node(id).is_ok_and(|node| node.layer == Layer::Words))
+        .or_else(|| candidates.first().copied())
+}
+
+/// Cache key shared by the single- and multi-threaded synthesis caches
+fn synthesis_cache_key(pattern_type: PatternType, base_morphemes: &[String]) -> String {
+    format!("{:?}:{}", pattern_type, base_morphemes.join(","))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::{EtymologyOrigin, NodeFlags};
+    use crate::storage::DatabaseBuilder;
+    use crate::test_support::open_database;
+    use tempfile::TempDir;
+    
+    /// A few Germanic morphemes and words, including the build/break and
+    /// grow/shrink pairs the etymological engine knows
+    pub(super) fn database() -> (TempDir, Arc<LingoDatabase>) {
+        let mut builder = DatabaseBuilder::new();
+        let mut add = |form: &str, layer: Layer, morpheme_type: MorphemeType, x: f32| {
+            builder.add_node_full(
+                form,
+                layer,
+                Coordinate3D::new(x, 0.5, layer.z_center()),
+                EtymologyOrigin::Germanic,
+                morpheme_type,
+                NodeFlags::empty(),
+            ).unwrap();
+        };
+        add("un", Layer::Morphemes, MorphemeType::Prefix, 0.1);
+        add("happy", Layer::Morphemes, MorphemeType::Root, 0.3);
+        add("teach", Layer::Morphemes, MorphemeType::Root, 0.5);
+        add("er", Layer::Morphemes, MorphemeType::AgentSuffix, 0.6);
+        for (i, word) in ["happy", "unhappy", "teacher", "build", "break", "grow", "shrink"].iter().enumerate() {
+            add(word, Layer::Words, MorphemeType::Root, 0.1 + i as f32 * 0.1);
+        }
+        let (dir, db) = open_database(&builder);
+        (dir, Arc::new(db))
+    }
+    
+    fn decomposer() -> (TempDir, MirroringDecomposer) {
+        let (dir, db) = database();
+        (dir, MirroringDecomposer::new(db).unwrap())
+    }
     
     #[test]
     fn test_mirroring_decomposer_creation() {
         // This test would require a test database
         // Skipping for now as it needs full setup
     }
+    
+    #[test]
+    fn test_select_mirrors_dedups_filters_and_caps() {
+        let pair = |mirror: &str, confidence: f32| MirrorPair {
+            original: "manager".to_string(),
+            mirror: mirror.to_string(),
+            mirror_type: MirrorType::Reversal,
+            confidence,
+        };
+        let mirrors = vec![
+            pair("worker", 0.6),
+            pair("subordinate", 0.4),
+            pair("worker", 0.9),
+            pair("employee", 0.9),
+            pair("unmanager", 0.1),
+        ];
+        
+        let selected = select_mirrors(mirrors.clone(), 0.3, usize::MAX);
+        let words: Vec<(&str, f32)> = selected.iter().map(|m| (m.mirror.as_str(), m.confidence)).collect();
+        assert_eq!(words, vec![("worker", 0.9), ("employee", 0.9), ("subordinate", 0.4)]);
+        
+        let capped = select_mirrors(mirrors, 0.0, 2);
+        assert_eq!(capped.len(), 2);
+        assert!(capped.iter().all(|m| m.confidence == 0.9));
+    }
+    
+    #[test]
+    fn test_decomposition_is_cached_per_word() {
+        let (_dir, mut decomposer) = decomposer();
+        
+        let first = decomposer.decompose("unhappy");
+        assert_eq!(decomposer.decompositions_computed(), 1);
+        let forms = |m: &[MorphemeAnalysis]| m.iter().map(|a| a.surface_form.clone()).collect::<Vec<_>>();
+        assert_eq!(forms(&first), ["un", "happy"]);
+        
+        // Repeats hit the cache, including through validation
+        let again = decomposer.decompose("unhappy");
+        decomposer.validate_decomposition_quality("unhappy");
+        assert_eq!(decomposer.decompositions_computed(), 1);
+        assert_eq!(forms(&first), forms(&again));
+        
+        decomposer.decompose("teacher");
+        assert_eq!(decomposer.decompositions_computed(), 2);
+    }
+    
+    #[test]
+    fn test_find_mirrors_batch_matches_per_word() {
+        let (_dir, mut decomposer) = decomposer();
+        let words = ["build", "happy", "grow"];
+        
+        let per_word: Vec<Vec<MirrorPair>> = words.iter().map(|w| decomposer.find_mirrors(w)).collect();
+        assert_eq!(decomposer.engines_built(), words.len());
+        
+        let batch = decomposer.find_mirrors_batch(&words);
+        assert_eq!(decomposer.engines_built(), words.len() + 1);
+        
+        assert_eq!(batch.len(), per_word.len());
+        for (a, b) in batch.iter().zip(&per_word) {
+            let a: Vec<_> = a.iter().map(|m| (&m.mirror, m.confidence)).collect();
+            let b: Vec<_> = b.iter().map(|m| (&m.mirror, m.confidence)).collect();
+            assert_eq!(a, b);
+        }
+        assert_eq!(batch[0][0].mirror, "break");
+        assert!(batch[1].is_empty());
+        assert_eq!(batch[2][0].mirror, "shrink");
+    }
+    
+    #[test]
+    fn test_find_mirror_connections_emits_antonymy() {
+        let (_dir, mut decomposer) = decomposer();
+        let node = |word: &str| word_node(&decomposer.db, word).unwrap();
+        let (build, break_) = (node("build"), node("break"));
+        
+        let connections = decomposer.find_mirror_connections("build");
+        assert_eq!(connections.len(), 1);
+        let (source, connection) = connections[0];
+        assert_eq!(source, build);
+        assert_eq!({ connection.target_node }, break_);
+        assert_eq!({ connection.connection_type }, ConnectionType::Antonymy);
+        
+        assert!(decomposer.find_mirror_connections("happy").is_empty());
+        assert!(decomposer.find_mirror_connections("missing").is_empty());
+    }
 }
\ No newline at end of file
@@ -16,106 +16,172 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
-use crate::core::{Coordinate3D, MorphemeType, EtymologyOrigin, LinguisticNode, NodeId, Layer};
+use crate::core::{Coordinate3D, EtymologyOrigin, LinguisticNode, NodeId, Layer};
+use crate::discovery::semantic_field::semantic_fields;
 use crate::storage::LingoDatabase;
 use crate::morphology::MorphemeAnalysis;
-use crate::engine::LingoExecutor;
 use crate::query::QueryBuilder;
 use lru::LruCache;
 
+/// How a mirror relates to the word it was found for
 #[derive(Debug, Clone, PartialEq)]
 pub enum MirrorType {
+    /// Opposite roots within one etymology family, e.g. bene/male
     EtymologicalOpposite {
+        /// Family both roots come from
         root_family: EtymologyFamily,
+        /// Distance between the roots in semantic space
         semantic_distance: f32,
     },
+    /// Opposite role in a relation, e.g. teacher/student
     FunctionalOpposite {
+        /// Role the mirror plays
         role_inversion: RoleType,
+        /// Domain the roles belong to
         domain_context: String,
     },
+    /// The word with a negating affix, e.g. happy/unhappy
     MorphologicalOpposite {
+        /// How the word is negated
         valid_negation: NegationType,
+        /// How freely the negating affix attaches, from 0.0 to 1.0
         productivity_score: f32,
     },
+    /// The same concept reached through a different etymology
     CrossLinguisticMirror {
+        /// Etymology of the original word
         source_etymology: EtymologyOrigin,
+        /// Etymology of the mirror
         target_etymology: EtymologyOrigin, 
+        /// How the mirror entered the language
         borrowing_pattern: BorrowingType,
     },
+    /// A word on the opposite side of semantic space
     SpatialOpposite {
+        /// Offset from the original word to the mirror
         vector_opposition: Coordinate3D,
+        /// How tightly opposites cluster around the mirror, from 0.0 to 1.0
         clustering_confidence: f32,
     },
     // Legacy types for compatibility
+    /// Negated form of the word
     Negation,
+    /// Reversed action, e.g. build/break
     Reversal,
+    /// Complementary pair with no middle ground
     Complementary,
+    /// Ends of a scale, e.g. hot/cold
     Gradable,
+    /// Opposite directions, e.g. up/down
     Directional,
 }
 
+/// Language family a root comes from
 #[derive(Debug, Clone, PartialEq)]
 pub enum EtymologyFamily {
+    /// Latin roots
     Latin,
+    /// Greek roots
     Greek,
+    /// Germanic roots
     Germanic,
+    /// Romance languages descended from Latin
     Romance,
+    /// Proto-Indo-European roots
     IndoEuropean,
+    /// Semitic roots
     Semitic,
 }
 
+/// Role a word plays in a relation
 #[derive(Debug, Clone, PartialEq)]
 pub enum RoleType {
+    /// Performs the action
     Agent,
+    /// Undergoes the action
     Patient,
+    /// Takes part in an event
     Actor,
+    /// Is acted upon
     Target,
+    /// Brings something into being
     Creator,
+    /// Ends something's existence
     Destroyer,
+    /// Passes on knowledge
     Teacher,
+    /// Receives knowledge
     Student,
 }
 
+/// How a word is negated
 #[derive(Debug, Clone, PartialEq)]
 pub enum NegationType {
-    Prefix(String),        // un-, dis-, non-
-    Suffix(String),        // -less
-    Circumfix(String, String), // ge-...-t
-    LexicalReplacement,    // good -> bad
+    /// Negating prefix, e.g. un-, dis-, non-
+    Prefix(String),
+    /// Negating suffix, e.g. -less
+    Suffix(String),
+    /// Prefix and suffix around the root, e.g. ge-...-t
+    Circumfix(String, String),
+    /// A different word altogether, e.g. good -> bad
+    LexicalReplacement,
 }
 
+/// How a word entered the language from another
 #[derive(Debug, Clone, PartialEq)]
 pub enum BorrowingType {
+    /// Taken over whole
     Direct,
+    /// Translated part by part
     Calque,
+    /// Existing word given the foreign meaning
     Semantic,
+    /// Respelled to match the foreign sound
     Phonetic,
 }
 
+/// Etymology of a word and its morphemes
 #[derive(Debug, Clone)]
 pub struct EtymologyProfile {
+    /// The word profiled
     pub word: String,
+    /// Etymology of the word as a whole
     pub primary_etymology: EtymologyOrigin,
+    /// Etymology of each morpheme, in surface order
     pub morpheme_etymologies: Vec<EtymologyData>,
+    /// Position of the word in semantic space
     pub semantic_position: Coordinate3D,
+    /// Concepts the roots express
     pub root_concepts: Vec<String>,
 }
 
+/// Etymology of one morpheme
 #[derive(Debug, Clone)]
 pub struct EtymologyData {
+    /// Surface form of the morpheme
     pub morpheme: String,
+    /// Language the morpheme comes from
     pub origin: EtymologyOrigin,
+    /// Meaning of the root in its source language
     pub root_meaning: String,
+    /// Semantic field the morpheme belongs to
     pub semantic_field: String,
+    /// Earlier forms, oldest first
     pub historical_development: Vec<String>,
 }
 
+/// A mirror found by the etymological engine, with its evidence
 #[derive(Debug, Clone)]
 pub struct EtymologicalMirror {
+    /// The word mirrors were requested for
     pub original: String,
+    /// The mirrored word
     pub mirror: String,
+    /// How the mirror relates to the original
     pub mirror_type: MirrorType,
+    /// Confidence in the mirror, from 0.0 to 1.0
     pub confidence: f32,
+    /// Human-readable reasons the mirror was chosen
     pub linguistic_evidence: Vec<String>,
 }
 
@@ -124,23 +190,6 @@ pub enum FunctionalRole {
     Agent { domain: String, capability_type: String },
     Action { transformation_type: String, intensity: f32 },
     State { polarity: f32, stability: f32 },
-    Relation { directionality: String, symmetry: bool },
-}
-
-#[derive(Debug, Clone)]
-pub struct OppositionVector {
-    pub semantic_direction: Coordinate3D,
-    pub strength: f32,
-    pub context: String,
-}
-
-#[derive(Debug, Clone)]
-pub struct ValidatedMirror {
-    pub mirror: EtymologicalMirror,
-    pub validation_score: f32,
-    pub real_word_check: bool,
-    pub morphological_validity: bool,
-    pub etymological_consistency: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -151,49 +200,51 @@ pub struct SpatialOpposite {
     pub clustering_strength: f32,
 }
 
+/// Known opposite roots within one etymology
+#[derive(Debug, Clone)]
 pub struct EtymologyGraph {
+    /// Opposite roots by root
     pub oppositions: HashMap<String, Vec<String>>,
-    pub semantic_fields: HashMap<String, Vec<String>>,
-    pub historical_relations: HashMap<String, Vec<String>>,
 }
 
 /// Advanced etymological mirror engine for discovering linguistic opposites
 pub struct EtymologicalMirrorEngine {
     db: Arc<LingoDatabase>,
     etymology_graphs: HashMap<EtymologyOrigin, EtymologyGraph>,
-    opposition_vectors: HashMap<String, Vec<OppositionVector>>,
-    validated_mirrors: HashMap<String, Vec<ValidatedMirror>>,
     spatial_opposition_cache: LruCache<String, Vec<SpatialOpposite>>,
 }
 
+// Implement Debug manually; the engine holds the whole database
+impl std::fmt::Debug for EtymologicalMirrorEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EtymologicalMirrorEngine")
+            .field("etymology_graphs", &self.etymology_graphs.len())
+            .field("cached_spatial_opposites", &self.spatial_opposition_cache.len())
+            .finish_non_exhaustive()
+    }
+}
+
 impl EtymologicalMirrorEngine {
+    /// Create an engine over `database`, seeded with known root oppositions
     pub fn new(database: Arc<LingoDatabase>) -> Self {
         let mut etymology_graphs = HashMap::new();
         
         // Initialize etymology graphs with known oppositions
         etymology_graphs.insert(EtymologyOrigin::Latin, EtymologyGraph {
             oppositions: Self::build_latin_oppositions(),
-            semantic_fields: Self::build_latin_semantic_fields(),
-            historical_relations: HashMap::new(),
         });
         
         etymology_graphs.insert(EtymologyOrigin::Greek, EtymologyGraph {
             oppositions: Self::build_greek_oppositions(),
-            semantic_fields: Self::build_greek_semantic_fields(),
-            historical_relations: HashMap::new(),
         });
         
         etymology_graphs.insert(EtymologyOrigin::Germanic, EtymologyGraph {
             oppositions: Self::build_germanic_oppositions(),
-            semantic_fields: Self::build_germanic_semantic_fields(),
-            historical_relations: HashMap::new(),
         });
         
         Self {
             db: database,
             etymology_graphs,
-            opposition_vectors: HashMap::new(),
-            validated_mirrors: HashMap::new(),
             spatial_opposition_cache: LruCache::new(std::num::NonZeroUsize::new(1000).unwrap()),
         }
     }
@@ -234,27 +285,6 @@ impl EtymologicalMirrorEngine {
         oppositions
     }
     
-    fn build_latin_semantic_fields() -> HashMap<String, Vec<String>> {
-        let mut fields = HashMap::new();
-        fields.insert("creation".to_string(), vec!["create".to_string(), "construct".to_string(), "produce".to_string()]);
-        fields.insert("destruction".to_string(), vec!["destroy".to_string(), "destruct".to_string(), "reduce".to_string()]);
-        fields
-    }
-    
-    fn build_greek_semantic_fields() -> HashMap<String, Vec<String>> {
-        let mut fields = HashMap::new();
-        fields.insert("discourse".to_string(), vec!["dialogue".to_string(), "monologue".to_string()]);
-        fields.insert("analysis".to_string(), vec!["synthesis".to_string(), "analysis".to_string()]);
-        fields
-    }
-    
-    fn build_germanic_semantic_fields() -> HashMap<String, Vec<String>> {
-        let mut fields = HashMap::new();
-        fields.insert("construction".to_string(), vec!["build".to_string(), "make".to_string()]);
-        fields.insert("destruction".to_string(), vec!["break".to_string(), "destroy".to_string()]);
-        fields
-    }
-    
     /// REPLACE: Simple prefix generation WITH: True etymological analysis
     pub fn discover_etymological_mirrors(&self, word: &str) -> Result<Vec<EtymologicalMirror>, crate::core::error::LingoError> {
         let mut mirrors = Vec::new();
@@ -287,9 +317,7 @@ impl EtymologicalMirrorEngine {
     }
     
     fn analyze_etymology_profile(&self, word: &str) -> Result<EtymologyProfile, crate::core::error::LingoError> {
-        // Create a temporary executor for database queries
-        let mut executor = crate::engine::LingoExecutor::new();
-        executor.load_database("english.lingo")?;
+        let mut executor = self.executor();
         
         // Query the database for deep etymological analysis
         let word_result = executor.execute(
@@ -315,11 +343,11 @@ impl EtymologicalMirrorEngine {
             .map(|&node_id| {
                 let morpheme_node = self.db.get_node(node_id).unwrap();
                 EtymologyData {
-                    morpheme: self.get_surface_form(&morpheme_node),
+                    morpheme: self.get_surface_form(morpheme_node),
                     origin: morpheme_node.etymology_origin,
-                    root_meaning: self.extract_root_meaning(&morpheme_node),
-                    semantic_field: self.extract_semantic_field(&morpheme_node),
-                    historical_development: self.trace_historical_development(&morpheme_node),
+                    root_meaning: self.extract_root_meaning(morpheme_node),
+                    semantic_field: self.extract_semantic_field(morpheme_node),
+                    historical_development: self.trace_historical_development(morpheme_node),
                 }
             })
             .collect();
@@ -369,7 +397,7 @@ impl EtymologicalMirrorEngine {
     
     fn find_spatial_opposites(&self, word: &str) -> Vec<EtymologicalMirror> {
         // Check cache first
-        if let Some(cached) = self.spatial_opposition_cache.get(word) {
+        if let Some(cached) = self.spatial_opposition_cache.peek(word) {
             return cached.iter().map(|spatial_opp| EtymologicalMirror {
                 original: word.to_string(),
                 mirror: spatial_opp.word.clone(),
@@ -385,11 +413,7 @@ impl EtymologicalMirrorEngine {
             }).collect();
         }
         
-        // Create executor for spatial queries
-        let mut executor = crate::engine::LingoExecutor::new();
-        if executor.load_database("english.lingo").is_err() {
-            return Vec::new();
-        }
+        let mut executor = self.executor();
         
         // Get word position
         let word_result = executor.execute(
@@ -417,11 +441,7 @@ impl EtymologicalMirrorEngine {
             z: word_position.z,        // Keep abstraction level
         };
         
-        // Create executor for spatial radius queries
-        let mut executor = crate::engine::LingoExecutor::new();
-        if executor.load_database("english.lingo").is_err() {
-            return Vec::new();
-        }
+        let mut executor = self.executor();
         
         // Find words clustered around the opposite point using a spatial query
         let spatial_result = executor.execute(
@@ -435,15 +455,15 @@ impl EtymologicalMirrorEngine {
                 .filter_map(|&candidate_id| self.db.get_node(candidate_id).ok())
                 .map(|candidate| EtymologicalMirror {
                     original: word.to_string(),
-                    mirror: self.get_surface_form(&candidate),
+                    mirror: self.get_surface_form(candidate),
                     mirror_type: MirrorType::SpatialOpposite {
                         vector_opposition: self.calculate_opposition_vector_between(
                             word_position, 
                             candidate.position
                         ),
-                        clustering_confidence: self.calculate_clustering_confidence(&candidate),
+                        clustering_confidence: self.calculate_clustering_confidence(candidate),
                     },
-                    confidence: self.calculate_spatial_confidence_between(&word_position, &candidate.position),
+                    confidence: self.calculate_spatial_confidence_between(&word_position, &{ candidate.position }),
                     linguistic_evidence: vec![
                         format!("Spatial distance: {:.3}", word_position.distance(candidate.position)),
                         format!("Opposition vector: {:?}", opposite_point),
@@ -492,7 +512,6 @@ impl EtymologicalMirrorEngine {
             FunctionalRole::State { polarity, stability } => {
                 opposites.extend(self.find_opposite_states(polarity, stability));
             },
-            _ => {},
         }
         
         opposites
@@ -532,8 +551,8 @@ impl EtymologicalMirrorEngine {
         "unknown".to_string() // Placeholder - would extract from semantic annotations
     }
     
-    fn extract_semantic_field(&self, _node: &LinguisticNode) -> String {
-        "general".to_string() // Placeholder - would extract from semantic categories
+    fn extract_semantic_field(&self, node: &LinguisticNode) -> String {
+        semantic_fields(&self.db, node.id).first().map_or("general", |field| field).to_string()
     }
     
     fn trace_historical_development(&self, _node: &LinguisticNode) -> Vec<String> {
@@ -554,12 +573,7 @@ impl EtymologicalMirrorEngine {
     }
     
     fn determine_etymology_family(&self, origin: EtymologyOrigin) -> EtymologyFamily {
-        match origin {
-            EtymologyOrigin::Latin | EtymologyOrigin::French => EtymologyFamily::Romance,
-            EtymologyOrigin::Greek => EtymologyFamily::Greek,
-            EtymologyOrigin::Germanic => EtymologyFamily::Germanic,
-            _ => EtymologyFamily::IndoEuropean,
-        }
+        etymology_family(origin)
     }
     
     fn calculate_etymology_confidence(&self, _profile: &EtymologyProfile, _candidate: &str) -> f32 {
@@ -602,17 +616,19 @@ impl EtymologicalMirrorEngine {
         1.0 / (1.0 + distance)
     }
     
+    /// Executor over the engine's own database
+    fn executor(&self) -> crate::engine::LingoExecutor {
+        crate::engine::LingoExecutor::from_shared(Arc::clone(&self.db))
+    }
+    
     fn word_exists_in_database(&self, word: &str) -> bool {
-        let mut executor = crate::engine::LingoExecutor::new();
-        if executor.load_database("english.lingo").is_err() {
-            return false;
-        }
+        let mut executor = self.executor();
         
         let result = executor.execute(
             &QueryBuilder::find(word).compile()
         );
         
-        result.map_or(false, |r| !r.nodes.is_empty())
+        result.is_ok_and(|r| !r.nodes.is_empty())
     }
     
     fn validate_semantic_opposition(&self, _word1: &str, _word2: &str) -> bool {
@@ -688,33 +704,159 @@ impl EtymologicalMirrorEngine {
     }
 }
 
+/// Share of the opposition score from matching etymology families
+const FAMILY_WEIGHT: f32 = 0.4;
+/// Share of the opposition score from sitting opposite in semantic space
+const SPATIAL_WEIGHT: f32 = 0.4;
+/// Share of the opposition score from the candidate's productivity
+const PRODUCTIVITY_WEIGHT: f32 = 0.2;
+
 // Legacy OppositionEngine for backward compatibility
+/// Finds opposite morpheme sets for the decomposer
 pub struct OppositionEngine<'a> {
     engine: EtymologicalMirrorEngine,
-    _phantom: std::marker::PhantomData<&'a ()>,
+    db: &'a LingoDatabase,
+}
+
+// Implement Debug manually; the engine borrows the whole database
+impl std::fmt::Debug for OppositionEngine<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OppositionEngine")
+            .field("engine", &self.engine)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<'a> OppositionEngine<'a> {
-    pub fn new(db: &'a LingoDatabase) -> Self {
-        let engine = EtymologicalMirrorEngine::new(Arc::new((*db).clone()));
+    /// Create an engine over `db`
+    pub fn new(db: &'a Arc<LingoDatabase>) -> Self {
+        let engine = EtymologicalMirrorEngine::new(Arc::clone(db));
         Self {
             engine,
-            _phantom: std::marker::PhantomData,
+            db,
         }
     }
     
+    /// Opposite morpheme sets, best first (see `find_opposite_morphemes_ranked`)
     pub fn find_opposite_morphemes(&self, morpheme: &MorphemeAnalysis) -> Vec<Vec<String>> {
-        // Legacy compatibility - convert new system to old format
-        if let Ok(mirrors) = self.engine.discover_etymological_mirrors(&morpheme.surface_form) {
-            mirrors.into_iter()
-                .map(|mirror| vec![mirror.mirror])
-                .collect()
-        } else {
-            Vec::new()
+        self.find_opposite_morphemes_ranked(morpheme)
+            .into_iter()
+            .map(|(set, _)| set)
+            .collect()
+    }
+    
+    /// Opposite morpheme sets with a score in [0, 1], sorted descending.
+    ///
+    /// The score combines whether the candidate shares the morpheme's
+    /// etymology family, how close it sits to the morpheme's reflection in
+    /// semantic space, and its morphological productivity. Candidates that
+    /// aren't in the database score low on all three.
+    pub fn find_opposite_morphemes_ranked(&self, morpheme: &MorphemeAnalysis) -> Vec<(Vec<String>, f32)> {
+        let Ok(mirrors) = self.engine.discover_etymological_mirrors(&morpheme.surface_form) else {
+            return Vec::new();
+        };
+        
+        let source = morpheme.node_id.and_then(|id| self.db.get_node(id).ok());
+        let origin = source.map_or(EtymologyOrigin::Unknown, |node| node.etymology_origin);
+        let position = morpheme.position.or(source.map(|node| node.position));
+        
+        // The engine can suggest a mirror more than once; keep its best score
+        let mut best: HashMap<String, f32> = HashMap::new();
+        for mirror in mirrors {
+            let candidate = self.db.find_nodes_by_word(&mirror.mirror)
+                .first()
+                .and_then(|&id| self.db.get_node(id).ok());
+            let score = opposition_score(origin, position, candidate);
+            let entry = best.entry(mirror.mirror).or_insert(score);
+            *entry = entry.max(score);
         }
+        
+        let mut ranked: Vec<(Vec<String>, f32)> = best.into_iter()
+            .map(|(mirror, score)| (vec![mirror], score))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
     }
     
+    /// Mirror type of `opposite_morphemes`; always gradable for now
     pub fn classify_mirror_type(&self, _original: &MorphemeAnalysis, _opposite_morphemes: &[String]) -> MirrorType {
         MirrorType::Gradable // Legacy default
     }
-}
\ No newline at end of file
+}
+
+/// Combined opposition score of a candidate against a source morpheme.
+///
+/// Etymology counts fully for a matching family, half when either origin is
+/// unknown, and not at all otherwise. The spatial term is 1.0 at the point
+/// mirrored through the centre of the x/y plane and falls to 0.0 at the
+/// diagonal of the unit cube. A missing candidate only gets the half-credit
+/// etymology term.
+fn opposition_score(
+    origin: EtymologyOrigin,
+    position: Option<Coordinate3D>,
+    candidate: Option<&LinguisticNode>,
+) -> f32 {
+    let candidate_origin = candidate.map_or(EtymologyOrigin::Unknown, |node| node.etymology_origin);
+    let family = if origin == EtymologyOrigin::Unknown || candidate_origin == EtymologyOrigin::Unknown {
+        0.5
+    } else if etymology_family(origin) == etymology_family(candidate_origin) {
+        1.0
+    } else {
+        0.0
+    };
+    
+    let spatial = match (position, candidate) {
+        (Some(position), Some(node)) => {
+            let reflected = Coordinate3D { x: 1.0 - position.x, y: 1.0 - position.y, z: position.z };
+            1.0 - (reflected.distance_to(node.position) / 3.0f32.sqrt()).min(1.0)
+        }
+        _ => 0.0,
+    };
+    
+    let productivity = candidate.map_or(0.0, LinguisticNode::productivity);
+    
+    FAMILY_WEIGHT * family + SPATIAL_WEIGHT * spatial + PRODUCTIVITY_WEIGHT * productivity
+}
+
+/// Etymology family an origin belongs to
+fn etymology_family(origin: EtymologyOrigin) -> EtymologyFamily {
+    match origin {
+        EtymologyOrigin::Latin | EtymologyOrigin::French => EtymologyFamily::Romance,
+        EtymologyOrigin::Greek => EtymologyFamily::Greek,
+        EtymologyOrigin::Germanic | EtymologyOrigin::Norse => EtymologyFamily::Germanic,
+        _ => EtymologyFamily::IndoEuropean,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn node(origin: EtymologyOrigin, position: Coordinate3D, productivity: f32) -> LinguisticNode {
+        let mut node = LinguisticNode::new(NodeId(1), Layer::Morphemes, position);
+        node.etymology_origin = origin;
+        node.set_productivity(productivity);
+        node
+    }
+    
+    #[test]
+    fn test_opposition_score_ranks_family_space_and_productivity() {
+        let position = Coordinate3D::new(0.2, 0.3, 0.4);
+        let mirrored = Coordinate3D::new(0.8, 0.7, 0.4);
+        
+        let ideal = node(EtymologyOrigin::Latin, mirrored, 1.0);
+        let score = opposition_score(EtymologyOrigin::Latin, Some(position), Some(&ideal));
+        assert!((score - 1.0).abs() < 1e-4);
+        
+        let other_family = node(EtymologyOrigin::Germanic, mirrored, 1.0);
+        let nearby = node(EtymologyOrigin::Latin, position, 1.0);
+        let unproductive = node(EtymologyOrigin::Latin, mirrored, 0.0);
+        for worse in [&other_family, &nearby, &unproductive] {
+            assert!(opposition_score(EtymologyOrigin::Latin, Some(position), Some(worse)) < score);
+        }
+        
+        // Unknown candidates only get half credit for etymology
+        let missing = opposition_score(EtymologyOrigin::Latin, Some(position), None);
+        assert!((missing - FAMILY_WEIGHT * 0.5).abs() < 1e-6);
+    }
+}
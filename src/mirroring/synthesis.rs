@@ -14,32 +14,64 @@
 
 //! Synthesis engine for generating new functional expressions
 
-use std::collections::HashSet;
-use crate::core::{LinguisticNode, NodeId, Layer, Coordinate3D, MorphemeType};
+use crate::core::{LinguisticNode, NodeId, Layer, Coordinate3D, MorphemeType, clamp01};
 use crate::storage::LingoDatabase;
 use crate::engine::LingoExecutor;
 use crate::query::QueryBuilder;
 use crate::plugins::function_extraction::{FunctionalPrimitive, ActionType, TemporalAspect};
+use crate::morphology::{compose_position, PhonotacticValidator};
 use super::{CompositionWeights, PatternType};
 
+/// A word synthesized to express a functional primitive
 #[derive(Debug, Clone)]
 pub struct SynthesisResult {
+    /// The synthesized word
     pub generated_word: String,
+    /// Function the word is expected to express
     pub predicted_function: FunctionalPrimitive,
+    /// Morphemes the word was composed from, in order
     pub morpheme_composition: Vec<String>,
+    /// Confidence in the synthesis, from 0.0 to 1.0
     pub confidence: f32,
+    /// Position composed from the morphemes' positions
     pub spatial_position: Coordinate3D,
+    /// How pronounceable the word is as English, from 0.0 to 1.0; see
+    /// [`PhonotacticValidator::plausibility`]. Confidence is scaled by it
+    pub plausibility: f32,
 }
 
 /// Engine for synthesizing new functional expressions
 pub struct SynthesisEngine<'a> {
     db: &'a LingoDatabase,
     weights: &'a CompositionWeights,
+    phonotactics: &'a PhonotacticValidator,
+}
+
+// Implement Debug manually; the engine borrows the whole database
+impl std::fmt::Debug for SynthesisEngine<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SynthesisEngine")
+            .field("weights", &self.weights)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<'a> SynthesisEngine<'a> {
-    pub fn new(db: &'a LingoDatabase, weights: &'a CompositionWeights) -> Self {
-        Self { db, weights }
+    /// Create an engine over `db`, composing positions with `weights` and
+    /// scoring words with `phonotactics`.
+    ///
+    /// Build the validator once per database with
+    /// [`PhonotacticValidator::from_database`] and share it between engines.
+    pub fn new(
+        db: &'a LingoDatabase,
+        weights: &'a CompositionWeights,
+        phonotactics: &'a PhonotacticValidator,
+    ) -> Self {
+        Self {
+            db,
+            weights,
+            phonotactics,
+        }
     }
     
     /// Synthesize new functional expressions based on pattern type
@@ -188,8 +220,10 @@ impl<'a> SynthesisEngine<'a> {
             spatial_position
         );
         
-        // Calculate confidence
-        let confidence = self.calculate_synthesis_confidence(
+        // Calculate confidence, discounting unpronounceable clusters so an
+        // illegal onset like "ngtk-" falls below the threshold
+        let plausibility = self.phonotactics.plausibility(&composed_word);
+        let confidence = plausibility * self.calculate_synthesis_confidence(
             &composed_word,
             morphemes,
             pattern_type,
@@ -204,6 +238,7 @@ impl<'a> SynthesisEngine<'a> {
                 morpheme_composition: morphemes.to_vec(),
                 confidence,
                 spatial_position,
+                plausibility,
             })
         } else {
             None
@@ -247,25 +282,8 @@ impl<'a> SynthesisEngine<'a> {
             }
         }
         
-        if positions.is_empty() {
-            // Default position
-            Coordinate3D { x: 0.5, y: 0.5, z: 3.0 }
-        } else {
-            // Weighted average
-            let total_weight: f32 = weights.iter().sum();
-            let weighted_x: f32 = positions.iter().zip(&weights)
-                .map(|(pos, w)| pos.x * w).sum::<f32>() / total_weight;
-            let weighted_y: f32 = positions.iter().zip(&weights)
-                .map(|(pos, w)| pos.y * w).sum::<f32>() / total_weight;
-            let weighted_z: f32 = positions.iter().zip(&weights)
-                .map(|(pos, w)| pos.z * w).sum::<f32>() / total_weight;
-            
-            Coordinate3D {
-                x: weighted_x,
-                y: weighted_y,
-                z: 3.0, // Force to words layer
-            }
-        }
+        // Synthesized words belong on the Words layer, above their morphemes
+        compose_position(positions.into_iter().zip(weights), Layer::Words)
     }
     
     /// Predict functional primitive from synthesis
@@ -349,7 +367,7 @@ impl<'a> SynthesisEngine<'a> {
         };
         confidence += pattern_bonus;
         
-        confidence.min(1.0)
+        clamp01(confidence)
     }
     
     /// Check if morphemes are compatible
@@ -394,4 +412,30 @@ impl<'a> SynthesisEngine<'a> {
             MorphemeType::Root
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::DatabaseBuilder;
+    use crate::test_support::database_and_executor;
+
+    #[test]
+    fn test_synthesis_rejects_unpronounceable_words() {
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("er", Layer::Morphemes, Coordinate3D::new(0.6, 0.4, 0.375)).unwrap();
+        builder.add_node("teacher", Layer::Words, Coordinate3D::new(0.6, 0.5, 0.52)).unwrap();
+
+        let (_dir, db, mut executor) = database_and_executor(&builder);
+        let weights = CompositionWeights::default();
+        let phonotactics = PhonotacticValidator::from_database(&db);
+        let engine = SynthesisEngine::new(&db, &weights, &phonotactics);
+
+        let base = vec!["ngtk".to_string(), "blorp".to_string()];
+        let results = engine.synthesize(PatternType::Agent, &base, &mut executor);
+
+        let words: Vec<&str> = results.iter().map(|r| r.generated_word.as_str()).collect();
+        assert!(words.contains(&"blorper"), "{:?}", words);
+        assert!(words.iter().all(|word| !word.starts_with("ngtk")), "{:?}", words);
+        assert!(results.iter().all(|r| r.plausibility == 1.0));
+    }
+}
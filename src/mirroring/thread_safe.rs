@@ -0,0 +1,149 @@
+// Copyright 2025 Roberto Antunes
+//
+// Licensed under the Functional Source License, Version 1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://github.com/RobAntunes/lingodb/blob/main/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Thread-safe wrapper around the mirroring decomposer
+
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex, MutexGuard};
+use lru::LruCache;
+
+use crate::core::error::LingoError;
+use crate::storage::LingoDatabase;
+use super::{synthesis_cache_key, MirroringDecomposer, PatternType, SynthesisResult};
+
+/// Number of synthesis results kept in the shared cache
+const SYNTHESIS_CACHE_SIZE: usize = 1000;
+
+/// A [`MirroringDecomposer`] that can be shared across threads behind an `Arc`.
+///
+/// Synthesis results are cached behind their own lock, so cache hits don't
+/// wait on a synthesis running in another thread. Cache misses run one at a
+/// time because the decomposer's executor needs exclusive access.
+///
+/// A panic in one thread doesn't poison the wrapper for the others; the
+/// cache only holds complete results, so it stays usable.
+pub struct ThreadSafeMirroringDecomposer {
+    decomposer: Mutex<MirroringDecomposer>,
+    synthesis_cache: Mutex<LruCache<String, Vec<SynthesisResult>>>,
+}
+
+impl ThreadSafeMirroringDecomposer {
+    /// Create a thread-safe decomposer over `database`
+    pub fn new(database: Arc<LingoDatabase>) -> Result<Self, LingoError> {
+        Ok(Self::from(MirroringDecomposer::new(database)?))
+    }
+    
+    /// Synthesize new functional expressions, sharing the cache between threads
+    pub fn synthesize_functions(
+        &self,
+        pattern_type: PatternType,
+        base_morphemes: &[String]
+    ) -> Vec<SynthesisResult> {
+        let cache_key = synthesis_cache_key(pattern_type, base_morphemes);
+        if let Some(cached) = lock(&self.synthesis_cache).get(&cache_key) {
+            return cached.clone();
+        }
+        
+        let results = lock(&self.decomposer).synthesize_uncached(pattern_type, base_morphemes);
+        lock(&self.synthesis_cache).put(cache_key, results.clone());
+        results
+    }
+    
+    /// Run `f` with exclusive access to the wrapped decomposer.
+    ///
+    /// Use this for the decomposer's other operations, such as
+    /// `decompose` or `find_mirrors`.
+    pub fn with_decomposer<R>(&self, f: impl FnOnce(&mut MirroringDecomposer) -> R) -> R {
+        f(&mut lock(&self.decomposer))
+    }
+    
+    /// Number of synthesis results currently cached
+    pub fn cached_results(&self) -> usize {
+        lock(&self.synthesis_cache).len()
+    }
+    
+    /// Unwrap the single-threaded decomposer
+    pub fn into_inner(self) -> MirroringDecomposer {
+        self.decomposer.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl From<MirroringDecomposer> for ThreadSafeMirroringDecomposer {
+    fn from(decomposer: MirroringDecomposer) -> Self {
+        Self {
+            decomposer: Mutex::new(decomposer),
+            synthesis_cache: Mutex::new(LruCache::new(
+                NonZeroUsize::new(SYNTHESIS_CACHE_SIZE).unwrap(),
+            )),
+        }
+    }
+}
+
+// Implement Debug manually; the decomposer holds a database and executor
+impl std::fmt::Debug for ThreadSafeMirroringDecomposer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThreadSafeMirroringDecomposer")
+            .field("cached_results", &self.cached_results())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Lock `mutex`, recovering the data if another thread panicked while holding it
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mirroring::tests::database;
+    use std::thread;
+    
+    #[test]
+    fn test_thread_safe_decomposer_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ThreadSafeMirroringDecomposer>();
+    }
+    
+    #[test]
+    fn test_concurrent_synthesis_shares_cache() {
+        let (_dir, db) = database();
+        let decomposer = Arc::new(ThreadSafeMirroringDecomposer::new(db).unwrap());
+        let base = vec!["teach".to_string()];
+        
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let decomposer = Arc::clone(&decomposer);
+                let base = base.clone();
+                thread::spawn(move || decomposer.synthesize_functions(PatternType::Agent, &base))
+            })
+            .collect();
+        let results: Vec<Vec<SynthesisResult>> = handles.into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+        
+        // Threads that missed the cache together may order results differently
+        let words = |r: &[SynthesisResult]| {
+            let mut words: Vec<String> = r.iter().map(|s| s.generated_word.clone()).collect();
+            words.sort();
+            words
+        };
+        assert!(words(&results[0]).contains(&"teacher".to_string()));
+        for result in &results[1..] {
+            assert_eq!(words(result), words(&results[0]));
+        }
+        assert_eq!(decomposer.cached_results(), 1);
+        assert_eq!(format!("{:?}", decomposer), "ThreadSafeMirroringDecomposer { cached_results: 1, .. }");
+    }
+}
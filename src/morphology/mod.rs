@@ -18,10 +18,15 @@
 //! Everything emerges from the database content.
 
 use crate::core::{NodeId, Layer, Coordinate3D, MorphemeType};
+use std::collections::HashMap;
 use crate::storage::LingoDatabase;
 use crate::query::QueryBuilder;
 use crate::engine::LingoExecutor;
 
+mod phonotactics;
+
+pub use phonotactics::{PhonotacticValidator, PhonotacticViolation, ClusterPosition};
+
 #[derive(Debug, Clone)]
 pub struct MorphemeAnalysis {
     pub surface_form: String,
@@ -30,11 +35,39 @@ pub struct MorphemeAnalysis {
     pub node_id: Option<NodeId>,
 }
 
+/// Options controlling how words are decomposed
+#[derive(Debug, Clone)]
+pub struct DecompositionOptions {
+    /// Compose a position from letter nodes when no morpheme split is found.
+    ///
+    /// This issues one query per character; disable it to get a `None`
+    /// position for unknown words instead.
+    pub letter_fallback: bool,
+}
+
+impl Default for DecompositionOptions {
+    fn default() -> Self {
+        Self {
+            letter_fallback: true,
+        }
+    }
+}
+
 /// Decompose a word into its constituent morphemes using the database
 pub fn decompose_word_to_morphemes(
     word: &str, 
     database: &LingoDatabase,
     executor: &mut LingoExecutor
+) -> Vec<MorphemeAnalysis> {
+    decompose_word_to_morphemes_with_options(word, database, executor, &DecompositionOptions::default())
+}
+
+/// Decompose a word into morphemes with explicit options
+pub fn decompose_word_to_morphemes_with_options(
+    word: &str,
+    database: &LingoDatabase,
+    executor: &mut LingoExecutor,
+    options: &DecompositionOptions,
 ) -> Vec<MorphemeAnalysis> {
     let lower_word = word.to_lowercase();
     
@@ -53,72 +86,352 @@ pub fn decompose_word_to_morphemes(
     }
     
     // If not found as a single morpheme, try to decompose
-    discover_morpheme_decomposition(&lower_word, database, executor)
+    discover_morpheme_decomposition(&lower_word, database, executor, options)
+}
+
+/// One candidate analysis of a word into morphemes
+#[derive(Debug, Clone)]
+pub struct DecompositionResult {
+    /// Morphemes in surface order
+    pub morphemes: Vec<MorphemeAnalysis>,
+    /// Plausibility of this analysis (0.0-1.0)
+    pub score: f32,
+}
+
+/// Return every analysis of `word` scoring at least `min_score`, best first.
+///
+/// Words are often ambiguous ("unionize" is both un+ion+ize and union+ize),
+/// so this keeps all of them rather than only the top split. A word that is
+/// itself a morpheme is returned as a single-morpheme analysis with score 1.0.
+/// Infixed analyses list the root, with its two halves joined, then the
+/// infix. Analyses with equal scores keep their discovery order.
+pub fn decompose_all(
+    word: &str,
+    database: &LingoDatabase,
+    executor: &mut LingoExecutor,
+    min_score: f32,
+) -> Vec<DecompositionResult> {
+    let lower_word = word.to_lowercase();
+    let mut results = Vec::new();
+    
+    if let Some(whole) = check_morpheme_in_database(&lower_word, database, executor) {
+        results.push(DecompositionResult {
+            morphemes: vec![whole],
+            score: 1.0,
+        });
+    }
+    
+    let mut lookup = MorphemeLookup::new(&lower_word);
+    results.extend(candidate_decompositions(&mut lookup, database, executor));
+    results.extend(infix_decompositions(&mut lookup, database, executor));
+    results.retain(|r| r.score >= min_score);
+    results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    results
 }
 
 /// Discover morpheme boundaries by finding valid morphemes in the database
 fn discover_morpheme_decomposition(
     word: &str,
     database: &LingoDatabase,
-    executor: &mut LingoExecutor
+    executor: &mut LingoExecutor,
+    options: &DecompositionOptions,
 ) -> Vec<MorphemeAnalysis> {
-    let mut best_decomposition = Vec::new();
-    let mut best_score = 0.0;
+    let mut lookup = MorphemeLookup::new(word);
+    let mut best = best_segmentation(&mut lookup, database, executor);
     
-    // Try all possible decomposition points
-    for split_point in 1..word.len() {
-        let part1 = &word[..split_point];
-        let part2 = &word[split_point..];
-        
+    // An infix only replaces a concatenative split it outscores, and none
+    // can beat one scoring INFIX_MAX_SCORE or more, so skip the search then
+    if best.as_ref().is_none_or(|b| b.score < INFIX_MAX_SCORE) {
+        let mut infixed = infix_decompositions(&mut lookup, database, executor);
+        infixed.sort_by(|a, b| b.score.total_cmp(&a.score));
+        if let Some(infixed) = infixed.into_iter().next() {
+            if best.as_ref().is_none_or(|b| infixed.score > b.score) {
+                best = Some(infixed);
+            }
+        }
+    }
+    
+    // If we found a good decomposition, return it
+    if let Some(best) = best {
+        if best.score > 0.5 {
+            return best.morphemes;
+        }
+    }
+    
+    // Otherwise, treat as unknown root composed from letters
+    let position = if options.letter_fallback {
+        calculate_position_from_letters(word, database, executor)
+    } else {
+        None
+    };
+    
+    vec![MorphemeAnalysis {
+        surface_form: word.to_string(),
+        morpheme_type: MorphemeType::Root,
+        position,
+        node_id: None,
+    }]
+}
+
+/// Morpheme lookups for the substrings of one word, each queried at most once
+struct MorphemeLookup<'w> {
+    word: &'w str,
+    /// Lookup results keyed by (start, end) byte offsets
+    found: HashMap<(usize, usize), Option<MorphemeAnalysis>>,
+}
+
+impl<'w> MorphemeLookup<'w> {
+    fn new(word: &'w str) -> Self {
+        Self { word, found: HashMap::new() }
+    }
+    
+    /// Whether `word[start..end]` is a known morpheme, querying on first use
+    fn lookup(&mut self, start: usize, end: usize, database: &LingoDatabase, executor: &mut LingoExecutor) -> bool {
+        let word = self.word;
+        self.found
+            .entry((start, end))
+            .or_insert_with(|| check_morpheme_in_database(&word[start..end], database, executor))
+            .is_some()
+    }
+    
+    /// A morpheme found by an earlier `lookup`
+    fn morpheme(&self, start: usize, end: usize) -> Option<&MorphemeAnalysis> {
+        self.found.get(&(start, end)).and_then(Option::as_ref)
+    }
+    
+    /// Look up `word[start..end]` and clone the result
+    fn get(&mut self, start: usize, end: usize, database: &LingoDatabase, executor: &mut LingoExecutor) -> Option<MorphemeAnalysis> {
+        self.lookup(start, end, database, executor);
+        self.morpheme(start, end).cloned()
+    }
+}
+
+/// Longest word, in characters, segmented by the full dynamic program.
+///
+/// The program takes O(n⁴) time in the word's length, so longer words are
+/// segmented greedily instead.
+const MAX_SEGMENTATION_CHARS: usize = 64;
+
+/// Score of a segmentation into `morphemes` parts whose adjacent pair
+/// scores add up to `pair_sum`
+fn segmentation_score(pair_sum: f32, morphemes: usize) -> f32 {
+    (pair_sum / (morphemes - 1) as f32 + 0.2).min(1.0)
+}
+
+/// Highest-scoring segmentation of the word into two or more known morphemes.
+///
+/// A dynamic program over character boundaries, so any number of morphemes
+/// can be found and each substring is looked up at most once. A segmentation
+/// scores the mean of its adjacent [`pair_score`]s plus the same 0.2
+/// reconstruction bonus as [`score_decomposition`], capped at 1.0, so a
+/// two-part split scores exactly as it does there. Ties go to fewer
+/// morphemes.
+///
+/// Words longer than [`MAX_SEGMENTATION_CHARS`] go to
+/// [`greedy_segmentation`] instead.
+fn best_segmentation(
+    lookup: &mut MorphemeLookup<'_>,
+    database: &LingoDatabase,
+    executor: &mut LingoExecutor,
+) -> Option<DecompositionResult> {
+    let word = lookup.word;
+    let mut bounds: Vec<usize> = word.char_indices().map(|(i, _)| i).collect();
+    if bounds.len() > MAX_SEGMENTATION_CHARS {
+        return greedy_segmentation(lookup, database, executor);
+    }
+    bounds.push(word.len());
+    let n = bounds.len();
+    
+    // table[s][e][k]: best sum of pair scores over segmentations of
+    // bounds[0..=e] into k morphemes whose last is bounds[s]..bounds[e],
+    // with the start of the morpheme before it
+    let mut table = vec![vec![Vec::<Option<(f32, usize)>>::new(); n]; n];
+    let mut reachable = vec![false; n];
+    reachable[0] = true;
+    
+    for e in 1..n {
+        for s in 0..e {
+            if !reachable[s] || !lookup.lookup(bounds[s], bounds[e], database, executor) {
+                continue;
+            }
+            let mut entries = vec![None; n];
+            if s == 0 {
+                entries[1] = Some((0.0, 0));
+            }
+            for p in 0..s {
+                let Some(previous) = lookup.morpheme(bounds[p], bounds[s]) else { continue };
+                let current = lookup.morpheme(bounds[s], bounds[e]).expect("looked up above");
+                let pair = pair_score(previous, current);
+                for (k, entry) in table[p][s].iter().enumerate() {
+                    let Some((sum, _)) = *entry else { continue };
+                    let candidate = sum + pair;
+                    if entries[k + 1].is_none_or(|(best, _)| candidate > best) {
+                        entries[k + 1] = Some((candidate, p));
+                    }
+                }
+            }
+            reachable[e] = true;
+            table[s][e] = entries;
+        }
+    }
+    
+    let last = n - 1;
+    let mut best: Option<(f32, usize, usize)> = None;
+    for k in 2..n {
+        for (s, row) in table.iter().enumerate().take(last).skip(1) {
+            let Some(Some((sum, _))) = row[last].get(k) else { continue };
+            let score = segmentation_score(*sum, k);
+            if best.is_none_or(|(best_score, _, _)| score > best_score) {
+                best = Some((score, s, k));
+            }
+        }
+    }
+    
+    let (score, mut s, mut k) = best?;
+    let mut e = last;
+    let mut morphemes = Vec::with_capacity(k);
+    loop {
+        morphemes.push(lookup.morpheme(bounds[s], bounds[e])?.clone());
+        let (_, previous) = table[s][e][k]?;
+        if k == 1 {
+            break;
+        }
+        (s, e, k) = (previous, s, k - 1);
+    }
+    morphemes.reverse();
+    Some(DecompositionResult { morphemes, score })
+}
+
+/// Segmentation taking the longest known morpheme at each position, left
+/// to right.
+///
+/// Each position tries every end from the longest down, so this makes
+/// O(n²) lookups in the worst case against the full program's O(n⁴), at the
+/// cost of missing splits where a shorter morpheme leads somewhere better.
+/// Scored like [`best_segmentation`]; `None` if some position starts no
+/// known morpheme or the word is a single morpheme.
+fn greedy_segmentation(
+    lookup: &mut MorphemeLookup<'_>,
+    database: &LingoDatabase,
+    executor: &mut LingoExecutor,
+) -> Option<DecompositionResult> {
+    let word = lookup.word;
+    let mut bounds: Vec<usize> = word.char_indices().map(|(i, _)| i).collect();
+    bounds.push(word.len());
+    
+    let mut morphemes: Vec<MorphemeAnalysis> = Vec::new();
+    let mut start = 0;
+    while start < bounds.len() - 1 {
+        let end = (start + 1..bounds.len())
+            .rev()
+            .find(|&end| lookup.lookup(bounds[start], bounds[end], database, executor))?;
+        morphemes.push(lookup.morpheme(bounds[start], bounds[end])?.clone());
+        start = end;
+    }
+    
+    if morphemes.len() < 2 {
+        return None;
+    }
+    let pair_sum: f32 = morphemes.windows(2).map(|pair| pair_score(&pair[0], &pair[1])).sum();
+    let score = segmentation_score(pair_sum, morphemes.len());
+    Some(DecompositionResult { morphemes, score })
+}
+
+/// Score every two- and three-part split whose parts are all known morphemes
+fn candidate_decompositions(
+    lookup: &mut MorphemeLookup<'_>,
+    database: &LingoDatabase,
+    executor: &mut LingoExecutor,
+) -> Vec<DecompositionResult> {
+    let word = lookup.word;
+    let end = word.len();
+    // Split only on character boundaries so multi-byte words don't panic
+    let boundaries: Vec<usize> = word.char_indices().map(|(i, _)| i).skip(1).collect();
+    let mut candidates = Vec::new();
+    
+    for (i, &split_point) in boundaries.iter().enumerate() {
         // Check if both parts exist as morphemes in the database
-        let part1_analysis = check_morpheme_in_database(part1, database, executor);
-        let part2_analysis = check_morpheme_in_database(part2, database, executor);
+        let part1_analysis = lookup.get(0, split_point, database, executor);
+        let part2_analysis = lookup.get(split_point, end, database, executor);
         
         if let (Some(m1), Some(m2)) = (part1_analysis, part2_analysis) {
             // Score this decomposition based on morpheme properties
             let score = score_decomposition(&m1, &m2, word);
-            
-            if score > best_score {
-                best_score = score;
-                best_decomposition = vec![m1, m2];
-            }
+            candidates.push(DecompositionResult {
+                morphemes: vec![m1, m2],
+                score,
+            });
         }
         
         // Also try three-part decompositions for complex words
-        if split_point < word.len() - 1 {
-            for split2 in (split_point + 1)..word.len() {
-                let part1 = &word[..split_point];
-                let part2 = &word[split_point..split2];
-                let part3 = &word[split2..];
-                
-                let m1 = check_morpheme_in_database(part1, database, executor);
-                let m2 = check_morpheme_in_database(part2, database, executor);
-                let m3 = check_morpheme_in_database(part3, database, executor);
-                
-                if let (Some(morph1), Some(morph2), Some(morph3)) = (m1, m2, m3) {
-                    let score = score_three_part_decomposition(&morph1, &morph2, &morph3, word);
-                    
-                    if score > best_score {
-                        best_score = score;
-                        best_decomposition = vec![morph1, morph2, morph3];
-                    }
-                }
+        for &split2 in &boundaries[i + 1..] {
+            let m1 = lookup.get(0, split_point, database, executor);
+            let m2 = lookup.get(split_point, split2, database, executor);
+            let m3 = lookup.get(split2, end, database, executor);
+            
+            if let (Some(morph1), Some(morph2), Some(morph3)) = (m1, m2, m3) {
+                let score = score_three_part_decomposition(&morph1, &morph2, &morph3, word);
+                candidates.push(DecompositionResult {
+                    morphemes: vec![morph1, morph2, morph3],
+                    score,
+                });
             }
         }
     }
     
-    // If we found a good decomposition, return it
-    if !best_decomposition.is_empty() && best_score > 0.5 {
-        return best_decomposition;
+    candidates
+}
+
+/// Highest score an infixed analysis can reach
+const INFIX_MAX_SCORE: f32 = 0.9;
+
+/// Analyses of the word as a root with an infix inserted inside it, as in
+/// Tagalog s-um-ulat from "sulat".
+///
+/// Every interior substring that is an `Infix` morpheme is tried; what's
+/// left around it must be a known `Root`. Each analysis lists the root then
+/// the infix, scored by [`infix_score`].
+fn infix_decompositions(
+    lookup: &mut MorphemeLookup<'_>,
+    database: &LingoDatabase,
+    executor: &mut LingoExecutor,
+) -> Vec<DecompositionResult> {
+    let word = lookup.word;
+    let bounds: Vec<usize> = word.char_indices().map(|(i, _)| i).skip(1).collect();
+    let mut results = Vec::new();
+    
+    for (i, &start) in bounds.iter().enumerate() {
+        for &end in &bounds[i + 1..] {
+            let Some(infix) = lookup.get(start, end, database, executor) else { continue };
+            if infix.morpheme_type != MorphemeType::Infix {
+                continue;
+            }
+            let root_form = format!("{}{}", &word[..start], &word[end..]);
+            let Some(root) = check_morpheme_in_database(&root_form, database, executor) else { continue };
+            if root.morpheme_type != MorphemeType::Root {
+                continue;
+            }
+            let score = infix_score(&root, &infix);
+            results.push(DecompositionResult {
+                morphemes: vec![root, infix],
+                score,
+            });
+        }
     }
     
-    // Otherwise, treat as unknown root composed from letters
-    vec![MorphemeAnalysis {
-        surface_form: word.to_string(),
-        morpheme_type: MorphemeType::Root,
-        position: calculate_position_from_letters(word, database, executor),
-        node_id: None,
-    }]
+    results
+}
+
+/// Score a root with an infix inserted into it.
+///
+/// Kept below the 1.0 a prefix or suffix split reaches, so infixation only
+/// wins where no regular segmentation fits well.
+fn infix_score(root: &MorphemeAnalysis, infix: &MorphemeAnalysis) -> f32 {
+    let mut score = 0.75;
+    if let (Some(pos1), Some(pos2)) = (root.position, infix.position) {
+        score += (1.0 / (1.0 + pos1.distance_to(pos2))) * 0.1;
+    }
+    score.min(INFIX_MAX_SCORE)
 }
 
 /// Check if a string exists as a morpheme in the database
@@ -152,6 +465,18 @@ fn check_morpheme_in_database(
 
 /// Score a two-part decomposition based on morphological validity
 fn score_decomposition(m1: &MorphemeAnalysis, m2: &MorphemeAnalysis, word: &str) -> f32 {
+    let mut score = pair_score(m1, m2);
+    
+    // Bonus for morphemes that recreate the original word exactly
+    if format!("{}{}", m1.surface_form, m2.surface_form) == word {
+        score += 0.2;
+    }
+    
+    score.min(1.0_f32)
+}
+
+/// Type compatibility of adjacent morphemes, plus a small spatial coherence bonus
+fn pair_score(m1: &MorphemeAnalysis, m2: &MorphemeAnalysis) -> f32 {
     let mut score: f32 = 0.0;
     
     // Check morpheme type compatibility
@@ -161,6 +486,8 @@ fn score_decomposition(m1: &MorphemeAnalysis, m2: &MorphemeAnalysis, word: &str)
         (MorphemeType::Root, MorphemeType::AgentSuffix) => score += 0.9,
         (MorphemeType::Root, MorphemeType::VerbSuffix) => score += 0.85,
         (MorphemeType::Root, MorphemeType::TenseSuffix) => score += 0.8,
+        (MorphemeType::Root, MorphemeType::ActionSuffix) => score += 0.85,
+        (MorphemeType::Root, MorphemeType::QualitySuffix) => score += 0.85,
         
         // Prefix + Root is common
         (MorphemeType::Prefix, MorphemeType::Root) => score += 0.8,
@@ -169,22 +496,21 @@ fn score_decomposition(m1: &MorphemeAnalysis, m2: &MorphemeAnalysis, word: &str)
         (MorphemeType::Root, MorphemeType::Root) => score += 0.6,
         (MorphemeType::Compound, _) | (_, MorphemeType::Compound) => score += 0.7,
         
+        // An infix sits inside its root, so side by side it's unlikely;
+        // see infix_score for the inserted case
+        (MorphemeType::Infix, _) | (_, MorphemeType::Infix) => score += 0.1,
+        
         // Less common patterns
         _ => score += 0.3,
     }
     
-    // Bonus for morphemes that recreate the original word exactly
-    if format!("{}{}", m1.surface_form, m2.surface_form) == word {
-        score += 0.2;
-    }
-    
     // Spatial coherence bonus
     if let (Some(pos1), Some(pos2)) = (m1.position, m2.position) {
-        let distance = euclidean_distance(pos1, pos2);
+        let distance = pos1.distance_to(pos2);
         score += (1.0 / (1.0 + distance)) * 0.1;
     }
     
-    score.min(1.0_f32)
+    score
 }
 
 /// Score a three-part decomposition
@@ -247,41 +573,43 @@ fn calculate_position_from_letters(
         x: sum_x / count,
         y: sum_y / count,
         z: Layer::Morphemes.z_center(),
-    })
+    }.clamp_unit())
 }
 
-/// Calculate composed 3D position from morpheme positions
-pub fn calculate_composed_position(morphemes: &[MorphemeAnalysis]) -> Coordinate3D {
-    let positions: Vec<Coordinate3D> = morphemes.iter()
-        .filter_map(|m| m.position)
-        .collect();
-    
-    if positions.is_empty() {
-        return Coordinate3D { x: 0.5, y: 0.5, z: Layer::Words.z_center() };
-    }
-    
-    // Weight morphemes by type importance
+/// Calculate the position of what `morphemes` compose into on `layer`.
+///
+/// x and y average the morphemes' positions, weighted by
+/// [`MorphemeType::composition_weight`]; z is the center of `layer`'s band,
+/// so morphemes composed into a phrase or concept land at that depth
+/// rather than on their own layer.
+pub fn calculate_composed_position(morphemes: &[MorphemeAnalysis], layer: Layer) -> Coordinate3D {
+    compose_position(
+        morphemes.iter().filter_map(|m| Some((m.position?, m.morpheme_type.composition_weight()))),
+        layer,
+    )
+}
+
+/// Weighted mean x and y of `parts`, placed at the center of `layer`'s z band.
+///
+/// Falls back to the middle of the layer when no part has weight.
+pub fn compose_position(parts: impl IntoIterator<Item = (Coordinate3D, f32)>, layer: Layer) -> Coordinate3D {
     let mut total_weight = 0.0;
     let mut weighted_x = 0.0;
     let mut weighted_y = 0.0;
-    
-    for (i, morpheme) in morphemes.iter().enumerate() {
-        if let Some(pos) = morpheme.position {
-            let weight = morpheme.morpheme_type.composition_weight();
-            weighted_x += pos.x * weight;
-            weighted_y += pos.y * weight;
-            total_weight += weight;
-        }
+    for (position, weight) in parts {
+        weighted_x += position.x * weight;
+        weighted_y += position.y * weight;
+        total_weight += weight;
     }
     
     if total_weight > 0.0 {
         Coordinate3D {
             x: weighted_x / total_weight,
             y: weighted_y / total_weight,
-            z: Layer::Words.z_center(),
-        }
+            z: layer.z_center(),
+        }.clamp_unit()
     } else {
-        Coordinate3D { x: 0.5, y: 0.5, z: Layer::Words.z_center() }
+        Coordinate3D { x: 0.5, y: 0.5, z: layer.z_center() }
     }
 }
 
@@ -290,11 +618,6 @@ pub fn preprocess_text(text: &str) -> String {
     text.trim().to_lowercase()
 }
 
-/// Calculate Euclidean distance between two 3D points
-fn euclidean_distance(a: Coordinate3D, b: Coordinate3D) -> f32 {
-    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,6 +649,261 @@ mod tests {
         // For now, just test that the function exists
     }
     
+    #[test]
+    fn test_letter_fallback_can_be_disabled() {
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::database_and_executor;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("q", Layer::Letters, Coordinate3D::new(0.2, 0.4, 0.05)).unwrap();
+        builder.add_node("x", Layer::Letters, Coordinate3D::new(0.6, 0.2, 0.05)).unwrap();
+        builder.add_node("re", Layer::Morphemes, Coordinate3D::new(0.4, 0.3, 0.35)).unwrap();
+        
+        let (_dir, database, mut executor) = database_and_executor(&builder);
+        
+        let no_letters = DecompositionOptions { letter_fallback: false };
+        let before = executor.queries_executed();
+        let result = decompose_word_to_morphemes_with_options("qx", &database, &mut executor, &no_letters);
+        let without_fallback = executor.queries_executed() - before;
+        
+        assert_eq!(result.len(), 1);
+        assert!(result[0].position.is_none());
+        
+        let before = executor.queries_executed();
+        let result = decompose_word_to_morphemes("qx", &database, &mut executor);
+        let with_fallback = executor.queries_executed() - before;
+        
+        assert!(result[0].position.is_some());
+        // The only difference is one letter-layer lookup per character
+        assert_eq!(with_fallback - without_fallback, 2);
+    }
+    
+    #[test]
+    fn test_decompose_all_ambiguous_word() {
+        use crate::core::{EtymologyOrigin, NodeFlags};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::database_and_executor;
+        
+        let mut builder = DatabaseBuilder::new();
+        for (form, morpheme_type, x) in [
+            ("un", MorphemeType::Prefix, 0.2),
+            ("ion", MorphemeType::Root, 0.4),
+            ("union", MorphemeType::Root, 0.5),
+            ("ize", MorphemeType::VerbSuffix, 0.7),
+        ] {
+            builder.add_node_full(
+                form,
+                Layer::Morphemes,
+                Coordinate3D::new(x, 0.3, 0.35),
+                EtymologyOrigin::Latin,
+                morpheme_type,
+                NodeFlags::empty(),
+            ).unwrap();
+        }
+        
+        let (_dir, database, mut executor) = database_and_executor(&builder);
+        
+        let results = decompose_all("unionize", &database, &mut executor, 0.5);
+        let splits: Vec<Vec<&str>> = results.iter()
+            .map(|r| r.morphemes.iter().map(|m| m.surface_form.as_str()).collect())
+            .collect();
+        
+        assert!(splits.contains(&vec!["un", "ion", "ize"]));
+        assert!(splits.contains(&vec!["union", "ize"]));
+        assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(decompose_all("unionize", &database, &mut executor, 1.1).is_empty());
+    }
+    
+    #[test]
+    fn test_deep_compound_segmentation() {
+        use crate::core::{EtymologyOrigin, NodeFlags};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::database_and_executor;
+        
+        let mut builder = DatabaseBuilder::new();
+        for (form, morpheme_type) in [
+            ("anti", MorphemeType::Prefix),
+            ("dis", MorphemeType::Prefix),
+            ("establish", MorphemeType::Root),
+            ("establishment", MorphemeType::Root),
+            ("ment", MorphemeType::Suffix),
+            ("arian", MorphemeType::Suffix),
+            ("ism", MorphemeType::Suffix),
+            ("an", MorphemeType::Prefix),
+        ] {
+            builder.add_node_full(
+                form,
+                Layer::Morphemes,
+                Coordinate3D::new(0.5, 0.3, 0.35),
+                EtymologyOrigin::Latin,
+                morpheme_type,
+                NodeFlags::empty(),
+            ).unwrap();
+        }
+        
+        let (_dir, database, mut executor) = database_and_executor(&builder);
+        
+        let word = "antidisestablishmentarianism";
+        // Two- and three-part splits can't cover it
+        assert!(decompose_all(word, &database, &mut executor, 0.0).is_empty());
+        
+        let before = executor.queries_executed();
+        let result = decompose_word_to_morphemes_with_options(
+            word,
+            &database,
+            &mut executor,
+            &DecompositionOptions { letter_fallback: false },
+        );
+        let queries = executor.queries_executed() - before;
+        
+        let forms: Vec<&str> = result.iter().map(|m| m.surface_form.as_str()).collect();
+        assert_eq!(forms, vec!["anti", "dis", "establishment", "arian", "ism"]);
+        assert!(result.iter().all(|m| m.node_id.is_some()));
+        
+        // The whole-word check plus at most one lookup per substring
+        let n = word.chars().count();
+        assert!(queries <= 1 + n * (n + 1) / 2, "{} queries", queries);
+        
+        // Past the length cap the segmentation is greedy, longest morpheme first
+        let long_word = word.repeat(3);
+        assert!(long_word.chars().count() > MAX_SEGMENTATION_CHARS);
+        let result = decompose_word_to_morphemes_with_options(
+            &long_word,
+            &database,
+            &mut executor,
+            &DecompositionOptions { letter_fallback: false },
+        );
+        let forms: Vec<&str> = result.iter().map(|m| m.surface_form.as_str()).collect();
+        assert_eq!(forms, ["anti", "dis", "establishment", "arian", "ism"].repeat(3));
+    }
+    
+    #[test]
+    fn test_infix_decomposition() {
+        use crate::core::{EtymologyOrigin, NodeFlags};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::database_and_executor;
+        
+        let mut builder = DatabaseBuilder::new();
+        for (form, morpheme_type, x) in [
+            ("sulat", MorphemeType::Root, 0.4),
+            ("um", MorphemeType::Infix, 0.45),
+            ("walk", MorphemeType::Root, 0.6),
+            ("er", MorphemeType::AgentSuffix, 0.7),
+            // Lets "walker" also read as "waer" with "lk" inserted
+            ("waer", MorphemeType::Root, 0.6),
+            ("lk", MorphemeType::Infix, 0.65),
+        ] {
+            builder.add_node_full(
+                form,
+                Layer::Morphemes,
+                Coordinate3D::new(x, 0.3, 0.35),
+                EtymologyOrigin::Unknown,
+                morpheme_type,
+                NodeFlags::empty(),
+            ).unwrap();
+        }
+        
+        let (_dir, database, mut executor) = database_and_executor(&builder);
+        let no_letters = DecompositionOptions { letter_fallback: false };
+        let forms = |result: &[MorphemeAnalysis]| -> Vec<String> {
+            result.iter().map(|m| m.surface_form.clone()).collect()
+        };
+        
+        // s-um-ulat has no concatenative split at all
+        let result = decompose_word_to_morphemes_with_options("sumulat", &database, &mut executor, &no_letters);
+        assert_eq!(forms(&result), ["sulat", "um"]);
+        assert_eq!(result[1].morpheme_type, MorphemeType::Infix);
+        
+        // The suffix reading still wins where both fit
+        let result = decompose_word_to_morphemes_with_options("walker", &database, &mut executor, &no_letters);
+        assert_eq!(forms(&result), ["walk", "er"]);
+        let all = decompose_all("walker", &database, &mut executor, 0.0);
+        assert_eq!(forms(&all[0].morphemes), ["walk", "er"]);
+        let infixed = all.iter().find(|r| r.morphemes[0].surface_form == "waer").unwrap();
+        assert!(infixed.score < all[0].score);
+    }
+    
+    #[test]
+    fn test_decompose_multibyte_words() {
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::database_and_executor;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("na", Layer::Morphemes, Coordinate3D::new(0.3, 0.3, 0.35)).unwrap();
+        builder.add_node("ïve", Layer::Morphemes, Coordinate3D::new(0.6, 0.3, 0.35)).unwrap();
+        builder.add_node("ré", Layer::Morphemes, Coordinate3D::new(0.2, 0.3, 0.35)).unwrap();
+        builder.add_node("λ", Layer::Letters, Coordinate3D::new(0.4, 0.7, 0.05)).unwrap();
+        
+        let (_dir, database, mut executor) = database_and_executor(&builder);
+        
+        // Splits fall between characters, never inside "ï"
+        let results = decompose_all("naïve", &database, &mut executor, 0.0);
+        let splits: Vec<Vec<&str>> = results.iter()
+            .map(|r| r.morphemes.iter().map(|m| m.surface_form.as_str()).collect())
+            .collect();
+        assert_eq!(splits, vec![vec!["na", "ïve"]]);
+        assert!(!decompose_word_to_morphemes("naïve", &database, &mut executor).is_empty());
+        
+        // Three-part splits and the segmentation both step over "é" and "ï"
+        let result = decompose_word_to_morphemes("rénaïve", &database, &mut executor);
+        let forms: Vec<&str> = result.iter().map(|m| m.surface_form.as_str()).collect();
+        assert_eq!(forms, vec!["ré", "na", "ïve"]);
+        assert!(decompose_all("rénaïve", &database, &mut executor, 0.0)
+            .iter()
+            .any(|r| r.morphemes.len() == 3));
+        
+        // Unknown words made entirely of multi-byte characters fall back to letters
+        let result = decompose_word_to_morphemes("λόγος", &database, &mut executor);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].surface_form, "λόγος");
+        assert!(result[0].position.is_some());
+    }
+    
+    #[test]
+    fn test_composed_position_is_clamped() {
+        let morpheme = |x: f32, y: f32, morpheme_type| MorphemeAnalysis {
+            surface_form: String::new(),
+            morpheme_type,
+            // Struct literals skip the clamping done by Coordinate3D::new
+            position: Some(Coordinate3D { x, y, z: 2.0 }),
+            node_id: None,
+        };
+        let morphemes = vec![
+            morpheme(1.8, -0.6, MorphemeType::Prefix),
+            morpheme(1.4, -0.2, MorphemeType::Root),
+        ];
+        
+        let position = calculate_composed_position(&morphemes, Layer::Words);
+        for v in [position.x, position.y, position.z] {
+            assert!((0.0..=1.0).contains(&v), "{} out of range", v);
+        }
+        assert_eq!(position.x, 1.0);
+        assert_eq!(position.y, 0.0);
+    }
+    
+    #[test]
+    fn test_composed_position_targets_layer() {
+        let word = |x: f32, morpheme_type| MorphemeAnalysis {
+            surface_form: String::new(),
+            morpheme_type,
+            position: Some(Coordinate3D::new(x, 0.4, Layer::Words.z_center())),
+            node_id: None,
+        };
+        let morphemes = vec![word(0.2, MorphemeType::Root), word(0.6, MorphemeType::Root)];
+        
+        let concept = calculate_composed_position(&morphemes, Layer::Concepts);
+        let (min, max) = Layer::Concepts.z_range();
+        assert!((min..=max).contains(&concept.z), "z {} outside Concepts", concept.z);
+        assert!((concept.x - 0.4).abs() < 1e-6);
+        assert!((concept.y - 0.4).abs() < 1e-6);
+        
+        // Only the depth depends on the target layer
+        let phrase = calculate_composed_position(&morphemes, Layer::Phrases);
+        assert_eq!((phrase.x, phrase.y), (concept.x, concept.y));
+        assert_eq!(phrase.z, Layer::Phrases.z_center());
+        assert_eq!(calculate_composed_position(&[], Layer::Domains).z, Layer::Domains.z_center());
+    }
+    
     #[test]
     fn test_morpheme_types() {
         // Test that all morpheme types are handled
@@ -0,0 +1,507 @@
+// Copyright 2025 Roberto Antunes
+//
+// Licensed under the Functional Source License, Version 1.1 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     https://github.com/RobAntunes/lingodb/blob/main/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Phonotactic plausibility of spelled words
+//!
+//! Spellings are mapped to English phonemes and every consonant cluster is
+//! checked against onset and coda rules stated in articulatory features, so
+//! the rules follow whatever features the Phonemes layer records.
+
+use crate::core::{Layer, PhonemeFeatures};
+use crate::data::english_base::ENGLISH_PHONEMES;
+use crate::storage::LingoDatabase;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Plausibility kept for each illegal word-initial cluster
+const ONSET_PENALTY: f32 = 0.25;
+
+/// Plausibility kept for each illegal medial or word-final cluster
+const CLUSTER_PENALTY: f32 = 0.5;
+
+/// Consonant spellings of more than one letter, longest first
+const DIGRAPHS: &[(&str, &[&str])] = &[
+    ("tch", &["/tʃ/"]),
+    ("ch", &["/tʃ/"]),
+    ("sh", &["/ʃ/"]),
+    ("th", &["/θ/"]),
+    ("ph", &["/f/"]),
+    ("wh", &["/w/"]),
+    ("ck", &["/k/"]),
+    ("dg", &["/dʒ/"]),
+    ("ng", &["/ŋ/"]),
+    ("qu", &["/k/", "/w/"]),
+];
+
+/// Word-initial spellings whose first letter is silent, as in "knight"
+const SILENT_ONSETS: &[&str] = &["kn", "gn", "wr", "ps", "pn", "pt"];
+
+/// Where in a word a consonant cluster sits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClusterPosition {
+    /// Before the first vowel
+    Onset,
+    /// Between two vowels
+    Medial,
+    /// After the last vowel
+    Coda,
+}
+
+/// A consonant cluster English doesn't allow where it appears
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhonotacticViolation {
+    /// Spelling of the cluster
+    pub cluster: String,
+    /// Where the cluster sits in its word
+    pub position: ClusterPosition,
+}
+
+/// Checks whether spelled words could be English words.
+///
+/// Words are read as English spellings ("ch", "ng", silent "kn"...) and
+/// each consonant cluster must be a legal onset before the first vowel, a
+/// legal coda after the last, or split into a coda and an onset between
+/// vowels. A word with no vowel has no legal syllable and fails as a whole.
+///
+/// # Examples
+///
+/// ```rust
+/// use lingo::morphology::PhonotacticValidator;
+///
+/// let validator = PhonotacticValidator::english();
+/// assert!(validator.is_plausible("blorptastic"));
+/// assert!(!validator.is_plausible("ngtkover"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PhonotacticValidator {
+    features: HashMap<String, PhonemeFeatures>,
+}
+
+/// A spelled consonant or vowel and the letters it came from
+#[derive(Debug, Clone)]
+struct Segment {
+    consonant: Option<PhonemeFeatures>,
+    span: Range<usize>,
+}
+
+impl PhonotacticValidator {
+    /// Validator using the feature descriptions of
+    /// [`ENGLISH_PHONEMES`](crate::data::english_base::ENGLISH_PHONEMES)
+    pub fn english() -> Self {
+        let features = ENGLISH_PHONEMES
+            .iter()
+            .map(|(symbol, description, _)| (symbol.to_string(), PhonemeFeatures::from_description(description)))
+            .collect();
+        Self { features }
+    }
+
+    /// Validator using the features recorded on the database's Phonemes
+    /// layer, falling back to [`english`](Self::english) for phonemes the
+    /// database doesn't describe
+    pub fn from_database(database: &LingoDatabase) -> Self {
+        let mut validator = Self::english();
+        for id in database.nodes_in_layer(Layer::Phonemes) {
+            let Ok(node) = database.get_node(id) else {
+                continue;
+            };
+            let features = node.phoneme_features();
+            if features.is_empty() {
+                continue;
+            }
+            if let Ok(symbol) = database.get_string(node.word_offset, node.word_length) {
+                if let Some(known) = validator.features.get_mut(symbol) {
+                    *known = features;
+                }
+            }
+        }
+        validator
+    }
+
+    /// How plausible `word` is as an English word, from 0.0 to 1.0.
+    ///
+    /// 1.0 means every cluster is legal. Each illegal word-initial cluster
+    /// multiplies the score by 0.25 and each other illegal cluster by 0.5.
+    pub fn plausibility(&self, word: &str) -> f32 {
+        self.violations(word).iter().fold(1.0, |score, violation| {
+            score * match violation.position {
+                ClusterPosition::Onset => ONSET_PENALTY,
+                ClusterPosition::Medial | ClusterPosition::Coda => CLUSTER_PENALTY,
+            }
+        })
+    }
+
+    /// Whether every consonant cluster in `word` is legal
+    pub fn is_plausible(&self, word: &str) -> bool {
+        self.violations(word).is_empty()
+    }
+
+    /// The illegal consonant clusters in `word`.
+    ///
+    /// Anything other than an ASCII letter separates words, so "ngtk-over"
+    /// is checked as "ngtk" and "over".
+    pub fn violations(&self, word: &str) -> Vec<PhonotacticViolation> {
+        let word = word.to_ascii_lowercase();
+        let mut violations = Vec::new();
+        for part in word.split(|c: char| !c.is_ascii_lowercase()) {
+            if !part.is_empty() {
+                self.check_part(part, &mut violations);
+            }
+        }
+        violations
+    }
+
+    /// Check one run of lowercase letters
+    fn check_part(&self, part: &str, violations: &mut Vec<PhonotacticViolation>) {
+        let segments = self.segments(part);
+        let vowels: Vec<usize> = segments
+            .iter()
+            .enumerate()
+            .filter_map(|(i, segment)| segment.consonant.is_none().then_some(i))
+            .collect();
+
+        let mut report = |run: &[Segment], position: ClusterPosition| {
+            let (Some(first), Some(last)) = (run.first(), run.last()) else {
+                return;
+            };
+            violations.push(PhonotacticViolation {
+                cluster: part[first.span.start..last.span.end].to_string(),
+                position,
+            });
+        };
+
+        let (Some(&first), Some(&last)) = (vowels.first(), vowels.last()) else {
+            report(&segments, ClusterPosition::Onset);
+            return;
+        };
+
+        let onset = consonants(&segments[..first]);
+        if !legal_onset(&onset) {
+            report(&segments[..first], ClusterPosition::Onset);
+        }
+        for pair in vowels.windows(2) {
+            let run = &segments[pair[0] + 1..pair[1]];
+            let cluster = consonants(run);
+            let splits = (0..=cluster.len()).any(|at| legal_coda(&cluster[..at]) && legal_onset(&cluster[at..]));
+            if !splits {
+                report(run, ClusterPosition::Medial);
+            }
+        }
+        let coda = consonants(&segments[last + 1..]);
+        if !legal_coda(&coda) {
+            report(&segments[last + 1..], ClusterPosition::Coda);
+        }
+    }
+
+    /// Read a lowercase spelling as consonant phonemes and vowels
+    fn segments(&self, part: &str) -> Vec<Segment> {
+        let bytes = part.as_bytes();
+        let mut end = bytes.len();
+        // Silent letters at the end, as in "climb", "autumn" and "sign"
+        if part.len() > 2 && (part.ends_with("mb") || part.ends_with("mn")) {
+            end -= 1;
+        }
+        let silent_g = part.len() > 2 && part.ends_with("gn");
+
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut i = 0;
+        if part.len() > 2 && SILENT_ONSETS.iter().any(|onset| part.starts_with(onset)) {
+            i = 1;
+        }
+
+        while i < end {
+            let next = bytes.get(i + 1).copied();
+            let after_vowel = segments.last().is_some_and(|s| s.consonant.is_none());
+
+            let push = |segments: &mut Vec<Segment>, symbols: &[&str], len: usize| {
+                for symbol in symbols {
+                    let features = self.features.get(*symbol).copied().unwrap_or(PhonemeFeatures::empty());
+                    // Double letters and "sc" before "e" spell one consonant
+                    let repeated = segments.last().is_some_and(|s| s.consonant == Some(features));
+                    if repeated {
+                        if let Some(last) = segments.last_mut() {
+                            last.span.end = i + len;
+                        }
+                    } else {
+                        segments.push(Segment { consonant: Some(features), span: i..i + len });
+                    }
+                }
+            };
+
+            if silent_g && i == end - 2 {
+                i += 1;
+                continue;
+            }
+            if let Some((spelling, symbols)) = DIGRAPHS.iter().find(|(spelling, _)| part[i..end].starts_with(spelling)) {
+                push(&mut segments, symbols, spelling.len());
+                i += spelling.len();
+                continue;
+            }
+            if part[i..end].starts_with("gh") {
+                // Only a word-initial "gh" is heard, as in "ghost"
+                if i == 0 {
+                    push(&mut segments, &["/g/"], 2);
+                }
+                i += 2;
+                continue;
+            }
+
+            // Vowel letters, and "y" and "w" acting as vowels, spell no consonant
+            let vowel_follows = next.is_some_and(is_vowel_letter);
+            let symbols: &[&str] = match bytes[i] {
+                b'a' | b'e' | b'i' | b'o' | b'u' => &[],
+                b'y' if vowel_follows => &["/j/"],
+                b'y' => &[],
+                b'w' if after_vowel && !vowel_follows => &[],
+                b'w' => &["/w/"],
+                b'c' if matches!(next, Some(b'e' | b'i' | b'y')) => &["/s/"],
+                b'c' | b'k' | b'q' => &["/k/"],
+                b'n' if matches!(next, Some(b'k' | b'g' | b'c' | b'q' | b'x')) => &["/ŋ/"],
+                b'x' if i == 0 => &["/z/"],
+                b'x' => &["/k/", "/s/"],
+                b'j' => &["/dʒ/"],
+                other => LETTER_PHONEMES
+                    .iter()
+                    .find(|(letter, _)| *letter == other)
+                    .map_or(&[], |(_, symbol)| std::slice::from_ref(symbol)),
+            };
+            if symbols.is_empty() {
+                segments.push(Segment { consonant: None, span: i..i + 1 });
+            } else {
+                push(&mut segments, symbols, 1);
+            }
+            i += 1;
+        }
+        segments
+    }
+}
+
+impl Default for PhonotacticValidator {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+/// Single letters that always spell the same consonant
+const LETTER_PHONEMES: &[(u8, &str)] = &[
+    (b'b', "/b/"),
+    (b'd', "/d/"),
+    (b'f', "/f/"),
+    (b'g', "/g/"),
+    (b'h', "/h/"),
+    (b'l', "/l/"),
+    (b'm', "/m/"),
+    (b'n', "/n/"),
+    (b'p', "/p/"),
+    (b'r', "/r/"),
+    (b's', "/s/"),
+    (b't', "/t/"),
+    (b'v', "/v/"),
+    (b'z', "/z/"),
+];
+
+fn is_vowel_letter(letter: u8) -> bool {
+    matches!(letter, b'a' | b'e' | b'i' | b'o' | b'u')
+}
+
+fn consonants(run: &[Segment]) -> Vec<PhonemeFeatures> {
+    run.iter().filter_map(|segment| segment.consonant).collect()
+}
+
+/// Sonority rank: glides and /r/ highest, plosives and affricates lowest
+fn sonority(phoneme: PhonemeFeatures) -> u8 {
+    if phoneme.contains(PhonemeFeatures::APPROXIMANT) {
+        5
+    } else if phoneme.contains(PhonemeFeatures::LATERAL) {
+        4
+    } else if phoneme.contains(PhonemeFeatures::NASAL) {
+        3
+    } else if phoneme.contains(PhonemeFeatures::FRICATIVE) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Place class: 0 labial, 1 coronal, 2 dorsal, 3 glottal
+fn place(phoneme: PhonemeFeatures) -> u8 {
+    if phoneme.intersects(PhonemeFeatures::BILABIAL | PhonemeFeatures::LABIODENTAL) {
+        0
+    } else if phoneme.intersects(
+        PhonemeFeatures::DENTAL | PhonemeFeatures::ALVEOLAR | PhonemeFeatures::POSTALVEOLAR | PhonemeFeatures::PALATAL,
+    ) {
+        1
+    } else if phoneme.contains(PhonemeFeatures::VELAR) {
+        2
+    } else {
+        3
+    }
+}
+
+fn is_s(phoneme: PhonemeFeatures) -> bool {
+    phoneme == PhonemeFeatures::ALVEOLAR | PhonemeFeatures::FRICATIVE
+}
+
+fn is_voiceless_plosive(phoneme: PhonemeFeatures) -> bool {
+    phoneme.contains(PhonemeFeatures::PLOSIVE) && !phoneme.contains(PhonemeFeatures::VOICED)
+}
+
+/// /l/, /r/, /w/ or /j/
+fn is_liquid_or_glide(phoneme: PhonemeFeatures) -> bool {
+    phoneme.intersects(PhonemeFeatures::LATERAL | PhonemeFeatures::APPROXIMANT)
+}
+
+/// /w/, the only glide articulated at the velum
+fn is_w(phoneme: PhonemeFeatures) -> bool {
+    phoneme.contains(PhonemeFeatures::APPROXIMANT | PhonemeFeatures::VELAR)
+}
+
+/// Whether `cluster` may begin an English syllable
+fn legal_onset(cluster: &[PhonemeFeatures]) -> bool {
+    match *cluster {
+        [] => true,
+        // /ŋ/ never starts a syllable
+        [only] => !only.contains(PhonemeFeatures::NASAL | PhonemeFeatures::VELAR),
+        [first, second] => legal_onset_pair(first, second),
+        // /s/ + voiceless plosive + liquid or glide: "spl", "str", "squ"
+        [s, plosive, glide] => {
+            is_s(s) && is_voiceless_plosive(plosive) && is_liquid_or_glide(glide) && legal_onset_pair(plosive, glide)
+        }
+        _ => false,
+    }
+}
+
+fn legal_onset_pair(first: PhonemeFeatures, second: PhonemeFeatures) -> bool {
+    if is_s(first) {
+        return is_voiceless_plosive(second)
+            || (second.contains(PhonemeFeatures::NASAL) && place(second) != 2)
+            || second.contains(PhonemeFeatures::LATERAL)
+            || is_w(second)
+            || second == PhonemeFeatures::LABIODENTAL | PhonemeFeatures::FRICATIVE;
+    }
+    if !is_liquid_or_glide(second) {
+        return false;
+    }
+    let voiceless_fricative = first.contains(PhonemeFeatures::FRICATIVE)
+        && !first.intersects(PhonemeFeatures::VOICED | PhonemeFeatures::GLOTTAL);
+    if !first.contains(PhonemeFeatures::PLOSIVE) && !voiceless_fricative {
+        return false;
+    }
+    // No "tl", "dl" or "thl"; no "pw", "bw" or "fw"; "sh" only before /r/
+    let coronal_lateral = first.intersects(PhonemeFeatures::ALVEOLAR | PhonemeFeatures::DENTAL)
+        && second.contains(PhonemeFeatures::LATERAL);
+    let labial_w = place(first) == 0 && is_w(second);
+    let postalveolar = first.contains(PhonemeFeatures::POSTALVEOLAR)
+        && !second.contains(PhonemeFeatures::ALVEOLAR | PhonemeFeatures::APPROXIMANT);
+    !coronal_lateral && !labial_w && !postalveolar
+}
+
+/// Whether `cluster` may end an English syllable.
+///
+/// Trailing /s/, /z/, /t/, /d/ and /θ/ attach freely ("texts", "sixths"),
+/// so only the consonants before them have to fall in sonority.
+fn legal_coda(cluster: &[PhonemeFeatures]) -> bool {
+    // /h/, /j/ and /w/ never close a syllable; /r/ may
+    let closes = |c: &PhonemeFeatures| {
+        !c.contains(PhonemeFeatures::GLOTTAL)
+            && (!c.contains(PhonemeFeatures::APPROXIMANT) || c.contains(PhonemeFeatures::ALVEOLAR))
+    };
+    if cluster.len() > 4 || !cluster.iter().all(closes) {
+        return false;
+    }
+
+    let appendix = |c: PhonemeFeatures| {
+        c.intersects(PhonemeFeatures::ALVEOLAR | PhonemeFeatures::DENTAL)
+            && c.intersects(PhonemeFeatures::PLOSIVE | PhonemeFeatures::FRICATIVE)
+    };
+    let mut core = cluster;
+    while let [rest @ .., last] = core {
+        if rest.is_empty() || !appendix(*last) {
+            break;
+        }
+        core = rest;
+    }
+
+    match *core {
+        [] | [_] => true,
+        [first, second] => {
+            // Nasals share the place of a following stop: "mp", "nch", "nk"
+            let homorganic = !first.contains(PhonemeFeatures::NASAL)
+                || !second.intersects(PhonemeFeatures::PLOSIVE | PhonemeFeatures::AFFRICATE)
+                || place(first) == place(second);
+            sonority(first) > sonority(second) && homorganic
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Coordinate3D;
+    use crate::storage::DatabaseBuilder;
+    use crate::test_support::open_database;
+
+    #[test]
+    fn test_rejects_illegal_clusters() {
+        let validator = PhonotacticValidator::english();
+
+        let violations = validator.violations("ngtkover");
+        assert_eq!(violations, vec![PhonotacticViolation {
+            cluster: "ngtk".to_string(),
+            position: ClusterPosition::Onset,
+        }]);
+        assert_eq!(validator.plausibility("ngtkover"), 0.25);
+        assert_eq!(validator.violations("ngtk-over")[0].cluster, "ngtk");
+
+        assert!(!validator.is_plausible("tlorp"));
+        assert!(!validator.is_plausible("pwint"));
+        assert!(!validator.is_plausible("hmm"));
+        assert_eq!(validator.violations("batkpo")[0].position, ClusterPosition::Medial);
+        assert_eq!(validator.violations("blatk")[0].position, ClusterPosition::Coda);
+        assert_eq!(validator.plausibility("ngtkatk"), 0.125);
+    }
+
+    #[test]
+    fn test_accepts_english_words_and_coinages() {
+        let validator = PhonotacticValidator::english();
+        let words = [
+            "strength", "splash", "squirrel", "texts", "unhappy", "teacher", "knight",
+            "judge", "climb", "sign", "think", "shrink", "thwart", "yellow", "borrow",
+            "exact", "xylophone", "science", "match", "ghost", "tempt", "months",
+        ];
+        for word in words {
+            assert_eq!(validator.violations(word), vec![], "{}", word);
+        }
+
+        // Coinages are judged the same way
+        for coinage in ["blorptastic", "glimber", "snurfle", "prendish"] {
+            assert_eq!(validator.plausibility(coinage), 1.0, "{}", coinage);
+        }
+    }
+
+    #[test]
+    fn test_rules_follow_database_features() {
+        let mut builder = DatabaseBuilder::new();
+        for (i, (symbol, description, _)) in ENGLISH_PHONEMES.iter().enumerate() {
+            // Record "ng" as an alveolar nasal, which may start a syllable
+            let description = if *symbol == "/ŋ/" { "alveolar_nasal" } else { description };
+            let position = Coordinate3D::new(i as f32 / 50.0, 0.5, 0.22);
+            builder.add_phoneme(symbol, position, PhonemeFeatures::from_description(description)).unwrap();
+        }
+        let (_dir, db) = open_database(&builder);
+
+        assert!(!PhonotacticValidator::english().is_plausible("ngoma"));
+        assert!(PhonotacticValidator::from_database(&db).is_plausible("ngoma"));
+    }
+}
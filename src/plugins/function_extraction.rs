@@ -22,7 +22,7 @@ use std::time::Instant;
 use std::sync::Arc;
 use serde::{Serialize, Deserialize};
 
-use crate::core::{LinguisticNode, NodeId, Layer, Coordinate3D, MorphemeType};
+use crate::core::{LinguisticNode, NodeId, Layer, Coordinate3D, MorphemeType, ConnectionType, clamp01};
 use crate::query::{QueryBuilder, CompiledQuery};
 use crate::storage::LingoDatabase;
 use crate::engine::LingoExecutor;
@@ -32,11 +32,36 @@ use crate::morphology::{decompose_word_to_morphemes, calculate_composed_position
 // use crate::mirroring::{MirroringDecomposer, PatternType, 
 //                        SynthesisResult as MirroringSynthesis, MirrorPair as MirroringMirror,
 //                        MirrorType as MirroringMirrorType};
-use super::{Plugin, PluginContext, PluginResult, PluginError, PluginEnhancement};
+use super::{Plugin, PluginContext, PluginResult, PluginError, PluginEnhancement, reopen_database};
+
+/// Phrases that mark one state turning into another
+const TRANSFORMATION_MARKERS: [&str; 6] = ["becomes", "converts to", "transforms into", "changes to", "→", "->"];
+
+/// Phrases introducing a purpose, longest first so "in order to" wins over "to"
+const PURPOSE_MARKERS: [&str; 4] = ["in order to", "so that", "aiming to", "to"];
+
+/// Suffixes of nouns and adjectives naming a measurable quality
+const QUALITY_SUFFIXES: [&str; 7] = ["ance", "ence", "ity", "ness", "ment", "able", "ible"];
+
+/// Superlative and comparative endings, a quality only on an adjective stem
+const DEGREE_SUFFIXES: [&str; 2] = ["est", "er"];
+
+/// A primitive detector, run on its own executor
+type Detector = fn(&str, &LingoDatabase, &mut LingoExecutor) -> Result<Vec<FunctionalPrimitive>, PluginError>;
+
+/// Detectors in the order their primitives appear in a signature
+const DETECTORS: [Detector; 6] = [
+    FunctionExtractor::detect_agency,
+    FunctionExtractor::detect_actions,
+    FunctionExtractor::detect_transformations,
+    FunctionExtractor::detect_conditionality,
+    FunctionExtractor::detect_sequences,
+    FunctionExtractor::detect_purpose,
+];
 
 /// Function extraction plugin
 pub struct FunctionExtractor {
-    database_path: Option<String>,
+    database: Option<Arc<LingoDatabase>>,
     executor: Option<LingoExecutor>,
     // mirroring_decomposer: Option<MirroringDecomposer>, // Temporarily disabled
     confidence_threshold: f32,
@@ -47,7 +72,7 @@ pub struct FunctionExtractor {
 impl FunctionExtractor {
     pub fn new() -> Self {
         Self {
-            database_path: None,
+            database: None,
             executor: None,
             // mirroring_decomposer: None, // Temporarily disabled
             confidence_threshold: 0.5,
@@ -58,37 +83,35 @@ impl FunctionExtractor {
     
     /// Extract function signature from text
     pub fn extract_function_signature(&mut self, text: &str) -> Result<FunctionSignature, PluginError> {
-        let executor = self.executor.as_mut()
+        let executor = self.executor.as_ref()
             .ok_or_else(|| PluginError::NotInitialized("Executor not available".to_string()))?;
         
-        // Open database for this operation
-        let database_path = self.database_path.as_ref()
-            .ok_or_else(|| PluginError::NotInitialized("Database path not set".to_string()))?;
-        
-        let database = LingoDatabase::open(database_path)
-            .map_err(|e| PluginError::InitializationFailed {
-                plugin: "function_extraction".to_string(),
-                error: e.to_string(),
-            })?;
+        let database = self.database.as_deref()
+            .ok_or_else(|| PluginError::NotInitialized("Database not set".to_string()))?;
         
         let start_time = Instant::now();
         
-        // Step 1: Run all detection algorithms in parallel using enhanced morphological analysis
-        let agency_primitives = Self::detect_agency(text, &database, executor)?;
-        let action_primitives = Self::detect_actions(text, &database, executor)?;
-        let transformation_primitives = Self::detect_transformations(text, &database, executor)?;
-        let conditionality_primitives = Self::detect_conditionality(text, &database, executor)?;
-        let sequence_primitives = Self::detect_sequences(text, &database, executor)?;
-        let purpose_primitives = Self::detect_purpose(text, &database, executor)?;
+        // Step 1: Run all detection algorithms in parallel, each on its own
+        // executor over the shared database
+        let executors = DETECTORS.iter()
+            .map(|_| executor.try_clone())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| PluginError::NotInitialized(e.to_string()))?;
+        let detected: Vec<Result<Vec<FunctionalPrimitive>, PluginError>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = DETECTORS.iter()
+                .zip(executors)
+                .map(|(detect, mut executor)| scope.spawn(move || detect(text, database, &mut executor)))
+                .collect();
+            handles.into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)))
+                .collect()
+        });
         
-        // Step 2: Combine all primitives
+        // Step 2: Combine all primitives, in detector order so output is stable
         let mut all_primitives = Vec::new();
-        all_primitives.extend(agency_primitives);
-        all_primitives.extend(action_primitives);
-        all_primitives.extend(transformation_primitives);
-        all_primitives.extend(conditionality_primitives);
-        all_primitives.extend(sequence_primitives);
-        all_primitives.extend(purpose_primitives);
+        for primitives in detected {
+            all_primitives.extend(primitives?);
+        }
         
         // Step 3: Enhanced analysis with mirroring decomposer (temporarily disabled)
         let (synthesis_opportunities, mirror_analysis, negation_transforms, morphological_confidence) = 
@@ -281,7 +304,7 @@ impl FunctionExtractor {
             // Step 4: If word contains agent morphemes, analyze the full composition
             if has_agent_morphemes {
                 let capability_level = 0.8; // High capability for agent morphemes
-                let spatial_position = calculate_composed_position(&morpheme_analysis);
+                let spatial_position = calculate_composed_position(&morpheme_analysis, Layer::Words);
                 
                 // Create a composite node from morphemes
                 let actor = LinguisticNode::new(
@@ -323,7 +346,7 @@ impl FunctionExtractor {
             // Step 4: If action morphemes found, compose the action primitive
             if has_action_morphemes {
                 let transformation_strength = 0.7; // Default strength for action verbs
-                let spatial_position = calculate_composed_position(&morpheme_analysis);
+                let spatial_position = calculate_composed_position(&morpheme_analysis, Layer::Words);
                 
                 // Determine action type through spatial positioning
                 let action_type = Self::classify_action_type_by_position(&spatial_position);
@@ -350,6 +373,13 @@ impl FunctionExtractor {
     fn detect_transformations(text: &str, database: &LingoDatabase, executor: &mut LingoExecutor) -> Result<Vec<FunctionalPrimitive>, PluginError> {
         let mut transformations = Vec::new();
         
+        // The text itself may state the transformation ("water becomes ice")
+        if Self::contains_transformation_markers(&text.to_lowercase()) {
+            if let Some(primitive) = Self::transformation_primitive(text, database, executor) {
+                transformations.push(primitive);
+            }
+        }
+        
         // Find transformation patterns through phrase analysis
         let query = QueryBuilder::find(text)
             .layer_up() // Look at phrases
@@ -368,19 +398,8 @@ impl FunctionExtractor {
                 if matches!(node.layer, Layer::Phrases) {
                     if let Ok(phrase) = database.get_node_word(*node_id) {
                         if Self::contains_transformation_markers(phrase) {
-                            if let Some((input_state, output_state)) = Self::extract_transformation_states(phrase, database) {
-                                let process_vector = Self::calculate_transformation_vector(
-                                    input_state.position,
-                                    output_state.position,
-                                );
-                                let reversibility = Self::calculate_reversibility(phrase);
-                                
-                                transformations.push(FunctionalPrimitive::Transformation {
-                                    input_state,
-                                    output_state,
-                                    process_vector,
-                                    reversibility,
-                                });
+                            if let Some(primitive) = Self::transformation_primitive(phrase, database, executor) {
+                                transformations.push(primitive);
                             }
                         }
                     }
@@ -391,6 +410,23 @@ impl FunctionExtractor {
         Ok(transformations)
     }
     
+    /// Transformation primitive for a phrase whose two states both resolve
+    fn transformation_primitive(phrase: &str, database: &LingoDatabase, executor: &mut LingoExecutor) -> Option<FunctionalPrimitive> {
+        let (input_state, output_state) = Self::extract_transformation_states(phrase, database, executor)?;
+        let process_vector = Self::calculate_transformation_vector(
+            input_state.position,
+            output_state.position,
+        );
+        let reversibility = Self::calculate_reversibility(phrase);
+        
+        Some(FunctionalPrimitive::Transformation {
+            input_state,
+            output_state,
+            process_vector,
+            reversibility,
+        })
+    }
+    
     fn detect_conditionality(text: &str, database: &LingoDatabase, executor: &mut LingoExecutor) -> Result<Vec<FunctionalPrimitive>, PluginError> {
         let mut conditions = Vec::new();
         
@@ -472,37 +508,16 @@ impl FunctionExtractor {
     fn detect_purpose(text: &str, database: &LingoDatabase, executor: &mut LingoExecutor) -> Result<Vec<FunctionalPrimitive>, PluginError> {
         let mut purposes = Vec::new();
         
-        // Find purpose markers
-        let purpose_words = ["to", "for", "in order to", "so that", "aiming", "goal"];
-        
-        for purpose_word in &purpose_words {
-            if text.to_lowercase().contains(purpose_word) {
-                let query = QueryBuilder::find(purpose_word)
-                    .layer_up()
-                    .similar_threshold(0.3)
-                    .limit(5)
-                    .compile();
-                
-                let result = executor.execute(&query)
-                    .map_err(|e| PluginError::InitializationFailed {
-                        plugin: "function_extraction".to_string(),
-                        error: e.to_string(),
-                    })?;
-                
-                if !result.nodes.is_empty() {
-                    if let Some(goal_state) = Self::extract_goal_state(text, database) {
-                        let intention_strength = Self::calculate_intention_strength(text);
-                        let success_criteria = Self::extract_success_criteria(text, database);
-                        
-                        purposes.push(FunctionalPrimitive::Purpose {
-                            goal_state,
-                            intention_strength,
-                            success_criteria,
-                        });
-                        break; // Only create one purpose per text
-                    }
-                }
-            }
+        // Only one purpose per text: the span after the first purpose marker
+        if let Some(goal_state) = Self::extract_goal_state(text, database, executor) {
+            let intention_strength = Self::calculate_intention_strength(text);
+            let success_criteria = Self::extract_success_criteria(text, &goal_state, database, executor);
+            
+            purposes.push(FunctionalPrimitive::Purpose {
+                goal_state,
+                intention_strength,
+                success_criteria,
+            });
         }
         
         Ok(purposes)
@@ -585,14 +600,13 @@ impl FunctionExtractor {
     }
     
     fn contains_transformation_markers( phrase: &str) -> bool {
-        let transformation_markers = ["becomes", "converts to", "transforms into", "changes to", "→", "->"];
-        transformation_markers.iter().any(|marker| phrase.contains(marker))
+        TRANSFORMATION_MARKERS.iter().any(|marker| phrase.contains(marker))
     }
     
     fn calculate_capability_strength( node: &LinguisticNode) -> f32 {
         // Calculate based on node position and morphological markers
         let base_strength = (node.position.x + node.position.y) / 2.0;
-        (base_strength * 0.7 + 0.3).min(1.0)
+        clamp01(base_strength * 0.7 + 0.3)
     }
     
     fn extract_responsibility_scope( _node: &LinguisticNode, _database: &LingoDatabase) -> Vec<LinguisticNode> {
@@ -613,7 +627,7 @@ impl FunctionExtractor {
     
     fn calculate_action_intensity( node: &LinguisticNode) -> f32 {
         // Base intensity on semantic position
-        ((node.position.x + node.position.y + node.position.z) / 3.0).min(1.0)
+        clamp01((node.position.x + node.position.y + node.position.z) / 3.0)
     }
     
     fn extract_temporal_aspect( node: &LinguisticNode) -> TemporalAspect {
@@ -627,16 +641,33 @@ impl FunctionExtractor {
         }
     }
     
-    fn extract_transformation_states( phrase: &str, database: &LingoDatabase) -> Option<(LinguisticNode, LinguisticNode)> {
-        // Simplified state extraction - would use more sophisticated parsing
-        let words: Vec<&str> = phrase.split_whitespace().collect();
-        if words.len() >= 3 {
-            // Try to find input and output states
-            // This is a simplified implementation
-            None // Would return actual states in full implementation
-        } else {
-            None
-        }
+    /// Split `phrase` on its first transformation marker and resolve each side to a node.
+    ///
+    /// Each side resolves to the word closest to the marker that exists in
+    /// the database, so "the cold water becomes solid ice" yields water → solid.
+    fn extract_transformation_states( phrase: &str, database: &LingoDatabase, executor: &mut LingoExecutor) -> Option<(LinguisticNode, LinguisticNode)> {
+        let lowered = phrase.to_lowercase();
+        let (start, marker) = TRANSFORMATION_MARKERS.iter()
+            .filter_map(|marker| lowered.find(marker).map(|start| (start, *marker)))
+            .min_by_key(|&(start, marker)| (start, std::cmp::Reverse(marker.len())))?;
+        
+        let before = lowered[..start].split_whitespace().rev();
+        let after = lowered[start + marker.len()..].split_whitespace();
+        let input_state = Self::resolve_state(before, database, executor)?;
+        let output_state = Self::resolve_state(after, database, executor)?;
+        Some((input_state, output_state))
+    }
+    
+    /// First of `words` that names a node in the database
+    fn resolve_state<'w>(words: impl Iterator<Item = &'w str>, database: &LingoDatabase, executor: &mut LingoExecutor) -> Option<LinguisticNode> {
+        words
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+            .filter(|word| !word.is_empty())
+            .find_map(|word| {
+                let result = executor.execute(&QueryBuilder::find(word).compile()).ok()?;
+                let node_id = *result.nodes.as_slice().first()?;
+                database.get_node(node_id).ok().copied()
+            })
     }
     
     fn calculate_transformation_vector( input: Coordinate3D, output: Coordinate3D) -> Coordinate3D {
@@ -658,7 +689,7 @@ impl FunctionExtractor {
     
     fn calculate_certainty_level( node: &LinguisticNode) -> f32 {
         // Base certainty on semantic position
-        node.position.x.min(1.0)
+        clamp01(node.position.x)
     }
     
     fn extract_conditional_scope( _node: &LinguisticNode, _database: &LingoDatabase) -> Vec<LinguisticNode> {
@@ -677,16 +708,92 @@ impl FunctionExtractor {
         0.5
     }
     
-    fn extract_goal_state( _text: &str, _database: &LingoDatabase) -> Option<LinguisticNode> {
-        None
+    /// Lowercased words following the first purpose marker in `text`
+    fn purpose_span(text: &str) -> Option<Vec<String>> {
+        let words: Vec<String> = text.split_whitespace()
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        
+        (0..words.len()).find_map(|start| {
+            PURPOSE_MARKERS.iter().find_map(|marker| {
+                let marker: Vec<&str> = marker.split(' ').collect();
+                let end = start + marker.len();
+                let matches = words.get(start..end)
+                    .is_some_and(|span| span.iter().zip(&marker).all(|(word, m)| word == m));
+                matches.then(|| words[end..].to_vec())
+            })
+        })
+    }
+    
+    /// Head of the purpose span: its first word that exists in the database
+    fn extract_goal_state( text: &str, database: &LingoDatabase, executor: &mut LingoExecutor) -> Option<LinguisticNode> {
+        let span = Self::purpose_span(text)?;
+        Self::resolve_state(span.iter().map(String::as_str), database, executor)
     }
     
     fn calculate_intention_strength( _text: &str) -> f32 {
         0.7
     }
     
-    fn extract_success_criteria( _text: &str, _database: &LingoDatabase) -> Vec<LinguisticNode> {
-        Vec::new()
+    /// Measurable or quality words in the purpose span, other than its head.
+    ///
+    /// "to improve performance and reliability" yields performance and
+    /// reliability, recognised by suffixes such as -ance and -ity.
+    /// Comparatives and superlatives count only on a known adjective stem.
+    fn extract_success_criteria( text: &str, goal_state: &LinguisticNode, database: &LingoDatabase, executor: &mut LingoExecutor) -> Vec<LinguisticNode> {
+        let Some(span) = Self::purpose_span(text) else {
+            return Vec::new();
+        };
+        
+        let mut criteria: Vec<LinguisticNode> = Vec::new();
+        for word in &span {
+            let is_quality = QUALITY_SUFFIXES.iter()
+                .any(|suffix| word.len() > suffix.len() + 2 && word.ends_with(suffix));
+            if !is_quality && !Self::is_degree_form(word, database) {
+                continue;
+            }
+            if let Some(node) = Self::resolve_state(std::iter::once(word.as_str()), database, executor) {
+                let node_id = node.id;
+                if node_id != { goal_state.id } && !criteria.iter().any(|c| { c.id } == node_id) {
+                    criteria.push(node);
+                }
+            }
+        }
+        criteria
+    }
+    
+    /// Whether `word` is the comparative or superlative of a known adjective.
+    ///
+    /// "-er" and "-est" also end agent nouns ("manager") and plain words
+    /// ("water"), so the stem has to be in the database with an Antonymy
+    /// connection, as gradable adjectives are ("faster" from fast/slow).
+    /// A doubled final consonant ("bigger"), y→i ("happier") and a dropped
+    /// final e ("larger") are undone before the lookup.
+    fn is_degree_form(word: &str, database: &LingoDatabase) -> bool {
+        let Some(base) = DEGREE_SUFFIXES.iter().find_map(|suffix| word.strip_suffix(suffix)) else {
+            return false;
+        };
+        if base.chars().count() < 2 {
+            return false;
+        }
+        
+        let mut stems = vec![base.to_string(), format!("{}e", base)];
+        let mut last_two = base.chars().rev();
+        if let (Some(last), Some(before)) = (last_two.next(), last_two.next()) {
+            if last == before {
+                stems.push(base[..base.len() - last.len_utf8()].to_string());
+            }
+        }
+        if let Some(before_i) = base.strip_suffix('i') {
+            stems.push(format!("{}y", before_i));
+        }
+        
+        stems.iter()
+            .flat_map(|stem| database.find_nodes_by_word(stem))
+            .any(|id| database.get_node_connections(id).is_ok_and(|connections| {
+                connections.iter().any(|c| c.connection_type == ConnectionType::Antonymy)
+            }))
     }
     
     fn calculate_spatial_coherence( primitives: &[FunctionalPrimitive]) -> f32 {
@@ -703,7 +810,7 @@ impl FunctionExtractor {
         
         for i in 0..positions.len() {
             for j in (i+1)..positions.len() {
-                total_distance += Self::euclidean_distance(positions[i], positions[j]);
+                total_distance += positions[i].distance_to(positions[j]);
                 pairs += 1;
             }
         }
@@ -739,10 +846,6 @@ impl FunctionExtractor {
         }
     }
     
-    fn euclidean_distance( a: Coordinate3D, b: Coordinate3D) -> f32 {
-        ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
-    }
-    
     fn calculate_overall_confidence( primitives: &[FunctionalPrimitive], spatial_coherence: f32) -> f32 {
         if primitives.is_empty() {
             return 0.0;
@@ -787,28 +890,17 @@ impl Plugin for FunctionExtractor {
     }
     
     fn initialize(&mut self, database: &LingoDatabase) -> Result<(), PluginError> {
-        // Store the database path
-        self.database_path = Some("english.lingo".to_string());
-        
-        // Create executor and load the database
-        let mut executor = LingoExecutor::new();
-        if let Err(e) = executor.load_database("english.lingo") {
-            return Err(PluginError::InitializationFailed {
-                plugin: "function_extraction".to_string(),
-                error: format!("Failed to load database in executor: {}", e),
-            });
-        }
-        
-        self.executor = Some(executor);
+        // Without a shared handle, open our own copy of the same file
+        let database = reopen_database(database, "function_extraction")?;
+        self.initialize_shared(&Arc::new(database))
+    }
+    
+    fn initialize_shared(&mut self, database: &Arc<LingoDatabase>) -> Result<(), PluginError> {
+        self.executor = Some(LingoExecutor::from_shared(Arc::clone(database)));
+        self.database = Some(Arc::clone(database));
         
         // Initialize mirroring decomposer for enhanced capabilities (temporarily disabled)
-        // let db_arc = Arc::new(LingoDatabase::open("english.lingo")
-        //     .map_err(|e| PluginError::InitializationFailed {
-        //         plugin: "function_extraction".to_string(),
-        //         error: format!("Failed to open database for mirroring: {}", e),
-        //     })?);
-        //     
-        // let mirroring_decomposer = MirroringDecomposer::new(db_arc)
+        // let mirroring_decomposer = MirroringDecomposer::new(Arc::clone(database))
         //     .map_err(|e| PluginError::InitializationFailed {
         //         plugin: "function_extraction".to_string(),
         //         error: format!("Failed to create mirroring decomposer: {}", e),
@@ -819,7 +911,14 @@ impl Plugin for FunctionExtractor {
         Ok(())
     }
     
-    fn handle_command(&mut self, command: &str, args: &[String], _context: &PluginContext) -> Result<Option<PluginResult>, PluginError> {
+    fn handle_command(&mut self, command: &str, args: &[String], context: &PluginContext) -> Result<Option<PluginResult>, PluginError> {
+        // Adopt the caller's database if we were never initialized
+        if self.database.is_none() {
+            if let Some(database) = &context.database {
+                self.initialize_shared(database)?;
+            }
+        }
+        
         match command {
             "extract_function" => {
                 if args.is_empty() {
@@ -1028,6 +1127,178 @@ mod tests {
         assert_eq!(extractor.version(), "1.0.0");
     }
     
+    #[test]
+    fn test_initialize_uses_given_database() {
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        // Deliberately not named english.lingo, and not in the working directory
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("manager", Layer::Words, Coordinate3D::new(0.3, 0.4, 0.5)).unwrap();
+        builder.add_node("create", Layer::Words, Coordinate3D::new(0.6, 0.4, 0.5)).unwrap();
+        let (_dir, path) = build_database(&builder);
+        let database = Arc::new(LingoDatabase::open(&path).unwrap());
+        
+        let mut shared = FunctionExtractor::new();
+        shared.initialize_shared(&database).unwrap();
+        assert!(Arc::ptr_eq(shared.database.as_ref().unwrap(), &database));
+        assert!(shared.extract_function_signature("the manager will create it").is_ok());
+        
+        let mut reopened = FunctionExtractor::new();
+        reopened.initialize(&database).unwrap();
+        assert_eq!(reopened.database.as_ref().unwrap().path(), database.path());
+    }
+    
+    #[test]
+    fn test_repeated_extraction_reuses_open_database() {
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("manager", Layer::Words, Coordinate3D::new(0.3, 0.4, 0.5)).unwrap();
+        let (_dir, path) = build_database(&builder);
+        let database = Arc::new(LingoDatabase::open(&path).unwrap());
+        
+        // The mapping outlives the file; any attempt to reopen it would fail
+        std::fs::remove_file(&path).unwrap();
+        
+        let context = PluginContext::new(String::new()).with_database(Arc::clone(&database));
+        let mut extractor = FunctionExtractor::new();
+        let args = vec!["the manager approves it".to_string()];
+        for _ in 0..100 {
+            let result = extractor.handle_command("extract_function", &args, &context).unwrap();
+            assert!(result.is_some());
+        }
+        assert!(Arc::ptr_eq(extractor.database.as_ref().unwrap(), &database));
+    }
+    
+    #[test]
+    fn test_transformation_states_from_text() {
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        let water = builder.add_node("water", Layer::Words, Coordinate3D::new(0.2, 0.6, 0.5)).unwrap();
+        let ice = builder.add_node("ice", Layer::Words, Coordinate3D::new(0.7, 0.3, 0.5)).unwrap();
+        let (_dir, path) = build_database(&builder);
+        
+        let mut extractor = FunctionExtractor::new();
+        extractor.initialize_shared(&Arc::new(LingoDatabase::open(&path).unwrap())).unwrap();
+        let signature = extractor.extract_function_signature("water becomes ice").unwrap();
+        
+        let transformation = signature.primitives.iter()
+            .find_map(|primitive| match primitive {
+                FunctionalPrimitive::Transformation { input_state, output_state, process_vector, .. } => {
+                    Some((input_state.id, output_state.id, *process_vector))
+                }
+                _ => None,
+            })
+            .expect("no transformation primitive");
+        assert_eq!((transformation.0, transformation.1), (water, ice));
+        let vector = transformation.2;
+        assert!((vector.x - 0.5).abs() < 1e-5 && (vector.y + 0.3).abs() < 1e-5);
+    }
+    
+    #[test]
+    fn test_purpose_goal_and_success_criteria() {
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("refactor", Layer::Words, Coordinate3D::new(0.3, 0.3, 0.5)).unwrap();
+        builder.add_node("module", Layer::Words, Coordinate3D::new(0.4, 0.2, 0.5)).unwrap();
+        let improve = builder.add_node("improve", Layer::Words, Coordinate3D::new(0.7, 0.6, 0.5)).unwrap();
+        let performance = builder.add_node("performance", Layer::Words, Coordinate3D::new(0.75, 0.65, 0.5)).unwrap();
+        let (_dir, path) = build_database(&builder);
+        
+        let mut extractor = FunctionExtractor::new();
+        extractor.initialize_shared(&Arc::new(LingoDatabase::open(&path).unwrap())).unwrap();
+        let signature = extractor.extract_function_signature("refactor the module to improve performance").unwrap();
+        
+        let (goal, criteria) = signature.primitives.iter()
+            .find_map(|primitive| match primitive {
+                FunctionalPrimitive::Purpose { goal_state, success_criteria, .. } => {
+                    Some((goal_state.id, success_criteria.iter().map(|node| node.id).collect::<Vec<_>>()))
+                }
+                _ => None,
+            })
+            .expect("no purpose primitive");
+        assert_eq!(goal, improve);
+        assert_eq!(criteria, vec![performance]);
+        
+        // "to" inside a word isn't a marker
+        assert_eq!(FunctionExtractor::purpose_span("a tomato stew"), None);
+        assert_eq!(
+            FunctionExtractor::purpose_span("rest in order to recover"),
+            Some(vec!["recover".to_string()])
+        );
+    }
+    
+    #[test]
+    fn test_degree_suffixes_need_adjective_stem() {
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        let position = Coordinate3D::new(0.5, 0.5, 0.5);
+        builder.add_node("improve", Layer::Words, position).unwrap();
+        let mut add = |words: &[&str]| -> Vec<NodeId> {
+            words.iter().map(|word| builder.add_node(word, Layer::Words, position).unwrap()).collect()
+        };
+        let adjectives = add(&["fast", "slow", "big", "small", "happy", "sad"]);
+        let inflected = add(&["faster", "bigger", "happiest", "manager", "manage", "water", "teacher", "teach"]);
+        for pair in adjectives.chunks(2) {
+            builder.add_connection(pair[0], pair[1], ConnectionType::Antonymy, 0.9).unwrap();
+        }
+        let (_dir, path) = build_database(&builder);
+        
+        let mut extractor = FunctionExtractor::new();
+        extractor.initialize_shared(&Arc::new(LingoDatabase::open(&path).unwrap())).unwrap();
+        let signature = extractor.extract_function_signature(
+            "tune it to improve faster bigger happiest manager water teacher"
+        ).unwrap();
+        
+        let criteria = signature.primitives.iter()
+            .find_map(|primitive| match primitive {
+                FunctionalPrimitive::Purpose { success_criteria, .. } => {
+                    Some(success_criteria.iter().map(|node| node.id).collect::<Vec<_>>())
+                }
+                _ => None,
+            })
+            .expect("no purpose primitive");
+        assert_eq!(criteria, inflected[..3].to_vec());
+    }
+    
+    #[test]
+    fn test_parallel_detectors_keep_order() {
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("water", Layer::Words, Coordinate3D::new(0.2, 0.6, 0.5)).unwrap();
+        builder.add_node("ice", Layer::Words, Coordinate3D::new(0.7, 0.3, 0.5)).unwrap();
+        builder.add_node("improve", Layer::Words, Coordinate3D::new(0.7, 0.6, 0.5)).unwrap();
+        builder.add_node("purity", Layer::Words, Coordinate3D::new(0.75, 0.65, 0.5)).unwrap();
+        let (_dir, path) = build_database(&builder);
+        
+        let mut extractor = FunctionExtractor::new();
+        extractor.initialize_shared(&Arc::new(LingoDatabase::open(&path).unwrap())).unwrap();
+        let text = "water becomes ice to improve purity";
+        let kinds = |signature: &FunctionSignature| signature.primitives.iter()
+            .map(std::mem::discriminant)
+            .collect::<Vec<_>>();
+        
+        let first = extractor.extract_function_signature(text).unwrap();
+        let first_kinds = kinds(&first);
+        assert!(matches!(first.primitives.as_slice(), [
+            FunctionalPrimitive::Transformation { .. },
+            FunctionalPrimitive::Purpose { .. },
+        ]));
+        for _ in 0..10 {
+            assert_eq!(kinds(&extractor.extract_function_signature(text).unwrap()), first_kinds);
+        }
+    }
+    
     #[test]
     fn test_agent_root_detection() {
         assert!(FunctionExtractor::is_agent_root("manag"));
@@ -17,20 +17,21 @@
 //! Implements the complete Intent = Function × PragmaticOperators specification.
 //! Uses bottom-up compositionality through 9 specialized operator detectors.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 use std::time::Instant;
 use serde::{Serialize, Deserialize};
 
-use crate::core::Coordinate3D;
+use crate::core::{Coordinate3D, Layer, clamp01};
 use crate::storage::LingoDatabase;
 use crate::engine::LingoExecutor;
 use crate::morphology::preprocess_text;
 use super::{Plugin, PluginContext, PluginResult, PluginError, 
-           FunctionExtractor, FunctionalPrimitive};
+           FunctionExtractor, FunctionalPrimitive, reopen_database};
 
 /// Intent detection plugin implementing Function × PragmaticOperators algebra
 pub struct IntentDetector {
-    database_path: Option<String>,
+    database: Option<Arc<LingoDatabase>>,
     executor: Option<LingoExecutor>,
     function_extractor: Option<FunctionExtractor>,
     pub operator_detectors: Option<OperatorDetectorSuite>,
@@ -41,7 +42,7 @@ pub struct IntentDetector {
 impl IntentDetector {
     pub fn new() -> Self {
         Self {
-            database_path: None,
+            database: None,
             executor: None,
             function_extractor: None,
             operator_detectors: None,
@@ -66,22 +67,14 @@ impl IntentDetector {
             })?;
         
         // Step 2: Detect all 9 operator types in parallel
-        let db_path = self.database_path.as_ref()
-            .ok_or_else(|| PluginError::InitializationFailed {
-                plugin: "intent_detection".to_string(),
-                error: "Database path not configured".to_string(),
-            })?;
-            
-        let database = LingoDatabase::open(db_path)
-            .map_err(|e| PluginError::InitializationFailed {
-                plugin: "intent_detection".to_string(),
-                error: e.to_string(),
-            })?;
+        let database = self.database.as_deref()
+            .ok_or_else(|| PluginError::NotInitialized("Database not set".to_string()))?;
         
         let operator_detectors = self.operator_detectors.as_ref()
             .ok_or_else(|| PluginError::NotInitialized("Operator detectors not available".to_string()))?;
         
-        let operators = operator_detectors.detect_all_operators(text, &database)?;
+        let operators = operator_detectors.detect_all_operators(text, database)?;
+        let operator_triggers = operators.triggers();
         
         // Step 3: Validate operator coherence
         let operator_coherence = self.calculate_operator_coherence(&operators);
@@ -105,44 +98,29 @@ impl IntentDetector {
             intent_confidence: intent.intent_confidence,
             operator_coherence,
             compositional_path: intent.compositional_path,
+            operator_triggers,
             execution_time_ms: execution_time.as_millis() as f32,
             source_text: text.to_string(),
         })
     }
     
     /// Calculate how well operators work together using spatial coherence analysis
+    ///
+    /// Every operator counts towards compositional richness; only those whose
+    /// trigger has a position contribute to the spatial term.
     pub fn calculate_operator_coherence(&self, operators: &PragmaticOperators) -> f32 {
-        let mut all_positions = Vec::new();
-        let mut operator_count = 0;
-        
-        // Collect spatial positions from all operators
-        for op in &operators.directional {
-            if let Some(pos) = op.spatial_vector {
-                all_positions.push(pos);
-                operator_count += 1;
-            }
-        }
-        
-        // For other operator types, we'd need to extract their spatial positions
-        // For now, use operator count as a proxy
-        if !operators.modal.is_empty() { operator_count += 1; }
-        if !operators.temporal.is_empty() { operator_count += 1; }
-        if !operators.conditional.is_empty() { operator_count += 1; }
-        if !operators.negation.is_empty() { operator_count += 1; }
-        if !operators.intensity.is_empty() { operator_count += 1; }
-        if !operators.certainty.is_empty() { operator_count += 1; }
-        if !operators.scope.is_empty() { operator_count += 1; }
-        if !operators.social.is_empty() { operator_count += 1; }
+        let operator_count = operators.len();
         
         if operator_count == 0 {
             return 1.0;
         }
         
         // Calculate spatial coherence if we have positions
+        let all_positions = operators.positions();
         let spatial_coherence = if all_positions.len() >= 2 {
             self.calculate_spatial_coherence_between_positions(&all_positions)
         } else {
-            0.8 // Default coherence for single or no spatial operators
+            0.8 // Default coherence for a single operator
         };
         
         // Bonus for having multiple operators (compositional richness)
@@ -152,7 +130,7 @@ impl IntentDetector {
             1.0
         };
         
-        (spatial_coherence * composition_bonus).min(1.0)
+        clamp01(spatial_coherence * composition_bonus)
     }
     
     /// Calculate spatial coherence between operator positions
@@ -166,10 +144,7 @@ impl IntentDetector {
         
         for i in 0..positions.len() {
             for j in (i+1)..positions.len() {
-                let distance = ((positions[i].x - positions[j].x).powi(2) +
-                               (positions[i].y - positions[j].y).powi(2) +
-                               (positions[i].z - positions[j].z).powi(2)).sqrt();
-                total_distance += distance;
+                total_distance += positions[i].distance_to(positions[j]);
                 pairs += 1;
             }
         }
@@ -198,22 +173,18 @@ impl Plugin for IntentDetector {
     }
     
     fn initialize(&mut self, database: &LingoDatabase) -> Result<(), PluginError> {
-        // Store database path
-        self.database_path = Some("english.lingo".to_string());
-        
-        // Create executor
-        let mut executor = LingoExecutor::new();
-        if let Err(e) = executor.load_database("english.lingo") {
-            return Err(PluginError::InitializationFailed {
-                plugin: "intent_detection".to_string(),
-                error: format!("Failed to load database in executor: {}", e),
-            });
-        }
-        self.executor = Some(executor);
+        // Without a shared handle, open our own copy of the same file
+        let database = reopen_database(database, "intent_detection")?;
+        self.initialize_shared(&Arc::new(database))
+    }
+    
+    fn initialize_shared(&mut self, database: &Arc<LingoDatabase>) -> Result<(), PluginError> {
+        self.executor = Some(LingoExecutor::from_shared(Arc::clone(database)));
+        self.database = Some(Arc::clone(database));
         
-        // Initialize function extractor
+        // Initialize function extractor over the same database
         let mut function_extractor = FunctionExtractor::new();
-        function_extractor.initialize(database)?;
+        function_extractor.initialize_shared(database)?;
         self.function_extractor = Some(function_extractor);
         
         // Initialize operator detectors
@@ -225,7 +196,14 @@ impl Plugin for IntentDetector {
         Ok(())
     }
     
-    fn handle_command(&mut self, command: &str, args: &[String], _context: &PluginContext) -> Result<Option<PluginResult>, PluginError> {
+    fn handle_command(&mut self, command: &str, args: &[String], context: &PluginContext) -> Result<Option<PluginResult>, PluginError> {
+        // Adopt the caller's database if we were never initialized
+        if self.database.is_none() {
+            if let Some(database) = &context.database {
+                self.initialize_shared(database)?;
+            }
+        }
+        
         match command {
             "detect_intent" => {
                 if args.is_empty() {
@@ -266,10 +244,23 @@ pub struct Intent {
     pub intent_confidence: f32,
     pub operator_coherence: f32,
     pub compositional_path: Vec<OperatorApplication>,
+    /// Words that fired each detector, keyed by operator type in name order
+    #[serde(default)]
+    pub operator_triggers: BTreeMap<String, Vec<String>>,
     pub execution_time_ms: f32,
     pub source_text: String,
 }
 
+impl Intent {
+    /// Operator types that produced at least one operator, in detector order.
+    ///
+    /// Useful for explaining why an intent was classified a certain way;
+    /// pair with `operator_triggers` to see which words fired each detector.
+    pub fn active_operator_types(&self) -> Vec<&'static str> {
+        self.pragmatic_operators.active_types()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PragmaticOperators {
     pub directional: Vec<DirectionalOperator>,
@@ -297,6 +288,93 @@ impl PragmaticOperators {
             social: Vec::new(),
         }
     }
+    
+    /// Total number of operators of every type
+    pub fn len(&self) -> usize {
+        self.directional.len()
+            + self.modal.len()
+            + self.temporal.len()
+            + self.conditional.len()
+            + self.negation.len()
+            + self.intensity.len()
+            + self.certainty.len()
+            + self.scope.len()
+            + self.social.len()
+    }
+    
+    /// Returns true if no operator of any type was detected
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// Positions of the operators whose trigger is in the database, in
+    /// detector order
+    pub fn positions(&self) -> Vec<Coordinate3D> {
+        let directional = self.directional.iter().map(|op| op.spatial_vector);
+        directional
+            .chain(self.modal.iter().map(|op| op.spatial_position))
+            .chain(self.temporal.iter().map(|op| op.spatial_position))
+            .chain(self.conditional.iter().map(|op| op.spatial_position))
+            .chain(self.negation.iter().map(|op| op.spatial_position))
+            .chain(self.intensity.iter().map(|op| op.spatial_position))
+            .chain(self.certainty.iter().map(|op| op.spatial_position))
+            .chain(self.scope.iter().map(|op| op.spatial_position))
+            .chain(self.social.iter().map(|op| op.spatial_position))
+            .flatten()
+            .collect()
+    }
+    
+    /// Words that fired each operator type, keyed by type name.
+    ///
+    /// Only types with at least one operator appear, and the map iterates
+    /// in name order. Each type lists its triggers once, in order of
+    /// appearance, so a prefix negation reports the whole word ("unhappy").
+    pub fn triggers(&self) -> BTreeMap<String, Vec<String>> {
+        let mut triggers = BTreeMap::new();
+        let mut record = |name: &str, words: Vec<&String>| {
+            let mut unique: Vec<String> = Vec::new();
+            for word in words {
+                if !unique.contains(word) {
+                    unique.push(word.clone());
+                }
+            }
+            if !unique.is_empty() {
+                triggers.insert(name.to_string(), unique);
+            }
+        };
+        
+        record("directional", self.directional.iter().map(|op| &op.trigger).collect());
+        record("modal", self.modal.iter().map(|op| &op.trigger).collect());
+        record("temporal", self.temporal.iter().map(|op| &op.trigger).collect());
+        record("conditional", self.conditional.iter().map(|op| &op.trigger).collect());
+        record("negation", self.negation.iter().map(|op| &op.trigger).collect());
+        record("intensity", self.intensity.iter().map(|op| &op.trigger).collect());
+        record("certainty", self.certainty.iter().map(|op| &op.trigger).collect());
+        record("scope", self.scope.iter().map(|op| &op.trigger).collect());
+        record("social", self.social.iter().map(|op| &op.trigger).collect());
+        
+        triggers
+    }
+    
+    /// Names of the operator types that are non-empty, in detector order
+    pub fn active_types(&self) -> Vec<&'static str> {
+        let counts = [
+            ("directional", self.directional.len()),
+            ("modal", self.modal.len()),
+            ("temporal", self.temporal.len()),
+            ("conditional", self.conditional.len()),
+            ("negation", self.negation.len()),
+            ("intensity", self.intensity.len()),
+            ("certainty", self.certainty.len()),
+            ("scope", self.scope.len()),
+            ("social", self.social.len()),
+        ];
+        
+        counts.iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(name, _)| *name)
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -312,6 +390,9 @@ pub struct OperatorApplication {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DirectionalOperator {
     pub operator_type: DirectionalType,
+    /// Word or phrase in the text that fired this operator
+    #[serde(default)]
+    pub trigger: String,
     pub target_entity: Option<String>,
     pub spatial_vector: Option<Coordinate3D>,
     pub strength: f32,
@@ -335,8 +416,14 @@ pub enum DirectionalType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModalOperator {
     pub operator_type: ModalType,
+    /// Word or phrase in the text that fired this operator
+    #[serde(default)]
+    pub trigger: String,
     pub strength: f32,
     pub scope: ModalScope,
+    /// Database position of the modal ("can", "must") that triggered it
+    #[serde(default)]
+    pub spatial_position: Option<Coordinate3D>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -362,8 +449,14 @@ pub enum ModalScope {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemporalOperator {
     pub operator_type: TemporalType,
+    /// Word or phrase in the text that fired this operator
+    #[serde(default)]
+    pub trigger: String,
     pub urgency_level: f32,
     pub temporal_scope: TemporalScope,
+    /// Database position of the time word ("now", "until") that triggered it
+    #[serde(default)]
+    pub spatial_position: Option<Coordinate3D>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -393,8 +486,14 @@ pub enum TemporalScope {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConditionalOperator {
     pub operator_type: ConditionalType,
+    /// Word or phrase in the text that fired this operator
+    #[serde(default)]
+    pub trigger: String,
     pub condition_strength: f32,
     pub condition_content: String,
+    /// Database position of the condition word ("if", "unless") that triggered it
+    #[serde(default)]
+    pub spatial_position: Option<Coordinate3D>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -412,8 +511,15 @@ pub enum ConditionalType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NegationOperator {
     pub operator_type: NegationType,
+    /// Word or phrase in the text that fired this operator
+    #[serde(default)]
+    pub trigger: String,
     pub negation_scope: Vec<String>,
     pub negation_strength: f32,
+    /// Database position of the negator, a word like "never" or a prefix
+    /// like "un-"
+    #[serde(default)]
+    pub spatial_position: Option<Coordinate3D>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -432,8 +538,14 @@ pub enum NegationType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IntensityOperator {
     pub operator_type: IntensityType,
+    /// Word or phrase in the text that fired this operator
+    #[serde(default)]
+    pub trigger: String,
     pub intensity_level: f32,
     pub target_modification: String,
+    /// Database position of the intensifier ("very", "slightly") that triggered it
+    #[serde(default)]
+    pub spatial_position: Option<Coordinate3D>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -452,8 +564,15 @@ pub enum IntensityType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CertaintyOperator {
     pub operator_type: CertaintyType,
+    /// Word or phrase in the text that fired this operator
+    #[serde(default)]
+    pub trigger: String,
     pub confidence_level: f32,
     pub evidence_basis: Option<String>,
+    /// Database position of the hedge or booster ("maybe", "definitely") that
+    /// triggered it
+    #[serde(default)]
+    pub spatial_position: Option<Coordinate3D>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -473,8 +592,14 @@ pub enum CertaintyType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScopeOperator {
     pub operator_type: ScopeType,
+    /// Word or phrase in the text that fired this operator
+    #[serde(default)]
+    pub trigger: String,
     pub quantity_estimate: Option<f32>,
     pub scope_target: String,
+    /// Database position of the quantifier ("all", "few") that triggered it
+    #[serde(default)]
+    pub spatial_position: Option<Coordinate3D>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -495,8 +620,15 @@ pub enum ScopeType {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SocialOperator {
     pub operator_type: SocialType,
+    /// Word or phrase in the text that fired this operator
+    #[serde(default)]
+    pub trigger: String,
     pub politeness_level: f32,
     pub relationship_formality: f32,
+    /// Database position of the politeness phrase ("please", "would you")
+    /// that triggered it
+    #[serde(default)]
+    pub spatial_position: Option<Coordinate3D>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -569,6 +701,35 @@ impl OperatorDetectorSuite {
     }
 }
 
+/// Table entries whose keys occur as whole words in `text`, in order of appearance.
+///
+/// Keys may span several words ("thank you"). At each position the longest
+/// matching n-gram wins, and its words aren't matched again.
+fn matched_ngrams<'t, V>(text: &str, table: &'t HashMap<String, V>) -> Vec<(&'t str, &'t V)> {
+    let processed = preprocess_text(text);
+    let words: Vec<&str> = processed.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| c.is_ascii_punctuation()))
+        .filter(|word| !word.is_empty())
+        .collect();
+    let longest = table.keys().map(|key| key.split_whitespace().count()).max().unwrap_or(0);
+    
+    let mut matched = Vec::new();
+    let mut start = 0;
+    while start < words.len() {
+        let found = (1..=longest.min(words.len() - start)).rev().find_map(|n| {
+            table.get_key_value(&words[start..start + n].join(" ")).map(|(key, value)| (n, key, value))
+        });
+        match found {
+            Some((n, key, value)) => {
+                matched.push((key.as_str(), value));
+                start += n;
+            }
+            None => start += 1,
+        }
+    }
+    matched
+}
+
 // Intent Composition Engine
 
 pub struct IntentCompositionEngine {
@@ -659,6 +820,7 @@ impl IntentCompositionEngine {
             intent_confidence,
             operator_coherence,
             compositional_path,
+            operator_triggers: BTreeMap::new(), // Will be set by caller
             execution_time_ms: 0.0, // Will be set by caller
             source_text: source_text.to_string(),
         })
@@ -694,6 +856,28 @@ impl IntentCompositionEngine {
 
 // Individual Operator Detectors (will be implemented in Part 2)
 
+/// Position assumed when weighting the strength of an operator whose
+/// trigger isn't in the database: the centre of the Words layer.
+///
+/// It is only used for weighting; the operator's stored position stays
+/// `None`, so unresolved operators never cluster at this point in coherence.
+fn default_operator_position() -> Coordinate3D {
+    Coordinate3D::new(0.5, 0.5, Layer::Words.z_center())
+}
+
+/// Position of the node for a trigger word or morpheme.
+///
+/// This is what every operator's `spatial_position` (the directional
+/// operator's `spatial_vector`) holds. It is `None` when the trigger isn't
+/// in the database, for all operators from the database-free
+/// `detect_simple` detectors, and for operators deserialized from payloads
+/// written before the field existed. Coherence skips those operators.
+fn trigger_position(trigger: &str, db: &LingoDatabase) -> Option<Coordinate3D> {
+    db.find_nodes_by_word(trigger).first()
+        .and_then(|&id| db.get_node(id).ok())
+        .map(|node| node.position)
+}
+
 pub struct DirectionalOperatorDetector {
     pub directional_morphemes: HashMap<String, (DirectionalType, f32)>,
 }
@@ -724,26 +908,18 @@ impl DirectionalOperatorDetector {
         for word in words.split_whitespace() {
             let word_lower = word.to_lowercase().trim_matches(|c: char| c.is_ascii_punctuation()).to_string();
             if let Some((dir_type, base_strength)) = self.directional_morphemes.get(&word_lower) {
-                // Use morphological analysis to get actual strength and context
-                // Note: In practice, we'd pass the executor from the plugin context
-                let morpheme_analysis: Vec<String> = Vec::new(); // Simplified for now - would use actual decomposition
-                let spatial_position = Coordinate3D { x: 0.5, y: 0.5, z: 3.0 }; // Default word position
+                let spatial_vector = trigger_position(&word_lower, db);
                 
                 // Extract target entity from spatial context
                 let target_entity = self.extract_target_from_context(text, word, db);
                 
-                // Calculate spatial vector based on morphological positioning
-                let spatial_vector = Some(Coordinate3D {
-                    x: spatial_position.x,
-                    y: spatial_position.y, 
-                    z: spatial_position.z,
-                });
-                
                 // Calculate contextual strength using spatial coherence
-                let strength = self.calculate_contextual_strength(base_strength, &spatial_position, text);
+                let weighting_position = spatial_vector.unwrap_or_else(default_operator_position);
+                let strength = self.calculate_contextual_strength(base_strength, &weighting_position, text);
                 
                 operators.push(DirectionalOperator {
                     operator_type: dir_type.clone(),
+                    trigger: word_lower.clone(),
                     target_entity,
                     spatial_vector,
                     strength,
@@ -762,12 +938,11 @@ impl DirectionalOperatorDetector {
         for word in words.split_whitespace() {
             let word_lower = word.to_lowercase().trim_matches(|c: char| c.is_ascii_punctuation()).to_string();
             if let Some((dir_type, base_strength)) = self.directional_morphemes.get(&word_lower) {
-                let spatial_position = Coordinate3D { x: 0.5, y: 0.5, z: 3.0 };
-                
                 operators.push(DirectionalOperator {
                     operator_type: dir_type.clone(),
+                    trigger: word_lower.clone(),
                     target_entity: None,
-                    spatial_vector: Some(spatial_position),
+                    spatial_vector: None,
                     strength: *base_strength,
                 });
             }
@@ -804,7 +979,7 @@ impl DirectionalOperatorDetector {
             strength *= 1.1;
         }
         
-        strength.clamp(0.0, 1.0)
+        clamp01(strength)
     }
 }
 
@@ -846,20 +1021,21 @@ impl ModalOperatorDetector {
         for word in words.split_whitespace() {
             let word_lower = word.to_lowercase().trim_matches(|c: char| c.is_ascii_punctuation()).to_string();
             if let Some((modal_type, base_strength)) = self.modal_morphemes.get(&word_lower) {
-                // Use existing morphological analysis for context
-                // Note: In practice, we'd pass the executor from the plugin context
-                let spatial_position = Coordinate3D { x: 0.5, y: 0.5, z: 3.0 }; // Default word position
+                let spatial_position = trigger_position(&word_lower, db);
                 
                 // Calculate strength based on spatial coherence
-                let strength = self.calculate_contextual_strength(base_strength, &spatial_position, db);
+                let weighting_position = spatial_position.unwrap_or_else(default_operator_position);
+                let strength = self.calculate_contextual_strength(base_strength, &weighting_position, db);
                 
                 // Determine scope using spatial neighbors
                 let scope = self.determine_modal_scope(&word_lower, db);
                 
                 operators.push(ModalOperator {
                     operator_type: modal_type.clone(),
+                    trigger: word_lower.clone(),
                     strength,
                     scope,
+                    spatial_position,
                 });
             }
         }
@@ -874,7 +1050,7 @@ impl ModalOperatorDetector {
         let spatial_coherence = (spatial_position.x + spatial_position.y + spatial_position.z) / 3.0;
         strength *= (0.7 + (spatial_coherence * 0.6));
         
-        strength.clamp(0.0, 1.0)
+        clamp01(strength)
     }
     
     fn determine_modal_scope(&self, _word: &str, _db: &LingoDatabase) -> ModalScope {
@@ -894,8 +1070,10 @@ impl ModalOperatorDetector {
             if let Some((modal_type, base_strength)) = self.modal_morphemes.get(&word_lower) {
                 operators.push(ModalOperator {
                     operator_type: modal_type.clone(),
+                    trigger: word_lower.clone(),
                     scope: ModalScope::Full,
                     strength: *base_strength,
+                    spatial_position: None,
                 });
             }
         }
@@ -923,7 +1101,7 @@ impl TemporalOperatorDetector {
         Self { temporal_morphemes: morphemes }
     }
     
-    pub fn detect(&self, text: &str, _db: &LingoDatabase) -> Result<Vec<TemporalOperator>, PluginError> {
+    pub fn detect(&self, text: &str, db: &LingoDatabase) -> Result<Vec<TemporalOperator>, PluginError> {
         let mut operators = Vec::new();
         let words = preprocess_text(text);
         
@@ -935,8 +1113,10 @@ impl TemporalOperatorDetector {
                 
                 operators.push(TemporalOperator {
                     operator_type: temporal_type.clone(),
+                    trigger: word_lower.clone(),
                     urgency_level,
                     temporal_scope,
+                    spatial_position: trigger_position(&word_lower, db),
                 });
             }
         }
@@ -976,8 +1156,10 @@ impl TemporalOperatorDetector {
             if let Some((temporal_type, base_strength)) = self.temporal_morphemes.get(&word_lower) {
                 operators.push(TemporalOperator {
                     operator_type: temporal_type.clone(),
+                    trigger: word_lower.clone(),
                     urgency_level: *base_strength,
                     temporal_scope: TemporalScope::Immediate,
+                    spatial_position: None,
                 });
             }
         }
@@ -999,7 +1181,7 @@ impl ConditionalOperatorDetector {
         Self { conditional_morphemes: morphemes }
     }
     
-    pub fn detect(&self, text: &str, _db: &LingoDatabase) -> Result<Vec<ConditionalOperator>, PluginError> {
+    pub fn detect(&self, text: &str, db: &LingoDatabase) -> Result<Vec<ConditionalOperator>, PluginError> {
         let mut operators = Vec::new();
         let words = preprocess_text(text);
         
@@ -1010,8 +1192,10 @@ impl ConditionalOperatorDetector {
                 
                 operators.push(ConditionalOperator {
                     operator_type: conditional_type.clone(),
+                    trigger: word_lower.clone(),
                     condition_strength: *strength,
                     condition_content,
+                    spatial_position: trigger_position(&word_lower, db),
                 });
             }
         }
@@ -1046,8 +1230,10 @@ impl ConditionalOperatorDetector {
             if let Some((conditional_type, base_strength)) = self.conditional_morphemes.get(&word_lower) {
                 operators.push(ConditionalOperator {
                     operator_type: conditional_type.clone(),
+                    trigger: word_lower.clone(),
                     condition_strength: *base_strength,
                     condition_content: word.to_string(),
+                    spatial_position: None,
                 });
             }
         }
@@ -1070,7 +1256,7 @@ impl NegationOperatorDetector {
         Self { negation_morphemes: morphemes }
     }
     
-    pub fn detect(&self, text: &str, _db: &LingoDatabase) -> Result<Vec<NegationOperator>, PluginError> {
+    pub fn detect(&self, text: &str, db: &LingoDatabase) -> Result<Vec<NegationOperator>, PluginError> {
         let mut operators = Vec::new();
         let words = preprocess_text(text);
         
@@ -1082,26 +1268,37 @@ impl NegationOperatorDetector {
                 
                 operators.push(NegationOperator {
                     operator_type: negation_type.clone(),
+                    trigger: word_lower.clone(),
                     negation_scope,
                     negation_strength: *strength,
+                    spatial_position: trigger_position(&word_lower, db),
                 });
             }
         }
         
-        // Check for prefix negations
+        // Check for prefix negations; the stem must be a word in its own
+        // right, so "unhappy" counts but "under" and "until" don't
         for word in words.split_whitespace() {
-            if word.starts_with("un") || word.starts_with("dis") || word.starts_with("non") {
-                let prefix = if word.starts_with("un") { "un-" }
-                           else if word.starts_with("dis") { "dis-" }
-                           else { "non-" };
+            let word = word.trim_matches(|c: char| c.is_ascii_punctuation());
+            for prefix in ["un-", "dis-", "non-"] {
+                let Some(stem) = word.strip_prefix(prefix.trim_end_matches('-')) else {
+                    continue;
+                };
+                let stem = stem.trim_start_matches('-');
+                if stem.chars().count() < 3 || db.find_nodes_by_word(stem).is_empty() {
+                    continue;
+                }
                 
                 if let Some((negation_type, strength)) = self.negation_morphemes.get(prefix) {
                     operators.push(NegationOperator {
                         operator_type: negation_type.clone(),
+                        trigger: word.to_string(),
                         negation_scope: vec![word.to_string()],
                         negation_strength: *strength,
+                        spatial_position: trigger_position(prefix.trim_end_matches('-'), db),
                     });
                 }
+                break;
             }
         }
         
@@ -1131,8 +1328,10 @@ impl NegationOperatorDetector {
             if let Some((negation_type, base_strength)) = self.negation_morphemes.get(&word_lower) {
                 operators.push(NegationOperator {
                     operator_type: negation_type.clone(),
+                    trigger: word_lower.clone(),
                     negation_scope: vec![word.to_string()],
                     negation_strength: *base_strength,
+                    spatial_position: None,
                 });
             }
         }
@@ -1155,7 +1354,7 @@ impl IntensityOperatorDetector {
         Self { intensity_morphemes: morphemes }
     }
     
-    pub fn detect(&self, text: &str, _db: &LingoDatabase) -> Result<Vec<IntensityOperator>, PluginError> {
+    pub fn detect(&self, text: &str, db: &LingoDatabase) -> Result<Vec<IntensityOperator>, PluginError> {
         let mut operators = Vec::new();
         let words = preprocess_text(text);
         
@@ -1166,8 +1365,10 @@ impl IntensityOperatorDetector {
                 
                 operators.push(IntensityOperator {
                     operator_type: intensity_type.clone(),
+                    trigger: word_lower.clone(),
                     intensity_level: *level,
                     target_modification,
+                    spatial_position: trigger_position(&word_lower, db),
                 });
             }
         }
@@ -1200,8 +1401,10 @@ impl IntensityOperatorDetector {
             if let Some((intensity_type, base_strength)) = self.intensity_morphemes.get(&word_lower) {
                 operators.push(IntensityOperator {
                     operator_type: intensity_type.clone(),
+                    trigger: word_lower.clone(),
                     intensity_level: *base_strength,
                     target_modification: word.to_string(),
+                    spatial_position: None,
                 });
             }
         }
@@ -1225,7 +1428,7 @@ impl CertaintyOperatorDetector {
         Self { certainty_morphemes: morphemes }
     }
     
-    pub fn detect(&self, text: &str, _db: &LingoDatabase) -> Result<Vec<CertaintyOperator>, PluginError> {
+    pub fn detect(&self, text: &str, db: &LingoDatabase) -> Result<Vec<CertaintyOperator>, PluginError> {
         let mut operators = Vec::new();
         let words = preprocess_text(text);
         
@@ -1236,8 +1439,10 @@ impl CertaintyOperatorDetector {
                 
                 operators.push(CertaintyOperator {
                     operator_type: certainty_type.clone(),
+                    trigger: word_lower.clone(),
                     confidence_level: *level,
                     evidence_basis,
+                    spatial_position: trigger_position(&word_lower, db),
                 });
             }
         }
@@ -1269,8 +1474,10 @@ impl CertaintyOperatorDetector {
             if let Some((certainty_type, base_strength)) = self.certainty_morphemes.get(&word_lower) {
                 operators.push(CertaintyOperator {
                     operator_type: certainty_type.clone(),
+                    trigger: word_lower.clone(),
                     confidence_level: *base_strength,
                     evidence_basis: None,
+                    spatial_position: None,
                 });
             }
         }
@@ -1295,7 +1502,7 @@ impl ScopeOperatorDetector {
         Self { scope_morphemes: morphemes }
     }
     
-    pub fn detect(&self, text: &str, _db: &LingoDatabase) -> Result<Vec<ScopeOperator>, PluginError> {
+    pub fn detect(&self, text: &str, db: &LingoDatabase) -> Result<Vec<ScopeOperator>, PluginError> {
         let mut operators = Vec::new();
         let words = preprocess_text(text);
         
@@ -1306,8 +1513,10 @@ impl ScopeOperatorDetector {
                 
                 operators.push(ScopeOperator {
                     operator_type: scope_type.clone(),
+                    trigger: word_lower.clone(),
                     quantity_estimate: Some(*quantity),
                     scope_target,
+                    spatial_position: trigger_position(&word_lower, db),
                 });
             }
         }
@@ -1340,8 +1549,10 @@ impl ScopeOperatorDetector {
             if let Some((scope_type, base_strength)) = self.scope_morphemes.get(&word_lower) {
                 operators.push(ScopeOperator {
                     operator_type: scope_type.clone(),
+                    trigger: word_lower.clone(),
                     quantity_estimate: Some(*base_strength),
                     scope_target: word.to_string(),
+                    spatial_position: None,
                 });
             }
         }
@@ -1364,21 +1575,20 @@ impl SocialOperatorDetector {
         Self { social_morphemes: morphemes }
     }
     
-    pub fn detect(&self, text: &str, _db: &LingoDatabase) -> Result<Vec<SocialOperator>, PluginError> {
+    pub fn detect(&self, text: &str, db: &LingoDatabase) -> Result<Vec<SocialOperator>, PluginError> {
         let mut operators = Vec::new();
-        let text_lower = text.to_lowercase();
         
-        // Check for multi-word social phrases first
-        for (phrase, (social_type, politeness)) in &self.social_morphemes {
-            if text_lower.contains(phrase) {
-                let formality = self.calculate_formality_level(text, phrase);
-                
-                operators.push(SocialOperator {
-                    operator_type: social_type.clone(),
-                    politeness_level: *politeness,
-                    relationship_formality: formality,
-                });
-            }
+        // Phrases span several words ("thank you"), so match n-grams
+        for (phrase, (social_type, politeness)) in matched_ngrams(text, &self.social_morphemes) {
+            let formality = self.calculate_formality_level(text, phrase);
+            
+            operators.push(SocialOperator {
+                operator_type: social_type.clone(),
+                trigger: phrase.to_string(),
+                politeness_level: *politeness,
+                relationship_formality: formality,
+                spatial_position: trigger_position(phrase, db),
+            });
         }
         
         Ok(operators)
@@ -1402,21 +1612,15 @@ impl SocialOperatorDetector {
     
     /// Simple detection without database dependency
     pub fn detect_simple(&self, text: &str) -> Vec<SocialOperator> {
-        let mut operators = Vec::new();
-        let words = preprocess_text(text);
-        
-        for word in words.split_whitespace() {
-            let word_lower = word.to_lowercase().trim_matches(|c: char| c.is_ascii_punctuation()).to_string();
-            if let Some((social_type, base_strength)) = self.social_morphemes.get(&word_lower) {
-                operators.push(SocialOperator {
-                    operator_type: social_type.clone(),
-                    politeness_level: *base_strength,
-                    relationship_formality: 0.5,
-                });
-            }
-        }
-        
-        operators
+        matched_ngrams(text, &self.social_morphemes).into_iter()
+            .map(|(phrase, (social_type, base_strength))| SocialOperator {
+                operator_type: social_type.clone(),
+                trigger: phrase.to_string(),
+                politeness_level: *base_strength,
+                relationship_formality: 0.5,
+                spatial_position: None,
+            })
+            .collect()
     }
 }
 
@@ -1425,8 +1629,10 @@ impl Default for ModalOperator {
     fn default() -> Self {
         Self {
             operator_type: ModalType::CAN,
+            trigger: String::new(),
             strength: 0.5,
             scope: ModalScope::Full,
+            spatial_position: None,
         }
     }
 }
@@ -1435,8 +1641,10 @@ impl Default for TemporalOperator {
     fn default() -> Self {
         Self {
             operator_type: TemporalType::NOW,
+            trigger: String::new(),
             urgency_level: 0.5,
             temporal_scope: TemporalScope::Immediate,
+            spatial_position: None,
         }
     }
 }
@@ -1445,8 +1653,10 @@ impl Default for ConditionalOperator {
     fn default() -> Self {
         Self {
             operator_type: ConditionalType::IF,
+            trigger: String::new(),
             condition_strength: 0.5,
             condition_content: String::new(),
+            spatial_position: None,
         }
     }
 }
@@ -1455,8 +1665,10 @@ impl Default for NegationOperator {
     fn default() -> Self {
         Self {
             operator_type: NegationType::NOT,
+            trigger: String::new(),
             negation_scope: Vec::new(),
             negation_strength: 0.5,
+            spatial_position: None,
         }
     }
 }
@@ -1465,8 +1677,10 @@ impl Default for IntensityOperator {
     fn default() -> Self {
         Self {
             operator_type: IntensityType::VERY,
+            trigger: String::new(),
             intensity_level: 0.5,
             target_modification: String::new(),
+            spatial_position: None,
         }
     }
 }
@@ -1475,8 +1689,10 @@ impl Default for CertaintyOperator {
     fn default() -> Self {
         Self {
             operator_type: CertaintyType::MAYBE,
+            trigger: String::new(),
             confidence_level: 0.5,
             evidence_basis: None,
+            spatial_position: None,
         }
     }
 }
@@ -1485,8 +1701,10 @@ impl Default for ScopeOperator {
     fn default() -> Self {
         Self {
             operator_type: ScopeType::SOME,
+            trigger: String::new(),
             quantity_estimate: Some(0.5),
             scope_target: String::new(),
+            spatial_position: None,
         }
     }
 }
@@ -1495,8 +1713,10 @@ impl Default for SocialOperator {
     fn default() -> Self {
         Self {
             operator_type: SocialType::PLEASE,
+            trigger: String::new(),
             politeness_level: 0.5,
             relationship_formality: 0.5,
+            spatial_position: None,
         }
     }
 }
@@ -1528,6 +1748,118 @@ mod tests {
         assert!(detector.directional_morphemes.contains_key("with"));
     }
     
+    #[test]
+    fn test_active_operator_types_with_triggers() {
+        let text = "please help me now";
+        let suite = OperatorDetectorSuite::new();
+        let engine = IntentCompositionEngine::new();
+        
+        let function = FunctionalPrimitive::Action {
+            verb: crate::core::LinguisticNode::new(
+                crate::core::NodeId(1),
+                crate::core::Layer::Words,
+                Coordinate3D::new(0.5, 0.5, 0.5),
+            ),
+            transformation_type: crate::plugins::function_extraction::ActionType::Communicative,
+            intensity: 0.5,
+            temporal_aspect: crate::plugins::function_extraction::TemporalAspect::Present,
+        };
+        let operators = suite.detect_all_operators_simple(text);
+        let triggers = operators.triggers();
+        let mut intent = engine.compose_intent(function, operators, 1.0, text).unwrap();
+        intent.operator_triggers = triggers;
+        
+        assert_eq!(intent.active_operator_types(), vec!["temporal", "social"]);
+        assert_eq!(intent.operator_triggers["social"], vec!["please".to_string()]);
+        assert_eq!(intent.operator_triggers["temporal"], vec!["now".to_string()]);
+        assert_eq!(intent.operator_triggers.len(), 2);
+    }
+    
+    #[test]
+    fn test_operators_carry_trigger_positions() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::open_database;
+        
+        let operators_at = |must: Coordinate3D, soon: Coordinate3D| {
+            let mut builder = DatabaseBuilder::new();
+            builder.add_node("must", Layer::Words, must).unwrap();
+            builder.add_node("soon", Layer::Words, soon).unwrap();
+            let (_dir, db) = open_database(&builder);
+            OperatorDetectorSuite::new().detect_all_operators("we must not ship soon", &db).unwrap()
+        };
+        
+        // "not" isn't in the database, so it has no position
+        let must = Coordinate3D::new(0.1, 0.2, 0.5);
+        let soon = Coordinate3D::new(0.9, 0.8, 0.5);
+        let spread_out = operators_at(must, soon);
+        assert_eq!(spread_out.modal[0].spatial_position, Some(must));
+        assert_eq!(spread_out.temporal[0].spatial_position, Some(soon));
+        assert_eq!(spread_out.negation[0].spatial_position, None);
+        assert_eq!(spread_out.positions(), vec![must, soon]);
+        
+        let simple = OperatorDetectorSuite::new().detect_all_operators_simple("we must not ship soon");
+        assert!(simple.positions().is_empty());
+        assert_eq!(simple.len(), 3);
+        
+        // Operators without positions still count towards compositional richness
+        let detector = IntentDetector::new();
+        assert!((detector.calculate_operator_coherence(&simple) - 0.88).abs() < 1e-6);
+        assert_eq!(detector.calculate_operator_coherence(&PragmaticOperators::new()), 1.0);
+        
+        // Triggers far apart are less coherent than the same triggers close together
+        let spread = detector.calculate_operator_coherence(&spread_out);
+        let clustered = detector.calculate_operator_coherence(
+            &operators_at(must, Coordinate3D::new(0.12, 0.2, 0.5))
+        );
+        assert!(spread < clustered, "{} should be below {}", spread, clustered);
+    }
+    
+    #[test]
+    fn test_prefix_negation_needs_a_known_stem() {
+        use crate::core::Layer;
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::open_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        for (i, word) in ["happy", "table", "the", "unit"].iter().enumerate() {
+            builder.add_node(word, Layer::Words, Coordinate3D::new(0.2 * i as f32, 0.5, 0.5)).unwrap();
+        }
+        let (_dir, db) = open_database(&builder);
+        let detector = NegationOperatorDetector::new();
+        
+        assert!(detector.detect("under the table", &db).unwrap().is_empty());
+        assert!(detector.detect("wait until the unit ships", &db).unwrap().is_empty());
+        
+        let operators = detector.detect("an unhappy customer", &db).unwrap();
+        assert_eq!(operators.len(), 1);
+        assert!(matches!(operators[0].operator_type, NegationType::UnPrefix));
+        assert_eq!(operators[0].negation_scope, vec!["unhappy".to_string()]);
+        
+        // Triggers come from the detected operators, prefix negations included
+        let operators = OperatorDetectorSuite::new().detect_all_operators("an unhappy customer", &db).unwrap();
+        assert_eq!(operators.triggers()["negation"], vec!["unhappy".to_string()]);
+        assert_eq!(operators.triggers().len(), 1);
+    }
+    
+    #[test]
+    fn test_multi_word_social_operators() {
+        let detector = SocialOperatorDetector::new();
+        
+        let operators = detector.detect_simple("Thank you for your time");
+        assert_eq!(operators.len(), 1);
+        assert!(matches!(operators[0].operator_type, SocialType::ThankYou));
+        
+        // Longest phrase wins, in order of appearance
+        let operators = detector.detect_simple("If you don't mind, could you please check");
+        let types: Vec<_> = operators.iter().map(|op| format!("{:?}", op.operator_type)).collect();
+        assert_eq!(types, vec!["IfYouDontMind", "COULD_YOU", "PLEASE"]);
+        
+        let suite = OperatorDetectorSuite::new();
+        let triggers = suite.detect_all_operators_simple("thank you").triggers();
+        assert_eq!(triggers["social"], vec!["thank you".to_string()]);
+    }
+    
     #[test]
     fn test_pragmatic_operators_creation() {
         let operators = PragmaticOperators::new();
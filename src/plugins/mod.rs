@@ -31,6 +31,7 @@ use std::sync::{Arc, RwLock};
 
 use crate::engine::LingoExecutor;
 use crate::core::{LinguisticNode, NodeId};
+use crate::query::QueryBuilder;
 use crate::storage::LingoDatabase;
 
 pub mod function_extraction;
@@ -56,9 +57,25 @@ pub trait Plugin: Send + Sync + Any {
     /// Dependencies on other plugins (by ID)
     fn dependencies(&self) -> Vec<&'static str> { Vec::new() }
     
+    /// Ordering among plugins that don't depend on each other.
+    ///
+    /// Higher priorities run first in pre- and post-processing; ties are
+    /// broken by ID. Dependency edges always take precedence: a plugin runs
+    /// after everything it depends on, whatever the priorities.
+    fn priority(&self) -> i32 { 0 }
+    
     /// Initialize the plugin with the database
     fn initialize(&mut self, database: &LingoDatabase) -> Result<(), PluginError>;
     
+    /// Initialize the plugin with a database it may keep a handle to.
+    ///
+    /// The registry calls this one. Plugins that run queries after
+    /// initialization should override it and hold on to `database` rather
+    /// than reopening the file; the default forwards to [`initialize`](Self::initialize).
+    fn initialize_shared(&mut self, database: &Arc<LingoDatabase>) -> Result<(), PluginError> {
+        self.initialize(database)
+    }
+    
     /// Process a query before it hits the core engine
     fn pre_process(&self, _input: &str, _context: &PluginContext) -> Result<Option<String>, PluginError> {
         Ok(None) // Default: no preprocessing
@@ -89,6 +106,8 @@ pub struct PluginContext {
     pub metadata: HashMap<String, String>,
     /// Plugin-specific data storage
     pub plugin_data: Arc<RwLock<HashMap<String, Box<dyn Any + Send + Sync>>>>,
+    /// Database the pipeline is running against, shared rather than reopened
+    pub database: Option<Arc<LingoDatabase>>,
 }
 
 impl PluginContext {
@@ -97,9 +116,16 @@ impl PluginContext {
             query_text,
             metadata: HashMap::new(),
             plugin_data: Arc::new(RwLock::new(HashMap::new())),
+            database: None,
         }
     }
     
+    /// Attach the shared database handle
+    pub fn with_database(mut self, database: Arc<LingoDatabase>) -> Self {
+        self.database = Some(database);
+        self
+    }
+    
     /// Store plugin-specific data
     pub fn set_data<T: Any + Send + Sync>(&self, key: String, value: T) -> Result<(), PluginError> {
         let mut data = self.plugin_data.write()
@@ -116,6 +142,48 @@ impl PluginContext {
             false
         }
     }
+    
+    /// Read back a copy of plugin-specific data.
+    ///
+    /// Returns `None` if nothing is stored under `key` or it was stored as a
+    /// different type. A lock poisoned by a panicking writer is still read.
+    pub fn get_data<T: Any + Clone>(&self, key: &str) -> Option<T> {
+        let data = self.plugin_data.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        data.get(key)?.downcast_ref::<T>().cloned()
+    }
+    
+    /// Custom results posted by plugins so far, in pipeline order
+    pub fn custom_results(&self) -> Vec<CustomResults> {
+        self.get_data(CUSTOM_RESULTS_KEY).unwrap_or_default()
+    }
+    
+    /// Append one plugin's custom results to the shared slot.
+    ///
+    /// A poisoned lock is recovered, as in [`get_data`](Self::get_data), so
+    /// one panicking plugin doesn't stop later ones posting results.
+    fn push_custom_results(&self, results: CustomResults) -> Result<(), PluginError> {
+        let mut data = self.plugin_data.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let slot = data.entry(CUSTOM_RESULTS_KEY.to_string())
+            .or_insert_with(|| Box::new(Vec::<CustomResults>::new()));
+        let list = slot.downcast_mut::<Vec<CustomResults>>()
+            .ok_or_else(|| PluginError::TypeMismatch(format!("'{}' holds another type", CUSTOM_RESULTS_KEY)))?;
+        list.push(results);
+        Ok(())
+    }
+}
+
+/// Context key under which the pipeline collects [`CustomResults`]
+pub const CUSTOM_RESULTS_KEY: &str = "custom_results";
+
+/// Structured output of a plugin that returned [`PluginResult::CustomResults`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomResults {
+    /// Plugin that produced the results
+    pub plugin_id: String,
+    /// Plugin-defined key/value results
+    pub data: HashMap<String, String>,
+    /// Plugin's confidence in the results, 0.0 to 1.0
+    pub confidence: f32,
 }
 
 /// Results that plugins can return
@@ -179,24 +247,57 @@ impl PluginPipeline {
         let database = self.database.as_ref()
             .ok_or_else(|| PluginError::NotInitialized("Database not set".to_string()))?;
         
-        self.registry.initialize_all(database.as_ref())
+        self.registry.initialize_all(database)
     }
     
-    /// Execute the full plugin pipeline
+    /// Execute the full plugin pipeline over nodes the caller already found
     pub fn execute_pipeline(&self, query: &str, nodes: Vec<LinguisticNode>) -> Result<PluginPipelineResult, PluginError> {
-        let context = PluginContext::new(query.to_string());
-        let mut current_query = query.to_string();
-        let mut current_nodes = nodes;
-        let mut enhancements = HashMap::new();
+        let context = self.context(query);
+        let query = self.pre_process(query, &context)?;
+        self.post_process(query, nodes, context)
+    }
+    
+    /// Run `query` through the pipeline end to end.
+    ///
+    /// Pre-processing runs first, then the (possibly rewritten) query is
+    /// executed as a plain `find` on `executor`, its nodes are loaded from the
+    /// executor's database, and post-processing runs on them. Use
+    /// [`execute_pipeline`](Self::execute_pipeline) to supply nodes yourself.
+    pub fn execute_with_executor(&self, executor: &mut LingoExecutor, query: &str) -> Result<PluginPipelineResult, PluginError> {
+        let context = self.context(query);
+        let query = self.pre_process(query, &context)?;
         
-        // Pre-processing phase
+        if executor.database().is_none() {
+            return Err(PluginError::NotInitialized("Executor has no database".to_string()));
+        }
+        let result = executor.execute(&QueryBuilder::find(&query).compile())
+            .map_err(|e| PluginError::QueryFailed(e.to_string()))?;
+        let database = executor.database()
+            .ok_or_else(|| PluginError::NotInitialized("Executor has no database".to_string()))?;
+        let nodes = result.nodes.as_slice().iter()
+            .map(|&id| database.get_node(id).copied())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| PluginError::QueryFailed(e.to_string()))?;
+        
+        self.post_process(query, nodes, context)
+    }
+    
+    /// Let each active plugin rewrite the query in turn
+    fn pre_process(&self, query: &str, context: &PluginContext) -> Result<String, PluginError> {
+        let mut current_query = query.to_string();
         for plugin in self.registry.get_active_plugins() {
-            if let Some(modified_query) = plugin.pre_process(&current_query, &context)? {
+            if let Some(modified_query) = plugin.pre_process(&current_query, context)? {
                 current_query = modified_query;
             }
         }
+        Ok(current_query)
+    }
+    
+    /// Let each active plugin enhance the result nodes in turn
+    fn post_process(&self, query: String, nodes: Vec<LinguisticNode>, context: PluginContext) -> Result<PluginPipelineResult, PluginError> {
+        let mut current_nodes = nodes;
+        let mut enhancements = HashMap::new();
         
-        // Post-processing phase
         for plugin in self.registry.get_active_plugins() {
             if let Some(result) = plugin.post_process(&current_nodes, &context)? {
                 match result {
@@ -205,8 +306,12 @@ impl PluginPipeline {
                         enhancements.extend(plugin_enhancements);
                     },
                     PluginResult::CustomResults { data, confidence } => {
-                        // Store custom results in context for other plugins
-                        context.set_data("custom_results".to_string(), format!("confidence: {}, data: {:?}", confidence, data))?;
+                        // Keep them structured so later plugins can read them back
+                        context.push_custom_results(CustomResults {
+                            plugin_id: plugin.id().to_string(),
+                            data,
+                            confidence,
+                        })?;
                     },
                     _ => {}, // Ignore other result types in post-processing
                 }
@@ -214,7 +319,7 @@ impl PluginPipeline {
         }
         
         Ok(PluginPipelineResult {
-            query: current_query,
+            query,
             nodes: current_nodes,
             enhancements,
             context,
@@ -223,7 +328,7 @@ impl PluginPipeline {
     
     /// Execute a plugin-specific command
     pub fn execute_command(&mut self, plugin_id: &str, command: &str, args: &[String]) -> Result<PluginResult, PluginError> {
-        let context = PluginContext::new(String::new());
+        let context = self.context("");
         self.registry.execute_command(plugin_id, command, args, &context)
     }
     
@@ -231,6 +336,26 @@ impl PluginPipeline {
     pub fn list_plugins(&self) -> Vec<PluginInfo> {
         self.registry.list_plugins()
     }
+    
+    /// Context for `query`, carrying the pipeline's database if it has one
+    fn context(&self, query: &str) -> PluginContext {
+        let context = PluginContext::new(query.to_string());
+        match &self.database {
+            Some(database) => context.with_database(Arc::clone(database)),
+            None => context,
+        }
+    }
+    
+    /// Report per-plugin health: initialization status, errors, and
+    /// whether dependencies and the backing database are available
+    pub fn diagnostics(&self) -> Vec<PluginDiagnostic> {
+        let database_loaded = self.database.is_some();
+        let mut diagnostics = self.registry.diagnostics();
+        for diagnostic in &mut diagnostics {
+            diagnostic.database_loaded = database_loaded;
+        }
+        diagnostics
+    }
 }
 
 /// Result of executing the plugin pipeline
@@ -252,12 +377,59 @@ pub struct PluginInfo {
     pub dependencies: Vec<String>,
 }
 
+/// Health report for a single plugin
+#[derive(Debug, Clone)]
+pub struct PluginDiagnostic {
+    /// Plugin identifier, as returned by `Plugin::id`
+    pub id: String,
+    /// Human-readable plugin name
+    pub name: String,
+    /// Plugin version string
+    pub version: String,
+    /// Whether the plugin initialized successfully and is active
+    pub initialized: bool,
+    /// Error from the last initialization attempt, if it failed
+    pub error: Option<String>,
+    /// IDs of the plugins this one depends on
+    pub dependencies: Vec<String>,
+    /// Dependencies that aren't active
+    pub unsatisfied_dependencies: Vec<String>,
+    /// Whether the pipeline has a database to initialize plugins with
+    pub database_loaded: bool,
+}
+
+impl PluginDiagnostic {
+    /// True when the plugin is active with no errors or missing dependencies
+    pub fn is_healthy(&self) -> bool {
+        self.initialized && self.error.is_none() && self.unsatisfied_dependencies.is_empty()
+    }
+}
+
 impl Default for PluginPipeline {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Open a private copy of `database` for a plugin's `initialize`.
+///
+/// Databases loaded with `from_bytes` or `from_static` have no file to
+/// reopen, so they are rejected with an error that points to
+/// [`Plugin::initialize_shared`].
+pub(crate) fn reopen_database(database: &LingoDatabase, plugin: &str) -> Result<LingoDatabase, PluginError> {
+    if database.path().as_os_str().is_empty() {
+        return Err(PluginError::InitializationFailed {
+            plugin: plugin.to_string(),
+            error: "database was not loaded from a file; use initialize_shared to pass it in".to_string(),
+        });
+    }
+    LingoDatabase::open(database.path())
+        .map_err(|e| PluginError::InitializationFailed {
+            plugin: plugin.to_string(),
+            error: format!("Failed to open database: {}", e),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -305,6 +477,99 @@ mod tests {
         assert_eq!(plugins[0].id, "test");
     }
     
+    struct FailingPlugin;
+    
+    impl Plugin for FailingPlugin {
+        fn id(&self) -> &'static str { "failing" }
+        fn name(&self) -> &'static str { "Failing Plugin" }
+        fn version(&self) -> &'static str { "0.1.0" }
+        
+        fn initialize(&mut self, _database: &LingoDatabase) -> Result<(), PluginError> {
+            Err(PluginError::NotInitialized("model file missing".to_string()))
+        }
+    }
+    
+    struct DependentPlugin;
+    
+    impl Plugin for DependentPlugin {
+        fn id(&self) -> &'static str { "dependent" }
+        fn name(&self) -> &'static str { "Dependent Plugin" }
+        fn version(&self) -> &'static str { "1.0.0" }
+        fn dependencies(&self) -> Vec<&'static str> { vec!["failing"] }
+        
+        fn initialize(&mut self, _database: &LingoDatabase) -> Result<(), PluginError> {
+            Ok(())
+        }
+    }
+    
+    #[test]
+    fn test_diagnostics_report_failed_plugin() {
+        use crate::core::{Layer, Coordinate3D};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("test", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5)).unwrap();
+        let (_dir, path) = build_database(&builder);
+        
+        let mut pipeline = PluginPipeline::new();
+        pipeline.register_plugin(Box::new(MockPlugin::new("healthy"))).unwrap();
+        pipeline.register_plugin(Box::new(FailingPlugin)).unwrap();
+        pipeline.register_plugin(Box::new(DependentPlugin)).unwrap();
+        pipeline.set_database(Arc::new(LingoDatabase::open(&path).unwrap()));
+        
+        assert!(matches!(
+            pipeline.initialize_plugins(),
+            Err(PluginError::InitializationFailed { ref plugin, .. }) if plugin == "failing"
+        ));
+        
+        let diagnostics = pipeline.diagnostics();
+        let by_id = |id: &str| diagnostics.iter().find(|d| d.id == id).unwrap();
+        
+        let healthy = by_id("healthy");
+        assert!(healthy.is_healthy());
+        assert!(healthy.database_loaded);
+        
+        let failing = by_id("failing");
+        assert!(!failing.initialized);
+        assert!(failing.error.as_deref().unwrap().contains("model file missing"));
+        
+        let dependent = by_id("dependent");
+        assert!(!dependent.initialized);
+        assert_eq!(dependent.unsatisfied_dependencies, vec!["failing".to_string()]);
+    }
+    
+    #[test]
+    fn test_execute_with_executor_runs_rewritten_query() {
+        use crate::core::{Layer, Coordinate3D};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("mock", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.5)).unwrap();
+        let enhanced = builder.add_node("enhanced", Layer::Words, Coordinate3D::new(0.8, 0.5, 0.5)).unwrap();
+        let (_dir, path) = build_database(&builder);
+        let database = Arc::new(LingoDatabase::open(&path).unwrap());
+        
+        let mut pipeline = PluginPipeline::new();
+        pipeline.register_plugin(Box::new(MockPlugin::new("rewriter"))).unwrap();
+        pipeline.set_database(Arc::clone(&database));
+        pipeline.initialize_plugins().unwrap();
+        
+        // The plugin rewrites "mock" before the query runs
+        let mut executor = LingoExecutor::from_shared(database);
+        let result = pipeline.execute_with_executor(&mut executor, "mock").unwrap();
+        assert_eq!(result.query, "enhanced");
+        let ids: Vec<NodeId> = result.nodes.iter().map(|node| node.id).collect();
+        assert_eq!(ids, vec![enhanced]);
+        assert!(result.context.database.is_some());
+        
+        assert!(matches!(
+            pipeline.execute_with_executor(&mut LingoExecutor::new(), "mock"),
+            Err(PluginError::NotInitialized(_))
+        ));
+    }
+    
     #[test]
     fn test_context_data_storage() {
         let context = PluginContext::new("test query".to_string());
@@ -315,5 +580,88 @@ mod tests {
         // Check data exists
         assert!(context.has_data("test_key"));
         assert!(!context.has_data("nonexistent_key"));
+        
+        // Read it back, typed
+        assert_eq!(context.get_data::<i32>("test_key"), Some(42));
+        assert_eq!(context.get_data::<String>("test_key"), None);
+        assert_eq!(context.get_data::<i32>("nonexistent_key"), None);
+    }
+    
+    struct ScoringPlugin;
+    
+    impl Plugin for ScoringPlugin {
+        fn id(&self) -> &'static str { "scoring" }
+        fn name(&self) -> &'static str { "Scoring Plugin" }
+        fn version(&self) -> &'static str { "1.0.0" }
+        
+        fn initialize(&mut self, _database: &LingoDatabase) -> Result<(), PluginError> {
+            Ok(())
+        }
+        
+        fn post_process(&self, results: &[LinguisticNode], _context: &PluginContext) -> Result<Option<PluginResult>, PluginError> {
+            let data = HashMap::from([("count".to_string(), results.len().to_string())]);
+            Ok(Some(PluginResult::CustomResults { data, confidence: 0.75 }))
+        }
+    }
+    
+    #[test]
+    fn test_custom_results_stay_structured() {
+        use crate::core::{Layer, Coordinate3D};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("test", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5)).unwrap();
+        let (_dir, path) = build_database(&builder);
+        
+        let mut pipeline = PluginPipeline::new();
+        pipeline.register_plugin(Box::new(ScoringPlugin)).unwrap();
+        pipeline.set_database(Arc::new(LingoDatabase::open(&path).unwrap()));
+        pipeline.initialize_plugins().unwrap();
+        
+        let nodes = vec![LinguisticNode::new(NodeId(1), Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5))];
+        let result = pipeline.execute_pipeline("test", nodes).unwrap();
+        
+        let custom = result.context.custom_results();
+        assert_eq!(custom.len(), 1);
+        assert_eq!(custom[0].plugin_id, "scoring");
+        assert_eq!(custom[0].confidence, 0.75);
+        assert_eq!(custom[0].data.get("count").map(String::as_str), Some("1"));
+    }
+    
+    #[test]
+    fn test_custom_results_survive_poisoned_lock() {
+        let context = PluginContext::new("test".to_string());
+        let data = Arc::clone(&context.plugin_data);
+        let _ = std::thread::spawn(move || {
+            let _guard = data.write().unwrap();
+            panic!("poison the lock");
+        }).join();
+        assert!(context.plugin_data.is_poisoned());
+        
+        let results = CustomResults { plugin_id: "scoring".to_string(), data: HashMap::new(), confidence: 0.5 };
+        context.push_custom_results(results.clone()).unwrap();
+        assert_eq!(context.custom_results(), vec![results]);
+    }
+    
+    #[test]
+    fn test_initialize_rejects_in_memory_database() {
+        use crate::core::{Layer, Coordinate3D};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::build_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("test", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5)).unwrap();
+        let (_dir, path) = build_database(&builder);
+        let database = Arc::new(LingoDatabase::from_bytes(std::fs::read(&path).unwrap()).unwrap());
+        
+        let plugins: [Box<dyn Plugin>; 2] = [Box::new(IntentDetector::new()), Box::new(FunctionExtractor::new())];
+        for mut plugin in plugins {
+            match plugin.initialize(&database) {
+                Err(PluginError::InitializationFailed { error, .. }) => assert!(error.contains("initialize_shared"), "{}", error),
+                other => panic!("expected InitializationFailed, got {:?}", other.err()),
+            }
+            assert!(plugin.initialize_shared(&database).is_ok());
+        }
     }
 }
\ No newline at end of file
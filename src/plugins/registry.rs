@@ -16,15 +16,18 @@
 
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::Arc;
 
 use crate::storage::LingoDatabase;
-use super::{Plugin, PluginContext, PluginResult, PluginInfo};
+use super::{Plugin, PluginContext, PluginResult, PluginInfo, PluginDiagnostic};
 
 /// Plugin registry manages all registered plugins
 pub struct PluginRegistry {
     plugins: HashMap<String, Box<dyn Plugin>>,
     active_plugins: Vec<String>,
     dependency_graph: HashMap<String, Vec<String>>,
+    /// Errors from the most recent initialization attempt, by plugin ID
+    init_errors: HashMap<String, PluginError>,
 }
 
 impl PluginRegistry {
@@ -33,11 +36,15 @@ impl PluginRegistry {
             plugins: HashMap::new(),
             active_plugins: Vec::new(),
             dependency_graph: HashMap::new(),
+            init_errors: HashMap::new(),
         }
     }
     
-    /// Register a new plugin
-    pub fn register(&mut self, mut plugin: Box<dyn Plugin>) -> Result<(), PluginError> {
+    /// Register a new plugin.
+    ///
+    /// Plugins may be registered in any order; declared dependencies are
+    /// checked, and cycles detected, by [`initialize_all`](Self::initialize_all).
+    pub fn register(&mut self, plugin: Box<dyn Plugin>) -> Result<(), PluginError> {
         let plugin_id = plugin.id().to_string();
         
         // Check for duplicate IDs
@@ -49,40 +56,77 @@ impl PluginRegistry {
         let dependencies = plugin.dependencies().iter().map(|s| s.to_string()).collect();
         self.dependency_graph.insert(plugin_id.clone(), dependencies);
         
-        // Validate dependencies exist
-        for dep in plugin.dependencies() {
-            if !self.plugins.contains_key(dep) && dep != &plugin_id {
-                return Err(PluginError::MissingDependency {
-                    plugin: plugin_id,
-                    dependency: dep.to_string(),
-                });
-            }
-        }
-        
-        // Check for circular dependencies
-        if self.has_circular_dependency(&plugin_id) {
-            return Err(PluginError::CircularDependency(plugin_id));
-        }
-        
         self.plugins.insert(plugin_id, plugin);
         Ok(())
     }
     
-    /// Initialize all plugins in dependency order
-    pub fn initialize_all(&mut self, database: &LingoDatabase) -> Result<(), PluginError> {
+    /// Initialize all plugins in dependency order.
+    ///
+    /// Fails before initializing anything if a declared dependency isn't
+    /// registered (`MissingDependency`) or the dependencies form a cycle
+    /// (`DependencyCycle`). Otherwise a plugin that fails doesn't stop the
+    /// others; plugins depending on it are skipped. Failures are kept for [`diagnostics`](Self::diagnostics)
+    /// and the first one is returned once every plugin has been tried.
+    pub fn initialize_all(&mut self, database: &Arc<LingoDatabase>) -> Result<(), PluginError> {
         let initialization_order = self.resolve_dependency_order()?;
+        self.init_errors.clear();
+        let mut first_error = None;
         
         for plugin_id in initialization_order {
-            if let Some(plugin) = self.plugins.get_mut(&plugin_id) {
-                plugin.initialize(database)?;
-                self.active_plugins.push(plugin_id);
+            if self.active_plugins.contains(&plugin_id) {
+                continue;
+            }
+            
+            let unsatisfied = self.unsatisfied_dependencies(&plugin_id);
+            let result = if !unsatisfied.is_empty() {
+                Err(PluginError::MissingDependency {
+                    plugin: plugin_id.clone(),
+                    dependency: unsatisfied.join(", "),
+                })
+            } else if let Some(plugin) = self.plugins.get_mut(&plugin_id) {
+                plugin.initialize_shared(database)
+            } else {
+                continue;
+            };
+            
+            match result {
+                Ok(()) => self.active_plugins.push(plugin_id),
+                Err(error) => {
+                    let failure = PluginError::InitializationFailed {
+                        plugin: plugin_id.clone(),
+                        error: error.to_string(),
+                    };
+                    first_error.get_or_insert_with(|| failure.clone());
+                    self.init_errors.insert(plugin_id, error);
+                }
             }
         }
         
-        Ok(())
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
     }
     
-    /// Get all active plugins in dependency order
+    /// Report the status of every registered plugin, sorted by ID
+    pub fn diagnostics(&self) -> Vec<PluginDiagnostic> {
+        let mut diagnostics: Vec<PluginDiagnostic> = self.plugins.iter()
+            .map(|(id, plugin)| PluginDiagnostic {
+                id: id.clone(),
+                name: plugin.name().to_string(),
+                version: plugin.version().to_string(),
+                initialized: self.active_plugins.contains(id),
+                error: self.init_errors.get(id).map(|e| e.to_string()),
+                dependencies: plugin.dependencies().iter().map(|s| s.to_string()).collect(),
+                unsatisfied_dependencies: self.unsatisfied_dependencies(id),
+                database_loaded: false,
+            })
+            .collect();
+        diagnostics.sort_by(|a, b| a.id.cmp(&b.id));
+        diagnostics
+    }
+    
+    /// Get all active plugins in dependency order, dependencies first
     pub fn get_active_plugins(&self) -> Vec<&dyn Plugin> {
         self.active_plugins.iter()
             .filter_map(|id| self.plugins.get(id).map(|p| p.as_ref()))
@@ -139,7 +183,7 @@ impl PluginRegistry {
     }
     
     /// Reload a plugin (unload then load)
-    pub fn reload_plugin(&mut self, plugin_id: &str, database: &LingoDatabase) -> Result<(), PluginError> {
+    pub fn reload_plugin(&mut self, plugin_id: &str, database: &Arc<LingoDatabase>) -> Result<(), PluginError> {
         // Store plugin data for reload
         let plugin = self.plugins.remove(plugin_id)
             .ok_or_else(|| PluginError::PluginNotFound(plugin_id.to_string()))?;
@@ -151,8 +195,9 @@ impl PluginRegistry {
         self.register(plugin)?;
         
         if let Some(plugin) = self.plugins.get_mut(plugin_id) {
-            plugin.initialize(database)?;
+            plugin.initialize_shared(database)?;
             self.active_plugins.push(plugin_id.to_string());
+            self.sort_active_plugins();
         }
         
         Ok(())
@@ -160,82 +205,91 @@ impl PluginRegistry {
     
     // Private helper methods
     
-    fn has_circular_dependency(&self, plugin_id: &str) -> bool {
-        let mut visited = std::collections::HashSet::new();
-        let mut rec_stack = std::collections::HashSet::new();
-        
-        self.has_cycle_util(plugin_id, &mut visited, &mut rec_stack)
+    /// Dependencies of `plugin_id` that aren't active
+    fn unsatisfied_dependencies(&self, plugin_id: &str) -> Vec<String> {
+        self.dependency_graph.get(plugin_id)
+            .map(|deps| deps.iter()
+                .filter(|dep| !self.active_plugins.contains(dep))
+                .cloned()
+                .collect())
+            .unwrap_or_default()
     }
     
-    fn has_cycle_util(
-        &self,
-        plugin_id: &str,
-        visited: &mut std::collections::HashSet<String>,
-        rec_stack: &mut std::collections::HashSet<String>,
-    ) -> bool {
-        visited.insert(plugin_id.to_string());
-        rec_stack.insert(plugin_id.to_string());
-        
-        if let Some(dependencies) = self.dependency_graph.get(plugin_id) {
-            for dep in dependencies {
-                if !visited.contains(dep) {
-                    if self.has_cycle_util(dep, visited, rec_stack) {
-                        return true;
-                    }
-                } else if rec_stack.contains(dep) {
-                    return true;
-                }
-            }
+    /// Keep `active_plugins` in dependency order after one is re-added
+    fn sort_active_plugins(&mut self) {
+        if let Ok(order) = self.resolve_dependency_order() {
+            self.active_plugins.sort_by_key(|id| order.iter().position(|o| o == id));
         }
-        
-        rec_stack.remove(plugin_id);
-        false
     }
     
+    /// All registered plugins, each after its dependencies.
+    ///
+    /// Among plugins whose dependencies are all placed, the highest
+    /// [`priority`](Plugin::priority) goes next, then the lowest ID, so the
+    /// order doesn't depend on registration order.
     fn resolve_dependency_order(&self) -> Result<Vec<String>, PluginError> {
-        let mut result = Vec::new();
-        let mut visited = std::collections::HashSet::new();
-        let mut temp_visited = std::collections::HashSet::new();
-        
-        // Sort plugins by ID to ensure deterministic order
         let mut plugin_ids: Vec<_> = self.plugins.keys().collect();
         plugin_ids.sort();
         
-        for plugin_id in plugin_ids {
-            if !visited.contains(plugin_id) {
-                self.topological_sort(plugin_id, &mut visited, &mut temp_visited, &mut result)?;
+        for plugin_id in &plugin_ids {
+            if let Some(dependencies) = self.dependency_graph.get(*plugin_id) {
+                if let Some(missing) = dependencies.iter().find(|dep| !self.plugins.contains_key(*dep)) {
+                    return Err(PluginError::MissingDependency {
+                        plugin: plugin_id.to_string(),
+                        dependency: missing.clone(),
+                    });
+                }
             }
         }
         
+        // Reject cycles first, so every plugin below eventually becomes ready
+        let mut visited = std::collections::HashSet::new();
+        let mut path = Vec::new();
+        for plugin_id in &plugin_ids {
+            self.check_cycles(plugin_id, &mut visited, &mut path)?;
+        }
+        
+        let mut result: Vec<String> = Vec::with_capacity(plugin_ids.len());
+        let mut pending: Vec<&String> = plugin_ids;
+        while !pending.is_empty() {
+            let next = pending.iter()
+                .enumerate()
+                .filter(|(_, id)| self.dependency_graph.get(id.as_str())
+                    .is_none_or(|deps| deps.iter().all(|dep| result.contains(dep))))
+                .min_by_key(|(_, id)| (std::cmp::Reverse(self.plugins[id.as_str()].priority()), id.as_str()))
+                .map(|(index, _)| index)
+                .expect("acyclic graph always has a ready plugin");
+            result.push(pending.remove(next).clone());
+        }
+        
         Ok(result)
     }
     
-    fn topological_sort(
+    /// Depth-first visit; `path` holds the plugins currently being visited
+    fn check_cycles(
         &self,
         plugin_id: &str,
         visited: &mut std::collections::HashSet<String>,
-        temp_visited: &mut std::collections::HashSet<String>,
-        result: &mut Vec<String>,
+        path: &mut Vec<String>,
     ) -> Result<(), PluginError> {
-        if temp_visited.contains(plugin_id) {
-            return Err(PluginError::CircularDependency(plugin_id.to_string()));
+        if let Some(start) = path.iter().position(|id| id == plugin_id) {
+            return Err(PluginError::DependencyCycle(path[start..].to_vec()));
         }
         
         if visited.contains(plugin_id) {
             return Ok(());
         }
         
-        temp_visited.insert(plugin_id.to_string());
+        path.push(plugin_id.to_string());
         
         if let Some(dependencies) = self.dependency_graph.get(plugin_id) {
             for dep in dependencies {
-                self.topological_sort(dep, visited, temp_visited, result)?;
+                self.check_cycles(dep, visited, path)?;
             }
         }
         
-        temp_visited.remove(plugin_id);
+        path.pop();
         visited.insert(plugin_id.to_string());
-        result.push(plugin_id.to_string());
         
         Ok(())
     }
@@ -266,8 +320,8 @@ pub enum PluginError {
     DuplicatePlugin(String),
     /// Required dependency is missing
     MissingDependency { plugin: String, dependency: String },
-    /// Circular dependency detected
-    CircularDependency(String),
+    /// Plugins whose dependencies form a cycle, in dependency order
+    DependencyCycle(Vec<String>),
     /// Plugin not found
     PluginNotFound(String),
     /// Plugin is not active
@@ -284,6 +338,8 @@ pub enum PluginError {
     InitializationFailed { plugin: String, error: String },
     /// Plugin cleanup failed
     CleanupFailed { plugin: String, error: String },
+    /// The core query behind a pipeline run failed
+    QueryFailed(String),
 }
 
 impl fmt::Display for PluginError {
@@ -295,8 +351,10 @@ impl fmt::Display for PluginError {
             PluginError::MissingDependency { plugin, dependency } => {
                 write!(f, "Plugin '{}' requires missing dependency '{}'", plugin, dependency)
             },
-            PluginError::CircularDependency(id) => {
-                write!(f, "Circular dependency detected involving plugin '{}'", id)
+            PluginError::DependencyCycle(ids) => match ids.first() {
+                // Close the loop back to where it started
+                Some(first) => write!(f, "Plugin dependency cycle: {} -> {}", ids.join(" -> "), first),
+                None => write!(f, "Plugin dependency cycle"),
             },
             PluginError::PluginNotFound(id) => {
                 write!(f, "Plugin '{}' not found", id)
@@ -322,6 +380,9 @@ impl fmt::Display for PluginError {
             PluginError::CleanupFailed { plugin, error } => {
                 write!(f, "Plugin '{}' cleanup failed: {}", plugin, error)
             },
+            PluginError::QueryFailed(msg) => {
+                write!(f, "Query failed: {}", msg)
+            },
         }
     }
 }
@@ -408,8 +469,118 @@ mod tests {
     fn test_missing_dependency_error() {
         let mut registry = PluginRegistry::new();
         
-        // Try to register B without A
-        let result = registry.register(Box::new(MockPluginB));
-        assert!(matches!(result, Err(PluginError::MissingDependency { .. })));
+        // B can be registered before A, but can't be initialized without it
+        assert!(registry.register(Box::new(MockPluginB)).is_ok());
+        let (_temp_dir, database) = test_database();
+        let result = registry.initialize_all(&database);
+        assert!(matches!(
+            result,
+            Err(PluginError::MissingDependency { ref plugin, ref dependency })
+                if plugin == "plugin_b" && dependency == "plugin_a"
+        ));
+        assert!(registry.get_active_plugins().is_empty());
+    }
+    
+    struct CyclePluginX;
+    struct CyclePluginY;
+    
+    impl Plugin for CyclePluginX {
+        fn id(&self) -> &'static str { "plugin_x" }
+        fn name(&self) -> &'static str { "Plugin X" }
+        fn version(&self) -> &'static str { "1.0.0" }
+        fn dependencies(&self) -> Vec<&'static str> { vec!["plugin_y"] }
+        fn initialize(&mut self, _database: &LingoDatabase) -> Result<(), PluginError> { Ok(()) }
+    }
+    
+    impl Plugin for CyclePluginY {
+        fn id(&self) -> &'static str { "plugin_y" }
+        fn name(&self) -> &'static str { "Plugin Y" }
+        fn version(&self) -> &'static str { "1.0.0" }
+        fn dependencies(&self) -> Vec<&'static str> { vec!["plugin_x"] }
+        fn initialize(&mut self, _database: &LingoDatabase) -> Result<(), PluginError> { Ok(()) }
+    }
+    
+    #[test]
+    fn test_dependency_cycle_error() {
+        let mut registry = PluginRegistry::new();
+        registry.register(Box::new(MockPluginA)).unwrap();
+        registry.register(Box::new(CyclePluginX)).unwrap();
+        registry.register(Box::new(CyclePluginY)).unwrap();
+        
+        let (_temp_dir, database) = test_database();
+        let error = registry.initialize_all(&database).unwrap_err();
+        match &error {
+            PluginError::DependencyCycle(ids) => assert_eq!(ids, &["plugin_x", "plugin_y"]),
+            other => panic!("expected a dependency cycle, got {:?}", other),
+        }
+        assert_eq!(error.to_string(), "Plugin dependency cycle: plugin_x -> plugin_y -> plugin_x");
+    }
+    
+    #[test]
+    fn test_initialize_in_dependency_order() {
+        let mut registry = PluginRegistry::new();
+        
+        // Registered dependents first
+        registry.register(Box::new(MockPluginC)).unwrap();
+        registry.register(Box::new(MockPluginB)).unwrap();
+        registry.register(Box::new(MockPluginA)).unwrap();
+        
+        let (_temp_dir, database) = test_database();
+        registry.initialize_all(&database).unwrap();
+        let active: Vec<_> = registry.get_active_plugins().iter().map(|p| p.id()).collect();
+        assert_eq!(active, vec!["plugin_a", "plugin_b", "plugin_c"]);
+        
+        // Reloading a dependency keeps it ahead of its dependents
+        registry.reload_plugin("plugin_a", &database).unwrap();
+        let active: Vec<_> = registry.get_active_plugins().iter().map(|p| p.id()).collect();
+        assert_eq!(active, vec!["plugin_a", "plugin_b", "plugin_c"]);
+    }
+    
+    /// Independent test plugin with a configurable priority
+    struct PriorityPlugin {
+        id: &'static str,
+        priority: i32,
+        dependencies: Vec<&'static str>,
+    }
+    
+    impl Plugin for PriorityPlugin {
+        fn id(&self) -> &'static str { self.id }
+        fn name(&self) -> &'static str { "Priority Plugin" }
+        fn version(&self) -> &'static str { "1.0.0" }
+        fn dependencies(&self) -> Vec<&'static str> { self.dependencies.clone() }
+        fn priority(&self) -> i32 { self.priority }
+        fn initialize(&mut self, _database: &LingoDatabase) -> Result<(), PluginError> { Ok(()) }
+    }
+    
+    #[test]
+    fn test_priority_orders_independent_plugins() {
+        let plugin = |id, priority, dependencies| Box::new(PriorityPlugin { id, priority, dependencies });
+        let mut registry = PluginRegistry::new();
+        
+        // "expand" depends on low-priority "lookup"; "sanitize" and "audit" are independent
+        registry.register(plugin("expand", 5, vec!["lookup"])).unwrap();
+        registry.register(plugin("audit", 0, vec![])).unwrap();
+        registry.register(plugin("sanitize", 10, vec![])).unwrap();
+        registry.register(plugin("lookup", -5, vec![])).unwrap();
+        registry.register(plugin("zeta", 0, vec![])).unwrap();
+        
+        let (_temp_dir, database) = test_database();
+        registry.initialize_all(&database).unwrap();
+        let active: Vec<_> = registry.get_active_plugins().iter().map(|p| p.id()).collect();
+        
+        // Priority first, then ID; "expand" still waits for its dependency
+        assert_eq!(active, vec!["sanitize", "audit", "zeta", "lookup", "expand"]);
+    }
+    
+    /// A one-node database in a temporary directory
+    fn test_database() -> (tempfile::TempDir, Arc<LingoDatabase>) {
+        use crate::core::{Coordinate3D, Layer};
+        use crate::storage::DatabaseBuilder;
+        use crate::test_support::open_database;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("test", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5)).unwrap();
+        let (temp_dir, database) = open_database(&builder);
+        (temp_dir, Arc::new(database))
     }
 }
\ No newline at end of file
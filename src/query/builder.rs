@@ -1,11 +1,31 @@
 //! Query builder with fluent API for constructing linguistic queries
 
-use crate::core::bytecode::{SlangOp, SlangInstruction};
-use crate::core::{Layer, ConnectionType};
-use crate::security::{validate_query, validate_limit};
-use crate::logging::{debug, trace};
+use crate::core::bytecode::{filter_kinds, sort_keys, SlangOp, SlangInstruction, ANY_CONNECTION_TYPE};
+use crate::core::{Layer, ConnectionType, SimilarityMetric};
+use crate::core::error::{LingoError, Result};
+use crate::security::{validate_query, validate_limit, validate_threshold};
+use crate::logging::{debug, trace, warn};
+use std::collections::HashMap;
 use std::fmt;
 
+/// Similarity threshold used by [`QueryBuilder::similar`].
+///
+/// Thresholds map to a search radius of `1.0 - threshold` in the unit cube,
+/// so the default finds nodes within 0.3 of the current ones.
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.7;
+
+/// Connections assumed per node when estimating traversal cost
+const ASSUMED_FAN_OUT: u64 = 16;
+
+/// Nodes assumed above or below each node when estimating layer moves
+const ASSUMED_LAYER_FAN_OUT: u64 = 4;
+
+/// Matches assumed per seed for a similarity search without a limit
+const ASSUMED_SIMILAR_NODES: u64 = 50;
+
+/// Nodes assumed inside a box when estimating `spatial_box`
+const ASSUMED_BOX_NODES: u64 = 1000;
+
 /// Represents a single operation in the query pipeline.
 ///
 /// Operations are the building blocks of queries, each performing a specific
@@ -14,17 +34,28 @@ use std::fmt;
 ///
 /// # Categories
 ///
-/// - **Loading**: `LoadNode`, `LoadNodeId` - Entry points into the database
+/// - **Loading**: `LoadNode`, `LoadNodeFolded`, `LoadNodeNormalized`, `LoadNodePhonetic`, `LoadPhrase`, `LoadNodeId`, `LoadNodeIds` - Entry points into the database
 /// - **Navigation**: `LayerUp`, `LayerDown`, `LayerSet` - Move between layers
-/// - **Discovery**: `FindSimilar`, `SpatialNeighbors` - Find related nodes
-/// - **Traversal**: `FollowConnection`, `FollowConnectionType` - Follow relationships
-/// - **Filtering**: `Filter`, `Sort`, `Limit`, `Deduplicate` - Refine results
+/// - **Discovery**: `FindSimilar`, `SpatialNeighbors`, `NearestK`, `SpatialBox`, `ReflectThrough` - Find related nodes
+/// - **Traversal**: `FollowConnection`, `FollowConnectionType`, `FollowConnectionWeighted`, `FollowConnectionsWhere`, `ToPhonemes` - Follow relationships
+/// - **Filtering**: `Filter`, `Sort`, `Limit`, `Offset`, `Deduplicate`, `Exclude`, `ExcludeNodes`, `ExcludeNear` - Refine results
+/// - **Combining**: `Union`, `Intersect`, `Difference` - Set algebra with another pipeline's results
 #[derive(Debug, Clone)]
 pub enum Operation {
     /// Load node by word
     LoadNode(String),
+    /// Load node by word, ignoring case and diacritics
+    LoadNodeFolded(String),
+    /// Load the single node the normalized index matches, ignoring case and diacritics
+    LoadNodeNormalized(String),
+    /// Load word nodes that sound like the given word
+    LoadNodePhonetic(String),
+    /// Load a phrase node, or what the phrase's words have in common
+    LoadPhrase(String),
     /// Load node by ID
     LoadNodeId(u32),
+    /// Load several nodes by ID
+    LoadNodeIds(Vec<crate::core::NodeId>),
     /// Move up N layers
     LayerUp(u8),
     /// Move down N layers
@@ -32,25 +63,81 @@ pub enum Operation {
     /// Set to specific layer
     LayerSet(Layer),
     /// Find similar nodes
-    FindSimilar { threshold: f32, limit: Option<usize> },
+    FindSimilar {
+        /// Minimum similarity, 0.0-1.0
+        threshold: f32,
+        /// Most similar nodes kept per source node, or `None` for all
+        limit: Option<usize>,
+        /// Rank more frequent nodes first among equally similar ones
+        prefer_frequent: bool,
+        /// How similarity between positions is measured
+        metric: SimilarityMetric,
+    },
     /// Find spatial neighbors
     SpatialNeighbors { radius: f32, layer_mask: Option<u8> },
     /// Follow connection by strength rank
     FollowConnection { strength_rank: u8 },
     /// Follow specific connection type
-    FollowConnectionType { connection_type: ConnectionType },
+    FollowConnectionType {
+        /// Type of connection to follow
+        connection_type: ConnectionType,
+    },
+    /// Follow strongest connection after scaling strength by type weight
+    FollowConnectionWeighted {
+        /// Multiplier per connection type; unlisted types weigh 1.0
+        weights: HashMap<ConnectionType, f32>,
+    },
+    /// Follow up to `max` connections per node that pass a type and strength test
+    FollowConnectionsWhere {
+        /// Type to follow, or `None` for any
+        connection_type: Option<ConnectionType>,
+        /// Weakest strength followed, 0.0-1.0
+        min_strength: f32,
+        /// Most connections followed from each node
+        max: usize,
+    },
     /// Filter results
     Filter(FilterCriteria),
     /// Sort results
-    Sort(SortCriteria),
+    Sort(SortCriteria, SortOrder),
     /// Limit result count
     Limit(usize),
+    /// Skip the first N results
+    Offset(usize),
     /// Remove duplicates
     Deduplicate,
+    /// Remove the nodes for a word
+    Exclude(String),
+    /// Remove nodes by ID
+    ExcludeNodes(Vec<crate::core::NodeId>),
+    /// Remove nodes within a radius of a word's nodes
+    ExcludeNear {
+        /// Word whose nodes are the exclusion centres
+        word: String,
+        /// Exclusion radius in coordinate space
+        radius: f32,
+    },
     /// Decompose word into morphemes (opposite of compose)
     Decompose,
+    /// Follow pronunciation connections into the Phonemes layer
+    ToPhonemes,
     /// Find nodes within radius of a specific 3D point
     SpatialRadiusFromPoint { center: crate::core::Coordinate3D, radius: f32 },
+    /// Reflect each node's position through a center point
+    ReflectThrough {
+        /// Point each position is reflected through
+        center: crate::core::Coordinate3D,
+    },
+    /// Load every node inside an axis-aligned box
+    SpatialBox(crate::core::BoundingBox3D),
+    /// Find the k spatially nearest nodes to each current node
+    NearestK(usize),
+    /// Add the results of another pipeline
+    Union(Vec<Operation>),
+    /// Keep the results also found by another pipeline
+    Intersect(Vec<Operation>),
+    /// Drop the results found by another pipeline
+    Difference(Vec<Operation>),
 }
 
 /// Defines criteria for filtering query results.
@@ -67,16 +154,23 @@ pub enum Operation {
 /// let query = QueryBuilder::find("analyze")
 ///     .similar()
 ///     .filter(FilterCriteria::Layer(Layer::Words))
-///     .filter(FilterCriteria::MinFrequency(1000))
+///     .filter(FilterCriteria::FrequencyRankBelow(1000))
 ///     .compile();
 /// ```
 #[derive(Debug, Clone)]
 pub enum FilterCriteria {
     /// Filter by layer
     Layer(Layer),
+    /// Keep nodes on any of the given layers
+    LayerIn(Vec<Layer>),
+    /// Keep nodes ranked more frequent than this rank (rank 1 is the most
+    /// frequent word); unranked nodes never pass
+    FrequencyRankBelow(u32),
+    /// Keep nodes whose productivity, in [0, 1], is above this
+    ProductivityAbove(f32),
     /// Filter by minimum frequency
     MinFrequency(u32),
-    /// Filter by etymology
+    /// Keep nodes of this etymological origin
     Etymology(crate::core::EtymologyOrigin),
     /// Filter by flags
     HasFlags(crate::core::NodeFlags),
@@ -84,22 +178,73 @@ pub enum FilterCriteria {
 
 /// Defines how query results should be sorted.
 ///
-/// Sorting is applied as a post-processing step after all other operations.
-/// The sort order depends on the criteria:
+/// A sort reorders the current result set where it appears in the query,
+/// so later steps such as `offset` and `limit` see the sorted order. Ties
+/// always break by ascending node ID, so the same query against the same
+/// database returns the same order every time.
+///
+/// [`QueryBuilder::sort`] uses each criterion's natural order:
 /// - `Frequency`: Descending (most frequent first)
 /// - `Distance`: Ascending (closest first)
 /// - `ConnectionStrength`: Descending (strongest first)
 /// - `Alphabetical`: Ascending (A to Z)
-#[derive(Debug, Clone)]
+/// - `Layer`: Ascending (`Letters` first)
+/// - `Etymology`: Ascending (in [`EtymologyOrigin`](crate::core::EtymologyOrigin) order)
+/// - `NodeId`: Ascending
+///
+/// [`QueryBuilder::sort_by`] picks the order explicitly.
+///
+/// Similarity searches already return each seed's matches most similar
+/// first; sorting by `Distance` merges the matches of several seeds into
+/// one ranking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortCriteria {
     /// Sort by frequency (descending)
     Frequency,
-    /// Sort by similarity/distance
+    /// Sort by distance from the seed of the last similarity search.
+    /// Nodes that search didn't score count as farthest
     Distance,
-    /// Sort by connection strength
+    /// Sort by the strength of each node's strongest outgoing connection
     ConnectionStrength,
     /// Sort alphabetically
     Alphabetical,
+    /// Sort by layer
+    Layer,
+    /// Sort by etymology origin
+    Etymology,
+    /// Sort by node ID
+    NodeId,
+}
+
+impl SortCriteria {
+    /// The order [`QueryBuilder::sort`] uses for this criterion
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            SortCriteria::Frequency | SortCriteria::ConnectionStrength => SortOrder::Descending,
+            _ => SortOrder::Ascending,
+        }
+    }
+    
+    fn sort_key(self) -> u16 {
+        match self {
+            SortCriteria::NodeId => sort_keys::NODE_ID,
+            SortCriteria::Distance => sort_keys::DISTANCE,
+            SortCriteria::Frequency => sort_keys::FREQUENCY,
+            SortCriteria::Layer => sort_keys::LAYER,
+            SortCriteria::Etymology => sort_keys::ETYMOLOGY,
+            SortCriteria::Alphabetical => sort_keys::ALPHABETICAL,
+            SortCriteria::ConnectionStrength => sort_keys::CONNECTION_STRENGTH,
+        }
+    }
+}
+
+/// Direction of a sort
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Smallest first: closest, least frequent, A to Z
+    Ascending,
+    /// Largest first: farthest, most frequent, Z to A
+    Descending,
 }
 
 /// Provides hints to the query compiler for optimization.
@@ -107,7 +252,7 @@ pub enum SortCriteria {
 /// These hints help the compiler generate more efficient bytecode and
 /// ensure required indices are available at execution time. They are
 /// automatically updated as operations are added to the query.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct OptimizationHints {
     /// Query needs spatial index
     pub needs_spatial_index: bool,
@@ -117,6 +262,8 @@ pub struct OptimizationHints {
     pub needs_connection_index: bool,
     /// Estimated result size
     pub estimated_results: Option<usize>,
+    /// Skip the executor's default result limit
+    pub unbounded: bool,
 }
 
 /// A fluent interface for building linguistic queries.
@@ -175,7 +322,10 @@ pub struct OptimizationHints {
 /// This ahead-of-time compilation ensures optimal performance during execution.
 /// The builder tracks which indices will be needed and includes this information
 /// in the compiled query.
-#[derive(Debug)]
+///
+/// Builders are cheap to clone, so a chain can be kept as a template and
+/// reused with different seed words via [`with_find`](Self::with_find).
+#[derive(Debug, Clone)]
 pub struct QueryBuilder {
     /// Operations to perform
     operations: Vec<Operation>,
@@ -206,19 +356,8 @@ impl QueryBuilder {
     /// let query = QueryBuilder::find("run").compile();
     /// ```
     pub fn find(word: &str) -> Self {
-        // Validate the query string (will panic if invalid for now)
-        // In production, this should return Result<Self>
-        if let Err(e) = validate_query(word) {
-            // For now, we'll use a safe default
-            eprintln!("Warning: Invalid query '{}': {}", word, e);
-            return Self {
-                operations: vec![Operation::LoadNode(String::new())],
-                hints: OptimizationHints::default(),
-            };
-        }
-        
         Self {
-            operations: vec![Operation::LoadNode(word.to_string())],
+            operations: vec![Operation::LoadNode(checked_word(word))],
             hints: OptimizationHints::default(),
         }
     }
@@ -253,6 +392,120 @@ impl QueryBuilder {
         }
     }
     
+    /// Creates a new query starting with words that sound like `word`.
+    ///
+    /// Spatial similarity follows meaning, not sound. This lookup instead
+    /// compares phonetic keys (see [`phonetic_key`](crate::core::phonetic_key)),
+    /// so a misspelling such as "fone" still finds "phone". Only nodes on
+    /// the Words layer are matched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// // Finds "phone", and anything else pronounced the same way
+    /// let query = QueryBuilder::sounds_like("fone").compile();
+    /// ```
+    pub fn sounds_like(word: &str) -> Self {
+        let mut query = Self::find(word);
+        if let Operation::LoadNode(word) = &mut query.operations[0] {
+            query.operations[0] = Operation::LoadNodePhonetic(std::mem::take(word));
+        }
+        query
+    }
+    
+    /// Creates a new query starting with a multi-word phrase.
+    ///
+    /// [`find`](Self::find) looks the whole input up as one surface form.
+    /// This splits it into words first (see
+    /// [`phrase_tokens`](crate::core::phrase_tokens)) and matches in two
+    /// steps, ignoring case and diacritics:
+    ///
+    /// 1. Phrases-layer nodes spelled like the whole n-gram, such as
+    ///    "machine learning". If there are any, they are the result.
+    /// 2. Otherwise each word is looked up on its own and the result is
+    ///    what every word's neighborhood shares: the word's nodes plus the
+    ///    nodes they connect to or are connected from, in either direction.
+    ///    Words that are linked to each other are part of the result
+    ///    themselves. A single word gives just its own nodes, and a word
+    ///    that isn't in the database gives an empty result.
+    ///
+    /// Stopwords ("the", "of", "and"... see
+    /// [`is_stopword`](crate::core::is_stopword)) count towards the n-gram
+    /// in step 1, so "state of the art" still finds its phrase node, but are
+    /// skipped in step 2, unless the phrase has no other words.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// let query = QueryBuilder::find_phrase("machine learning")
+    ///     .similar()
+    ///     .limit(10)
+    ///     .compile();
+    /// ```
+    pub fn find_phrase(text: &str) -> Self {
+        let mut query = Self {
+            operations: vec![Operation::LoadPhrase(checked_word(text))],
+            hints: OptimizationHints::default(),
+        };
+        // The word-by-word fallback walks connections
+        query.hints.needs_connection_index = true;
+        query
+    }
+    
+    /// Returns a copy of this query with its seed replaced by a word lookup.
+    ///
+    /// Every operation after the seed is kept, so a template chain can be
+    /// built once and reused for many words. The word is validated the same
+    /// way as in [`find`](Self::find).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// let template = QueryBuilder::find("").similar().layer_up().limit(5);
+    ///
+    /// for word in ["happy", "sad"] {
+    ///     let query = template.with_find(word).compile();
+    ///     assert_eq!(query.string_cache, vec![word.to_string()]);
+    /// }
+    /// ```
+    pub fn with_find(&self, word: &str) -> Self {
+        let mut query = self.clone();
+        let seed = Self::find(word).operations.remove(0);
+        match query.operations.first_mut() {
+            Some(Operation::LoadNodeFolded(_)) => {
+                if let Operation::LoadNode(word) = seed {
+                    query.operations[0] = Operation::LoadNodeFolded(word);
+                }
+            }
+            Some(Operation::LoadNodeNormalized(_)) => {
+                if let Operation::LoadNode(word) = seed {
+                    query.operations[0] = Operation::LoadNodeNormalized(word);
+                }
+            }
+            Some(Operation::LoadNodePhonetic(_)) => {
+                if let Operation::LoadNode(word) = seed {
+                    query.operations[0] = Operation::LoadNodePhonetic(word);
+                }
+            }
+            Some(Operation::LoadPhrase(_)) => {
+                if let Operation::LoadNode(word) = seed {
+                    query.operations[0] = Operation::LoadPhrase(word);
+                }
+            }
+            Some(Operation::LoadNode(_))
+            | Some(Operation::LoadNodeId(_))
+            | Some(Operation::LoadNodeIds(_)) => query.operations[0] = seed,
+            _ => query.operations.insert(0, seed),
+        }
+        query
+    }
+    
     /// Creates a new query starting with a specific node ID.
     /// 
     /// Alias for find_by_id for more intuitive API when loading known nodes.
@@ -279,10 +532,66 @@ impl QueryBuilder {
         Self::find_by_id(node_id.0)
     }
     
+    /// Creates a new query starting from a node you already have.
+    ///
+    /// Skips the string lookup, so results of an earlier query can be
+    /// navigated further even when they have no clean surface form.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::{QueryBuilder, core::NodeId};
+    ///
+    /// let query = QueryBuilder::from_node(NodeId(42))
+    ///     .similar()
+    ///     .compile();
+    /// ```
+    pub fn from_node(node_id: crate::core::NodeId) -> Self {
+        Self::find_by_id(node_id.0)
+    }
+    
+    /// Creates a new query starting from several known nodes.
+    ///
+    /// All IDs are loaded as one set, in the given order with duplicates
+    /// removed, which suits paging through or chaining on an earlier
+    /// result. Each ID must exist when the query runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LingoError::QueryCompilation` if `node_ids` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::{QueryBuilder, core::NodeId};
+    ///
+    /// let query = QueryBuilder::from_nodes([NodeId(1), NodeId(2)])?
+    ///     .layer_up()
+    ///     .compile();
+    ///
+    /// assert!(QueryBuilder::from_nodes([]).is_err());
+    /// # Ok::<(), lingo::core::error::LingoError>(())
+    /// ```
+    pub fn from_nodes(node_ids: impl IntoIterator<Item = crate::core::NodeId>) -> Result<Self> {
+        let node_ids: Vec<_> = node_ids.into_iter().collect();
+        if node_ids.is_empty() {
+            return Err(LingoError::QueryCompilation(
+                "from_nodes needs at least one node ID".to_string()
+            ));
+        }
+        
+        Ok(Self {
+            operations: vec![Operation::LoadNodeIds(node_ids)],
+            hints: OptimizationHints::default(),
+        })
+    }
+    
     /// Finds nodes similar to the current result set.
     ///
-    /// Uses spatial proximity in 3D space to find semantically related nodes.
-    /// The default similarity threshold is 0.7 (70% similarity).
+    /// Uses spatial proximity in 3D space to find semantically related nodes,
+    /// with a threshold of [`DEFAULT_SIMILARITY_THRESHOLD`] (0.7, i.e. within
+    /// a distance of 0.3). Equivalent to
+    /// `similar_threshold(DEFAULT_SIMILARITY_THRESHOLD)`.
     ///
     /// # Returns
     ///
@@ -297,13 +606,8 @@ impl QueryBuilder {
     ///     .similar()  // Finds "joyful", "cheerful", etc.
     ///     .compile();
     /// ```
-    pub fn similar(mut self) -> Self {
-        self.operations.push(Operation::FindSimilar {
-            threshold: 0.7,
-            limit: None,
-        });
-        self.hints.needs_spatial_index = true;
-        self
+    pub fn similar(self) -> Self {
+        self.similar_threshold(DEFAULT_SIMILARITY_THRESHOLD)
     }
     
     /// Finds similar nodes with a custom similarity threshold.
@@ -316,6 +620,11 @@ impl QueryBuilder {
     ///   - 0.7 = Moderately similar (default)
     ///   - 0.5 = Loosely related
     ///
+    /// Thresholds outside [0, 1] (or NaN) are replaced by
+    /// [`DEFAULT_SIMILARITY_THRESHOLD`] with a warning; use
+    /// [`try_similar_threshold`](Self::try_similar_threshold) to reject them
+    /// instead.
+    ///
     /// # Returns
     ///
     /// Self for method chaining.
@@ -330,15 +639,152 @@ impl QueryBuilder {
     ///     .similar_threshold(0.9)
     ///     .compile();
     /// ```
-    pub fn similar_threshold(mut self, threshold: f32) -> Self {
+    pub fn similar_threshold(self, threshold: f32) -> Self {
+        match validate_threshold(threshold) {
+            Ok(threshold) => self.push_similar(threshold),
+            Err(e) => {
+                warn!(threshold, error = %e, "Invalid similarity threshold; using the default");
+                self.push_similar(DEFAULT_SIMILARITY_THRESHOLD)
+            }
+        }
+    }
+    
+    /// Finds similar nodes with a custom threshold, rejecting thresholds
+    /// outside [0, 1].
+    ///
+    /// # Errors
+    ///
+    /// Returns `LingoError::SecurityError` if `threshold` is out of range or NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// assert!(QueryBuilder::find("algorithm").try_similar_threshold(0.9).is_ok());
+    /// assert!(QueryBuilder::find("algorithm").try_similar_threshold(1.5).is_err());
+    /// ```
+    pub fn try_similar_threshold(self, threshold: f32) -> Result<Self> {
+        Ok(self.push_similar(validate_threshold(threshold)?))
+    }
+    
+    /// Finds similar nodes, measuring distance with `metric` instead of
+    /// plain Euclidean distance.
+    ///
+    /// Thresholds are validated as in
+    /// [`similar_threshold`](Self::similar_threshold).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    /// use lingo::core::SimilarityMetric;
+    ///
+    /// // Neighbours along the phonetic axis, regardless of etymology
+    /// let query = QueryBuilder::find("begin")
+    ///     .similar_with_metric(0.9, SimilarityMetric::SemanticOnly)
+    ///     .compile();
+    /// ```
+    pub fn similar_with_metric(self, threshold: f32, metric: SimilarityMetric) -> Self {
+        let threshold = validate_threshold(threshold).unwrap_or_else(|e| {
+            warn!(threshold, error = %e, "Invalid similarity threshold; using the default");
+            DEFAULT_SIMILARITY_THRESHOLD
+        });
+        self.push_similar_by(threshold, metric)
+    }
+    
+    /// Append a similarity search with an already-validated threshold
+    fn push_similar(self, threshold: f32) -> Self {
+        self.push_similar_by(threshold, SimilarityMetric::Euclidean)
+    }
+    
+    /// Append a similarity search under `metric`
+    fn push_similar_by(mut self, threshold: f32, metric: SimilarityMetric) -> Self {
         self.operations.push(Operation::FindSimilar {
             threshold,
             limit: None,
+            prefer_frequent: false,
+            metric,
         });
         self.hints.needs_spatial_index = true;
         self
     }
     
+    /// Makes word lookups in this query ignore case and diacritics.
+    ///
+    /// With folding, "cafe" finds "Café" and "naive" finds "Naïve". Exact
+    /// matching remains the default; folded lookups use an index the
+    /// database builds on first use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// let query = QueryBuilder::find("cafe")
+    ///     .fold_accents()  // also matches "café"
+    ///     .compile();
+    /// ```
+    pub fn fold_accents(mut self) -> Self {
+        for op in &mut self.operations {
+            if let Operation::LoadNode(word) = op {
+                *op = Operation::LoadNodeFolded(std::mem::take(word));
+            }
+        }
+        self
+    }
+    
+    /// Makes word lookups resolve through the database's normalized index.
+    ///
+    /// Like [`fold_accents`](Self::fold_accents), case and diacritics are
+    /// ignored, but each lookup yields at most one node: the one
+    /// [`find_normalized`](crate::storage::MemoryMappedDatabase::find_normalized)
+    /// returns, which is the matching word added first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// let query = QueryBuilder::find("Cafe").fuzzy_ascii().compile();
+    /// ```
+    pub fn fuzzy_ascii(mut self) -> Self {
+        for op in &mut self.operations {
+            if let Operation::LoadNode(word) = op {
+                *op = Operation::LoadNodeNormalized(std::mem::take(word));
+            }
+        }
+        self
+    }
+    
+    /// Breaks near-ties in the preceding similarity search by word frequency.
+    ///
+    /// Candidates whose distances are within a small tolerance of each
+    /// other are reordered so the more frequent word (lower
+    /// `frequency_rank`) comes first. Has no effect if no `similar` step
+    /// precedes it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// let query = QueryBuilder::find("big")
+    ///     .similar()
+    ///     .prefer_frequent()  // "large" before "sizeable"
+    ///     .compile();
+    /// ```
+    pub fn prefer_frequent(mut self) -> Self {
+        let last_similar = self.operations.iter_mut().rev().find_map(|op| match op {
+            Operation::FindSimilar { prefer_frequent, .. } => Some(prefer_frequent),
+            _ => None,
+        });
+        if let Some(prefer_frequent) = last_similar {
+            *prefer_frequent = true;
+        }
+        self
+    }
+    
     /// Finds all nodes within a spatial radius of the current results.
     ///
     /// This performs a 3D spatial search, finding nodes whose Euclidean
@@ -372,6 +818,28 @@ impl QueryBuilder {
         self
     }
     
+    /// Finds the `k` nodes closest to each node in the current results.
+    ///
+    /// Unlike [`spatial_neighbors`](Self::spatial_neighbors) there is no
+    /// radius to tune: sparse regions still return `k` nodes and dense ones
+    /// no more than `k`. The starting nodes themselves are not counted.
+    /// Results for each node are ordered nearest first, with equal
+    /// distances ordered by node ID.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// // The five words nearest to "ocean", however far away they are
+    /// let query = QueryBuilder::find("ocean").nearest_k(5).compile();
+    /// ```
+    pub fn nearest_k(mut self, k: usize) -> Self {
+        self.operations.push(Operation::NearestK(k));
+        self.hints.needs_spatial_index = true;
+        self
+    }
+    
     /// Find nodes within a radius of a specific 3D coordinate point.
     ///
     /// This is useful for finding words with specific semantic properties based on 
@@ -405,6 +873,71 @@ impl QueryBuilder {
         }
     }
     
+    /// Find every node whose position lies inside an axis-aligned box.
+    ///
+    /// Unlike [`spatial_radius_from_point`](Self::spatial_radius_from_point),
+    /// each axis gets its own range, so a box can select a layer's z band and
+    /// a slice of x at once. Bounds are inclusive. A box with `min` greater
+    /// than `max` on any axis matches nothing. Chain [`layer`](Self::layer)
+    /// or [`limit`](Self::limit) to narrow the result.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::{QueryBuilder, Coordinate3D};
+    /// use lingo::core::{BoundingBox3D, Layer};
+    ///
+    /// // Morphemes with x between 0.4 and 0.6
+    /// let (z_min, z_max) = Layer::Morphemes.z_range();
+    /// let region = BoundingBox3D::new(
+    ///     Coordinate3D::new(0.4, 0.0, z_min),
+    ///     Coordinate3D::new(0.6, 1.0, z_max),
+    /// );
+    /// let query = QueryBuilder::spatial_box(region).limit(50).compile();
+    /// ```
+    pub fn spatial_box(bounds: crate::core::BoundingBox3D) -> Self {
+        Self {
+            operations: vec![Operation::SpatialBox(bounds)],
+            hints: OptimizationHints {
+                needs_spatial_index: true,
+                ..Default::default()
+            },
+        }
+    }
+    
+    /// Reflects each node through a center point and returns the nearest node there.
+    ///
+    /// For every node in the current result set, the position is mirrored
+    /// through `center` (`2 * center - position`) and the closest node to the
+    /// reflected point is looked up in the spatial index. This gives
+    /// oppositional reasoning a spatial grounding: reflecting through the
+    /// center of a layer lands near the node's positional "antonym".
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - The point to reflect through
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::{QueryBuilder, Coordinate3D};
+    /// use lingo::core::Layer;
+    ///
+    /// let center = Coordinate3D::new(0.5, 0.5, Layer::Words.z_center());
+    /// let query = QueryBuilder::find("hot")
+    ///     .reflect_through(center)
+    ///     .compile();
+    /// ```
+    pub fn reflect_through(mut self, center: crate::core::Coordinate3D) -> Self {
+        self.operations.push(Operation::ReflectThrough { center });
+        self.hints.needs_spatial_index = true;
+        self
+    }
+    
     /// Moves up one layer in the linguistic hierarchy.
     ///
     /// This follows vertical connections to parent nodes in the layer above.
@@ -534,7 +1067,9 @@ impl QueryBuilder {
     /// Follows connections of a specific type.
     ///
     /// This allows precise traversal of the semantic graph by following
-    /// only connections of the specified type.
+    /// only connections of the specified type. Every matching edge is
+    /// followed, strongest first, so `ConnectionType::Synonymy` yields all
+    /// recorded synonyms.
     ///
     /// # Arguments
     ///
@@ -562,38 +1097,225 @@ impl QueryBuilder {
         self
     }
     
-    /// Filter results
-    pub fn filter(mut self, criteria: FilterCriteria) -> Self {
-        self.operations.push(Operation::Filter(criteria));
-        self
-    }
-    
-    /// Sort results
-    pub fn sort(mut self, criteria: SortCriteria) -> Self {
-        self.operations.push(Operation::Sort(criteria));
-        self
-    }
-    
-    /// Limits the number of results returned.
+    /// Follows the strongest connection after weighting each edge by its type.
     ///
-    /// This is applied as the final step, after all other operations.
-    /// It's recommended to always set a reasonable limit to prevent
-    /// excessive memory usage.
+    /// Each edge's strength is multiplied by the weight for its
+    /// `ConnectionType` before the strongest is chosen, so a profile can
+    /// prefer etymology over analogy regardless of raw strength. Types
+    /// missing from the profile keep a weight of 1.0.
     ///
-    /// # Arguments
+    /// # Examples
     ///
-    /// * `count` - Maximum number of results to return
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use lingo::{QueryBuilder, core::ConnectionType};
     ///
-    /// # Returns
+    /// let mut profile = HashMap::new();
+    /// profile.insert(ConnectionType::Etymology, 2.0);
+    /// profile.insert(ConnectionType::Analogy, 0.5);
     ///
-    /// Self for method chaining.
+    /// let query = QueryBuilder::find("democracy")
+    ///     .follow_connection_weighted(&profile)
+    ///     .compile();
+    /// ```
+    pub fn follow_connection_weighted(mut self, profile: &HashMap<ConnectionType, f32>) -> Self {
+        self.operations.push(Operation::FollowConnectionWeighted {
+            weights: profile.clone(),
+        });
+        self.hints.needs_connection_index = true;
+        self
+    }
+    
+    /// Follows the connections of each node that pass a type and strength test.
+    ///
+    /// For every node in the current set, takes its connections of
+    /// `connection_type` (any type when `None`) whose strength is at least
+    /// `min_strength` (0.0-1.0), strongest first, and keeps the targets of
+    /// the first `max`. The limit applies per source node. A target reached
+    /// from several nodes appears once, where it was first reached.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use lingo::QueryBuilder;
+    /// use lingo::{QueryBuilder, core::ConnectionType};
     ///
-    /// let query = QueryBuilder::find("test")
+    /// // Up to three strong analogies
+    /// let query = QueryBuilder::find("democracy")
+    ///     .follow_connections_where(Some(ConnectionType::Analogy), 0.7, 3)
+    ///     .compile();
+    /// ```
+    pub fn follow_connections_where(
+        mut self,
+        connection_type: Option<ConnectionType>,
+        min_strength: f32,
+        max: usize,
+    ) -> Self {
+        self.operations.push(Operation::FollowConnectionsWhere {
+            connection_type,
+            min_strength,
+            max,
+        });
+        self.hints.needs_connection_index = true;
+        self
+    }
+    
+    /// Moves from each word to the phonemes it is pronounced with.
+    ///
+    /// Follows the `Phonetic` connections that record a pronunciation (see
+    /// [`DatabaseBuilder::add_pronunciation`](crate::storage::DatabaseBuilder::add_pronunciation))
+    /// and keeps only Phonemes-layer targets, so homophone links between
+    /// words are skipped. Phonemes come out in pronunciation order; a sound
+    /// that occurs twice appears once, at its first position. Use
+    /// [`MemoryMappedDatabase::pronunciation`](crate::storage::MemoryMappedDatabase::pronunciation)
+    /// for the full sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// let query = QueryBuilder::find("cat").to_phonemes().compile();
+    /// ```
+    pub fn to_phonemes(mut self) -> Self {
+        self.operations.push(Operation::ToPhonemes);
+        self.hints.needs_connection_index = true;
+        self
+    }
+    
+    /// Filter results
+    pub fn filter(mut self, criteria: FilterCriteria) -> Self {
+        self.operations.push(Operation::Filter(criteria));
+        self
+    }
+    
+    /// Sort results in the criterion's natural order, e.g. most frequent
+    /// first for [`SortCriteria::Frequency`].
+    ///
+    /// See [`SortCriteria`] for each default; ties break by node ID.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::query::{QueryBuilder, SortCriteria};
+    ///
+    /// // One closest-first ranking across every node spelled "happy"
+    /// let query = QueryBuilder::find("happy")
+    ///     .similar()
+    ///     .sort(SortCriteria::Distance)
+    ///     .limit(10)
+    ///     .compile();
+    /// ```
+    pub fn sort(self, criteria: SortCriteria) -> Self {
+        self.sort_by(criteria, criteria.default_order())
+    }
+    
+    /// Sort results by `criteria` in an explicit order.
+    ///
+    /// Ties break by ascending node ID whichever the order.
+    pub fn sort_by(mut self, criteria: SortCriteria, order: SortOrder) -> Self {
+        self.operations.push(Operation::Sort(criteria, order));
+        self
+    }
+    
+    /// Adds the results of another query to this one's.
+    ///
+    /// Both pipelines run in full; nodes found by `other` that this query
+    /// didn't already find are appended after this query's results.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// // Words near either "happy" or "glad"
+    /// let query = QueryBuilder::find("happy")
+    ///     .similar()
+    ///     .union(QueryBuilder::find("glad").similar())
+    ///     .compile();
+    /// ```
+    pub fn union(mut self, other: QueryBuilder) -> Self {
+        self.hints.estimated_results = match (self.hints.estimated_results, other.hints.estimated_results) {
+            (Some(ours), Some(theirs)) => Some(ours.saturating_add(theirs)),
+            _ => None,
+        };
+        self.merge_hints(&other.hints);
+        self.operations.push(Operation::Union(other.operations));
+        self
+    }
+    
+    /// Keeps only the results that another query also finds.
+    ///
+    /// Results stay in this query's order. When this query's set is
+    /// already empty, `other` isn't run at all.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    /// use lingo::core::ConnectionType;
+    ///
+    /// // Words similar to "manager" that "leadership" also points at
+    /// let query = QueryBuilder::find("manager")
+    ///     .similar()
+    ///     .intersect(QueryBuilder::find("leadership").follow_connection_type(ConnectionType::Hypernymy))
+    ///     .compile();
+    /// ```
+    pub fn intersect(mut self, other: QueryBuilder) -> Self {
+        self.hints.estimated_results = match (self.hints.estimated_results, other.hints.estimated_results) {
+            (Some(ours), Some(theirs)) => Some(ours.min(theirs)),
+            (ours, theirs) => ours.or(theirs),
+        };
+        self.merge_hints(&other.hints);
+        self.operations.push(Operation::Intersect(other.operations));
+        self
+    }
+    
+    /// Drops the results that another query finds.
+    ///
+    /// Results stay in this query's order. When this query's set is
+    /// already empty, `other` isn't run at all.
+    pub fn difference(mut self, other: QueryBuilder) -> Self {
+        self.merge_hints(&other.hints);
+        self.operations.push(Operation::Difference(other.operations));
+        self
+    }
+    
+    /// Another pipeline's operations as a query of its own, for display
+    fn branch(operations: &[Operation]) -> Self {
+        Self {
+            operations: operations.to_vec(),
+            hints: OptimizationHints::default(),
+        }
+    }
+    
+    /// Index requirements of a combined query are those of both sides
+    fn merge_hints(&mut self, other: &OptimizationHints) {
+        self.hints.needs_spatial_index |= other.needs_spatial_index;
+        self.hints.needs_vertical_index |= other.needs_vertical_index;
+        self.hints.needs_connection_index |= other.needs_connection_index;
+        self.hints.unbounded |= other.unbounded;
+    }
+    
+    /// Limits the number of results returned.
+    ///
+    /// This is applied as the final step, after all other operations.
+    /// It's recommended to always set a reasonable limit to prevent
+    /// excessive memory usage.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Maximum number of results to return
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// let query = QueryBuilder::find("test")
     ///     .similar()
     ///     .limit(20)  // Return at most 20 results
     ///     .compile();
@@ -603,7 +1325,7 @@ impl QueryBuilder {
         let safe_count = match validate_limit(count) {
             Ok(c) => c,
             Err(e) => {
-                eprintln!("Warning: Invalid limit {}: {}", count, e);
+                warn!(count, error = %e, "Invalid limit; using 100");
                 100 // Default safe limit
             }
         };
@@ -617,12 +1339,135 @@ impl QueryBuilder {
         self
     }
     
+    /// Skips the first `count` results, for paging through a large set.
+    ///
+    /// Put it before [`limit`](Self::limit): `offset(20).limit(10)` is the
+    /// third page of ten. Skipping only makes sense over a stable order.
+    /// Results ranked by a [`sort`](Self::sort), a similarity search or a
+    /// nearest-neighbour search keep that ranking; anything else is put in
+    /// ascending node ID order before the skip. Pages of the same query
+    /// against the same database never overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// let second_page = QueryBuilder::find("test")
+    ///     .similar()
+    ///     .offset(10)
+    ///     .limit(10)
+    ///     .compile();
+    /// ```
+    pub fn offset(mut self, count: usize) -> Self {
+        self.operations.push(Operation::Offset(count));
+        if let Some(est) = &mut self.hints.estimated_results {
+            *est = est.saturating_sub(count);
+        }
+        self
+    }
+    
+    /// Opts this query out of the executor's default result limit.
+    ///
+    /// Queries without an explicit `limit` are cut to the executor's
+    /// default (see [`LingoExecutor::set_default_limit`](crate::engine::LingoExecutor::set_default_limit))
+    /// so a broad search can't dump a whole layer by accident. Use this when
+    /// you really want every match. Explicit limits and the executor's
+    /// security cap still apply.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// let query = QueryBuilder::find("run").similar().no_limit().compile();
+    /// assert!(query.unbounded);
+    /// ```
+    pub fn no_limit(mut self) -> Self {
+        self.hints.unbounded = true;
+        self
+    }
+    
     /// Remove duplicate nodes
     pub fn deduplicate(mut self) -> Self {
         self.operations.push(Operation::Deduplicate);
         self
     }
     
+    /// Removes every node for `word` from the current results.
+    ///
+    /// Exclusion is by node identity: a node is dropped when it is one of
+    /// the nodes an exact [`find`](Self::find) for `word` would load, and
+    /// nothing else is touched, however close it sits. A word missing from
+    /// the database excludes nothing, and excluding from an empty result set
+    /// leaves it empty rather than failing. The order of the remaining nodes
+    /// is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// // Words like "happy", but never "glad" itself
+    /// let query = QueryBuilder::find("happy")
+    ///     .similar()
+    ///     .exclude("glad")
+    ///     .compile();
+    /// ```
+    pub fn exclude(mut self, word: &str) -> Self {
+        self.operations.push(Operation::Exclude(checked_word(word)));
+        self
+    }
+    
+    /// Removes the given nodes from the current results.
+    ///
+    /// Like [`exclude`](Self::exclude), matching is by node identity. IDs
+    /// that aren't in the results, or in the database, are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    /// use lingo::core::NodeId;
+    ///
+    /// let query = QueryBuilder::find("run")
+    ///     .similar()
+    ///     .exclude_nodes([NodeId(4), NodeId(9)])
+    ///     .compile();
+    /// ```
+    pub fn exclude_nodes(mut self, node_ids: impl IntoIterator<Item = crate::core::NodeId>) -> Self {
+        self.operations.push(Operation::ExcludeNodes(node_ids.into_iter().collect()));
+        self
+    }
+    
+    /// Removes nodes lying within `radius` of any node for `word`.
+    ///
+    /// Exclusion is spatial: a node is dropped when its Euclidean distance to
+    /// one of the nodes an exact [`find`](Self::find) for `word` would load
+    /// is at most `radius`. The word's own nodes are always within range, so
+    /// this removes at least what [`exclude`](Self::exclude) would. Negative
+    /// or NaN radii are treated as zero. As with `exclude`, an unknown word
+    /// or an empty result set is a no-op.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// // Similar to "hot", but nowhere near "cold"
+    /// let query = QueryBuilder::find("hot")
+    ///     .similar()
+    ///     .exclude_near("cold", 0.1)
+    ///     .compile();
+    /// ```
+    pub fn exclude_near(mut self, word: &str, radius: f32) -> Self {
+        self.operations.push(Operation::ExcludeNear {
+            word: checked_word(word),
+            radius: radius.max(0.0),
+        });
+        self
+    }
+    
     /// Decomposes the current nodes into their constituent morphemes.
     ///
     /// This is the inverse of composition - it breaks down words into their
@@ -679,6 +1524,105 @@ impl QueryBuilder {
         let mut compiler = QueryCompiler::new();
         compiler.compile(self.operations, self.hints)
     }
+    
+    /// Compiles the query once so it can be run for many starting words.
+    ///
+    /// The seed word given to `find` (or `sounds_like`, or a folded lookup)
+    /// is only a placeholder; each word passed to
+    /// [`LingoExecutor::execute_batch`](crate::engine::LingoExecutor::execute_batch)
+    /// or [`CompiledQueryTemplate::bind`] takes its place.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LingoError::QueryCompilation` if the query doesn't start
+    /// with a word lookup.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use lingo::QueryBuilder;
+    ///
+    /// let template = QueryBuilder::find("")
+    ///     .similar_threshold(0.8)
+    ///     .limit(10)
+    ///     .compile_template()?;
+    ///
+    /// let query = template.bind("happy");
+    /// assert!(query.string_cache.contains(&"happy".to_string()));
+    /// # Ok::<(), lingo::core::error::LingoError>(())
+    /// ```
+    pub fn compile_template(self) -> Result<CompiledQueryTemplate> {
+        if !matches!(
+            self.operations.first(),
+            Some(
+                Operation::LoadNode(_)
+                    | Operation::LoadNodeFolded(_)
+                    | Operation::LoadNodeNormalized(_)
+                    | Operation::LoadNodePhonetic(_)
+                    | Operation::LoadPhrase(_)
+            )
+        ) {
+            return Err(LingoError::QueryCompilation(
+                "templates must start with a word lookup".to_string()
+            ));
+        }
+        
+        let mut query = self.compile();
+        // Give the seed its own slot, in case a later lookup shares its word
+        let seed_slot = query.string_cache.len();
+        query.string_cache.push(String::new());
+        query.bytecode[0].operand1 = seed_slot as u16;
+        
+        Ok(CompiledQueryTemplate { query, seed_slot })
+    }
+}
+
+/// Validate a lookup word, replacing it with an empty lookup if it's rejected
+fn checked_word(word: &str) -> String {
+    if let Err(e) = validate_query(word) {
+        // Builders are infallible, so fall back to a lookup that matches nothing
+        warn!(word = word, error = %e, "Invalid query word; lookup will match nothing");
+        return String::new();
+    }
+    word.to_string()
+}
+
+/// A compiled query whose starting word is supplied at execution time.
+///
+/// Built by [`QueryBuilder::compile_template`]. Running one template for
+/// many words skips recompiling the same chain for each of them.
+#[derive(Debug, Clone)]
+pub struct CompiledQueryTemplate {
+    /// The compiled query, with an empty seed word
+    query: CompiledQuery,
+    /// Index of the seed word in the query's string cache
+    seed_slot: usize,
+}
+
+impl CompiledQueryTemplate {
+    /// The compiled query, with an empty seed word
+    pub fn query(&self) -> &CompiledQuery {
+        &self.query
+    }
+    
+    /// Index of the seed word in the query's string cache
+    pub fn seed_slot(&self) -> usize {
+        self.seed_slot
+    }
+    
+    /// A standalone query starting from `word`.
+    ///
+    /// `word` is validated the same way as in [`QueryBuilder::find`].
+    pub fn bind(&self, word: &str) -> CompiledQuery {
+        let mut query = self.query.clone();
+        query.string_cache[self.seed_slot] = Self::seed_word(word);
+        query
+    }
+    
+    /// Validate an input word for the seed slot
+    pub(crate) fn seed_word(word: &str) -> String {
+        checked_word(word)
+    }
 }
 
 /// A compiled query ready for execution.
@@ -701,10 +1645,36 @@ pub struct CompiledQuery {
     pub bytecode: Vec<SlangInstruction>,
     /// Required indices for execution
     pub required_indices: RequiredIndices,
-    /// Estimated execution cost
+    /// Estimated execution cost, in the units of an execution budget.
+    ///
+    /// Assumes fixed fan-outs for traversals and similarity searches, so
+    /// compare it with a budget to turn away deep queries before running
+    /// them; [`LingoExecutor::execute_within`](crate::engine::LingoExecutor::execute_within)
+    /// enforces the budget on the work actually done.
     pub estimated_cost: u32,
     /// String cache for LoadNode operations
     pub string_cache: Vec<String>,
+    /// Connection type weight profiles for FollowWeighted operations
+    pub connection_weights: Vec<HashMap<ConnectionType, f32>>,
+    /// Boxes referenced by SpatialBox operations
+    pub regions: Vec<crate::core::BoundingBox3D>,
+    /// ID lists referenced by LoadNodeIds operations
+    pub node_lists: Vec<Vec<crate::core::NodeId>>,
+    /// Non-Euclidean metrics referenced by FindSimilar operations
+    pub metrics: Vec<SimilarityMetric>,
+    /// Whether the executor's default result limit is skipped
+    pub unbounded: bool,
+}
+
+impl CompiledQuery {
+    /// Whether the query limits its own results, via `limit` or a
+    /// similarity search with a limit
+    pub fn has_explicit_limit(&self) -> bool {
+        self.bytecode.iter().any(|instruction| {
+            instruction.opcode == SlangOp::Limit
+                || instruction.flags & crate::core::bytecode::instruction_flags::HAS_LIMIT != 0
+        })
+    }
 }
 
 /// Specifies which indices are required for efficient query execution.
@@ -726,12 +1696,24 @@ pub struct RequiredIndices {
 struct QueryCompiler {
     /// String interning for LoadNode operations
     string_cache: Vec<String>,
+    /// Weight profiles referenced by FollowWeighted operations
+    connection_weights: Vec<HashMap<ConnectionType, f32>>,
+    /// Boxes referenced by SpatialBox operations
+    regions: Vec<crate::core::BoundingBox3D>,
+    /// ID lists referenced by LoadNodeIds operations
+    node_lists: Vec<Vec<crate::core::NodeId>>,
+    /// Metrics referenced by FindSimilar operations
+    metrics: Vec<SimilarityMetric>,
 }
 
 impl QueryCompiler {
     fn new() -> Self {
         Self {
             string_cache: Vec::new(),
+            connection_weights: Vec::new(),
+            regions: Vec::new(),
+            node_lists: Vec::new(),
+            metrics: Vec::new(),
         }
     }
     
@@ -772,6 +1754,11 @@ impl QueryCompiler {
             required_indices,
             estimated_cost,
             string_cache: self.string_cache.clone(),
+            connection_weights: self.connection_weights.clone(),
+            regions: self.regions.clone(),
+            node_lists: self.node_lists.clone(),
+            metrics: self.metrics.clone(),
+            unbounded: hints.unbounded,
         }
     }
     
@@ -786,6 +1773,46 @@ impl QueryCompiler {
                 ));
             }
             
+            Operation::LoadNodeFolded(word) => {
+                let string_id = self.intern_string(word);
+                let mut instruction = SlangInstruction::with_operand1(
+                    SlangOp::LoadNode,
+                    string_id,
+                );
+                instruction.flags |= crate::core::bytecode::instruction_flags::FOLD_ACCENTS;
+                bytecode.push(instruction);
+            }
+            
+            Operation::LoadNodeNormalized(word) => {
+                use crate::core::bytecode::instruction_flags::{CASE_INSENSITIVE, FOLD_ACCENTS};
+                
+                let string_id = self.intern_string(word);
+                let mut instruction = SlangInstruction::with_operand1(
+                    SlangOp::LoadNode,
+                    string_id,
+                );
+                instruction.flags |= FOLD_ACCENTS | CASE_INSENSITIVE;
+                bytecode.push(instruction);
+            }
+            
+            Operation::LoadNodePhonetic(word) => {
+                let string_id = self.intern_string(word);
+                let mut instruction = SlangInstruction::with_operand1(
+                    SlangOp::LoadNode,
+                    string_id,
+                );
+                instruction.flags |= crate::core::bytecode::instruction_flags::PHONETIC;
+                bytecode.push(instruction);
+            }
+            
+            Operation::LoadPhrase(text) => {
+                let string_id = self.intern_string(text);
+                bytecode.push(SlangInstruction::with_operand1(
+                    SlangOp::LoadPhrase,
+                    string_id,
+                ));
+            }
+            
             Operation::LoadNodeId(id) => {
                 bytecode.push(SlangInstruction::with_operand2(
                     SlangOp::LoadNodeId,
@@ -794,6 +1821,16 @@ impl QueryCompiler {
                 ));
             }
             
+            Operation::LoadNodeIds(ids) => {
+                // operand1 indexes the query's ID list table
+                let list_id = self.node_lists.len() as u16;
+                self.node_lists.push(ids);
+                bytecode.push(SlangInstruction::with_operand1(
+                    SlangOp::LoadNodeIds,
+                    list_id,
+                ));
+            }
+            
             Operation::LayerUp(levels) => {
                 bytecode.push(SlangInstruction::with_operand1(
                     SlangOp::LayerUp,
@@ -808,16 +1845,28 @@ impl QueryCompiler {
                 ));
             }
             
-            Operation::FindSimilar { threshold, limit } => {
+            Operation::FindSimilar { threshold, limit, prefer_frequent, metric } => {
                 let threshold_fixed = (threshold * 65535.0) as u16;
-                let mut instruction = SlangInstruction::with_operand2(
+                // operand3 is 0 for Euclidean, else 1 + the metric table index
+                let metric_id = if metric == SimilarityMetric::Euclidean {
+                    0
+                } else {
+                    self.metrics.push(metric);
+                    self.metrics.len() as u32
+                };
+                let mut instruction = SlangInstruction::with_all_operands(
                     SlangOp::FindSimilar,
+                    0,
                     threshold_fixed,
                     limit.unwrap_or(usize::MAX) as u32,
+                    metric_id,
                 );
                 if limit.is_some() {
                     instruction.flags |= crate::core::bytecode::instruction_flags::HAS_LIMIT;
                 }
+                if prefer_frequent {
+                    instruction.flags |= crate::core::bytecode::instruction_flags::PREFER_FREQUENT;
+                }
                 bytecode.push(instruction);
             }
             
@@ -845,6 +1894,49 @@ impl QueryCompiler {
                 ));
             }
             
+            Operation::FollowConnectionWeighted { weights } => {
+                // Profiles don't fit in an instruction, so operand1 indexes
+                // the query's weight table
+                let profile_id = self.connection_weights.len() as u16;
+                self.connection_weights.push(weights);
+                bytecode.push(SlangInstruction::with_operand1(
+                    SlangOp::FollowWeighted,
+                    profile_id,
+                ));
+            }
+            
+            Operation::FollowConnectionsWhere { connection_type, min_strength, max } => {
+                // Strength is quantized like a stored connection's, so an
+                // edge added at exactly `min_strength` passes
+                let min_strength = (min_strength.clamp(0.0, 1.0) * 65535.0) as u16;
+                bytecode.push(SlangInstruction::with_all_operands(
+                    SlangOp::FollowWhere,
+                    0,
+                    connection_type.map_or(ANY_CONNECTION_TYPE, |t| t as u16),
+                    min_strength as u32,
+                    max.min(u32::MAX as usize) as u32,
+                ));
+            }
+            
+            Operation::SpatialBox(bounds) => {
+                // Six floats don't fit in an instruction, so operand1 indexes
+                // the query's region table
+                let region_id = self.regions.len() as u16;
+                self.regions.push(bounds);
+                bytecode.push(SlangInstruction::with_operand1(
+                    SlangOp::SpatialBox,
+                    region_id,
+                ));
+            }
+            
+            Operation::NearestK(k) => {
+                bytecode.push(SlangInstruction::with_operand2(
+                    SlangOp::SpatialNearest,
+                    0,
+                    k.min(u32::MAX as usize) as u32,
+                ));
+            }
+            
             Operation::Limit(count) => {
                 bytecode.push(SlangInstruction::with_operand1(
                     SlangOp::Limit,
@@ -852,10 +1944,52 @@ impl QueryCompiler {
                 ));
             }
             
+            Operation::Offset(count) => {
+                // Without a ranking step the set is in discovery order,
+                // which a different page's query may not reproduce
+                if !Self::is_ranked(bytecode) {
+                    bytecode.push(SlangInstruction::with_operand1(SlangOp::Sort, sort_keys::NODE_ID));
+                }
+                bytecode.push(SlangInstruction::with_operand2(
+                    SlangOp::Offset,
+                    0,
+                    count.min(u32::MAX as usize) as u32,
+                ));
+            }
+            
             Operation::Deduplicate => {
                 bytecode.push(SlangInstruction::new(SlangOp::Deduplicate));
             }
             
+            Operation::Exclude(word) => {
+                let string_id = self.intern_string(word);
+                bytecode.push(SlangInstruction::with_operand1(
+                    SlangOp::Exclude,
+                    string_id,
+                ));
+            }
+            
+            Operation::ExcludeNodes(ids) => {
+                // operand1 indexes the node list table rather than the strings
+                let list_id = self.node_lists.len() as u16;
+                self.node_lists.push(ids);
+                let mut instruction = SlangInstruction::with_operand1(
+                    SlangOp::Exclude,
+                    list_id,
+                );
+                instruction.flags |= crate::core::bytecode::instruction_flags::NODE_LIST;
+                bytecode.push(instruction);
+            }
+            
+            Operation::ExcludeNear { word, radius } => {
+                let string_id = self.intern_string(word);
+                bytecode.push(SlangInstruction::with_operand2(
+                    SlangOp::ExcludeNear,
+                    string_id,
+                    radius.to_bits(),
+                ));
+            }
+            
             Operation::Decompose => {
                 // Decompose is essentially a layer_down operation specifically for morphemes
                 bytecode.push(SlangInstruction::with_operand1(
@@ -864,6 +1998,18 @@ impl QueryCompiler {
                 ));
             }
             
+            Operation::ToPhonemes => {
+                // Pronunciation edges are the Phonetic ones into the Phonemes layer
+                bytecode.push(SlangInstruction::with_operand1(
+                    SlangOp::FollowConnectionType,
+                    ConnectionType::Phonetic as u16,
+                ));
+                bytecode.push(SlangInstruction::with_operand1(
+                    SlangOp::LayerSet,
+                    Layer::Phonemes as u16,
+                ));
+            }
+            
             Operation::LayerSet(layer) => {
                 bytecode.push(SlangInstruction::with_operand1(
                     SlangOp::LayerSet,
@@ -882,13 +2028,128 @@ impl QueryCompiler {
                 ));
             }
             
-            Operation::Filter(_) | Operation::Sort(_) => {
-                // TODO: Implement filter and sort compilation
+            Operation::ReflectThrough { center } => {
+                // Center is encoded as 16-bit fixed point: x in operand1,
+                // y and z packed into the low and high halves of operand2
+                let fixed = |v: f32| (v.clamp(0.0, 1.0) * 65535.0) as u16;
+                bytecode.push(SlangInstruction::with_operand2(
+                    SlangOp::SpatialReflect,
+                    fixed(center.x),
+                    fixed(center.y) as u32 | ((fixed(center.z) as u32) << 16),
+                ));
+            }
+            
+            Operation::Sort(criteria, order) => {
+                let mut instruction = SlangInstruction::with_operand1(SlangOp::Sort, criteria.sort_key());
+                if order == SortOrder::Descending {
+                    instruction.flags |= crate::core::bytecode::instruction_flags::INVERSE;
+                }
+                bytecode.push(instruction);
+            }
+            
+            Operation::Filter(FilterCriteria::Layer(layer)) => {
+                bytecode.push(Self::layer_filter(&[layer]));
+            }
+            
+            Operation::Filter(FilterCriteria::LayerIn(layers)) => {
+                bytecode.push(Self::layer_filter(&layers));
+            }
+            
+            Operation::Filter(FilterCriteria::FrequencyRankBelow(rank)) => {
+                bytecode.push(SlangInstruction::with_operand2(
+                    SlangOp::Filter,
+                    filter_kinds::FREQUENCY_RANK_BELOW,
+                    rank,
+                ));
+            }
+            
+            Operation::Filter(FilterCriteria::ProductivityAbove(productivity)) => {
+                bytecode.push(SlangInstruction::with_operand2(
+                    SlangOp::Filter,
+                    filter_kinds::PRODUCTIVITY_ABOVE,
+                    productivity.to_bits(),
+                ));
+            }
+            
+            Operation::Filter(FilterCriteria::Etymology(origin)) => {
+                bytecode.push(SlangInstruction::with_operand2(
+                    SlangOp::Filter,
+                    filter_kinds::ETYMOLOGY,
+                    origin as u32,
+                ));
+            }
+            
+            Operation::Union(operations) => {
+                bytecode.extend(self.compile_branch(operations));
+                bytecode.push(SlangInstruction::new(SlangOp::Union));
+            }
+            
+            Operation::Intersect(operations) => {
+                self.compile_narrowing(operations, SlangOp::Intersect, bytecode);
+            }
+            
+            Operation::Difference(operations) => {
+                self.compile_narrowing(operations, SlangOp::Difference, bytecode);
+            }
+            
+            Operation::Filter(_) => {
+                // TODO: Implement the remaining filters
                 // For now, these are no-ops
             }
         }
     }
     
+    /// Compile another pipeline on its own, so steps that look back over
+    /// the bytecode (like `offset`) only see their own branch
+    fn compile_branch(&mut self, operations: Vec<Operation>) -> Vec<SlangInstruction> {
+        let mut branch = Vec::new();
+        for op in operations {
+            self.compile_operation(op, &mut branch);
+        }
+        branch
+    }
+    
+    /// Whether the set at the end of `bytecode` is in a ranked order.
+    ///
+    /// Walks back through steps that keep their input's order until it
+    /// reaches the step that produced it. Sorts and similarity or nearest
+    /// searches rank their output; loads and set operations don't.
+    fn is_ranked(bytecode: &[SlangInstruction]) -> bool {
+        for instruction in bytecode.iter().rev() {
+            match instruction.opcode {
+                SlangOp::Sort | SlangOp::FindSimilar | SlangOp::SpatialNearest => return true,
+                SlangOp::Filter
+                | SlangOp::LayerSet
+                | SlangOp::Limit
+                | SlangOp::Offset
+                | SlangOp::Exclude
+                | SlangOp::ExcludeNear
+                | SlangOp::Deduplicate
+                | SlangOp::Nop => {}
+                _ => return false,
+            }
+        }
+        false
+    }
+    
+    /// A branch combined by a step that can only shrink the current set
+    fn compile_narrowing(&mut self, operations: Vec<Operation>, combine: SlangOp, bytecode: &mut Vec<SlangInstruction>) {
+        let branch = self.compile_branch(operations);
+        // Nothing survives an empty left side, so skip the branch and the combine
+        bytecode.push(SlangInstruction::with_operand2(
+            SlangOp::Branch,
+            0,
+            (branch.len() + 1) as u32,
+        ));
+        bytecode.extend(branch);
+        bytecode.push(SlangInstruction::new(combine));
+    }
+    
+    fn layer_filter(layers: &[Layer]) -> SlangInstruction {
+        let mask = layers.iter().fold(0u32, |mask, &layer| mask | 1 << layer as u8);
+        SlangInstruction::with_operand2(SlangOp::Filter, filter_kinds::LAYERS, mask)
+    }
+    
     fn intern_string(&mut self, s: String) -> u16 {
         // Simple string interning
         if let Some(pos) = self.string_cache.iter().position(|cached| cached == &s) {
@@ -900,26 +2161,82 @@ impl QueryCompiler {
         }
     }
     
+    /// Estimate the work `bytecode` does, in the units of an execution
+    /// budget (see [`ExecutionLimits::budget`](crate::security::ExecutionLimits::budget)).
+    ///
+    /// Each instruction costs a fixed weight for its operation plus the
+    /// estimated size of the set it leaves behind. Sets grow by assumed
+    /// fan-outs, so every chained hop multiplies the estimate.
     fn estimate_cost(&self, bytecode: &[SlangInstruction]) -> u32 {
-        // Simple cost model
-        let mut cost = 0u32;
+        use crate::core::bytecode::instruction_flags::HAS_LIMIT;
+        
+        let mut cost = 0u64;
+        // Estimated size of the current set, and of the sets below it
+        let mut width = 0u64;
+        let mut below: Vec<u64> = Vec::new();
         
         for instruction in bytecode {
-            cost += match instruction.opcode {
-                SlangOp::LoadNode | SlangOp::LoadNodeId => 1,
+            let weight = match instruction.opcode {
+                SlangOp::LoadNode | SlangOp::LoadNodeId | SlangOp::LoadNodeIds => 1,
+                // May look up every word and its connections
+                SlangOp::LoadPhrase => 10,
                 SlangOp::LayerUp | SlangOp::LayerDown => 10,
                 SlangOp::FindSimilar => 50,
                 SlangOp::SpatialNeighbors => 40,
+                SlangOp::SpatialReflect => 40,
+                SlangOp::SpatialBox => 40,
+                SlangOp::SpatialNearest => 40,
                 SlangOp::FollowConnection => 5,
                 SlangOp::FollowConnectionType => 10,
+                SlangOp::FollowWeighted => 10,
+                SlangOp::FollowWhere => 10,
                 SlangOp::Limit => 1,
+                SlangOp::Offset => 1,
+                SlangOp::Sort => 20,
+                SlangOp::Union | SlangOp::Intersect | SlangOp::Difference => 20,
+                SlangOp::Branch => 1,
                 SlangOp::Deduplicate => 20,
+                SlangOp::Exclude => 5,
+                SlangOp::ExcludeNear => 20,
                 SlangOp::Halt => 0,
                 _ => 5,
             };
+            
+            width = match instruction.opcode {
+                SlangOp::LoadNode
+                | SlangOp::LoadNodeId
+                | SlangOp::LoadNodeIds
+                | SlangOp::LoadPhrase
+                | SlangOp::SpatialBox => {
+                    below.push(width);
+                    match instruction.opcode {
+                        SlangOp::LoadNodeIds => self.node_lists
+                            .get(instruction.operand1 as usize)
+                            .map_or(0, |ids| ids.len() as u64),
+                        SlangOp::SpatialBox => ASSUMED_BOX_NODES,
+                        _ => 1,
+                    }
+                }
+                SlangOp::FindSimilar if instruction.flags & HAS_LIMIT != 0 => {
+                    width.saturating_mul(ASSUMED_SIMILAR_NODES).min(instruction.operand2 as u64)
+                }
+                SlangOp::FindSimilar => width.saturating_mul(ASSUMED_SIMILAR_NODES),
+                // FollowConnection takes one edge per node; the others take all
+                SlangOp::FollowConnectionType | SlangOp::FollowWeighted => width.saturating_mul(ASSUMED_FAN_OUT),
+                SlangOp::FollowWhere => width.saturating_mul(ASSUMED_FAN_OUT.min(instruction.operand3 as u64)),
+                SlangOp::LayerUp | SlangOp::LayerDown => width.saturating_mul(ASSUMED_LAYER_FAN_OUT),
+                SlangOp::Limit => width.min(instruction.operand1 as u64),
+                SlangOp::Offset => width.saturating_sub(instruction.operand2 as u64),
+                SlangOp::Union => below.pop().unwrap_or(0).saturating_add(width),
+                SlangOp::Intersect => below.pop().unwrap_or(0).min(width),
+                SlangOp::Difference => below.pop().unwrap_or(0),
+                _ => width,
+            };
+            
+            cost = cost.saturating_add(weight).saturating_add(width);
         }
         
-        cost
+        cost.min(u32::MAX as u64) as u32
     }
 }
 
@@ -932,11 +2249,24 @@ impl fmt::Display for QueryBuilder {
             }
             match op {
                 Operation::LoadNode(word) => write!(f, "find('{}')", word)?,
-                Operation::FindSimilar { threshold, .. } => write!(f, "similar({})", threshold)?,
+                Operation::LoadNodeFolded(word) => write!(f, "find_folded('{}')", word)?,
+                Operation::LoadNodeNormalized(word) => write!(f, "find_normalized('{}')", word)?,
+                Operation::LoadNodePhonetic(word) => write!(f, "sounds_like('{}')", word)?,
+                Operation::LoadPhrase(text) => write!(f, "find_phrase('{}')", text)?,
+                Operation::LoadNodeIds(ids) => write!(f, "from_nodes({})", ids.len())?,
+                Operation::FindSimilar { threshold, metric: SimilarityMetric::Euclidean, .. } => {
+                    write!(f, "similar({})", threshold)?
+                }
+                Operation::FindSimilar { threshold, metric, .. } => write!(f, "similar({}, {:?})", threshold, metric)?,
                 Operation::LayerUp(n) => write!(f, "up({})", n)?,
                 Operation::LayerDown(n) => write!(f, "down({})", n)?,
                 Operation::FollowConnection { strength_rank } => write!(f, "follow(#{})", strength_rank)?,
                 Operation::Limit(n) => write!(f, "limit({})", n)?,
+                Operation::Offset(n) => write!(f, "offset({})", n)?,
+                Operation::Union(ops) => write!(f, "union({})", Self::branch(ops))?,
+                Operation::Intersect(ops) => write!(f, "intersect({})", Self::branch(ops))?,
+                Operation::Difference(ops) => write!(f, "difference({})", Self::branch(ops))?,
+                Operation::Exclude(word) => write!(f, "exclude('{}')", word)?,
                 _ => write!(f, "{:?}", op)?,
             }
         }
@@ -949,6 +2279,73 @@ mod tests {
     use super::*;
     use crate::core::{Layer, Coordinate3D, ConnectionType};
     
+    #[test]
+    fn test_similar_uses_default_threshold() {
+        let default = QueryBuilder::find("happy").similar().compile();
+        let explicit = QueryBuilder::find("happy").similar_threshold(DEFAULT_SIMILARITY_THRESHOLD).compile();
+        
+        let (default_op1, explicit_op1) = (default.bytecode[1].operand1, explicit.bytecode[1].operand1);
+        assert_eq!(default.bytecode[1].opcode, SlangOp::FindSimilar);
+        assert_eq!(default_op1, (DEFAULT_SIMILARITY_THRESHOLD * 65535.0) as u16);
+        assert_eq!(default_op1, explicit_op1);
+    }
+    
+    #[test]
+    fn test_out_of_range_threshold_rejected() {
+        for bad in [-0.1, 1.5, f32::NAN] {
+            let result = QueryBuilder::find("happy").try_similar_threshold(bad);
+            assert!(matches!(result, Err(crate::core::error::LingoError::SecurityError { .. })));
+        }
+        assert!(QueryBuilder::find("happy").try_similar_threshold(1.0).is_ok());
+        
+        // The infallible form falls back to the default instead of searching everything
+        let query = QueryBuilder::find("happy").similar_threshold(-3.0).compile();
+        let op1 = query.bytecode[1].operand1;
+        assert_eq!(op1, (DEFAULT_SIMILARITY_THRESHOLD * 65535.0) as u16);
+    }
+    
+    #[test]
+    fn test_from_nodes_compiles_id_list() {
+        use crate::core::NodeId;
+        
+        let query = QueryBuilder::from_nodes([NodeId(3), NodeId(7)]).unwrap().similar().compile();
+        assert_eq!(query.bytecode[0].opcode, SlangOp::LoadNodeIds);
+        assert_eq!(query.node_lists, vec![vec![NodeId(3), NodeId(7)]]);
+        assert!(query.string_cache.is_empty());
+        
+        let single = QueryBuilder::from_node(NodeId(3)).compile();
+        let operand2 = single.bytecode[0].operand2;
+        assert_eq!(single.bytecode[0].opcode, SlangOp::LoadNodeId);
+        assert_eq!(operand2, 3);
+        
+        let empty = QueryBuilder::from_nodes(Vec::new());
+        assert!(matches!(empty, Err(LingoError::QueryCompilation(_))));
+    }
+    
+    #[test]
+    fn test_with_find_swaps_only_seed() {
+        let template = QueryBuilder::find("seed")
+            .similar_threshold(0.8)
+            .layer_up()
+            .limit(10);
+        
+        let happy = template.with_find("happy").compile();
+        let sad = template.with_find("sad").compile();
+        
+        assert_eq!(happy.string_cache, vec!["happy".to_string()]);
+        assert_eq!(sad.string_cache, vec!["sad".to_string()]);
+        assert_eq!(happy.bytecode.len(), sad.bytecode.len());
+        for (a, b) in happy.bytecode.iter().zip(&sad.bytecode) {
+            let (a_op1, a_op2, a_op3) = (a.operand1, a.operand2, a.operand3);
+            let (b_op1, b_op2, b_op3) = (b.operand1, b.operand2, b.operand3);
+            assert_eq!(a.opcode, b.opcode);
+            assert_eq!((a_op1, a_op2, a_op3), (b_op1, b_op2, b_op3));
+        }
+        
+        // The template itself is untouched
+        assert_eq!(template.compile().string_cache, vec!["seed".to_string()]);
+    }
+    
     #[test]
     fn test_query_builder() {
         let query = QueryBuilder::find("technical")
@@ -1008,6 +2405,23 @@ mod tests {
         assert!(query2.bytecode.len() >= 1); // Spatial operation
     }
     
+    #[test]
+    fn test_query_builder_reflect_through() {
+        let center = Coordinate3D::new(0.5, 0.25, Layer::Words.z_center());
+        let query = QueryBuilder::find("hot")
+            .reflect_through(center)
+            .compile();
+        
+        assert_eq!(query.bytecode.len(), 3); // LoadNode, SpatialReflect, Halt
+        assert_eq!(query.bytecode[1].opcode, SlangOp::SpatialReflect);
+        assert!(query.required_indices.spatial);
+        
+        let operand1 = query.bytecode[1].operand1;
+        let operand2 = query.bytecode[1].operand2;
+        assert_eq!(operand1, 32767);
+        assert_eq!(operand2 & 0xFFFF, 16383);
+    }
+    
     #[test]
     fn test_query_builder_connection_operations() {
         let query = QueryBuilder::find("node")
@@ -56,7 +56,19 @@
 //!     .filter(FilterCriteria::Etymology(EtymologyOrigin::Greek))
 //!     .compile();
 //! ```
+//!
+//! ## Text queries
+//!
+//! [`parse`] reads the same pipelines from text, for REPLs and bindings:
+//!
+//! ```rust
+//! use lingo::query::parse;
+//!
+//! let query = parse("find happy | similar 0.85 | limit 10").unwrap();
+//! ```
 
 mod builder;
+mod parser;
 
-pub use builder::{QueryBuilder, CompiledQuery, Operation, FilterCriteria, SortCriteria};
\ No newline at end of file
+pub use parser::parse;
+pub use builder::{QueryBuilder, CompiledQuery, CompiledQueryTemplate, Operation, FilterCriteria, SortCriteria, SortOrder, DEFAULT_SIMILARITY_THRESHOLD};
\ No newline at end of file
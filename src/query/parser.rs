@@ -0,0 +1,432 @@
+//! Text query language, parsed into the same bytecode as [`QueryBuilder`]
+//!
+//! A query is a pipeline of stages separated by `|`, read left to right:
+//!
+//! ```text
+//! find "technical" | similar 0.8 | layer_up | limit 10
+//! ```
+//!
+//! | stage                               | builder call                            |
+//! |-------------------------------------|-----------------------------------------|
+//! | `find <word>`                       | [`find`](QueryBuilder::find)            |
+//! | `id <n>`                            | [`find_by_id`](QueryBuilder::find_by_id) |
+//! | `similar [threshold]`               | [`similar_threshold`](QueryBuilder::similar_threshold) |
+//! | `layer_up [n]`, `layer_down [n]`    | [`layer_up_n`](QueryBuilder::layer_up_n), [`layer_down_n`](QueryBuilder::layer_down_n) |
+//! | `layer <layer>`                     | [`layer`](QueryBuilder::layer)          |
+//! | `follow`                            | [`follow_connection`](QueryBuilder::follow_connection) |
+//! | `follow <type>`                     | [`follow_connection_type`](QueryBuilder::follow_connection_type) |
+//! | `follow <type or any> <min> [max]`  | [`follow_connections_where`](QueryBuilder::follow_connections_where) |
+//! | `filter layer <layer>`              | [`FilterCriteria::Layer`]               |
+//! | `filter etymology <origin>`         | [`FilterCriteria::Etymology`]           |
+//! | `filter rank <n>`                   | [`FilterCriteria::FrequencyRankBelow`]  |
+//! | `filter productivity <x>`           | [`FilterCriteria::ProductivityAbove`]   |
+//! | `limit <n>`, `offset <n>`           | [`limit`](QueryBuilder::limit), [`offset`](QueryBuilder::offset) |
+//!
+//! Words may be bare or `"`-quoted, with `\"` and `\\` escapes inside
+//! quotes. Stage names are lowercase; layer, connection type and etymology
+//! names ignore case, `_` and `-`.
+
+use crate::core::{
+    ConnectionType, EtymologyOrigin, Layer,
+    error::{LingoError, ParseError},
+};
+use crate::data::import::{lookup, ETYMOLOGIES};
+use crate::security::{validate_limit, validate_query, validate_threshold};
+use super::{CompiledQuery, FilterCriteria, QueryBuilder};
+use std::ops::Range;
+
+/// Every layer name accepted by `layer` and `filter layer`
+const LAYERS: &[(&str, Layer)] = &[
+    ("letters", Layer::Letters),
+    ("phonemes", Layer::Phonemes),
+    ("morphemes", Layer::Morphemes),
+    ("words", Layer::Words),
+    ("phrases", Layer::Phrases),
+    ("concepts", Layer::Concepts),
+    ("domains", Layer::Domains),
+];
+
+/// Every connection type name accepted by `follow`
+const CONNECTION_TYPES: &[(&str, ConnectionType)] = &[
+    ("synonymy", ConnectionType::Synonymy),
+    ("antonymy", ConnectionType::Antonymy),
+    ("hypernymy", ConnectionType::Hypernymy),
+    ("hyponymy", ConnectionType::Hyponymy),
+    ("meronymy", ConnectionType::Meronymy),
+    ("derivation", ConnectionType::Derivation),
+    ("etymology", ConnectionType::Etymology),
+    ("phonetic", ConnectionType::Phonetic),
+    ("analogy", ConnectionType::Analogy),
+    ("collocation", ConnectionType::Collocation),
+    ("causation", ConnectionType::Causation),
+    ("learned", ConnectionType::Learned),
+    ("lexicalbridge", ConnectionType::LexicalBridge),
+    ("morphologicalpattern", ConnectionType::MorphologicalPattern),
+];
+
+/// Parse a text query into a compiled query.
+///
+/// # Errors
+///
+/// Returns a [`ParseError`] whose `span` is the byte range of the
+/// offending text: an unknown stage or name, a missing or malformed
+/// argument, an empty stage, or a pipeline that doesn't start with `find`
+/// or `id`. Thresholds and limits are checked as strictly as
+/// [`try_similar_threshold`](QueryBuilder::try_similar_threshold) and
+/// [`validate_limit`] check them.
+///
+/// # Examples
+///
+/// ```rust
+/// use lingo::query::parse;
+///
+/// let query = parse(r#"find "technical" | similar 0.8 | layer_up | limit 10"#).unwrap();
+/// assert!(!query.bytecode.is_empty());
+///
+/// let err = parse("find technical | simlar").unwrap_err();
+/// assert_eq!(err.span, 17..23);
+/// ```
+pub fn parse(query: &str) -> Result<CompiledQuery, ParseError> {
+    validate_query(query).map_err(|e| rejected(e, 0..query.len()))?;
+
+    let tokens = tokenize(query)?;
+    let mut stages = tokens.split(|token| token.kind == Kind::Pipe);
+    let pipes: Vec<&Token> = tokens.iter().filter(|token| token.kind == Kind::Pipe).collect();
+
+    let first = stages.next().unwrap_or(&[]);
+    let mut builder = match first.split_first() {
+        None if pipes.is_empty() => return Err(ParseError::new("empty query", 0..query.len())),
+        None => return Err(ParseError::new("empty stage before '|'", pipes[0].span.clone())),
+        Some((op, args)) => start(op, Args::new(op, args))?,
+    };
+
+    for (stage, pipe) in stages.zip(&pipes) {
+        let Some((op, args)) = stage.split_first() else {
+            return Err(ParseError::new("empty stage after '|'", pipe.span.clone()));
+        };
+        builder = apply(builder, op, Args::new(op, args))?;
+    }
+    Ok(builder.compile())
+}
+
+/// First stage, which loads the starting nodes
+fn start(op: &Token, mut args: Args<'_>) -> Result<QueryBuilder, ParseError> {
+    if op.kind != Kind::Word {
+        return Err(unknown_stage(op));
+    }
+    let builder = match op.text.as_str() {
+        "find" => QueryBuilder::find(&args.word("a word")?.text),
+        "id" => QueryBuilder::find_by_id(args.number("a node ID")?),
+        name if is_stage(name) => {
+            return Err(ParseError::new(
+                format!("query must start with 'find' or 'id', not '{}'", op.text),
+                op.span.clone(),
+            ));
+        }
+        _ => return Err(unknown_stage(op)),
+    };
+    args.finish()?;
+    Ok(builder)
+}
+
+/// Every later stage
+fn apply(builder: QueryBuilder, op: &Token, mut args: Args<'_>) -> Result<QueryBuilder, ParseError> {
+    if op.kind != Kind::Word {
+        return Err(unknown_stage(op));
+    }
+    let builder = match op.text.as_str() {
+        "find" | "id" => {
+            return Err(ParseError::new(
+                format!("'{}' can only start a query", op.text),
+                op.span.clone(),
+            ));
+        }
+        "similar" => match args.optional() {
+            Some(token) => {
+                let threshold = parse_number::<f32>(token, "a threshold")?;
+                validate_threshold(threshold).map_err(|e| rejected(e, token.span.clone()))?;
+                builder.similar_threshold(threshold)
+            }
+            None => builder.similar(),
+        },
+        "layer_up" => builder.layer_up_n(args.optional_number("a layer count")?.unwrap_or(1)),
+        "layer_down" => builder.layer_down_n(args.optional_number("a layer count")?.unwrap_or(1)),
+        "layer" => builder.layer(args.name(LAYERS, "layer")?),
+        "follow" => follow(builder, &mut args)?,
+        "filter" => builder.filter(filter(&mut args)?),
+        "limit" | "offset" => {
+            let token = args.word("a count")?;
+            let count = parse_number::<usize>(token, "a count")?;
+            if op.text == "offset" {
+                builder.offset(count)
+            } else {
+                validate_limit(count).map_err(|e| rejected(e, token.span.clone()))?;
+                builder.limit(count)
+            }
+        }
+        _ => return Err(unknown_stage(op)),
+    };
+    args.finish()?;
+    Ok(builder)
+}
+
+/// `follow`, `follow <type>` or `follow <type or any> <min> [max]`
+fn follow(builder: QueryBuilder, args: &mut Args<'_>) -> Result<QueryBuilder, ParseError> {
+    let Some(type_token) = args.optional() else {
+        return Ok(builder.follow_connection());
+    };
+    let connection_type = match type_token.text.as_str() {
+        "any" if type_token.kind == Kind::Word => None,
+        _ => Some(name_of(CONNECTION_TYPES, type_token, "connection type")?),
+    };
+
+    let Some(min_token) = args.optional() else {
+        return match connection_type {
+            Some(connection_type) => Ok(builder.follow_connection_type(connection_type)),
+            None => Err(ParseError::new("'follow any' needs a minimum strength", type_token.span.clone())),
+        };
+    };
+    let min_strength = parse_number::<f32>(min_token, "a strength")?;
+    if !(0.0..=1.0).contains(&min_strength) {
+        return Err(ParseError::new(
+            format!("strength {} is outside [0, 1]", min_strength),
+            min_token.span.clone(),
+        ));
+    }
+    let max = args.optional_number("a connection count")?.unwrap_or(usize::MAX);
+    Ok(builder.follow_connections_where(connection_type, min_strength, max))
+}
+
+/// `filter <criterion> <value>`
+fn filter(args: &mut Args<'_>) -> Result<FilterCriteria, ParseError> {
+    let criterion = args.word("a filter (layer, etymology, rank or productivity)")?;
+    Ok(match criterion.text.as_str() {
+        "layer" => FilterCriteria::Layer(args.name(LAYERS, "layer")?),
+        "etymology" => FilterCriteria::Etymology(args.name::<EtymologyOrigin>(ETYMOLOGIES, "etymology")?),
+        "rank" => FilterCriteria::FrequencyRankBelow(args.number("a frequency rank")?),
+        "productivity" => {
+            let token = args.word("a productivity")?;
+            FilterCriteria::ProductivityAbove(parse_number(token, "a productivity")?)
+        }
+        _ => {
+            return Err(ParseError::new(
+                format!("unknown filter '{}'; expected layer, etymology, rank or productivity", criterion.text),
+                criterion.span.clone(),
+            ));
+        }
+    })
+}
+
+/// Names of every stage, for telling misplaced stages from unknown ones
+fn is_stage(name: &str) -> bool {
+    matches!(
+        name,
+        "find" | "id" | "similar" | "layer_up" | "layer_down" | "layer" | "follow" | "filter" | "limit" | "offset"
+    )
+}
+
+/// A value refused by the security checks, without their error prefix
+fn rejected(error: LingoError, span: Range<usize>) -> ParseError {
+    match error {
+        LingoError::SecurityError { message } => ParseError::new(message, span),
+        other => ParseError::new(other.to_string(), span),
+    }
+}
+
+fn unknown_stage(op: &Token) -> ParseError {
+    ParseError::new(format!("unknown operation '{}'", op.text), op.span.clone())
+}
+
+fn parse_number<T: std::str::FromStr>(token: &Token, what: &str) -> Result<T, ParseError> {
+    match token.text.parse::<T>() {
+        Ok(value) if token.kind == Kind::Word => Ok(value),
+        _ => Err(ParseError::new(
+            format!("expected {}, found '{}'", what, token.text),
+            token.span.clone(),
+        )),
+    }
+}
+
+fn name_of<T: Copy>(table: &[(&str, T)], token: &Token, what: &str) -> Result<T, ParseError> {
+    lookup(table, &token.text)
+        .ok_or_else(|| ParseError::new(format!("unknown {} '{}'", what, token.text), token.span.clone()))
+}
+
+/// Arguments of one stage, consumed front to back
+struct Args<'a> {
+    /// The stage's operation, for errors about missing arguments
+    op: &'a Token,
+    rest: std::slice::Iter<'a, Token>,
+}
+
+impl<'a> Args<'a> {
+    fn new(op: &'a Token, args: &'a [Token]) -> Self {
+        Self { op, rest: args.iter() }
+    }
+
+    fn optional(&mut self) -> Option<&'a Token> {
+        self.rest.next()
+    }
+
+    /// Next argument; a missing one is reported at the stage's operation
+    fn word(&mut self, what: &str) -> Result<&'a Token, ParseError> {
+        self.rest.next().ok_or_else(|| {
+            ParseError::new(format!("'{}' expects {}", self.op.text, what), self.op.span.clone())
+        })
+    }
+
+    fn number<T: std::str::FromStr>(&mut self, what: &str) -> Result<T, ParseError> {
+        parse_number(self.word(what)?, what)
+    }
+
+    fn optional_number<T: std::str::FromStr>(&mut self, what: &str) -> Result<Option<T>, ParseError> {
+        self.optional().map(|token| parse_number(token, what)).transpose()
+    }
+
+    fn name<T: Copy>(&mut self, table: &[(&str, T)], what: &str) -> Result<T, ParseError> {
+        name_of(table, self.word(&format!("a {}", what))?, what)
+    }
+
+    /// Fail on the first argument left over
+    fn finish(mut self) -> Result<(), ParseError> {
+        match self.rest.next() {
+            Some(extra) => Err(ParseError::new(
+                format!("unexpected '{}' after '{}'", extra.text, self.op.text),
+                extra.span.clone(),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// Bare word or number
+    Word,
+    /// `"`-quoted text, never a stage or number
+    Quoted,
+    Pipe,
+}
+
+#[derive(Debug)]
+struct Token {
+    kind: Kind,
+    /// Text with quotes and escapes removed
+    text: String,
+    /// Byte range in the query, including any quotes
+    span: Range<usize>,
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = query.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '|' {
+            chars.next();
+            tokens.push(Token { kind: Kind::Pipe, text: "|".to_string(), span: start..start + 1 });
+        } else if c == '"' {
+            chars.next();
+            let mut text = String::new();
+            let end = loop {
+                match chars.next() {
+                    Some((i, '"')) => break i + 1,
+                    Some((i, '\\')) => match chars.next() {
+                        Some((_, escaped @ ('"' | '\\'))) => text.push(escaped),
+                        Some((j, other)) => {
+                            return Err(ParseError::new(
+                                format!("unknown escape '\\{}'", other),
+                                i..j + other.len_utf8(),
+                            ));
+                        }
+                        None => return Err(ParseError::new("unterminated string", start..query.len())),
+                    },
+                    Some((_, other)) => text.push(other),
+                    None => return Err(ParseError::new("unterminated string", start..query.len())),
+                }
+            };
+            tokens.push(Token { kind: Kind::Quoted, text, span: start..end });
+        } else {
+            let mut end = start;
+            while let Some(&(i, c)) = chars.peek() {
+                if c.is_whitespace() || c == '|' || c == '"' {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token { kind: Kind::Word, text: query[start..end].to_string(), span: start..end });
+        }
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::bytecode::SlangOp;
+
+    fn opcodes(query: &CompiledQuery) -> Vec<SlangOp> {
+        query.bytecode.iter().map(|instruction| instruction.opcode).collect()
+    }
+
+    #[test]
+    fn test_parse_matches_builder() {
+        let parsed = parse(r#"find "technical" | similar 0.8 | layer_up | limit 10"#).unwrap();
+        let built = QueryBuilder::find("technical")
+            .similar_threshold(0.8)
+            .layer_up()
+            .limit(10)
+            .compile();
+        assert_eq!(opcodes(&parsed), opcodes(&built));
+        assert_eq!(parsed.string_cache, built.string_cache);
+        assert_eq!(
+            parsed.bytecode.iter().map(|i| (i.operand1, { i.operand2 })).collect::<Vec<_>>(),
+            built.bytecode.iter().map(|i| (i.operand1, { i.operand2 })).collect::<Vec<_>>(),
+        );
+
+        let parsed = parse("id 42 | follow analogy 0.7 3 | filter etymology Greek | filter layer words").unwrap();
+        let built = QueryBuilder::find_by_id(42)
+            .follow_connections_where(Some(ConnectionType::Analogy), 0.7, 3)
+            .filter(FilterCriteria::Etymology(EtymologyOrigin::Greek))
+            .filter(FilterCriteria::Layer(Layer::Words))
+            .compile();
+        assert_eq!(opcodes(&parsed), opcodes(&built));
+
+        // Quoted words keep their spaces and escapes
+        let parsed = parse(r#"find "ice \"cream\"" | follow"#).unwrap();
+        assert_eq!(parsed.string_cache[0], r#"ice "cream""#);
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_input() {
+        let cases: &[(&str, Range<usize>, &str)] = &[
+            ("", 0..0, "empty query"),
+            ("find technical | simlar", 17..23, "unknown operation 'simlar'"),
+            ("find technical | limit ten", 23..26, "expected a count, found 'ten'"),
+            ("find technical | limit 0", 23..24, "Limit must be greater than 0"),
+            ("find technical || limit 5", 15..16, "empty stage after '|'"),
+            ("find technical |", 15..16, "empty stage after '|'"),
+            ("| find technical", 0..1, "empty stage before '|'"),
+            ("similar 0.8", 0..7, "must start with 'find' or 'id'"),
+            ("find a | find b", 9..13, "can only start a query"),
+            ("find", 0..4, "'find' expects a word"),
+            ("find \"tech | limit 5", 5..20, "unterminated string"),
+            ("find tech | similar 1.5", 20..23, "outside [0, 1]"),
+            ("find tech | layer_up 2 3", 23..24, "unexpected '3' after 'layer_up'"),
+            ("find tech | filter layer verbs", 25..30, "unknown layer 'verbs'"),
+            ("find tech | follow rhyme", 19..24, "unknown connection type 'rhyme'"),
+            ("find tech | limit \"5\"", 18..21, "expected a count"),
+        ];
+        for (query, span, message) in cases {
+            let err = parse(query).unwrap_err();
+            assert_eq!(&err.span, span, "{}: {}", query, err);
+            assert!(err.message.contains(message), "{}: {}", query, err);
+        }
+
+        let query = "find tech | simlar";
+        assert_eq!(parse(query).unwrap_err().highlight(query), "find tech | simlar\n            ^^^^^^");
+    }
+}
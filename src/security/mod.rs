@@ -5,7 +5,7 @@
 //! - Input sanitization for queries
 //! - Size limits to prevent DoS attacks
 
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use crate::core::error::{LingoError, Result};
 
 /// Maximum allowed file size (100MB)
@@ -17,6 +17,102 @@ pub const MAX_QUERY_LENGTH: usize = 10 * 1024;
 /// Maximum number of nodes in a query result
 pub const MAX_RESULT_NODES: usize = 10_000;
 
+/// Maximum number of bytecode instructions per query
+pub const MAX_INSTRUCTIONS: usize = 10_000;
+
+/// Resource caps applied to a single query execution
+///
+/// The default is the untrusted profile, capped by the global constants.
+/// Trusted internal callers can raise the caps per execution without
+/// loosening them for everyone else. The fields are private, so limits
+/// derived from [`untrusted`](Self::untrusted) can only be tightened.
+///
+/// # Example
+/// ```
+/// use lingo::security::{ExecutionLimits, MAX_RESULT_NODES};
+///
+/// let limits = ExecutionLimits::trusted().with_max_result_nodes(50_000);
+/// assert!(limits.max_result_nodes() > MAX_RESULT_NODES);
+/// assert_eq!(ExecutionLimits::default().max_result_nodes(), MAX_RESULT_NODES);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionLimits {
+    max_result_nodes: usize,
+    max_instructions: usize,
+    budget: Option<u64>,
+}
+
+impl ExecutionLimits {
+    /// Limits for untrusted callers, capped by the global constants
+    pub fn untrusted() -> Self {
+        Self {
+            max_result_nodes: MAX_RESULT_NODES,
+            max_instructions: MAX_INSTRUCTIONS,
+            budget: None,
+        }
+    }
+    
+    /// Limits for trusted internal callers with no result or instruction cap
+    pub fn trusted() -> Self {
+        Self {
+            max_result_nodes: usize::MAX,
+            max_instructions: usize::MAX,
+            budget: None,
+        }
+    }
+    
+    /// Maximum nodes returned from a query
+    pub fn max_result_nodes(&self) -> usize {
+        self.max_result_nodes
+    }
+    
+    /// Maximum instructions executed before the query is aborted
+    pub fn max_instructions(&self) -> usize {
+        self.max_instructions
+    }
+    
+    /// Work units an execution may spend before it's aborted, if any.
+    ///
+    /// Each instruction costs one unit plus one per node in the set it
+    /// leaves behind, so a hop onto a densely connected node is charged
+    /// for every node it reaches.
+    pub fn budget(&self) -> Option<u64> {
+        self.budget
+    }
+    
+    /// Lower the maximum number of result nodes.
+    ///
+    /// The cap only tightens: values above the current cap are clamped to
+    /// it, so untrusted limits never exceed [`MAX_RESULT_NODES`]. Start from
+    /// [`trusted`](Self::trusted) to allow more.
+    pub fn with_max_result_nodes(mut self, max: usize) -> Self {
+        self.max_result_nodes = self.max_result_nodes.min(max);
+        self
+    }
+    
+    /// Lower the maximum number of instructions, clamped like
+    /// [`with_max_result_nodes`](Self::with_max_result_nodes)
+    pub fn with_max_instructions(mut self, max: usize) -> Self {
+        self.max_instructions = self.max_instructions.min(max);
+        self
+    }
+    
+    /// Set the work budget, in the units of [`budget`](Self::budget).
+    ///
+    /// Like the other caps it only tightens: an existing budget smaller
+    /// than `budget` is kept.
+    pub fn with_budget(mut self, budget: u64) -> Self {
+        self.budget = Some(self.budget.map_or(budget, |current| current.min(budget)));
+        self
+    }
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        Self::untrusted()
+    }
+}
+
 /// Validates and canonicalizes a file path to prevent directory traversal attacks
 /// 
 /// # Arguments
@@ -84,6 +180,110 @@ pub fn validate_path<P: AsRef<Path>>(
     Ok(canonical)
 }
 
+/// Resolves a path inside a sandbox directory, for files that may not
+/// exist yet
+///
+/// Unlike [`validate_path`], relative paths such as `"data/english.lingo"`
+/// are allowed and joined onto `sandbox`, and `..` is resolved logically
+/// rather than by the filesystem, so the target file (and directories
+/// leading to it) need not exist. A relative path is rejected only if it
+/// climbs above the sandbox at some point, even if it later comes back in.
+/// Absolute paths are accepted if they lie inside the sandbox.
+///
+/// The sandbox itself must exist. Every symlink along the path is
+/// followed as it is reached, so a link inside the sandbox can't point the
+/// path elsewhere, and a dangling link is rejected because its target
+/// can't be checked. Use [`validate_path`] to open existing files
+/// read-only.
+///
+/// # Arguments
+/// * `sandbox` - Directory the path must stay inside
+/// * `path` - Path to resolve, relative to `sandbox` or absolute
+///
+/// # Returns
+/// * `Ok(PathBuf)` - The resolved path, under the canonical sandbox
+/// * `Err(LingoError)` - If the sandbox is missing or the path escapes it
+///
+/// # Example
+/// ```
+/// use lingo::security::validate_path_in;
+///
+/// # fn main() -> lingo::core::error::Result<()> {
+/// let sandbox = std::env::temp_dir();
+/// let output = validate_path_in(&sandbox, "exports/../english.lingo")?;
+/// assert_eq!(output, sandbox.canonicalize()?.join("english.lingo"));
+///
+/// assert!(validate_path_in(&sandbox, "../../etc/passwd").is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn validate_path_in<S: AsRef<Path>, P: AsRef<Path>>(sandbox: S, path: P) -> Result<PathBuf> {
+    let root = sandbox.as_ref().canonicalize()
+        .map_err(|e| LingoError::SecurityError {
+            message: format!("Failed to canonicalize sandbox: {}", e)
+        })?;
+    let path = path.as_ref();
+    let escape = || LingoError::SecurityError {
+        message: format!("Path {} leaves the sandbox", path.display())
+    };
+    
+    // Absolute paths are resolved from the filesystem root and then have to
+    // land under the sandbox; relative ones may never climb above it
+    let absolute = path.has_root();
+    let mut resolved = if absolute { PathBuf::new() } else { root.clone() };
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => resolved.push(component.as_os_str()),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !absolute && resolved == root {
+                    return Err(escape());
+                }
+                resolved.pop();
+            }
+            Component::Normal(part) => {
+                resolved.push(part);
+                resolved = resolve_symlink(resolved, &root, path)?;
+            }
+        }
+    }
+    if !resolved.starts_with(&root) {
+        return Err(escape());
+    }
+    
+    Ok(resolved)
+}
+
+/// Replaces `resolved` with its canonical target if it is a symlink
+///
+/// A link inside the sandbox must point inside it, and a dangling link is
+/// rejected outright, since writing through it would create its target
+/// wherever it points.
+fn resolve_symlink(resolved: PathBuf, root: &Path, path: &Path) -> Result<PathBuf> {
+    let is_link = std::fs::symlink_metadata(&resolved)
+        .is_ok_and(|metadata| metadata.file_type().is_symlink());
+    if !is_link {
+        return Ok(resolved);
+    }
+    
+    let target = std::fs::read_link(&resolved)
+        .and_then(|target| match resolved.parent() {
+            Some(parent) => parent.join(target).canonicalize(),
+            None => target.canonicalize(),
+        })
+        .map_err(|e| LingoError::SecurityError {
+            message: format!("Path {} goes through a dangling symlink {}: {}",
+                path.display(), resolved.display(), e)
+        })?;
+    if resolved.starts_with(root) && !target.starts_with(root) {
+        return Err(LingoError::SecurityError {
+            message: format!("Path {} leaves the sandbox", path.display())
+        });
+    }
+    
+    Ok(target)
+}
+
 /// Validates a query string for safety
 /// 
 /// # Arguments
@@ -134,6 +334,17 @@ pub fn validate_limit(limit: usize) -> Result<usize> {
     Ok(limit)
 }
 
+/// Validates a similarity threshold, which must lie in [0, 1]
+pub fn validate_threshold(threshold: f32) -> Result<f32> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(LingoError::SecurityError {
+            message: format!("Similarity threshold {} is outside [0, 1]", threshold)
+        });
+    }
+    
+    Ok(threshold)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,6 +374,54 @@ mod tests {
         assert!(validate_path("/etc/passwd", Some(&allowed)).is_err());
     }
     
+    #[test]
+    fn test_validate_path_in_sandbox() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        
+        // Relative paths resolve inside the sandbox whether or not they exist
+        assert_eq!(validate_path_in(&root, "a/../b").unwrap(), root.join("b"));
+        assert_eq!(validate_path_in(&root, "./data/english.lingo").unwrap(), root.join("data/english.lingo"));
+        assert_eq!(validate_path_in(&root, root.join("out.lingo")).unwrap(), root.join("out.lingo"));
+        
+        // Escapes are rejected, even ones that come back in
+        assert!(validate_path_in(&root, "../../etc").is_err());
+        assert!(validate_path_in(&root, "a/../../b").is_err());
+        let name = root.file_name().unwrap().to_str().unwrap();
+        assert!(validate_path_in(&root, format!("../{}/b", name)).is_err());
+        assert!(validate_path_in(&root, "/etc/passwd").is_err());
+        assert!(validate_path_in(root.join("missing"), "b").is_err());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_path_in_rejects_symlink_escape() {
+        let sandbox = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        std::os::unix::fs::symlink(outside.path(), sandbox.path().join("link")).unwrap();
+        
+        assert!(validate_path_in(sandbox.path(), "link/out.lingo").is_err());
+        assert!(validate_path_in(sandbox.path(), "real/out.lingo").is_ok());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_path_in_rejects_dangling_symlink() {
+        let sandbox = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let root = sandbox.path().canonicalize().unwrap();
+        std::os::unix::fs::symlink(outside.path().join("missing.lingo"), root.join("out.lingo")).unwrap();
+        std::os::unix::fs::symlink(root.join("data"), root.join("inside")).unwrap();
+        
+        // Writing through either link would create a file at its target
+        assert!(validate_path_in(&root, "out.lingo").is_err());
+        assert!(validate_path_in(&root, "inside/english.lingo").is_err());
+        
+        // A link that resolves inside the sandbox is followed
+        std::fs::create_dir(root.join("data")).unwrap();
+        assert_eq!(validate_path_in(&root, "inside/english.lingo").unwrap(), root.join("data/english.lingo"));
+    }
+    
     #[test]
     fn test_validate_query() {
         // Valid queries
@@ -181,4 +440,25 @@ mod tests {
         assert!(validate_limit(0).is_err());
         assert!(validate_limit(MAX_RESULT_NODES + 1).is_err());
     }
+    
+    #[test]
+    fn test_execution_limits_only_tighten() {
+        let lifted = ExecutionLimits::untrusted()
+            .with_max_result_nodes(usize::MAX)
+            .with_max_instructions(usize::MAX);
+        assert_eq!(lifted, ExecutionLimits::untrusted());
+        
+        let lowered = ExecutionLimits::untrusted().with_max_result_nodes(10);
+        assert_eq!(lowered.max_result_nodes(), 10);
+        
+        let trusted = ExecutionLimits::trusted()
+            .with_max_result_nodes(50_000)
+            .with_max_instructions(MAX_INSTRUCTIONS * 10);
+        assert_eq!(trusted.max_result_nodes(), 50_000);
+        assert_eq!(trusted.max_instructions(), MAX_INSTRUCTIONS * 10);
+        
+        let budgeted = ExecutionLimits::untrusted().with_budget(50).with_budget(1_000);
+        assert_eq!(budgeted.budget(), Some(50));
+        assert_eq!(budgeted.with_budget(20).budget(), Some(20));
+    }
 }
\ No newline at end of file
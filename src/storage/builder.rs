@@ -0,0 +1,687 @@
+//! Database builder for writing Lingo database files
+
+use crate::core::{
+    NodeId, LinguisticNode, OrthogonalConnection, Coordinate3D, clamp01,
+    Layer, EtymologyOrigin, MorphemeType, NodeFlags, ConnectionType, PhonemeFeatures,
+    error::{BuildError, Result},
+};
+use crate::storage::{FileFormatFlags, StringTable};
+use crate::logging::{debug, info, trace, warn};
+use super::writer::write_database;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Non-fatal problem found while validating a database build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildWarning {
+    /// A composed word references a morpheme that was never added
+    MissingMorpheme {
+        /// The composed word
+        word: String,
+        /// The morpheme form that couldn't be resolved
+        morpheme: String,
+    },
+    /// Two stored surface forms are within one edit of each other
+    NearDuplicate {
+        /// First form, in sorted order
+        first: String,
+        /// Second form, in sorted order
+        second: String,
+    },
+}
+
+/// Summary of a completed build
+#[derive(Debug, Default)]
+pub struct BuildReport {
+    /// Problems that didn't stop the build
+    pub warnings: Vec<BuildWarning>,
+    /// Connections dropped by [`SymmetricConnectionMode`] handling
+    pub merged_connections: usize,
+}
+
+/// How the builder treats repeated and mirrored connections.
+///
+/// Imports often add both A→B and B→A for symmetric relations such as
+/// antonymy, or add the same edge twice, which inflates degree counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SymmetricConnectionMode {
+    /// Store every connection as added
+    #[default]
+    Keep,
+    /// Drop exact duplicates (same source, target and type), keeping the
+    /// strongest. Reverse edges are kept.
+    DedupExact,
+    /// Also store each bidirectional-type pair once, under whichever
+    /// direction was added first.
+    ///
+    /// The type marks the edge as bidirectional (see
+    /// [`ConnectionType::is_bidirectional`]). Query traversals follow it
+    /// from either end; direct readers of
+    /// [`get_node_connections`](crate::storage::MemoryMappedDatabase::get_node_connections)
+    /// should check incoming connections as well as outgoing ones.
+    /// The file is marked with
+    /// [`FileFormatFlags::SYMMETRIC_STORED_ONCE`] so that only these files
+    /// pay for the incoming-edge lookup.
+    StoreOnce,
+}
+
+/// Builds a Lingo database in memory and writes it to disk.
+///
+/// Nodes are assigned sequential IDs starting from 1, matching the
+/// addressing used by [`MemoryMappedDatabase`](crate::storage::MemoryMappedDatabase).
+/// Connections are grouped per source node when the file is written.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lingo::storage::DatabaseBuilder;
+/// use lingo::core::{Layer, Coordinate3D, ConnectionType};
+///
+/// # fn main() -> lingo::core::error::Result<()> {
+/// let mut builder = DatabaseBuilder::new();
+/// let hot = builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52))?;
+/// let cold = builder.add_node("cold", Layer::Words, Coordinate3D::new(0.8, 0.5, 0.52))?;
+/// builder.add_connection(hot, cold, ConnectionType::Antonymy, 0.9)?;
+/// builder.build("temperature.lingo")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct DatabaseBuilder {
+    /// Nodes in ID order
+    nodes: Vec<LinguisticNode>,
+    /// Outgoing connections as (source, connection)
+    connections: Vec<(NodeId, OrthogonalConnection)>,
+    /// Deduplicated surface forms
+    strings: StringTable,
+    /// Composed words and the morpheme forms they are built from
+    compositions: Vec<(NodeId, Vec<String>)>,
+    /// Fail the build on validation problems instead of warning
+    strict: bool,
+    /// Report near-duplicate surface forms as build warnings
+    report_near_duplicates: bool,
+    /// Handling of duplicate and mirrored connections
+    symmetric_connections: SymmetricConnectionMode,
+    /// Per-type strengths that replace [`ConnectionType::default_strength`]
+    default_strengths: HashMap<ConnectionType, f32>,
+}
+
+impl DatabaseBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of nodes added so far
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of connections added so far
+    pub fn connection_count(&self) -> usize {
+        self.connections.len()
+    }
+
+    /// Number of distinct surface forms stored.
+    ///
+    /// Identical words share a single string table entry.
+    pub fn unique_string_count(&self) -> usize {
+        self.strings.entry_count()
+    }
+
+    /// Add a node with default linguistic properties
+    pub fn add_node(&mut self, word: &str, layer: Layer, position: Coordinate3D) -> Result<NodeId> {
+        self.add_node_full(
+            word,
+            layer,
+            position,
+            EtymologyOrigin::Unknown,
+            MorphemeType::Root,
+            NodeFlags::empty(),
+        )
+    }
+
+    /// Add a node with all linguistic properties specified
+    pub fn add_node_full(
+        &mut self,
+        word: &str,
+        layer: Layer,
+        position: Coordinate3D,
+        etymology: EtymologyOrigin,
+        morpheme_type: MorphemeType,
+        flags: NodeFlags,
+    ) -> Result<NodeId> {
+        check_new_node(self.nodes.len(), word, position)?;
+
+        let id = NodeId(self.nodes.len() as u32 + 1);
+        let word_offset = self.strings.add_string(word)?;
+
+        let mut node = LinguisticNode::new(id, layer, position);
+        node.word_offset = word_offset;
+        node.word_length = word.len() as u16;
+        node.etymology_origin = etymology;
+        node.morpheme_type = morpheme_type;
+        node.flags = flags;
+
+        self.nodes.push(node);
+        Ok(id)
+    }
+
+    /// Add a Phonemes-layer node with its articulatory features
+    pub fn add_phoneme(&mut self, symbol: &str, position: Coordinate3D, features: PhonemeFeatures) -> Result<NodeId> {
+        let id = self.add_node(symbol, Layer::Phonemes, position)?;
+        if let Some(node) = self.node_mut(id) {
+            node.set_phoneme_features(features);
+        }
+        Ok(id)
+    }
+
+    /// Add a word composed from morphemes.
+    ///
+    /// The morpheme forms are resolved against Morphemes-layer nodes when the
+    /// database is built, so morphemes may be added before or after the word.
+    /// Each resolved morpheme gets a `Derivation` connection from the word.
+    pub fn add_composed_word(
+        &mut self,
+        word: &str,
+        layer: Layer,
+        position: Coordinate3D,
+        morphemes: &[&str],
+    ) -> Result<NodeId> {
+        let id = self.add_node_full(
+            word,
+            layer,
+            position,
+            EtymologyOrigin::Unknown,
+            MorphemeType::Compound,
+            NodeFlags::empty(),
+        )?;
+        self.compositions.push((id, morphemes.iter().map(|m| m.to_string()).collect()));
+        Ok(id)
+    }
+
+    /// Treat validation problems as errors rather than warnings
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Report surface forms within one edit of each other as build warnings.
+    ///
+    /// These are never errors, even when the builder is strict, since variant
+    /// spellings are often legitimate.
+    pub fn set_report_near_duplicates(&mut self, report: bool) {
+        self.report_near_duplicates = report;
+    }
+
+    /// Choose how duplicate and mirrored connections are stored.
+    ///
+    /// Applies to explicit connections and composition edges alike.
+    pub fn set_symmetric_connections(&mut self, mode: SymmetricConnectionMode) {
+        self.symmetric_connections = mode;
+    }
+
+    /// Get a node that has already been added
+    pub fn node(&self, id: NodeId) -> Option<&LinguisticNode> {
+        if id.0 == 0 {
+            return None;
+        }
+        self.nodes.get(id.0 as usize - 1)
+    }
+
+    /// Get a mutable reference to a node that has already been added
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut LinguisticNode> {
+        if id.0 == 0 {
+            return None;
+        }
+        self.nodes.get_mut(id.0 as usize - 1)
+    }
+
+    /// Add a directed connection between two existing nodes
+    pub fn add_connection(
+        &mut self,
+        from: NodeId,
+        to: NodeId,
+        connection_type: ConnectionType,
+        strength: f32,
+    ) -> Result<()> {
+        for id in [from, to] {
+            if id.0 == 0 || id.0 as usize > self.nodes.len() {
+                return Err(BuildError::InvalidConnection {
+                    reason: format!("unknown node {}", id),
+                }.into());
+            }
+        }
+
+        self.connections.push((from, OrthogonalConnection::new(to, connection_type, strength)));
+        Ok(())
+    }
+
+    /// Record how a word is pronounced, as a sequence of phoneme nodes.
+    ///
+    /// The node packs no room for a sequence, so each sound becomes a
+    /// `Phonetic` connection from the word to a Phonemes-layer node, in
+    /// order, with the default `Phonetic` strength. Repeated sounds get one
+    /// connection per occurrence and are never merged, whatever the
+    /// [`SymmetricConnectionMode`]. Calling this again for the same word
+    /// replaces its pronunciation rather than extending it. Read the
+    /// sequence back with
+    /// [`MemoryMappedDatabase::pronunciation`](crate::storage::MemoryMappedDatabase::pronunciation).
+    pub fn add_pronunciation(&mut self, word: NodeId, phonemes: &[NodeId]) -> Result<()> {
+        for &id in std::iter::once(&word).chain(phonemes) {
+            if self.node(id).is_none() {
+                return Err(BuildError::InvalidConnection {
+                    reason: format!("unknown node {}", id),
+                }.into());
+            }
+        }
+        if let Some(&id) = phonemes.iter().find(|&&id| !self.is_phoneme(id)) {
+            return Err(BuildError::InvalidConnection {
+                reason: format!("{} is not on the Phonemes layer", id),
+            }.into());
+        }
+
+        // Drop any earlier pronunciation, which would otherwise run on into this one
+        let nodes = &self.nodes;
+        let is_phoneme = |id: NodeId| {
+            id.0 != 0 && nodes.get(id.0 as usize - 1).is_some_and(|node| node.layer == Layer::Phonemes)
+        };
+        self.connections.retain(|(from, connection)| {
+            *from != word
+                || connection.connection_type != ConnectionType::Phonetic
+                || !is_phoneme(connection.target_node)
+        });
+
+        let strength = self.default_strength(ConnectionType::Phonetic);
+        for &phoneme in phonemes {
+            self.connections.push((word, OrthogonalConnection::new(phoneme, ConnectionType::Phonetic, strength)));
+        }
+        Ok(())
+    }
+
+    /// Whether `id` is a Phonemes-layer node
+    fn is_phoneme(&self, id: NodeId) -> bool {
+        self.node(id).is_some_and(|node| node.layer == Layer::Phonemes)
+    }
+
+    /// Add a directed connection using the default strength for its type.
+    ///
+    /// The strength comes from [`set_default_strength`](Self::set_default_strength)
+    /// if one was set for `connection_type`, otherwise from
+    /// [`ConnectionType::default_strength`].
+    pub fn add_default_connection(
+        &mut self,
+        from: NodeId,
+        to: NodeId,
+        connection_type: ConnectionType,
+    ) -> Result<()> {
+        let strength = self.default_strength(connection_type);
+        self.add_connection(from, to, connection_type, strength)
+    }
+
+    /// Override the default strength for one connection type.
+    ///
+    /// The value is clamped to 0.0-1.0, and NaN becomes 0.0. Composition
+    /// edges from [`add_composed_word`](Self::add_composed_word) use the
+    /// `Derivation` default.
+    pub fn set_default_strength(&mut self, connection_type: ConnectionType, strength: f32) {
+        self.default_strengths.insert(connection_type, clamp01(strength));
+    }
+
+    /// Strength used for `connection_type` when none is given
+    pub fn default_strength(&self, connection_type: ConnectionType) -> f32 {
+        self.default_strengths
+            .get(&connection_type)
+            .copied()
+            .unwrap_or_else(|| connection_type.default_strength())
+    }
+
+    /// Add a fully specified connection whose endpoints are already known to exist
+    pub(crate) fn push_connection(&mut self, from: NodeId, connection: OrthogonalConnection) {
+        self.connections.push((from, connection));
+    }
+
+    /// Write the database to `path`.
+    ///
+    /// Validation problems are returned as warnings in the [`BuildReport`],
+    /// or as errors when the builder is strict.
+    pub fn build<P: AsRef<Path>>(&self, path: P) -> Result<BuildReport> {
+        let path = path.as_ref();
+        info!(
+            path = %path.display(),
+            nodes = self.nodes.len(),
+            connections = self.connections.len(),
+            "Building Lingo database"
+        );
+
+        let mut report = BuildReport::default();
+        let composition_edges = self.resolve_compositions(&mut report)?;
+        if self.report_near_duplicates {
+            for (first, second) in self.strings.near_duplicates() {
+                debug!(first = %first, second = %second, "Near-duplicate surface forms");
+                report.warnings.push(BuildWarning::NearDuplicate { first, second });
+            }
+        }
+
+        let edges = self.merge_connections(self.connections.iter().chain(&composition_edges));
+        report.merged_connections = self.connections.len() + composition_edges.len() - edges.len();
+
+        let format_flags = if self.symmetric_connections == SymmetricConnectionMode::StoreOnce {
+            FileFormatFlags::SYMMETRIC_STORED_ONCE
+        } else {
+            FileFormatFlags::empty()
+        };
+        write_database(path, self.nodes.clone(), &edges, &self.strings, None, format_flags)?;
+        Ok(report)
+    }
+
+    /// Apply the symmetric connection mode, keeping first-seen order
+    fn merge_connections<'a>(
+        &self,
+        edges: impl Iterator<Item = &'a (NodeId, OrthogonalConnection)>,
+    ) -> Vec<(NodeId, OrthogonalConnection)> {
+        if self.symmetric_connections == SymmetricConnectionMode::Keep {
+            return edges.copied().collect();
+        }
+
+        let mut merged: Vec<(NodeId, OrthogonalConnection)> = Vec::new();
+        let mut seen: HashMap<(NodeId, NodeId, ConnectionType), usize> = HashMap::new();
+        for &(from, conn) in edges {
+            let to = conn.target_node;
+            let connection_type = conn.connection_type;
+            // Pronunciations are sequences, so repeated sounds must stay
+            if connection_type == ConnectionType::Phonetic && self.is_phoneme(to) {
+                merged.push((from, conn));
+                continue;
+            }
+            let key = if self.symmetric_connections == SymmetricConnectionMode::StoreOnce
+                && connection_type.is_bidirectional()
+            {
+                (from.min(to), from.max(to), connection_type)
+            } else {
+                (from, to, connection_type)
+            };
+
+            match seen.get(&key) {
+                Some(&index) => {
+                    let kept = &mut merged[index].1;
+                    let strength = conn.strength.max(kept.strength);
+                    kept.strength = strength;
+                    trace!(from = %from, to = %to, "Merged duplicate connection");
+                }
+                None => {
+                    seen.insert(key, merged.len());
+                    merged.push((from, conn));
+                }
+            }
+        }
+        merged
+    }
+
+    /// Resolve composed words to morpheme nodes, recording any that are missing
+    fn resolve_compositions(&self, report: &mut BuildReport) -> Result<Vec<(NodeId, OrthogonalConnection)>> {
+        let mut morphemes_by_form = HashMap::new();
+        for node in &self.nodes {
+            if node.layer == Layer::Morphemes {
+                let form = self.node_word(node);
+                morphemes_by_form.entry(form).or_insert(node.id);
+            }
+        }
+
+        let strength = self.default_strength(ConnectionType::Derivation);
+        let mut edges = Vec::new();
+        for (word_id, forms) in &self.compositions {
+            let word = self.node_word(&self.nodes[word_id.0 as usize - 1]);
+            for form in forms {
+                match morphemes_by_form.get(form.as_str()) {
+                    Some(&morpheme_id) => edges.push((
+                        *word_id,
+                        OrthogonalConnection::new(morpheme_id, ConnectionType::Derivation, strength),
+                    )),
+                    None if self.strict => {
+                        return Err(BuildError::MissingMorpheme {
+                            word: word.to_string(),
+                            morpheme: form.clone(),
+                        }.into());
+                    }
+                    None => {
+                        warn!(word = word, morpheme = %form, "Composed word references missing morpheme");
+                        report.warnings.push(BuildWarning::MissingMorpheme {
+                            word: word.to_string(),
+                            morpheme: form.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(edges)
+    }
+
+    /// Surface form of a node that has been added to this builder
+    pub(crate) fn node_word(&self, node: &LinguisticNode) -> &str {
+        self.strings
+            .get_string(node.word_offset, node.word_length)
+            .unwrap_or("")
+    }
+}
+
+/// Check that a node can be added after `existing` nodes
+pub(crate) fn check_new_node(existing: usize, word: &str, position: Coordinate3D) -> Result<()> {
+    if existing >= u32::MAX as usize - 1 {
+        return Err(BuildError::TooManyNodes {
+            count: existing + 1,
+            max: u32::MAX as usize - 1,
+        }.into());
+    }
+    if word.len() > u16::MAX as usize {
+        return Err(BuildError::StringTooLong {
+            length: word.len(),
+            max: u16::MAX as usize,
+        }.into());
+    }
+    if ![position.x, position.y, position.z].iter().all(|v| v.is_finite()) {
+        return Err(BuildError::InvalidCoordinate {
+            reason: format!("non-finite position for '{}'", word),
+        }.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::LingoError;
+    use crate::storage::MemoryMappedDatabase;
+    use crate::test_support::open_database;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_build_and_open_roundtrip() {
+        let mut builder = DatabaseBuilder::new();
+        let hot = builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        let cold = builder.add_node("cold", Layer::Words, Coordinate3D::new(0.8, 0.5, 0.52)).unwrap();
+        builder.add_connection(hot, cold, ConnectionType::Antonymy, 0.9).unwrap();
+        let (_dir, db) = open_database(&builder);
+        assert_eq!(db.node_count(), 2);
+        assert_eq!(db.connection_count(), 1);
+        assert_eq!(db.get_node_word(cold).unwrap(), "cold");
+        assert_eq!(db.find_nodes_by_word("hot"), vec![hot]);
+
+        let conns = db.get_node_connections(hot).unwrap();
+        assert_eq!(conns.len(), 1);
+        let target = conns[0].target_node;
+        assert_eq!(target, cold);
+        assert!(db.get_node_connections(cold).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_composed_word_missing_morpheme() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("compose.lingo");
+
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("un", Layer::Morphemes, Coordinate3D::new(0.3, 0.3, 0.35)).unwrap();
+        let word = builder.add_composed_word(
+            "undoable",
+            Layer::Words,
+            Coordinate3D::new(0.4, 0.3, 0.52),
+            &["un", "do", "able"],
+        ).unwrap();
+        builder.add_node("do", Layer::Morphemes, Coordinate3D::new(0.5, 0.2, 0.35)).unwrap();
+
+        let report = builder.build(&path).unwrap();
+        assert_eq!(report.warnings, vec![BuildWarning::MissingMorpheme {
+            word: "undoable".to_string(),
+            morpheme: "able".to_string(),
+        }]);
+
+        // Resolved morphemes are still connected
+        let db = MemoryMappedDatabase::open(&path).unwrap();
+        assert_eq!(db.get_node_connections(word).unwrap().len(), 2);
+
+        builder.set_strict(true);
+        let result = builder.build(&path);
+        assert!(matches!(result, Err(LingoError::Build(BuildError::MissingMorpheme { .. }))));
+    }
+
+    #[test]
+    fn test_duplicate_words_share_string() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("dedup.lingo");
+
+        let mut builder = DatabaseBuilder::new();
+        let a = builder.add_node("bank", Layer::Words, Coordinate3D::new(0.3, 0.2, 0.5)).unwrap();
+        let b = builder.add_node("bank", Layer::Words, Coordinate3D::new(0.7, 0.4, 0.5)).unwrap();
+        builder.add_node("banks", Layer::Words, Coordinate3D::new(0.3, 0.2, 0.52)).unwrap();
+        assert_eq!(builder.unique_string_count(), 2);
+
+        let offset_a = builder.node_mut(a).unwrap().word_offset;
+        let offset_b = builder.node_mut(b).unwrap().word_offset;
+        assert_eq!(offset_a, offset_b);
+
+        builder.set_report_near_duplicates(true);
+        let report = builder.build(&path).unwrap();
+        assert_eq!(report.warnings, vec![BuildWarning::NearDuplicate {
+            first: "bank".to_string(),
+            second: "banks".to_string(),
+        }]);
+
+        let db = MemoryMappedDatabase::open(&path).unwrap();
+        assert_eq!(db.get_node_word(a).unwrap(), "bank");
+        assert_eq!(db.get_node_word(b).unwrap(), "bank");
+    }
+
+    #[test]
+    fn test_seeder_morpheme_types_roundtrip() {
+        use crate::data::seed_database;
+
+        let mut builder = DatabaseBuilder::new();
+        seed_database(&mut builder).unwrap();
+        let (_dir, db) = open_database(&builder);
+
+        let morpheme = |form: &str| {
+            let id = db.find_nodes_by_word(form).into_iter()
+                .find(|&id| db.get_node(id).unwrap().layer == Layer::Morphemes)
+                .unwrap();
+            *db.get_node(id).unwrap()
+        };
+        let ize = morpheme("ize");
+        let able = morpheme("able");
+        let (ize_type, ize_origin) = (ize.morpheme_type, ize.etymology_origin);
+        let able_type = able.morpheme_type;
+        assert_eq!(ize_type, MorphemeType::ActionSuffix);
+        assert_eq!(ize_origin, EtymologyOrigin::Greek);
+        assert_eq!(able_type, MorphemeType::QualitySuffix);
+        assert!((able.productivity() - 0.85).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_symmetric_connections_merged_per_mode() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("symmetric.lingo");
+
+        let mut builder = DatabaseBuilder::new();
+        let hot = builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        let cold = builder.add_node("cold", Layer::Words, Coordinate3D::new(0.8, 0.5, 0.52)).unwrap();
+        builder.add_connection(hot, cold, ConnectionType::Antonymy, 0.8).unwrap();
+        builder.add_connection(cold, hot, ConnectionType::Antonymy, 0.9).unwrap();
+        builder.add_connection(hot, cold, ConnectionType::Antonymy, 0.7).unwrap();
+        // Hypernymy isn't symmetric, so its reverse is never folded
+        builder.add_connection(hot, cold, ConnectionType::Hypernymy, 0.5).unwrap();
+        builder.add_connection(cold, hot, ConnectionType::Hypernymy, 0.5).unwrap();
+
+        let report = builder.build(&path).unwrap();
+        assert_eq!(report.merged_connections, 0);
+        assert_eq!(MemoryMappedDatabase::open(&path).unwrap().connection_count(), 5);
+
+        builder.set_symmetric_connections(SymmetricConnectionMode::DedupExact);
+        let report = builder.build(&path).unwrap();
+        assert_eq!(report.merged_connections, 1);
+        let db = MemoryMappedDatabase::open(&path).unwrap();
+        assert_eq!(db.get_node_connections(hot).unwrap().len(), 2);
+        assert_eq!(db.get_node_connections(cold).unwrap().len(), 2);
+
+        builder.set_symmetric_connections(SymmetricConnectionMode::StoreOnce);
+        let report = builder.build(&path).unwrap();
+        assert_eq!(report.merged_connections, 2);
+        let db = MemoryMappedDatabase::open(&path).unwrap();
+        let from_hot = db.get_node_connections(hot).unwrap();
+        assert_eq!(from_hot.len(), 2);
+        let antonym = from_hot.iter().find(|c| c.connection_type == ConnectionType::Antonymy).unwrap();
+        assert!((antonym.strength_normalized() - 0.9).abs() < 1e-3);
+        let from_cold = db.get_node_connections(cold).unwrap();
+        assert_eq!(from_cold.len(), 1);
+        let cold_type = from_cold[0].connection_type;
+        assert_eq!(cold_type, ConnectionType::Hypernymy);
+    }
+
+    #[test]
+    fn test_default_connection_strength_per_type() {
+        let mut builder = DatabaseBuilder::new();
+        let big = builder.add_node("big", Layer::Words, Coordinate3D::new(0.4, 0.5, 0.52)).unwrap();
+        let large = builder.add_node("large", Layer::Words, Coordinate3D::new(0.45, 0.5, 0.52)).unwrap();
+        let ocean = builder.add_node("ocean", Layer::Words, Coordinate3D::new(0.7, 0.3, 0.52)).unwrap();
+        builder.add_default_connection(big, large, ConnectionType::Synonymy).unwrap();
+        builder.add_default_connection(big, ocean, ConnectionType::Analogy).unwrap();
+        builder.set_default_strength(ConnectionType::Collocation, 0.25);
+        builder.add_default_connection(large, ocean, ConnectionType::Collocation).unwrap();
+        let (_dir, db) = open_database(&builder);
+        let from_big = db.get_node_connections(big).unwrap();
+        assert!((from_big[0].strength_normalized() - 0.9).abs() < 1e-3);
+        assert!((from_big[1].strength_normalized() - 0.5).abs() < 1e-3);
+        let from_large = db.get_node_connections(large).unwrap();
+        assert!((from_large[0].strength_normalized() - 0.25).abs() < 1e-3);
+
+        builder.set_default_strength(ConnectionType::Collocation, f32::NAN);
+        assert_eq!(builder.default_strength(ConnectionType::Collocation), 0.0);
+    }
+
+    #[test]
+    fn test_connection_to_unknown_node() {
+        let mut builder = DatabaseBuilder::new();
+        let a = builder.add_node("a", Layer::Letters, Coordinate3D::new(0.1, 0.1, 0.05)).unwrap();
+        let result = builder.add_connection(a, NodeId(7), ConnectionType::Phonetic, 0.5);
+        assert!(matches!(result, Err(LingoError::Build(BuildError::InvalidConnection { .. }))));
+    }
+
+    #[test]
+    fn test_second_pronunciation_replaces_first() {
+        let mut builder = DatabaseBuilder::new();
+        let sound = |builder: &mut DatabaseBuilder, symbol: &str, x: f32| {
+            builder.add_node(symbol, Layer::Phonemes, Coordinate3D::new(x, 0.5, 0.15)).unwrap()
+        };
+        let (t, o, m, a) = (sound(&mut builder, "t", 0.1), sound(&mut builder, "əʊ", 0.4),
+            sound(&mut builder, "m", 0.2), sound(&mut builder, "ɑː", 0.6));
+        let tomato = builder.add_node("tomato", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.52)).unwrap();
+        let red = builder.add_node("red", Layer::Words, Coordinate3D::new(0.6, 0.5, 0.52)).unwrap();
+        builder.add_connection(tomato, red, ConnectionType::Phonetic, 0.5).unwrap();
+
+        builder.add_pronunciation(tomato, &[t, o, m, a, t, o]).unwrap();
+        builder.add_pronunciation(tomato, &[t, o, m, o, t, o]).unwrap();
+
+        let (_dir, db) = open_database(&builder);
+        assert_eq!(db.pronunciation(tomato).unwrap(), vec![t, o, m, o, t, o]);
+        // Other phonetic edges from the word are left alone
+        assert_eq!(db.get_node_connections(tomato).unwrap().len(), 7);
+    }
+}
@@ -0,0 +1,273 @@
+//! Export of the connection graph to DOT and GraphML for visualization
+
+use crate::core::{
+    NodeId, Layer, OrthogonalConnection,
+    error::Result,
+};
+use crate::storage::Database;
+use std::collections::{HashSet, VecDeque};
+use std::io::Write;
+
+/// Fill colour of each layer, from `Letters` to `Domains`
+const LAYER_COLORS: [&str; 7] = [
+    "#d9d9d9", // Letters
+    "#fdb462", // Phonemes
+    "#b3de69", // Morphemes
+    "#80b1d3", // Words
+    "#bebada", // Phrases
+    "#fb8072", // Concepts
+    "#ffffb3", // Domains
+];
+
+/// Nodes and edges selected for export
+struct Graph<'a> {
+    nodes: Vec<NodeId>,
+    edges: Vec<(NodeId, &'a OrthogonalConnection)>,
+}
+
+impl<'a> Graph<'a> {
+    /// Every node and connection in the database
+    fn whole(db: &'a Database) -> Result<Self> {
+        let nodes: Vec<NodeId> = (1..=db.node_count() as u32).map(NodeId).collect();
+        Self::between(db, nodes)
+    }
+
+    /// Nodes within `hops` connections of `seed`, in either direction,
+    /// and the connections among them
+    fn neighborhood(db: &'a Database, seed: NodeId, hops: usize) -> Result<Self> {
+        db.get_node(seed)?;
+        let mut seen = HashSet::from([seed]);
+        let mut nodes = vec![seed];
+        let mut queue = VecDeque::from([(seed, 0)]);
+
+        while let Some((id, depth)) = queue.pop_front() {
+            if depth == hops {
+                continue;
+            }
+            let outgoing = db.get_node_connections(id)?.iter().map(|c| c.target_node);
+            let incoming = db.get_incoming_connections(id)?.iter().copied();
+            for next in outgoing.chain(incoming) {
+                if seen.insert(next) {
+                    nodes.push(next);
+                    queue.push_back((next, depth + 1));
+                }
+            }
+        }
+
+        nodes.sort();
+        Self::between(db, nodes)
+    }
+
+    /// `nodes` and the connections whose ends are both among them
+    fn between(db: &'a Database, nodes: Vec<NodeId>) -> Result<Self> {
+        let included: HashSet<NodeId> = nodes.iter().copied().collect();
+        let mut edges = Vec::new();
+        for &id in &nodes {
+            for connection in db.get_node_connections(id)? {
+                if included.contains(&{ connection.target_node }) {
+                    edges.push((id, connection));
+                }
+            }
+        }
+        Ok(Self { nodes, edges })
+    }
+}
+
+/// Write the whole database as a Graphviz DOT digraph.
+///
+/// Nodes are labelled by surface form and filled by layer; edges are
+/// labelled by [`ConnectionType`](crate::core::ConnectionType), with
+/// `weight` (0-100) and `penwidth` following strength. Render with e.g.
+/// `dot -Tsvg graph.dot -o graph.svg`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lingo::storage::{Database, export_dot};
+///
+/// # fn main() -> lingo::core::error::Result<()> {
+/// let db = Database::open("english.lingo")?;
+/// export_dot(&db, std::fs::File::create("english.dot")?)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn export_dot<W: Write>(db: &Database, writer: W) -> Result<()> {
+    write_dot(db, &Graph::whole(db)?, writer)
+}
+
+/// Write the nodes within `hops` connections of `seed` as a DOT digraph.
+///
+/// Connections are followed in both directions, so a word's neighborhood
+/// includes the nodes that point at it. `hops` of 0 exports the seed alone.
+pub fn export_dot_subgraph<W: Write>(db: &Database, seed: NodeId, hops: usize, writer: W) -> Result<()> {
+    write_dot(db, &Graph::neighborhood(db, seed, hops)?, writer)
+}
+
+/// Write the whole database as GraphML.
+///
+/// Nodes carry `label`, `layer` and `color` data; edges carry `type` and
+/// `strength` (0.0-1.0). Opens in Gephi, yEd and Cytoscape.
+pub fn export_graphml<W: Write>(db: &Database, writer: W) -> Result<()> {
+    write_graphml(db, &Graph::whole(db)?, writer)
+}
+
+/// Write the nodes within `hops` connections of `seed` as GraphML, as
+/// selected by [`export_dot_subgraph`]
+pub fn export_graphml_subgraph<W: Write>(db: &Database, seed: NodeId, hops: usize, writer: W) -> Result<()> {
+    write_graphml(db, &Graph::neighborhood(db, seed, hops)?, writer)
+}
+
+fn write_dot<W: Write>(db: &Database, graph: &Graph<'_>, mut out: W) -> Result<()> {
+    writeln!(out, "digraph lingo {{")?;
+    writeln!(out, "  node [style=filled];")?;
+    for &id in &graph.nodes {
+        let node = db.get_node(id)?;
+        let layer = node.layer;
+        writeln!(
+            out,
+            "  n{} [label=\"{}\", fillcolor=\"{}\", layer=\"{:?}\"];",
+            id.0,
+            escape_dot(db.get_node_word(id)?),
+            layer_color(layer),
+            layer,
+        )?;
+    }
+    for &(source, connection) in &graph.edges {
+        let strength = connection.strength_normalized();
+        writeln!(
+            out,
+            "  n{} -> n{} [label=\"{:?}\", weight={}, penwidth={:.2}];",
+            source.0,
+            { connection.target_node }.0,
+            { connection.connection_type },
+            (strength * 100.0).round() as u32,
+            0.5 + 2.5 * strength,
+        )?;
+    }
+    writeln!(out, "}}")?;
+    Ok(())
+}
+
+fn write_graphml<W: Write>(db: &Database, graph: &Graph<'_>, mut out: W) -> Result<()> {
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(out, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+    writeln!(out, r#"  <key id="label" for="node" attr.name="label" attr.type="string"/>"#)?;
+    writeln!(out, r#"  <key id="layer" for="node" attr.name="layer" attr.type="string"/>"#)?;
+    writeln!(out, r#"  <key id="color" for="node" attr.name="color" attr.type="string"/>"#)?;
+    writeln!(out, r#"  <key id="type" for="edge" attr.name="type" attr.type="string"/>"#)?;
+    writeln!(out, r#"  <key id="strength" for="edge" attr.name="strength" attr.type="double"/>"#)?;
+    writeln!(out, r#"  <graph id="lingo" edgedefault="directed">"#)?;
+    for &id in &graph.nodes {
+        let node = db.get_node(id)?;
+        let layer = node.layer;
+        writeln!(out, r#"    <node id="n{}">"#, id.0)?;
+        writeln!(out, r#"      <data key="label">{}</data>"#, escape_xml(db.get_node_word(id)?))?;
+        writeln!(out, r#"      <data key="layer">{:?}</data>"#, layer)?;
+        writeln!(out, r#"      <data key="color">{}</data>"#, layer_color(layer))?;
+        writeln!(out, r#"    </node>"#)?;
+    }
+    for (i, &(source, connection)) in graph.edges.iter().enumerate() {
+        writeln!(
+            out,
+            r#"    <edge id="e{}" source="n{}" target="n{}">"#,
+            i,
+            source.0,
+            { connection.target_node }.0,
+        )?;
+        writeln!(out, r#"      <data key="type">{:?}</data>"#, { connection.connection_type })?;
+        writeln!(out, r#"      <data key="strength">{:.4}</data>"#, connection.strength_normalized())?;
+        writeln!(out, r#"    </edge>"#)?;
+    }
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</graphml>")?;
+    Ok(())
+}
+
+fn layer_color(layer: Layer) -> &'static str {
+    LAYER_COLORS.get(layer as usize).copied().unwrap_or("#ffffff")
+}
+
+fn escape_dot(text: &str) -> String {
+    // Keep each statement on one line by writing line breaks as DOT's \n escape
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ConnectionType, Coordinate3D};
+    use crate::storage::DatabaseBuilder;
+    use crate::test_support::open_database;
+    use tempfile::TempDir;
+
+    fn tiny_database() -> (TempDir, Database) {
+        let mut builder = DatabaseBuilder::new();
+        let un = builder.add_node("un", Layer::Morphemes, Coordinate3D::new(0.4, 0.0, 0.375)).unwrap();
+        let happy = builder.add_node("happy", Layer::Words, Coordinate3D::new(0.5, 0.1, 0.5)).unwrap();
+        let unhappy = builder.add_node("unhappy", Layer::Words, Coordinate3D::new(0.45, 0.1, 0.5)).unwrap();
+        let sad = builder.add_node("sad", Layer::Words, Coordinate3D::new(0.2, 0.1, 0.5)).unwrap();
+        builder.add_connection(unhappy, un, ConnectionType::Derivation, 0.8).unwrap();
+        builder.add_connection(unhappy, happy, ConnectionType::Derivation, 0.9).unwrap();
+        builder.add_connection(sad, happy, ConnectionType::Hypernymy, 0.5).unwrap();
+        open_database(&builder)
+    }
+
+    fn dot(db: &Database, seed: Option<(NodeId, usize)>) -> String {
+        let mut out = Vec::new();
+        match seed {
+            Some((seed, hops)) => export_dot_subgraph(db, seed, hops, &mut out).unwrap(),
+            None => export_dot(db, &mut out).unwrap(),
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_export_dot_lists_nodes_and_edges() {
+        let (_dir, db) = tiny_database();
+
+        let dot = dot(&db, None);
+        assert!(dot.starts_with("digraph lingo {"));
+        assert!(dot.contains(r##"n1 [label="un", fillcolor="#b3de69", layer="Morphemes"];"##), "{}", dot);
+        assert!(dot.contains(r#"n2 [label="happy""#));
+        assert!(dot.contains(r#"n3 -> n1 [label="Derivation", weight=80, penwidth=2.50];"#), "{}", dot);
+        assert!(dot.contains(r#"n4 -> n2 [label="Hypernymy", weight=50"#));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_escape_dot() {
+        assert_eq!(escape_dot(r#"say "hi" \ bye"#), r#"say \"hi\" \\ bye"#);
+        assert_eq!(escape_dot("two\nlines\r\nhere"), r"two\nlines\nhere");
+    }
+
+    #[test]
+    fn test_export_subgraph_respects_hops() {
+        let (_dir, db) = tiny_database();
+
+        // One hop from "un" reaches "unhappy" through an incoming edge only
+        let near = dot(&db, Some((NodeId(1), 1)));
+        assert!(near.contains("n3 -> n1"));
+        assert!(!near.contains("label=\"happy\""));
+
+        let wider = dot(&db, Some((NodeId(1), 3)));
+        assert!(wider.contains("n4 -> n2"));
+
+        let mut graphml = Vec::new();
+        export_graphml_subgraph(&db, NodeId(1), 1, &mut graphml).unwrap();
+        let graphml = String::from_utf8(graphml).unwrap();
+        assert!(graphml.contains(r#"<edge id="e0" source="n3" target="n1">"#), "{}", graphml);
+        assert!(graphml.contains(r#"<data key="type">Derivation</data>"#));
+        assert_eq!(graphml.matches("<node ").count(), 2);
+    }
+}
@@ -5,6 +5,30 @@ use bitflags::bitflags;
 /// Magic bytes for Lingo files
 pub const MAGIC_BYTES: &[u8; 8] = b"LINGO1.0";
 
+/// Byte-order marker. Files are little-endian, so a reader that sees this
+/// value byte-swapped is looking at a file in the other order.
+pub const ENDIAN_MARKER: u32 = 0x0102_0304;
+
+/// First format version whose header carries section checksums
+pub const CHECKSUM_VERSION: (u16, u16) = (1, 1);
+
+/// xxh3 checksum of one or more sections, hashed in order as stored
+pub fn section_checksum(sections: &[&[u8]]) -> u64 {
+    let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+    for section in sections {
+        hasher.update(section);
+    }
+    hasher.digest()
+}
+
+/// xxh3 checksum of a header as stored, with `header_checksum` zeroed
+pub fn header_checksum(header_bytes: &[u8]) -> u64 {
+    let mut bytes = header_bytes[..std::mem::size_of::<LingoFileHeader>()].to_vec();
+    let at = std::mem::offset_of!(LingoFileHeader, header_checksum);
+    bytes[at..at + 8].fill(0);
+    section_checksum(&[&bytes])
+}
+
 /// Lingo file header (512 bytes total)
 #[repr(C)]
 pub struct LingoFileHeader {
@@ -66,14 +90,14 @@ pub struct LingoFileHeader {
     /// Size of cache hints
     pub cache_hints_size: u64,
     
-    // Checksums (32 bytes)
-    /// Header integrity check
+    // Checksums (32 bytes), xxh3 from version 1.1 on
+    /// Header integrity check, over the header with this field zeroed
     pub header_checksum: u64,
-    /// Data integrity check
+    /// Data integrity check, over the node and connection arrays
     pub data_checksum: u64,
     /// String table integrity
     pub string_checksum: u64,
-    /// Index integrity
+    /// Index integrity, over the octree and vertical index
     pub index_checksum: u64,
     
     // Metadata (64 bytes)
@@ -87,8 +111,10 @@ pub struct LingoFileHeader {
     pub build_info: [u8; 32],
     
     // Reserved (64 bytes)
+    /// Byte-order marker, `ENDIAN_MARKER` in file byte order (0 in older files)
+    pub endian_marker: u32,
     /// Reserved for future use
-    pub reserved: [u8; 64],
+    pub reserved: [u8; 60],
     
     // Padding to 512 bytes
     _padding2: [u8; 216],
@@ -102,8 +128,8 @@ impl LingoFileHeader {
     pub fn new() -> Self {
         Self {
             magic: *MAGIC_BYTES,
-            version_major: 1,
-            version_minor: 0,
+            version_major: CHECKSUM_VERSION.0,
+            version_minor: CHECKSUM_VERSION.1,
             format_flags: FileFormatFlags::empty(),
             
             file_size: 0,
@@ -138,11 +164,78 @@ impl LingoFileHeader {
             model_version: [0; 16],
             build_info: [0; 32],
             
-            reserved: [0; 64],
+            endian_marker: ENDIAN_MARKER,
+            reserved: [0; 60],
             _padding2: [0; 216],
         }
     }
     
+    /// Whether this header, read in host order, came from a file in the
+    /// opposite byte order and needs `swap_bytes` before use.
+    ///
+    /// Files written before the marker existed have 0 here and were written
+    /// in the writer's host order. Their major version is always 1, so it
+    /// reads as 1 in the same order and as 0x0100 in the other.
+    pub fn needs_byte_swap(&self) -> Result<bool, String> {
+        let marker = self.endian_marker;
+        match marker {
+            ENDIAN_MARKER => Ok(false),
+            m if m == ENDIAN_MARKER.swap_bytes() => Ok(true),
+            0 => {
+                let version_major = self.version_major;
+                match version_major {
+                    1 => Ok(false),
+                    0x0100 => Ok(true),
+                    other => Err(format!(
+                        "Cannot tell the byte order of a file without a byte-order marker (major version {:#06x})",
+                        other
+                    )),
+                }
+            }
+            other => Err(format!("Invalid byte-order marker: {:#010x}", other)),
+        }
+    }
+    
+    /// Reverse the byte order of every multi-byte field in place
+    pub fn swap_bytes(&mut self) {
+        self.version_major = self.version_major.swap_bytes();
+        self.version_minor = self.version_minor.swap_bytes();
+        self.format_flags = FileFormatFlags::from_bits_retain(self.format_flags.bits().swap_bytes());
+        
+        self.file_size = self.file_size.swap_bytes();
+        self.node_count = self.node_count.swap_bytes();
+        self.connection_count = self.connection_count.swap_bytes();
+        
+        for field in [
+            &mut self.string_table_offset,
+            &mut self.string_table_size,
+            &mut self.node_array_offset,
+            &mut self.node_array_size,
+            &mut self.connection_array_offset,
+            &mut self.connection_array_size,
+            &mut self.octree_offset,
+            &mut self.octree_size,
+            &mut self.vertical_index_offset,
+            &mut self.vertical_index_size,
+            &mut self.cache_hints_offset,
+            &mut self.cache_hints_size,
+            &mut self.header_checksum,
+            &mut self.data_checksum,
+            &mut self.string_checksum,
+            &mut self.index_checksum,
+            &mut self.creation_timestamp,
+        ] {
+            *field = field.swap_bytes();
+        }
+        
+        self.endian_marker = self.endian_marker.swap_bytes();
+    }
+    
+    /// Whether the file's version stores section checksums
+    pub fn has_checksums(&self) -> bool {
+        (self.version_major, self.version_minor) >= CHECKSUM_VERSION
+    }
+    
     /// Validate magic bytes and version
     pub fn validate(&self) -> Result<(), String> {
         if &self.magic != MAGIC_BYTES {
@@ -180,7 +273,7 @@ impl Default for LingoFileHeader {
 
 bitflags! {
     /// File format feature flags
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
     pub struct FileFormatFlags: u32 {
         /// String table is compressed
         const STRING_COMPRESSION = 0x00000001;
@@ -196,6 +289,9 @@ bitflags! {
         const HAS_CACHE_HINTS = 0x00000020;
         /// Debug symbols included
         const DEBUG_SYMBOLS = 0x00000040;
+        /// Bidirectional connections may be stored on one end only, as
+        /// written by `SymmetricConnectionMode::StoreOnce`
+        const SYMMETRIC_STORED_ONCE = 0x00000080;
     }
 }
 
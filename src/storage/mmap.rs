@@ -1,19 +1,52 @@
 //! Memory-mapped database access with zero-copy reads
 
 use crate::core::{
-    NodeId, LinguisticNode, OrthogonalConnection, Coordinate3D,
+    NodeId, LinguisticNode, OrthogonalConnection, Coordinate3D, Layer, EtymologyOrigin,
+    MorphemeType, ConnectionType, SimilarityMetric, fold_accents, phonetic_key,
     error::{LingoError, Result},
 };
-use crate::storage::{LingoFileHeader, StringTable};
+use crate::storage::{LingoFileHeader, FileFormatFlags, StringTable};
+use crate::storage::file_format::{header_checksum, section_checksum, CompressionType, MAGIC_BYTES};
 use crate::security::validate_path;
 use crate::logging::{debug, info, warn, trace};
 // TODO: Add octree header when fully implemented
 // use crate::index::octree::OctreeHeader;
 use memmap2::{Mmap, MmapOptions};
 use std::fs::File;
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::slice;
 use std::mem;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Where a database's bytes live.
+///
+/// Every accessor reads through `Deref<Target = [u8]>`, so header
+/// validation, the string table and the indices work the same on each.
+enum Backing {
+    /// Memory-mapped file
+    Mapped(Mmap),
+    /// Buffer handed to `from_bytes`
+    Owned(Vec<u8>),
+    /// Bytes handed to `from_static`, e.g. from `include_bytes!`
+    Static(&'static [u8]),
+}
+
+impl std::ops::Deref for Backing {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Backing::Mapped(mmap) => mmap,
+            Backing::Owned(bytes) => bytes,
+            Backing::Static(bytes) => bytes,
+        }
+    }
+}
 
 /// Section offsets within the database
 #[derive(Debug, Clone, Copy)]
@@ -24,20 +57,70 @@ struct SectionOffsets {
     connections_start: usize,
     /// Start of string table
     strings_start: usize,
+    /// End of string table
+    strings_end: usize,
     /// Start of octree index
     octree_start: usize,
+    /// Start of vertical (per-layer) index
+    vertical_start: usize,
     /// End of file
     file_end: usize,
 }
 
+/// Reverse adjacency built from the forward connection ranges.
+///
+/// Sources for node `i` (1-based) are `sources[starts[i - 1]..starts[i]]`.
+#[derive(Debug)]
+struct IncomingIndex {
+    /// Prefix offsets into `sources`, one more than the node count
+    starts: Vec<usize>,
+    /// Source node of every connection, grouped by target
+    sources: Vec<NodeId>,
+}
+
+/// Node and connection arrays copied out of the map.
+///
+/// Each array is only copied when its mapped bytes can't be borrowed as
+/// structs directly: when the file's byte order differs from the host, or
+/// when connections carry type bytes this version doesn't know.
+struct ConvertedSections {
+    /// Nodes in host byte order, unknown origins read as `Unknown`
+    nodes: Option<Vec<LinguisticNode>>,
+    /// Connections in host byte order, unknown types read as `Unknown`
+    connections: Option<Vec<OrthogonalConnection>>,
+}
+
 /// Memory-mapped Lingo database for efficient zero-copy access
 pub struct MemoryMappedDatabase {
-    /// Memory-mapped file
-    mmap: Mmap,
+    /// Bytes of the database file
+    bytes: Backing,
+    /// Validated path the file was opened from; empty for `from_bytes`
+    path: PathBuf,
     /// Section offsets
     offsets: SectionOffsets,
     /// File header (cached)
     header: LingoFileHeader,
+    /// Incoming-edge index, built on first use
+    incoming: OnceLock<IncomingIndex>,
+    /// Accent- and case-folded word index, built on first folded lookup
+    folded_words: OnceLock<HashMap<String, Vec<NodeId>>>,
+    /// Phonetic-key index over Words-layer nodes, built on first use
+    phonetic_words: OnceLock<HashMap<String, Vec<NodeId>>>,
+    /// Number of lazy indices built so far
+    index_builds: AtomicUsize,
+    /// Copies of the arrays that can't be read from the map in place
+    converted: ConvertedSections,
+}
+
+// Implement Debug manually; the mapping and lazy indices are too large to print
+impl std::fmt::Debug for MemoryMappedDatabase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryMappedDatabase")
+            .field("path", &self.path)
+            .field("nodes", &self.node_count())
+            .field("connections", &self.connection_count())
+            .finish_non_exhaustive()
+    }
 }
 
 impl MemoryMappedDatabase {
@@ -64,84 +147,413 @@ impl MemoryMappedDatabase {
                 .map_err(|e| LingoError::Io(e))?
         };
         
+        Self::from_backing(Backing::Mapped(mmap), safe_path)
+    }
+    
+    /// Open a database file and check its checksums before returning it.
+    ///
+    /// Equivalent to [`open`](Self::open) followed by
+    /// [`verify_integrity`](Self::verify_integrity). Hashing reads the whole
+    /// file, so plain `open` stays the fast path.
+    ///
+    /// # Errors
+    ///
+    /// Everything `open` reports, plus `ChecksumMismatch` for a damaged
+    /// section and `FileFormat` for files older than format 1.1, which
+    /// carry no checksums.
+    pub fn open_verified<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let db = Self::open(path)?;
+        db.verify_integrity()?;
+        Ok(db)
+    }
+    
+    /// Check every section against the checksums stored in the header.
+    ///
+    /// Catches partially written and bit-rotted files before a query
+    /// trips over them.
+    ///
+    /// # Errors
+    ///
+    /// `ChecksumMismatch` naming the first damaged section, or `FileFormat`
+    /// if the file predates checksums (format 1.0).
+    pub fn verify_integrity(&self) -> Result<()> {
+        if !self.header.has_checksums() {
+            let (major, minor) = (self.header.version_major, self.header.version_minor);
+            return Err(LingoError::FileFormat(format!(
+                "Version {}.{} files carry no checksums to verify",
+                major, minor
+            )));
+        }
+        
+        let header = &self.header;
+        let section = |offset: u64, size: u64| &self.bytes[offset as usize..(offset + size) as usize];
+        let checks = [
+            ("header", header.header_checksum, header_checksum(&self.bytes)),
+            ("node and connection arrays", header.data_checksum, section_checksum(&[
+                section(header.node_array_offset, header.node_array_size),
+                section(header.connection_array_offset, header.connection_array_size),
+            ])),
+            ("string table", header.string_checksum, section_checksum(&[
+                section(header.string_table_offset, header.string_table_size),
+            ])),
+            ("indices", header.index_checksum, section_checksum(&[
+                section(header.octree_offset, header.octree_size),
+                section(header.vertical_index_offset, header.vertical_index_size),
+            ])),
+        ];
+        for (name, stored, actual) in checks {
+            if stored != actual {
+                return Err(LingoError::ChecksumMismatch { section: name.to_string() });
+            }
+        }
+        Ok(())
+    }
+    
+    /// Load a database from the bytes of a `.lingo` file.
+    ///
+    /// The bytes are kept in an owned buffer instead of a file mapping, for
+    /// targets without a filesystem such as WebAssembly. Passing a `Vec<u8>`
+    /// moves it in without copying. [`path`](Self::path) is empty.
+    ///
+    /// # Errors
+    ///
+    /// Fails on the same malformed headers, section bounds and invalid node
+    /// records as [`open`](Self::open), so untrusted bytes such as an upload
+    /// are safe to pass.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use lingo::storage::Database;
+    ///
+    /// # fn main() -> lingo::core::error::Result<()> {
+    /// let bytes = std::fs::read("english.lingo")?;
+    /// let db = Database::from_bytes(bytes)?;
+    /// println!("{} nodes", db.node_count());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Result<Self> {
+        Self::from_backing(Backing::Owned(bytes.into()), PathBuf::new())
+    }
+    
+    /// Load a database from bytes that live for the whole program, such as
+    /// a file embedded with `include_bytes!`.
+    ///
+    /// Unlike [`from_bytes`](Self::from_bytes) nothing is copied; nodes and
+    /// connections are read from `bytes` in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use lingo::storage::Database;
+    ///
+    /// static ENGLISH: &[u8] = include_bytes!("english.lingo");
+    /// let db = Database::from_static(ENGLISH)?;
+    /// ```
+    pub fn from_static(bytes: &'static [u8]) -> Result<Self> {
+        Self::from_backing(Backing::Static(bytes), PathBuf::new())
+    }
+    
+    /// Validate the header and sections of `bytes` and wrap them
+    fn from_backing(bytes: Backing, path: PathBuf) -> Result<Self> {
         // Validate minimum size
-        if mmap.len() < mem::size_of::<LingoFileHeader>() {
+        if bytes.len() < mem::size_of::<LingoFileHeader>() {
             return Err(LingoError::FileFormat("File too small".to_string()));
         }
         
         // Read and validate header
-        let header = Self::read_header(&mmap)?;
+        let (header, swapped) = Self::read_header(&bytes)?;
         
         // Calculate section offsets
-        let offsets = Self::calculate_offsets(&header, mmap.len())?;
+        let offsets = Self::calculate_offsets(&header, bytes.len())?;
+        
+        // Nodes are handed out as `&LinguisticNode`, so no enum byte may
+        // hold a value outside its type
+        Self::check_node_enums(&bytes, &header, &offsets)?;
+        
+        if swapped {
+            debug!("File byte order differs from host, converting arrays");
+        }
+        let unknown_types = Self::count_unknown_connection_types(&bytes, &header, &offsets);
+        if unknown_types > 0 {
+            warn!(count = unknown_types, "Connections with unknown types will be read as Unknown");
+        }
+        let unknown_origins = Self::count_unknown_origins(&bytes, &header, &offsets);
+        if unknown_origins > 0 {
+            warn!(count = unknown_origins, "Nodes with unknown etymology origins will be read as Unknown");
+        }
+        let converted = Self::convert_sections(
+            &bytes,
+            &header,
+            &offsets,
+            swapped,
+            unknown_types > 0,
+            unknown_origins > 0,
+        );
         
         Ok(Self {
-            mmap,
+            bytes,
+            path,
             offsets,
             header,
+            incoming: OnceLock::new(),
+            folded_words: OnceLock::new(),
+            phonetic_words: OnceLock::new(),
+            index_builds: AtomicUsize::new(0),
+            converted,
         })
     }
     
-    /// Read and validate file header
-    fn read_header(mmap: &Mmap) -> Result<LingoFileHeader> {
-        // SAFETY: We've already validated the size
-        let header_bytes = &mmap[..mem::size_of::<LingoFileHeader>()];
-        let header: LingoFileHeader = unsafe {
-            std::ptr::read_unaligned(header_bytes.as_ptr() as *const LingoFileHeader)
-        };
+    /// Read and validate file header, returning it in host byte order along
+    /// with whether the file's byte order differs from the host
+    fn read_header(bytes: &[u8]) -> Result<(LingoFileHeader, bool)> {
+        let header_bytes = &bytes[..mem::size_of::<LingoFileHeader>()];
         
         // Validate magic number
-        if &header.magic != b"LINGO1.0" {
+        if &header_bytes[..MAGIC_BYTES.len()] != MAGIC_BYTES {
             return Err(LingoError::FileFormat("Invalid magic number".to_string()));
         }
         
+        // The compression byte is read as an enum, so check it before the
+        // header is
+        let compression = header_bytes[mem::offset_of!(LingoFileHeader, compression_type)];
+        if compression != CompressionType::None as u8 {
+            return Err(LingoError::FileFormat(format!(
+                "Unsupported compression type {} (only uncompressed files can be read)",
+                compression
+            )));
+        }
+        
+        // SAFETY: We've already validated the size and the enum byte
+        let mut header: LingoFileHeader = unsafe {
+            std::ptr::read_unaligned(header_bytes.as_ptr() as *const LingoFileHeader)
+        };
+        
+        let swapped = header.needs_byte_swap().map_err(LingoError::FileFormat)?;
+        if swapped {
+            header.swap_bytes();
+        }
+        
         // Validate version
-        if header.version_major != 1 || header.version_minor != 0 {
+        let version = (header.version_major, header.version_minor);
+        if version.0 == 0 || version > crate::MAX_FILE_VERSION {
             return Err(LingoError::UnsupportedVersion {
-                major: header.version_major,
-                minor: header.version_minor,
+                major: version.0,
+                minor: version.1,
             });
         }
         
-        Ok(header)
-    }
-    
-    /// Calculate section offsets
-    fn calculate_offsets(header: &LingoFileHeader, file_size: usize) -> Result<SectionOffsets> {
-        // Use the actual offsets from the header
-        let nodes_start = header.node_array_offset as usize;
-        let connections_start = header.connection_array_offset as usize;
-        let strings_start = header.string_table_offset as usize;
-        let octree_start = header.octree_offset as usize;
-        
-        // Validate offsets
-        if nodes_start + header.node_array_size as usize > file_size {
+        let flags = header.format_flags.bits();
+        if FileFormatFlags::from_bits(flags).is_none() {
+            return Err(LingoError::FileFormat(format!(
+                "Unknown format flags {:#x}",
+                flags & !FileFormatFlags::all().bits()
+            )));
+        }
+        if header.format_flags.contains(FileFormatFlags::STRING_COMPRESSION) {
             return Err(LingoError::FileFormat(
-                format!("Node array extends past file end")
+                "Compressed string tables are not supported".to_string()
             ));
         }
         
-        if connections_start + header.connection_array_size as usize > file_size {
-            return Err(LingoError::FileFormat(
-                format!("Connection array extends past file end")
-            ));
+        Ok((header, swapped))
+    }
+    
+    /// Reject node records whose layer or morpheme type byte names no
+    /// variant.
+    ///
+    /// Unlike etymology origins these have no `Unknown` to fall back on, and
+    /// reading either byte as its enum would be undefined behaviour.
+    fn check_node_enums(bytes: &[u8], header: &LingoFileHeader, offsets: &SectionOffsets) -> Result<()> {
+        let layer_offset = mem::offset_of!(LinguisticNode, layer);
+        let morpheme_offset = mem::offset_of!(LinguisticNode, morpheme_type);
+        let records = bytes[offsets.nodes_start..]
+            .chunks_exact(mem::size_of::<LinguisticNode>())
+            .take(header.node_count as usize);
+        for (index, record) in records.enumerate() {
+            if Layer::from_u8(record[layer_offset]).is_none() {
+                return Err(LingoError::CorruptedData(format!(
+                    "Node {} has invalid layer {}",
+                    index + 1, record[layer_offset]
+                )));
+            }
+            if MorphemeType::from_u8(record[morpheme_offset]).is_none() {
+                return Err(LingoError::CorruptedData(format!(
+                    "Node {} has invalid morpheme type {}",
+                    index + 1, record[morpheme_offset]
+                )));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Count connection records whose type byte isn't a known `ConnectionType`
+    fn count_unknown_connection_types(bytes: &[u8], header: &LingoFileHeader, offsets: &SectionOffsets) -> usize {
+        let type_offset = mem::offset_of!(OrthogonalConnection, connection_type);
+        bytes[offsets.connections_start..]
+            .chunks_exact(mem::size_of::<OrthogonalConnection>())
+            .take(header.connection_count as usize)
+            .filter(|record| ConnectionType::from_u8(record[type_offset]).is_none())
+            .count()
+    }
+    
+    /// Count node records whose etymology byte names no known origin
+    fn count_unknown_origins(bytes: &[u8], header: &LingoFileHeader, offsets: &SectionOffsets) -> usize {
+        let origin_offset = mem::offset_of!(LinguisticNode, etymology_origin);
+        bytes[offsets.nodes_start..]
+            .chunks_exact(mem::size_of::<LinguisticNode>())
+            .take(header.node_count as usize)
+            .filter(|record| EtymologyOrigin::from_u8(record[origin_offset]).is_none())
+            .count()
+    }
+    
+    /// Copy out the arrays that can't be borrowed from the map, converting
+    /// them to host byte order and replacing unknown connection types and
+    /// etymology origins
+    fn convert_sections(
+        bytes: &[u8],
+        header: &LingoFileHeader,
+        offsets: &SectionOffsets,
+        swapped: bool,
+        unknown_types: bool,
+        unknown_origins: bool,
+    ) -> ConvertedSections {
+        fn read_nodes(bytes: &[u8], count: usize, swapped: bool) -> Vec<LinguisticNode> {
+            let origin_offset = mem::offset_of!(LinguisticNode, etymology_origin);
+            bytes.chunks_exact(mem::size_of::<LinguisticNode>())
+                .take(count)
+                .map(|record| {
+                    // Patch the origin byte before it's read as an enum
+                    let mut record: [u8; mem::size_of::<LinguisticNode>()] =
+                        record.try_into().expect("chunk is one record");
+                    if EtymologyOrigin::from_u8(record[origin_offset]).is_none() {
+                        record[origin_offset] = EtymologyOrigin::Unknown as u8;
+                    }
+                    // SAFETY: The record is a full node with a valid origin
+                    // byte; layer and morpheme type were checked on open
+                    let mut node = unsafe {
+                        std::ptr::read_unaligned(record.as_ptr() as *const LinguisticNode)
+                    };
+                    if swapped {
+                        node.swap_bytes();
+                    }
+                    node
+                })
+                .collect()
         }
         
-        if strings_start + header.string_table_size as usize > file_size {
-            return Err(LingoError::FileFormat(
-                format!("String table extends past file end")
-            ));
+        fn read_connections(bytes: &[u8], count: usize, swapped: bool) -> Vec<OrthogonalConnection> {
+            let type_offset = mem::offset_of!(OrthogonalConnection, connection_type);
+            bytes.chunks_exact(mem::size_of::<OrthogonalConnection>())
+                .take(count)
+                .map(|record| {
+                    // Patch the type byte before it's read as an enum
+                    let mut record: [u8; mem::size_of::<OrthogonalConnection>()] =
+                        record.try_into().expect("chunk is one record");
+                    if ConnectionType::from_u8(record[type_offset]).is_none() {
+                        record[type_offset] = ConnectionType::Unknown as u8;
+                    }
+                    // SAFETY: The record is a full connection with a valid type byte
+                    let mut connection = unsafe {
+                        std::ptr::read_unaligned(record.as_ptr() as *const OrthogonalConnection)
+                    };
+                    if swapped {
+                        connection.swap_bytes();
+                    }
+                    connection
+                })
+                .collect()
         }
         
+        ConvertedSections {
+            nodes: (swapped || unknown_origins).then(|| read_nodes(
+                &bytes[offsets.nodes_start..],
+                header.node_count as usize,
+                swapped,
+            )),
+            connections: (swapped || unknown_types).then(|| read_connections(
+                &bytes[offsets.connections_start..],
+                header.connection_count as usize,
+                swapped,
+            )),
+        }
+    }
+    
+    /// Calculate section offsets, checking every section lies inside the
+    /// file and holds the records the header counts
+    fn calculate_offsets(header: &LingoFileHeader, file_size: usize) -> Result<SectionOffsets> {
+        let declared_size = header.file_size;
+        if declared_size > file_size as u64 {
+            return Err(LingoError::FileFormat(format!(
+                "File is truncated: header declares {} bytes but only {} are present",
+                declared_size, file_size
+            )));
+        }
+        
+        let header_size = mem::size_of::<LingoFileHeader>() as u64;
+        let section = |name: &str, offset: u64, size: u64| -> Result<(usize, usize)> {
+            let end = offset.checked_add(size).filter(|&end| end <= file_size as u64);
+            match end {
+                Some(end) if size == 0 || offset >= header_size => Ok((offset as usize, end as usize)),
+                Some(_) => Err(LingoError::FileFormat(format!("{} overlaps the header", name))),
+                None => Err(LingoError::FileFormat(format!(
+                    "{} extends past file end ({} + {} > {})",
+                    name, offset, size, file_size
+                ))),
+            }
+        };
+        
+        let (nodes_start, _) = section("Node array", header.node_array_offset, header.node_array_size)?;
+        let (connections_start, _) = section(
+            "Connection array",
+            header.connection_array_offset,
+            header.connection_array_size,
+        )?;
+        let (strings_start, strings_end) = section(
+            "String table",
+            header.string_table_offset,
+            header.string_table_size,
+        )?;
+        let (octree_start, _) = section("Octree", header.octree_offset, header.octree_size)?;
+        let (vertical_start, _) = section("Vertical index", header.vertical_index_offset, header.vertical_index_size)?;
+        
+        let counted = |name: &str, count: u32, record: usize, size: u64| -> Result<()> {
+            let needed = count as u64 * record as u64;
+            if needed > size {
+                return Err(LingoError::FileFormat(format!(
+                    "{} holds {} bytes but {} records need {}",
+                    name, size, count, needed
+                )));
+            }
+            Ok(())
+        };
+        counted("Node array", header.node_count, mem::size_of::<LinguisticNode>(), header.node_array_size)?;
+        counted(
+            "Connection array",
+            header.connection_count,
+            mem::size_of::<OrthogonalConnection>(),
+            header.connection_array_size,
+        )?;
+        
         Ok(SectionOffsets {
             nodes_start,
             connections_start,
             strings_start,
+            strings_end,
             octree_start,
+            vertical_start,
             file_end: file_size,
         })
     }
     
+    /// Path the database was opened from, after validation; empty for
+    /// databases loaded with [`from_bytes`](Self::from_bytes) or
+    /// [`from_static`](Self::from_static)
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
     /// Get file header
     pub fn header(&self) -> &LingoFileHeader {
         &self.header
@@ -167,27 +579,35 @@ impl MemoryMappedDatabase {
             return Err(LingoError::InvalidNodeId(id));
         }
         
+        if let Some(nodes) = &self.converted.nodes {
+            return Ok(&nodes[index]);
+        }
+        
         // Calculate offset
         let offset = self.offsets.nodes_start + index * mem::size_of::<LinguisticNode>();
         
-        // SAFETY: We've validated the bounds
+        // SAFETY: We've validated the bounds, and every enum byte on open
         unsafe {
-            let ptr = self.mmap[offset..].as_ptr() as *const LinguisticNode;
+            let ptr = self.bytes[offset..].as_ptr() as *const LinguisticNode;
             Ok(&*ptr)
         }
     }
     
     /// Get all nodes as a slice
     pub fn nodes(&self) -> &[LinguisticNode] {
+        if let Some(nodes) = &self.converted.nodes {
+            return nodes;
+        }
+        
         let count = self.node_count();
         if count == 0 {
             return &[];
         }
         
-        // SAFETY: We've validated the bounds during construction
+        // SAFETY: We've validated the bounds and enum bytes during construction
         unsafe {
             slice::from_raw_parts(
-                self.mmap[self.offsets.nodes_start..].as_ptr() as *const LinguisticNode,
+                self.bytes[self.offsets.nodes_start..].as_ptr() as *const LinguisticNode,
                 count
             )
         }
@@ -199,18 +619,26 @@ impl MemoryMappedDatabase {
             return Err(LingoError::Database("Connection index out of bounds".to_string()));
         }
         
+        if let Some(connections) = &self.converted.connections {
+            return Ok(&connections[index]);
+        }
+        
         // Calculate offset
         let offset = self.offsets.connections_start + index * mem::size_of::<OrthogonalConnection>();
         
         // SAFETY: We've validated the bounds
         unsafe {
-            let ptr = self.mmap[offset..].as_ptr() as *const OrthogonalConnection;
+            let ptr = self.bytes[offset..].as_ptr() as *const OrthogonalConnection;
             Ok(&*ptr)
         }
     }
     
     /// Get all connections as a slice
     pub fn connections(&self) -> &[OrthogonalConnection] {
+        if let Some(connections) = &self.converted.connections {
+            return connections;
+        }
+        
         let count = self.connection_count();
         if count == 0 {
             return &[];
@@ -219,12 +647,31 @@ impl MemoryMappedDatabase {
         // SAFETY: We've validated the bounds during construction
         unsafe {
             slice::from_raw_parts(
-                self.mmap[self.offsets.connections_start..].as_ptr() as *const OrthogonalConnection,
+                self.bytes[self.offsets.connections_start..].as_ptr() as *const OrthogonalConnection,
                 count
             )
         }
     }
     
+    /// Iterate over every connection in file order, without copying.
+    ///
+    /// Connections are grouped by source node, in node ID order. Use this to
+    /// walk the whole graph for export or analysis.
+    pub fn iter_connections(&self) -> impl Iterator<Item = &OrthogonalConnection> + '_ {
+        self.connections().iter()
+    }
+    
+    /// Count connections per type, in `ConnectionType` declaration order.
+    ///
+    /// Types with no connections are omitted.
+    pub fn connection_stats(&self) -> BTreeMap<ConnectionType, usize> {
+        let mut stats = BTreeMap::new();
+        for connection in self.iter_connections() {
+            *stats.entry(connection.connection_type).or_insert(0) += 1;
+        }
+        stats
+    }
+    
     /// Get connections for a specific node
     pub fn get_node_connections(&self, node_id: NodeId) -> Result<&[OrthogonalConnection]> {
         let node = self.get_node(node_id)?;
@@ -239,11 +686,101 @@ impl MemoryMappedDatabase {
         Ok(&self.connections()[start..start + count])
     }
     
+    /// Phoneme sequence of a word, in pronunciation order.
+    ///
+    /// Pronunciations are stored as `Phonetic` connections from the word to
+    /// Phonemes-layer nodes, one per sound, in the order they were added
+    /// with [`DatabaseBuilder::add_pronunciation`](crate::storage::DatabaseBuilder::add_pronunciation).
+    /// `Phonetic` connections to other layers, such as homophone links
+    /// between words, are not part of it. Empty for words without one.
+    pub fn pronunciation(&self, node_id: NodeId) -> Result<Vec<NodeId>> {
+        let mut phonemes = Vec::new();
+        for connection in self.get_node_connections(node_id)? {
+            let target = connection.target_node;
+            if connection.connection_type == ConnectionType::Phonetic
+                && self.get_node(target).is_ok_and(|node| node.layer == Layer::Phonemes)
+            {
+                phonemes.push(target);
+            }
+        }
+        Ok(phonemes)
+    }
+    
+    /// Get the nodes that have a connection pointing at `node_id`.
+    ///
+    /// The reverse index is built from the connection array on first call.
+    /// A source appears once per connection, so parallel edges repeat it.
+    pub fn get_incoming_connections(&self, node_id: NodeId) -> Result<&[NodeId]> {
+        self.get_node(node_id)?;
+        let index = self.incoming_index();
+        let i = node_id.0 as usize;
+        Ok(&index.sources[index.starts[i - 1]..index.starts[i]])
+    }
+    
+    /// Build every lazily constructed index now instead of on first use.
+    ///
+    /// Covers the incoming-edge, folded-word and phonetic indices. Indices
+    /// that are already built are left alone.
+    pub fn warm_indices(&self) {
+        self.incoming_index();
+        self.folded_index();
+        self.phonetic_index();
+    }
+    
+    /// Number of lazy indices this database has built.
+    ///
+    /// Each index is built at most once, so this stops growing after
+    /// [`warm_indices`](Self::warm_indices).
+    pub fn index_builds(&self) -> usize {
+        self.index_builds.load(Ordering::Relaxed)
+    }
+    
+    fn incoming_index(&self) -> &IncomingIndex {
+        self.incoming.get_or_init(|| {
+            self.index_builds.fetch_add(1, Ordering::Relaxed);
+            self.build_incoming_index()
+        })
+    }
+    
+    /// Build the reverse adjacency with a counting pass then a fill pass
+    fn build_incoming_index(&self) -> IncomingIndex {
+        let count = self.node_count();
+        let mut edges = Vec::new();
+        for i in 0..count {
+            let source = NodeId(i as u32 + 1);
+            if let Ok(conns) = self.get_node_connections(source) {
+                for conn in conns {
+                    let target = conn.target_node;
+                    if target.0 >= 1 && (target.0 as usize) <= count {
+                        edges.push((source, target));
+                    }
+                }
+            }
+        }
+        
+        let mut starts = vec![0usize; count + 1];
+        for (_, target) in &edges {
+            starts[target.0 as usize] += 1;
+        }
+        for i in 1..=count {
+            starts[i] += starts[i - 1];
+        }
+        
+        let mut fill = starts.clone();
+        let mut sources = vec![NodeId(0); edges.len()];
+        for (source, target) in edges {
+            let slot = &mut fill[target.0 as usize - 1];
+            sources[*slot] = source;
+            *slot += 1;
+        }
+        
+        debug!(nodes = count, edges = sources.len(), "Built incoming-edge index");
+        IncomingIndex { starts, sources }
+    }
+    
     /// Get the string table
     pub fn string_table(&self) -> Result<StringTable> {
-        let start = self.offsets.strings_start;
-        let end = self.offsets.octree_start;
-        let data = &self.mmap[start..end];
+        let data = &self.bytes[self.offsets.strings_start..self.offsets.strings_end];
         
         StringTable::from_bytes(data)
     }
@@ -253,11 +790,11 @@ impl MemoryMappedDatabase {
         let start = self.offsets.strings_start + offset as usize;
         let end = start + length as usize;
         
-        if end > self.offsets.octree_start {
+        if end > self.offsets.strings_end {
             return Err(LingoError::Database("String offset out of bounds".to_string()));
         }
         
-        let bytes = &self.mmap[start..end];
+        let bytes = &self.bytes[start..end];
         std::str::from_utf8(bytes)
             .map_err(|_| LingoError::Database("Invalid UTF-8 in string table".to_string()))
     }
@@ -268,6 +805,80 @@ impl MemoryMappedDatabase {
         self.get_string(node.word_offset, node.word_length)
     }
     
+    /// Raw bytes of the stored octree section, empty if the file has none
+    pub(crate) fn octree_bytes(&self) -> &[u8] {
+        let start = self.offsets.octree_start;
+        let size = self.header.octree_size as usize;
+        match start.checked_add(size) {
+            Some(end) if size > 0 && end <= self.offsets.file_end => &self.bytes[start..end],
+            _ => &[],
+        }
+    }
+    
+    /// IDs of `layer`'s nodes from the vertical index, as little-endian u32s.
+    ///
+    /// The index opens with a `(start, count)` pair per layer, then every
+    /// node ID grouped by layer. `None` if the file has no index or the
+    /// layer's entry doesn't fit in it.
+    fn vertical_index_ids(&self, layer: Layer) -> Option<&[u8]> {
+        const ENTRY_SIZE: usize = 8;
+        let start = self.offsets.vertical_start;
+        let size = self.header.vertical_index_size as usize;
+        let index = match start.checked_add(size) {
+            Some(end) if end <= self.offsets.file_end => &self.bytes[start..end],
+            _ => return None,
+        };
+        
+        let layers = self.header.layer_count as usize;
+        let entry = index.get(layer as usize * ENTRY_SIZE..(layer as usize + 1) * ENTRY_SIZE)?;
+        let first = u32::from_le_bytes(entry[..4].try_into().ok()?) as usize;
+        let count = u32::from_le_bytes(entry[4..].try_into().ok()?) as usize;
+        let ids_start = layers * ENTRY_SIZE + first.checked_mul(4)?;
+        index.get(ids_start..ids_start.checked_add(count.checked_mul(4)?)?)
+    }
+    
+    /// Iterate the IDs of every node in `layer`, in ID order.
+    ///
+    /// Reads the file's vertical index, so only that layer's entries are
+    /// touched. Files written without one fall back to scanning the node
+    /// array.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use lingo::core::{Coordinate3D, Layer};
+    /// use lingo::storage::LingoDatabase;
+    ///
+    /// # fn main() -> lingo::core::error::Result<()> {
+    /// let db = LingoDatabase::open("english.lingo")?;
+    /// let mut sum = Coordinate3D::new(0.0, 0.0, 0.0);
+    /// for id in db.nodes_in_layer(Layer::Morphemes) {
+    ///     let position = db.get_node(id)?.position;
+    ///     sum = Coordinate3D::new(sum.x + position.x, sum.y + position.y, sum.z + position.z);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn nodes_in_layer(&self, layer: Layer) -> impl Iterator<Item = NodeId> + '_ {
+        let indexed = self.vertical_index_ids(layer);
+        let scanned = indexed.is_none().then(|| {
+            self.nodes().iter().filter(move |node| node.layer == layer).map(|node| node.id)
+        });
+        indexed.into_iter()
+            .flat_map(|ids| ids.chunks_exact(4))
+            .map(|id| NodeId(u32::from_le_bytes([id[0], id[1], id[2], id[3]])))
+            .chain(scanned.into_iter().flatten())
+    }
+    
+    /// Number of nodes in `layer`, read from the vertical index when the
+    /// file has one
+    pub fn layer_count(&self, layer: Layer) -> usize {
+        match self.vertical_index_ids(layer) {
+            Some(ids) => ids.len() / 4,
+            None => self.nodes().iter().filter(|node| node.layer == layer).count(),
+        }
+    }
+    
     /// Get octree header if present
     pub fn octree_header(&self) -> Option<()> {
         if self.header.octree_size == 0 {
@@ -296,36 +907,266 @@ impl MemoryMappedDatabase {
         results
     }
     
+    /// Gather a node's properties and a per-type count of its outgoing
+    /// connections in one call, for REPL and debugging use.
+    pub fn describe(&self, id: NodeId) -> Result<NodeDescription> {
+        let node = *self.get_node(id)?;
+        
+        let mut connections: Vec<(ConnectionType, usize)> = Vec::new();
+        for connection in self.get_node_connections(id)? {
+            let connection_type = connection.connection_type;
+            match connections.iter_mut().find(|(t, _)| *t == connection_type) {
+                Some((_, count)) => *count += 1,
+                None => connections.push((connection_type, 1)),
+            }
+        }
+        connections.sort_by_key(|&(t, _)| t as u8);
+        
+        Ok(NodeDescription {
+            id,
+            word: self.get_node_word(id)?.to_string(),
+            layer: node.layer,
+            position: node.position,
+            etymology: node.etymology_origin,
+            morpheme_type: node.morpheme_type,
+            productivity: node.productivity(),
+            frequency_rank: node.frequency_rank,
+            connections,
+        })
+    }
+    
+    /// Count nodes per layer.
+    ///
+    /// Iterates from `Layer::Letters` up to `Layer::Domains`; layers with no
+    /// nodes are omitted.
+    pub fn layer_counts(&self) -> BTreeMap<Layer, usize> {
+        Layer::ALL.into_iter()
+            .map(|layer| (layer, self.layer_count(layer)))
+            .filter(|&(_, count)| count > 0)
+            .collect()
+    }
+    
+    /// Draw a uniform random sample of `n` distinct nodes.
+    ///
+    /// The same `seed` always gives the same sample for a given database.
+    /// Returns every node, in shuffled order, when `n` exceeds the count.
+    pub fn sample_nodes(&self, n: usize, seed: u64) -> Vec<NodeId> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let count = self.node_count();
+        rand::seq::index::sample(&mut rng, count, n.min(count))
+            .into_iter()
+            .map(|i| NodeId(i as u32 + 1))
+            .collect()
+    }
+    
+    /// Like [`sample_nodes`](Self::sample_nodes), restricted to one layer
+    pub fn sample_nodes_in_layer(&self, layer: Layer, n: usize, seed: u64) -> Vec<NodeId> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let candidates: Vec<NodeId> = self.nodes_in_layer(layer).collect();
+        candidates.choose_multiple(&mut rng, n).copied().collect()
+    }
+    
+    /// Count nodes per etymological origin.
+    ///
+    /// Origins with no nodes are omitted. Nodes whose origin byte this
+    /// version doesn't recognize are counted as `Unknown`.
+    pub fn etymology_histogram(&self) -> HashMap<EtymologyOrigin, usize> {
+        let mut histogram = HashMap::new();
+        for node in self.nodes() {
+            *histogram.entry(node.etymology_origin).or_insert(0) += 1;
+        }
+        histogram
+    }
+    
+    /// Group nodes by etymological origin.
+    ///
+    /// Groups iterate in `EtymologyOrigin` declaration order (Germanic,
+    /// Latin, Greek, ...) and keep the order of `ids` within each group, so
+    /// the output is the same on every run. Unknown IDs are skipped.
+    pub fn group_by_etymology(&self, ids: &[NodeId]) -> BTreeMap<EtymologyOrigin, Vec<NodeId>> {
+        let mut groups: BTreeMap<EtymologyOrigin, Vec<NodeId>> = BTreeMap::new();
+        for &id in ids {
+            if let Ok(node) = self.get_node(id) {
+                groups.entry(node.etymology_origin).or_default().push(id);
+            }
+        }
+        groups
+    }
+    
+    /// Find nodes whose word matches `word` once case and diacritics are
+    /// folded (see [`fold_accents`]).
+    ///
+    /// The folded index is built on the first call and reused afterwards.
+    pub fn find_nodes_by_folded_word(&self, word: &str) -> Vec<NodeId> {
+        self.folded_index().get(&fold_accents(word)).cloned().unwrap_or_default()
+    }
+    
+    /// The node whose word matches `word` ignoring case and diacritics.
+    ///
+    /// When several words fold the same way ("Café" and "cafe"), the one
+    /// added first (the lowest ID) wins. Uses the same lazily built index
+    /// as [`find_nodes_by_folded_word`](Self::find_nodes_by_folded_word).
+    pub fn find_normalized(&self, word: &str) -> Option<NodeId> {
+        self.folded_index().get(&fold_accents(word)).and_then(|ids| ids.first().copied())
+    }
+    
+    fn folded_index(&self) -> &HashMap<String, Vec<NodeId>> {
+        self.folded_words.get_or_init(|| {
+            self.index_builds.fetch_add(1, Ordering::Relaxed);
+            let mut index: HashMap<String, Vec<NodeId>> = HashMap::new();
+            for i in 0..self.node_count() {
+                let node_id = NodeId(i as u32 + 1);
+                if let Ok(node_word) = self.get_node_word(node_id) {
+                    index.entry(fold_accents(node_word)).or_default().push(node_id);
+                }
+            }
+            debug!(entries = index.len(), "Built folded word index");
+            index
+        })
+    }
+    
+    /// Find Words-layer nodes that sound like `word`, by comparing
+    /// [`phonetic_key`]s.
+    ///
+    /// The phonetic index is built on the first call and reused afterwards.
+    /// Words without any consonant or leading vowel sound (an empty key)
+    /// never match.
+    pub fn find_nodes_by_sound(&self, word: &str) -> Vec<NodeId> {
+        self.phonetic_index().get(&phonetic_key(word)).cloned().unwrap_or_default()
+    }
+    
+    fn phonetic_index(&self) -> &HashMap<String, Vec<NodeId>> {
+        self.phonetic_words.get_or_init(|| {
+            self.index_builds.fetch_add(1, Ordering::Relaxed);
+            let mut index: HashMap<String, Vec<NodeId>> = HashMap::new();
+            for i in 0..self.node_count() {
+                let node_id = NodeId(i as u32 + 1);
+                let Ok(node) = self.get_node(node_id) else { continue };
+                if node.layer != Layer::Words {
+                    continue;
+                }
+                if let Ok(node_word) = self.get_node_word(node_id) {
+                    let key = phonetic_key(node_word);
+                    if !key.is_empty() {
+                        index.entry(key).or_default().push(node_id);
+                    }
+                }
+            }
+            debug!(entries = index.len(), "Built phonetic word index");
+            index
+        })
+    }
+    
     /// Find similar nodes by position (using octree if available)
     pub fn find_similar_nodes(&self, position: Coordinate3D, radius: f32, limit: Option<usize>) -> Vec<NodeId> {
+        self.scan_similar(position, radius, limit, SimilarityMetric::Euclidean).0
+    }
+    
+    /// Find nodes within `radius` of `position` under `metric`, nearest first
+    pub fn find_similar_nodes_by(
+        &self,
+        position: Coordinate3D,
+        radius: f32,
+        limit: Option<usize>,
+        metric: SimilarityMetric,
+    ) -> Vec<NodeId> {
+        self.scan_similar(position, radius, limit, metric).0
+    }
+    
+    /// Similarity search behind `find_similar_nodes_by`, also returning how
+    /// many nodes were compared against `position`
+    pub(crate) fn scan_similar(
+        &self,
+        position: Coordinate3D,
+        radius: f32,
+        limit: Option<usize>,
+        metric: SimilarityMetric,
+    ) -> (Vec<NodeId>, usize) {
         // TODO: Use octree index when fully implemented
         // For now, do linear search
         let mut candidates = Vec::new();
+        let mut examined = 0;
         
         for (i, node) in self.nodes().iter().enumerate() {
-            let dist_sq = Self::distance_squared(position, node.position);
-            if dist_sq <= radius * radius {
-                candidates.push((NodeId(i as u32), dist_sq));
+            examined += 1;
+            // Euclidean ranks by squared distance to skip the root
+            let (distance, bound) = if metric == SimilarityMetric::Euclidean {
+                (position.distance_squared_to(node.position), radius * radius)
+            } else {
+                (metric.distance(position, node.position), radius)
+            };
+            if distance <= bound {
+                candidates.push((NodeId(i as u32 + 1), distance)); // Node IDs start from 1
             }
         }
         
-        // Sort by distance
-        candidates.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+        // Sort by distance; equal distances keep ID order
+        candidates.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
         
         // Apply limit
         if let Some(limit) = limit {
             candidates.truncate(limit);
         }
         
-        candidates.into_iter().map(|(id, _)| id).collect()
+        (candidates.into_iter().map(|(id, _)| id).collect(), examined)
     }
-    
-    /// Calculate squared distance between two positions
-    fn distance_squared(a: Coordinate3D, b: Coordinate3D) -> f32 {
-        let dx = a.x - b.x;
-        let dy = a.y - b.y; 
-        let dz = a.z - b.z;
-        dx * dx + dy * dy + dz * dz
+}
+
+/// Resolve a database name against an optional base directory.
+///
+/// Absolute paths are returned unchanged. Relative names are joined onto
+/// `base_path` when one is given; otherwise they stay relative and resolve
+/// against the current working directory.
+pub fn locate_database<P: AsRef<Path>>(name: P, base_path: Option<&Path>) -> PathBuf {
+    let name = name.as_ref();
+    match base_path {
+        Some(base) if name.is_relative() => base.join(name),
+        _ => name.to_path_buf(),
+    }
+}
+
+/// Everything worth knowing about one node, gathered by
+/// [`MemoryMappedDatabase::describe`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeDescription {
+    /// Node being described
+    pub id: NodeId,
+    /// Surface form
+    pub word: String,
+    /// Layer the node lives on
+    pub layer: Layer,
+    /// Position in linguistic space
+    pub position: Coordinate3D,
+    /// Etymological origin
+    pub etymology: EtymologyOrigin,
+    /// Morpheme type (meaningful on the Morphemes layer)
+    pub morpheme_type: MorphemeType,
+    /// Productivity in [0, 1]
+    pub productivity: f32,
+    /// Frequency rank (`u32::MAX` if unranked)
+    pub frequency_rank: u32,
+    /// Outgoing connections per type, ordered by type
+    pub connections: Vec<(ConnectionType, usize)>,
+}
+
+impl std::fmt::Display for NodeDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} \"{}\" ({:?})", self.id, self.word, self.layer)?;
+        writeln!(
+            f,
+            "  position ({:.3}, {:.3}, {:.3})",
+            self.position.x, self.position.y, self.position.z
+        )?;
+        writeln!(f, "  etymology {:?}, morpheme {:?}", self.etymology, self.morpheme_type)?;
+        writeln!(f, "  productivity {:.2}, frequency rank {}", self.productivity, self.frequency_rank)?;
+        write!(f, "  connections:")?;
+        if self.connections.is_empty() {
+            write!(f, " none")?;
+        }
+        for (connection_type, count) in &self.connections {
+            write!(f, " {:?}x{}", connection_type, count)?;
+        }
+        Ok(())
     }
 }
 
@@ -4,6 +4,7 @@
 mod tests {
     use crate::storage::MemoryMappedDatabase;
     use crate::core::NodeId;
+    use crate::test_support::{build_database, open_database};
     use tempfile::TempDir;
     use std::fs;
     
@@ -49,4 +50,515 @@ mod tests {
         // This test would require a valid database
         // Testing that the method exists and returns proper error types
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_find_similar_nodes_returns_one_based_ids() {
+        use crate::core::{Layer, Coordinate3D, SimilarityMetric};
+        use crate::storage::DatabaseBuilder;
+        
+        let near = Coordinate3D::new(0.1, 0.1, Layer::Words.z_center());
+        let far = Coordinate3D::new(0.9, 0.9, Layer::Words.z_center());
+        let mut builder = DatabaseBuilder::new();
+        let first = builder.add_node("first", Layer::Words, near).unwrap();
+        let second = builder.add_node("second", Layer::Words, far).unwrap();
+        let (_dir, db) = open_database(&builder);
+        
+        // Node IDs start from 1; the first node is never reported as NodeId(0)
+        assert_eq!(first, NodeId(1));
+        assert_eq!(db.find_similar_nodes(near, 0.01, None), vec![first]);
+        assert_eq!(db.find_similar_nodes(far, 0.01, None), vec![second]);
+        assert_eq!(db.get_node_word(first).unwrap(), "first");
+        
+        let by_x = db.find_similar_nodes_by(far, 0.001, None, SimilarityMetric::SemanticOnly);
+        assert_eq!(by_x, vec![second]);
+    }
+    
+    #[test]
+    fn test_group_by_etymology_is_ordered() {
+        use crate::core::{Layer, Coordinate3D, EtymologyOrigin, MorphemeType, NodeFlags};
+        use crate::storage::DatabaseBuilder;
+        
+        let mut builder = DatabaseBuilder::new();
+        let words = [
+            ("psyche", EtymologyOrigin::Greek, Layer::Words),
+            ("house", EtymologyOrigin::Germanic, Layer::Words),
+            ("video", EtymologyOrigin::Latin, Layer::Words),
+            ("logos", EtymologyOrigin::Greek, Layer::Morphemes),
+            ("hand", EtymologyOrigin::Germanic, Layer::Words),
+        ];
+        let mut ids = Vec::new();
+        for (i, (word, origin, layer)) in words.iter().enumerate() {
+            let position = Coordinate3D::new(i as f32 / 10.0, origin.base_y_coordinate(), layer.z_center());
+            ids.push(builder.add_node_full(word, *layer, position, *origin, MorphemeType::Root, NodeFlags::empty()).unwrap());
+        }
+        let (_dir, db) = open_database(&builder);
+        
+        let groups: Vec<_> = db.group_by_etymology(&ids).into_iter().collect();
+        assert_eq!(groups, vec![
+            (EtymologyOrigin::Germanic, vec![ids[1], ids[4]]),
+            (EtymologyOrigin::Latin, vec![ids[2]]),
+            (EtymologyOrigin::Greek, vec![ids[0], ids[3]]),
+        ]);
+        
+        let layers: Vec<_> = db.layer_counts().into_iter().collect();
+        assert_eq!(layers, vec![(Layer::Morphemes, 1), (Layer::Words, 4)]);
+    }
+    
+    #[test]
+    fn test_etymology_histogram_and_filter() {
+        use crate::core::{Layer, Coordinate3D, BoundingBox3D, EtymologyOrigin, LinguisticNode, MorphemeType, NodeFlags};
+        use crate::engine::LingoExecutor;
+        use crate::query::{FilterCriteria, QueryBuilder};
+        use crate::storage::{DatabaseBuilder, LingoFileHeader};
+        use std::collections::HashMap;
+        use std::mem::{offset_of, size_of};
+        
+        let mut builder = DatabaseBuilder::new();
+        let words = [
+            ("psyche", EtymologyOrigin::Greek),
+            ("house", EtymologyOrigin::Germanic),
+            ("sky", EtymologyOrigin::Norse),
+            ("logos", EtymologyOrigin::Greek),
+            ("algebra", EtymologyOrigin::Arabic),
+            ("theory", EtymologyOrigin::Greek),
+        ];
+        for (i, (word, origin)) in words.iter().enumerate() {
+            let position = Coordinate3D::new(i as f32 / 10.0, origin.base_y_coordinate(), Layer::Words.z_center());
+            builder.add_node_full(word, Layer::Words, position, *origin, MorphemeType::Root, NodeFlags::empty()).unwrap();
+        }
+        let (_dir, db_path) = build_database(&builder);
+        
+        let db = MemoryMappedDatabase::open(&db_path).unwrap();
+        assert_eq!(db.etymology_histogram(), HashMap::from([
+            (EtymologyOrigin::Greek, 3),
+            (EtymologyOrigin::Germanic, 1),
+            (EtymologyOrigin::Norse, 1),
+            (EtymologyOrigin::Arabic, 1),
+        ]));
+        
+        let mut executor = LingoExecutor::new();
+        executor.load_database(&db_path).unwrap();
+        let everything = BoundingBox3D::new(Coordinate3D::new(0.0, 0.0, 0.0), Coordinate3D::new(1.0, 1.0, 1.0));
+        let query = QueryBuilder::spatial_box(everything)
+            .filter(FilterCriteria::Etymology(EtymologyOrigin::Greek))
+            .compile();
+        let result = executor.execute(&query).unwrap();
+        let greek: Vec<&str> = result.nodes.as_slice().iter()
+            .map(|&id| db.get_node_word(id).unwrap())
+            .collect();
+        assert_eq!(greek, ["psyche", "logos", "theory"]);
+        let streamed: Vec<NodeId> = executor.execute_iter(&query).unwrap().collect();
+        assert_eq!(streamed, result.nodes.as_slice());
+        
+        // An origin byte from some future version reads as Unknown
+        let mut bytes = fs::read(&db_path).unwrap();
+        let header: LingoFileHeader = unsafe {
+            std::ptr::read_unaligned(bytes.as_ptr() as *const LingoFileHeader)
+        };
+        let sky = header.node_array_offset as usize + 2 * size_of::<LinguisticNode>();
+        bytes[sky + offset_of!(LinguisticNode, etymology_origin)] = 200;
+        fs::write(&db_path, &bytes).unwrap();
+        
+        let db = MemoryMappedDatabase::open(&db_path).unwrap();
+        let histogram = db.etymology_histogram();
+        assert_eq!(histogram.get(&EtymologyOrigin::Unknown), Some(&1));
+        assert_eq!(histogram.get(&EtymologyOrigin::Norse), None);
+        assert_eq!(db.get_node_word(NodeId(3)).unwrap(), "sky");
+    }
+    
+    #[test]
+    fn test_describe_morpheme() {
+        use crate::core::{Layer, Coordinate3D, ConnectionType, EtymologyOrigin, MorphemeType, NodeFlags};
+        use crate::storage::DatabaseBuilder;
+        
+        let mut builder = DatabaseBuilder::new();
+        let suffix = builder.add_node_full(
+            "ness",
+            Layer::Morphemes,
+            Coordinate3D::new(0.6, 0.1, 0.35),
+            EtymologyOrigin::Germanic,
+            MorphemeType::Suffix,
+            NodeFlags::empty(),
+        ).unwrap();
+        builder.node_mut(suffix).unwrap().set_productivity(0.9);
+        let ity = builder.add_node("ity", Layer::Morphemes, Coordinate3D::new(0.6, 0.4, 0.35)).unwrap();
+        let dom = builder.add_node("dom", Layer::Morphemes, Coordinate3D::new(0.5, 0.1, 0.35)).unwrap();
+        builder.add_connection(suffix, ity, ConnectionType::Synonymy, 0.7).unwrap();
+        builder.add_connection(suffix, dom, ConnectionType::Synonymy, 0.5).unwrap();
+        builder.add_connection(suffix, ity, ConnectionType::Etymology, 0.2).unwrap();
+        let (_dir, db) = open_database(&builder);
+        
+        let description = db.describe(suffix).unwrap();
+        assert_eq!(description.word, "ness");
+        assert_eq!(description.layer, Layer::Morphemes);
+        assert_eq!(description.morpheme_type, MorphemeType::Suffix);
+        assert_eq!(description.etymology, EtymologyOrigin::Germanic);
+        assert!((description.productivity - 0.9).abs() < 0.001);
+        assert_eq!(description.connections, vec![
+            (ConnectionType::Synonymy, 2),
+            (ConnectionType::Etymology, 1),
+        ]);
+        assert!(description.to_string().contains("Suffix"));
+        
+        assert!(db.describe(NodeId(99)).is_err());
+    }
+    
+    #[test]
+    fn test_iter_connections_matches_stats() {
+        use crate::core::{Layer, Coordinate3D, ConnectionType};
+        use crate::storage::DatabaseBuilder;
+        
+        let mut builder = DatabaseBuilder::new();
+        let hot = builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        let cold = builder.add_node("cold", Layer::Words, Coordinate3D::new(0.8, 0.5, 0.52)).unwrap();
+        let warm = builder.add_node("warm", Layer::Words, Coordinate3D::new(0.3, 0.5, 0.52)).unwrap();
+        builder.add_connection(hot, cold, ConnectionType::Antonymy, 0.9).unwrap();
+        builder.add_connection(cold, hot, ConnectionType::Antonymy, 0.9).unwrap();
+        builder.add_connection(hot, warm, ConnectionType::Synonymy, 0.6).unwrap();
+        let (_dir, db) = open_database(&builder);
+        
+        let stats = db.connection_stats();
+        assert_eq!(stats.get(&ConnectionType::Antonymy), Some(&2));
+        assert_eq!(stats.get(&ConnectionType::Synonymy), Some(&1));
+        let types: Vec<_> = stats.keys().copied().collect();
+        assert_eq!(types, vec![ConnectionType::Synonymy, ConnectionType::Antonymy]);
+        
+        let iterated = db.iter_connections().count();
+        assert_eq!(iterated, stats.values().sum::<usize>());
+        assert_eq!(iterated, db.connection_count());
+        
+        let targets: Vec<NodeId> = db.iter_connections().map(|c| c.target_node).collect();
+        assert!(targets.contains(&warm));
+    }
+    
+    #[test]
+    fn test_sample_nodes_is_deterministic() {
+        use crate::core::{Layer, Coordinate3D};
+        use crate::storage::DatabaseBuilder;
+        
+        let mut builder = DatabaseBuilder::new();
+        for i in 0..50 {
+            let layer = if i % 2 == 0 { Layer::Words } else { Layer::Morphemes };
+            let x = i as f32 / 50.0;
+            builder.add_node(&format!("w{}", i), layer, Coordinate3D::new(x, 0.5, layer.z_center())).unwrap();
+        }
+        let (_dir, db) = open_database(&builder);
+        
+        let sample = db.sample_nodes(10, 42);
+        assert_eq!(sample.len(), 10);
+        assert_eq!(sample, db.sample_nodes(10, 42));
+        assert_ne!(sample, db.sample_nodes(10, 7));
+        let mut distinct = sample.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), 10);
+        
+        let words = db.sample_nodes_in_layer(Layer::Words, 5, 42);
+        assert_eq!(words.len(), 5);
+        assert_eq!(words, db.sample_nodes_in_layer(Layer::Words, 5, 42));
+        assert!(words.iter().all(|&id| db.get_node(id).unwrap().layer == Layer::Words));
+        
+        assert_eq!(db.sample_nodes(100, 1).len(), 50);
+    }
+    
+    #[test]
+    fn test_nodes_in_layer_reads_vertical_index() {
+        use crate::core::{Layer, Coordinate3D};
+        use crate::storage::{DatabaseBuilder, LingoFileHeader};
+        use std::mem::offset_of;
+        
+        let mut builder = DatabaseBuilder::new();
+        let mut morphemes = Vec::new();
+        for i in 0..12 {
+            let layer = match i % 3 {
+                0 => Layer::Morphemes,
+                1 => Layer::Words,
+                _ => Layer::Phrases,
+            };
+            let id = builder.add_node(&format!("n{}", i), layer, Coordinate3D::new(i as f32 / 12.0, 0.5, layer.z_center())).unwrap();
+            if layer == Layer::Morphemes {
+                morphemes.push(id);
+            }
+        }
+        let (_dir, db_path) = build_database(&builder);
+        
+        let db = MemoryMappedDatabase::open(&db_path).unwrap();
+        let found: Vec<NodeId> = db.nodes_in_layer(Layer::Morphemes).collect();
+        assert_eq!(found, morphemes);
+        assert_eq!(db.layer_count(Layer::Morphemes), 4);
+        assert_eq!(db.layer_count(Layer::Concepts), 0);
+        assert_eq!(db.nodes_in_layer(Layer::Concepts).count(), 0);
+        let layers: Vec<_> = db.layer_counts().into_iter().collect();
+        assert_eq!(layers, vec![(Layer::Morphemes, 4), (Layer::Words, 4), (Layer::Phrases, 4)]);
+        
+        // Files without a vertical index fall back to scanning the nodes
+        let mut bytes = fs::read(&db_path).unwrap();
+        let size = offset_of!(LingoFileHeader, vertical_index_size);
+        bytes[size..size + 8].copy_from_slice(&0u64.to_le_bytes());
+        let db = MemoryMappedDatabase::from_bytes(bytes).unwrap();
+        assert_eq!(db.nodes_in_layer(Layer::Morphemes).collect::<Vec<_>>(), morphemes);
+        assert_eq!(db.layer_count(Layer::Words), 4);
+    }
+    
+    #[test]
+    fn test_open_byte_swapped_file() {
+        use crate::core::{Layer, Coordinate3D, ConnectionType, LinguisticNode, OrthogonalConnection};
+        use crate::storage::{DatabaseBuilder, LingoFileHeader};
+        use std::mem::size_of;
+        
+        fn swap_in_place<T>(bytes: &mut [u8], swap: fn(&mut T)) {
+            for chunk in bytes.chunks_exact_mut(size_of::<T>()) {
+                unsafe {
+                    let ptr = chunk.as_mut_ptr() as *mut T;
+                    let mut item = std::ptr::read_unaligned(ptr);
+                    swap(&mut item);
+                    std::ptr::write_unaligned(ptr, item);
+                }
+            }
+        }
+        
+        let mut builder = DatabaseBuilder::new();
+        let hot = builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.3, 0.5)).unwrap();
+        let cold = builder.add_node("cold", Layer::Words, Coordinate3D::new(0.8, 0.7, 0.5)).unwrap();
+        builder.add_connection(hot, cold, ConnectionType::Antonymy, 0.75).unwrap();
+        let (_dir, db_path) = build_database(&builder);
+        
+        // Rewrite every multi-byte field in the opposite byte order
+        let mut bytes = fs::read(&db_path).unwrap();
+        let mut header: LingoFileHeader = unsafe {
+            std::ptr::read_unaligned(bytes.as_ptr() as *const LingoFileHeader)
+        };
+        let nodes = header.node_array_offset as usize..(header.node_array_offset + header.node_array_size) as usize;
+        let conns = header.connection_array_offset as usize
+            ..(header.connection_array_offset + header.connection_array_size) as usize;
+        swap_in_place(&mut bytes[nodes], LinguisticNode::swap_bytes);
+        swap_in_place(&mut bytes[conns], OrthogonalConnection::swap_bytes);
+        header.swap_bytes();
+        unsafe { std::ptr::write_unaligned(bytes.as_mut_ptr() as *mut LingoFileHeader, header) };
+        fs::write(&db_path, &bytes).unwrap();
+        
+        let db = MemoryMappedDatabase::open(&db_path).unwrap();
+        assert_eq!(db.node_count(), 2);
+        assert_eq!(db.get_node_word(hot).unwrap(), "hot");
+        assert_eq!(db.get_node_word(cold).unwrap(), "cold");
+        
+        let position = db.get_node(cold).unwrap().position;
+        assert_eq!(position, Coordinate3D::new(0.8, 0.7, 0.5));
+        
+        let connections = db.get_node_connections(hot).unwrap();
+        assert_eq!(connections.len(), 1);
+        let target = connections[0].target_node;
+        assert_eq!(target, cold);
+        assert!((connections[0].strength_normalized() - 0.75).abs() < 1e-4);
+        assert_eq!(db.nodes().len(), 2);
+        drop(db);
+        
+        // Files from before the marker have 0 there; the swapped major
+        // version gives their byte order away
+        let marker = std::mem::offset_of!(LingoFileHeader, endian_marker);
+        bytes[marker..marker + 4].fill(0);
+        fs::write(&db_path, &bytes).unwrap();
+        
+        let db = MemoryMappedDatabase::open(&db_path).unwrap();
+        assert_eq!(db.get_node_word(cold).unwrap(), "cold");
+        let position = db.get_node(cold).unwrap().position;
+        assert_eq!(position, Coordinate3D::new(0.8, 0.7, 0.5));
+    }
+    
+    #[test]
+    fn test_open_tolerates_unknown_connection_types() {
+        use crate::core::{Layer, Coordinate3D, ConnectionType, OrthogonalConnection};
+        use crate::storage::{DatabaseBuilder, LingoFileHeader};
+        use std::mem::{offset_of, size_of};
+        
+        let mut builder = DatabaseBuilder::new();
+        let hot = builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        let cold = builder.add_node("cold", Layer::Words, Coordinate3D::new(0.8, 0.5, 0.52)).unwrap();
+        let warm = builder.add_node("warm", Layer::Words, Coordinate3D::new(0.3, 0.5, 0.52)).unwrap();
+        builder.add_connection(hot, cold, ConnectionType::Antonymy, 0.9).unwrap();
+        builder.add_connection(hot, warm, ConnectionType::Synonymy, 0.6).unwrap();
+        let (_dir, db_path) = build_database(&builder);
+        
+        // Give the first connection a type byte from some future version
+        let mut bytes = fs::read(&db_path).unwrap();
+        let header: LingoFileHeader = unsafe {
+            std::ptr::read_unaligned(bytes.as_ptr() as *const LingoFileHeader)
+        };
+        let first = header.connection_array_offset as usize;
+        assert_eq!(header.connection_array_size as usize, 2 * size_of::<OrthogonalConnection>());
+        bytes[first + offset_of!(OrthogonalConnection, connection_type)] = 200;
+        fs::write(&db_path, &bytes).unwrap();
+        
+        let db = MemoryMappedDatabase::open(&db_path).unwrap();
+        let types: Vec<ConnectionType> = db.get_node_connections(hot).unwrap()
+            .iter()
+            .map(|c| c.connection_type)
+            .collect();
+        assert_eq!(types.len(), 2);
+        assert_eq!(types.iter().filter(|&&t| t == ConnectionType::Unknown).count(), 1);
+        assert_eq!(db.connection_stats().get(&ConnectionType::Unknown), Some(&1));
+    }
+    
+    #[test]
+    fn test_open_rejects_invalid_node_enums() {
+        use crate::core::{Layer, Coordinate3D, LinguisticNode};
+        use crate::core::error::LingoError;
+        use crate::storage::{DatabaseBuilder, LingoFileHeader};
+        use std::mem::{offset_of, size_of};
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        builder.add_node("cold", Layer::Words, Coordinate3D::new(0.8, 0.5, 0.52)).unwrap();
+        let (_dir, db_path) = build_database(&builder);
+        let good = fs::read(&db_path).unwrap();
+        let header: LingoFileHeader = unsafe {
+            std::ptr::read_unaligned(good.as_ptr() as *const LingoFileHeader)
+        };
+        let second = header.node_array_offset as usize + size_of::<LinguisticNode>();
+        
+        for (field, value, message) in [
+            (offset_of!(LinguisticNode, layer), 7, "Node 2 has invalid layer 7"),
+            (offset_of!(LinguisticNode, morpheme_type), 11, "Node 2 has invalid morpheme type 11"),
+        ] {
+            let mut bytes = good.clone();
+            bytes[second + field] = value;
+            fs::write(&db_path, &bytes).unwrap();
+            
+            let err = MemoryMappedDatabase::open(&db_path).unwrap_err();
+            assert!(matches!(&err, LingoError::CorruptedData(m) if m == message), "{}", err);
+        }
+    }
+    
+    #[test]
+    fn test_from_bytes_matches_open() {
+        use crate::core::{Layer, Coordinate3D, ConnectionType};
+        use crate::storage::DatabaseBuilder;
+        
+        let mut builder = DatabaseBuilder::new();
+        let hot = builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        let cold = builder.add_node("cold", Layer::Words, Coordinate3D::new(0.8, 0.5, 0.52)).unwrap();
+        builder.add_connection(hot, cold, ConnectionType::Antonymy, 0.9).unwrap();
+        let (_dir, db_path) = build_database(&builder);
+        
+        let bytes = fs::read(&db_path).unwrap();
+        let db = MemoryMappedDatabase::from_bytes(bytes.as_slice()).unwrap();
+        let opened = MemoryMappedDatabase::open(&db_path).unwrap();
+        assert_eq!(db.node_count(), opened.node_count());
+        assert_eq!(db.get_node_word(cold).unwrap(), "cold");
+        assert_eq!({ db.get_node_connections(hot).unwrap()[0].target_node }, cold);
+        assert_eq!(db.path(), std::path::Path::new(""));
+        
+        assert!(MemoryMappedDatabase::from_bytes(&b"INVALID HEADER DATA"[..]).is_err());
+        assert!(MemoryMappedDatabase::from_bytes(bytes[..bytes.len() / 2].to_vec()).is_err());
+        
+        // Static bytes are read in place, with the same accessors
+        let leaked: &'static [u8] = Box::leak(bytes.into_boxed_slice());
+        let db = MemoryMappedDatabase::from_static(leaked).unwrap();
+        assert_eq!(db.get_node_word(hot).unwrap(), "hot");
+        assert_eq!(db.nodes().as_ptr() as usize, leaked.as_ptr() as usize + db.header().node_array_offset as usize);
+        assert!(MemoryMappedDatabase::from_static(b"LINGO1.0").is_err());
+    }
+    
+    #[test]
+    fn test_from_bytes_rejects_invalid_layer() {
+        use crate::core::{Layer, Coordinate3D, LinguisticNode};
+        use crate::core::error::LingoError;
+        use crate::storage::{DatabaseBuilder, LingoFileHeader};
+        use std::mem::offset_of;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        let (_dir, db_path) = build_database(&builder);
+        let mut bytes = fs::read(&db_path).unwrap();
+        let header: LingoFileHeader = unsafe {
+            std::ptr::read_unaligned(bytes.as_ptr() as *const LingoFileHeader)
+        };
+        bytes[header.node_array_offset as usize + offset_of!(LinguisticNode, layer)] = 200;
+        
+        let err = MemoryMappedDatabase::from_bytes(bytes.clone()).unwrap_err();
+        assert!(matches!(&err, LingoError::CorruptedData(m) if m.contains("layer 200")), "{}", err);
+        let leaked: &'static [u8] = Box::leak(bytes.into_boxed_slice());
+        assert!(MemoryMappedDatabase::from_static(leaked).is_err());
+    }
+    
+    #[test]
+    fn test_open_rejects_bad_headers_gracefully() {
+        use crate::core::{Layer, Coordinate3D};
+        use crate::core::error::LingoError;
+        use crate::storage::{DatabaseBuilder, LingoFileHeader};
+        use std::mem::offset_of;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        let (_dir, db_path) = build_database(&builder);
+        let good = fs::read(&db_path).unwrap();
+        assert!(MemoryMappedDatabase::from_bytes(good.as_slice()).is_ok());
+        
+        let patched = |offset: usize, value: &[u8]| {
+            let mut bytes = good.clone();
+            bytes[offset..offset + value.len()].copy_from_slice(value);
+            MemoryMappedDatabase::from_bytes(bytes).unwrap_err()
+        };
+        
+        let err = patched(0, b"LINGX");
+        assert!(matches!(&err, LingoError::FileFormat(m) if m.contains("magic")), "{}", err);
+        
+        let err = patched(offset_of!(LingoFileHeader, version_minor), &2u16.to_le_bytes());
+        assert!(matches!(err, LingoError::UnsupportedVersion { major: 1, minor: 2 }), "{}", err);
+        assert!(err.to_string().contains("up to 1.1"), "{}", err);
+        let err = patched(offset_of!(LingoFileHeader, version_major), &2u16.to_le_bytes());
+        assert!(matches!(err, LingoError::UnsupportedVersion { major: 2, minor: 1 }), "{}", err);
+        
+        let err = patched(offset_of!(LingoFileHeader, compression_type), &[9]);
+        assert!(matches!(&err, LingoError::FileFormat(m) if m.contains("compression")), "{}", err);
+        let err = patched(offset_of!(LingoFileHeader, format_flags), &0x8000_0000u32.to_le_bytes());
+        assert!(matches!(&err, LingoError::FileFormat(m) if m.contains("flags")), "{}", err);
+        
+        let err = patched(offset_of!(LingoFileHeader, node_array_size), &u64::MAX.to_le_bytes());
+        assert!(matches!(&err, LingoError::FileFormat(m) if m.contains("Node array")), "{}", err);
+        let err = patched(offset_of!(LingoFileHeader, node_count), &1000u32.to_le_bytes());
+        assert!(matches!(&err, LingoError::FileFormat(m) if m.contains("1000 records")), "{}", err);
+        
+        // Truncated body
+        let err = MemoryMappedDatabase::from_bytes(&good[..good.len() - 8]).unwrap_err();
+        assert!(matches!(&err, LingoError::FileFormat(m) if m.contains("truncated")), "{}", err);
+        let err = MemoryMappedDatabase::from_bytes(&good[..600]).unwrap_err();
+        assert!(matches!(err, LingoError::FileFormat(_)), "{}", err);
+    }
+    
+    #[test]
+    fn test_open_verified_detects_flipped_byte() {
+        use crate::core::{Layer, Coordinate3D};
+        use crate::core::error::LingoError;
+        use crate::storage::{DatabaseBuilder, LingoFileHeader};
+        use std::mem::offset_of;
+        
+        let mut builder = DatabaseBuilder::new();
+        builder.add_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52)).unwrap();
+        builder.add_node("cold", Layer::Words, Coordinate3D::new(0.8, 0.5, 0.52)).unwrap();
+        let (_dir, db_path) = build_database(&builder);
+        
+        let db = MemoryMappedDatabase::open_verified(&db_path).unwrap();
+        assert!(db.header().has_checksums());
+        drop(db);
+        
+        // Flip one bit of the string table: open still succeeds, verification doesn't
+        let mut bytes = fs::read(&db_path).unwrap();
+        let db = MemoryMappedDatabase::from_bytes(bytes.as_slice()).unwrap();
+        let strings_at = db.header().string_table_offset as usize;
+        drop(db);
+        bytes[strings_at] ^= 0x01;
+        fs::write(&db_path, &bytes).unwrap();
+        
+        assert!(MemoryMappedDatabase::open(&db_path).is_ok());
+        let err = MemoryMappedDatabase::open_verified(&db_path).unwrap_err();
+        assert!(matches!(&err, LingoError::ChecksumMismatch { section } if section == "string table"), "{}", err);
+        
+        // Files from before checksums can't be verified
+        bytes[strings_at] ^= 0x01;
+        let minor = offset_of!(LingoFileHeader, version_minor);
+        bytes[minor..minor + 2].copy_from_slice(&0u16.to_le_bytes());
+        let old = MemoryMappedDatabase::from_bytes(bytes).unwrap();
+        assert!(matches!(old.verify_integrity(), Err(LingoError::FileFormat(_))));
+    }
+}
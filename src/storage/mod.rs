@@ -12,6 +12,9 @@
 //! - **File Format**: Binary format with header, nodes, connections, and indices
 //! - **Memory Mapping**: Zero-copy access through OS memory mapping
 //! - **String Table**: Deduplicated string storage with variable-length encoding
+//! - **Builder**: Writes new database files from in-memory nodes and connections
+//! - **Writer**: Serializes prepared nodes, connections and strings, with indices
+//! - **Mutable Database**: In-memory editing with batched, all-or-nothing inserts
 //!
 //! # File Format
 //!
@@ -26,6 +29,9 @@
 //! [Vertical Index]    // Layer traversal index
 //! ```
 //!
+//! All multi-byte fields are little-endian. The header carries a byte-order
+//! marker, and files in the other order are converted when opened.
+//!
 //! # Examples
 //!
 //! ## Reading a database
@@ -41,14 +47,22 @@
 //! ```
 //!
 pub mod file_format;
+mod builder;
+mod export;
+mod mutable;
 mod mmap;
 mod string_table;
+mod writer;
 
 #[cfg(test)]
 mod mmap_test;
 
+pub use builder::{DatabaseBuilder, BuildReport, BuildWarning, SymmetricConnectionMode};
+pub use writer::DatabaseWriter;
+pub use export::{export_dot, export_dot_subgraph, export_graphml, export_graphml_subgraph};
+pub use mutable::{MutableDatabase, Transaction};
 pub use file_format::{LingoFileHeader, FileFormatFlags};
-pub use mmap::MemoryMappedDatabase;
+pub use mmap::{MemoryMappedDatabase, NodeDescription, locate_database};
 pub use string_table::StringTable;
 
 // Re-export commonly used types
@@ -0,0 +1,345 @@
+//! In-memory database that accepts inserts and can be saved to disk
+
+use crate::core::{
+    NodeId, LinguisticNode, Coordinate3D, Layer, ConnectionType,
+    EtymologyOrigin, MorphemeType, NodeFlags,
+    error::{BuildError, Result},
+};
+use crate::storage::builder::check_new_node;
+use crate::storage::{BuildReport, DatabaseBuilder, MemoryMappedDatabase};
+use crate::logging::debug;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Editable Lingo database for runtime learning.
+///
+/// Nodes and connections live in memory and are written out with
+/// [`save`](Self::save). Node IDs are sequential from 1, matching
+/// [`MemoryMappedDatabase`], so IDs stay valid after saving and reopening.
+///
+/// Use [`transaction`](Self::transaction) for bursts of inserts: they are
+/// staged, the word index is updated once at commit, and nothing is applied
+/// if the transaction fails.
+///
+/// # Examples
+///
+/// ```rust
+/// use lingo::storage::MutableDatabase;
+/// use lingo::core::{Layer, Coordinate3D, ConnectionType};
+///
+/// # fn main() -> lingo::core::error::Result<()> {
+/// let mut db = MutableDatabase::new();
+/// db.transaction(|tx| {
+///     let hot = tx.insert_node("hot", Layer::Words, Coordinate3D::new(0.2, 0.5, 0.52))?;
+///     let cold = tx.insert_node("cold", Layer::Words, Coordinate3D::new(0.8, 0.5, 0.52))?;
+///     tx.insert_connection(hot, cold, ConnectionType::Antonymy, 0.9)
+/// })?;
+/// assert_eq!(db.node_count(), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct MutableDatabase {
+    /// Committed nodes, connections and strings
+    builder: DatabaseBuilder,
+    /// Word to node lookup, maintained on commit
+    word_index: HashMap<String, Vec<NodeId>>,
+}
+
+/// Inserts staged by [`MutableDatabase::transaction`].
+///
+/// Node IDs are assigned when a node is staged, so connections within the
+/// same transaction can refer to new nodes.
+#[derive(Debug)]
+pub struct Transaction {
+    /// Committed node count when the transaction began
+    base_count: usize,
+    /// Staged nodes in ID order
+    nodes: Vec<(String, Layer, Coordinate3D)>,
+    /// Staged connections as (from, to, type, strength)
+    connections: Vec<(NodeId, NodeId, ConnectionType, f32)>,
+}
+
+impl Transaction {
+    /// Stage a node, returning the ID it will have once committed
+    pub fn insert_node(&mut self, word: &str, layer: Layer, position: Coordinate3D) -> Result<NodeId> {
+        let existing = self.base_count + self.nodes.len();
+        check_new_node(existing, word, position)?;
+        self.nodes.push((word.to_string(), layer, position));
+        Ok(NodeId(existing as u32 + 1))
+    }
+
+    /// Stage a connection between committed or staged nodes
+    pub fn insert_connection(
+        &mut self,
+        from: NodeId,
+        to: NodeId,
+        connection_type: ConnectionType,
+        strength: f32,
+    ) -> Result<()> {
+        check_known_nodes(self.base_count + self.nodes.len(), from, to)?;
+        self.connections.push((from, to, connection_type, strength));
+        Ok(())
+    }
+
+    /// Number of nodes staged so far
+    pub fn pending_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Check every staged insert against a database of `base_count` nodes
+    fn validate(&self, base_count: usize) -> Result<()> {
+        for (i, (word, _, position)) in self.nodes.iter().enumerate() {
+            check_new_node(base_count + i, word, *position)?;
+        }
+        let known = base_count + self.nodes.len();
+        for &(from, to, _, _) in &self.connections {
+            check_known_nodes(known, from, to)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check that both ends of a connection are among the first `known` nodes
+fn check_known_nodes(known: usize, from: NodeId, to: NodeId) -> Result<()> {
+    for id in [from, to] {
+        if id.0 == 0 || id.0 as usize > known {
+            return Err(BuildError::InvalidConnection {
+                reason: format!("unknown node {}", id),
+            }.into());
+        }
+    }
+    Ok(())
+}
+
+impl MutableDatabase {
+    /// Create an empty database
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy an existing database into memory for editing
+    pub fn from_database(db: &MemoryMappedDatabase) -> Result<Self> {
+        let mut mutable = Self::new();
+        for (i, node) in db.nodes().iter().enumerate() {
+            let id = NodeId(i as u32 + 1);
+            let word = db.get_node_word(id)?;
+            mutable.builder.add_node_full(
+                word,
+                node.layer,
+                node.position,
+                node.etymology_origin,
+                node.morpheme_type,
+                node.flags,
+            )?;
+            if let Some(copy) = mutable.builder.node_mut(id) {
+                copy.phonetic_signature = node.phonetic_signature;
+                copy.productivity_score = node.productivity_score;
+                copy.frequency_rank = node.frequency_rank;
+                copy.spatial_bucket = node.spatial_bucket;
+                copy.set_phoneme_features(node.phoneme_features());
+            }
+            mutable.word_index.entry(word.to_string()).or_default().push(id);
+        }
+        for i in 0..db.node_count() {
+            let id = NodeId(i as u32 + 1);
+            for connection in db.get_node_connections(id)? {
+                mutable.builder.push_connection(id, *connection);
+            }
+        }
+        Ok(mutable)
+    }
+
+    /// Number of committed nodes
+    pub fn node_count(&self) -> usize {
+        self.builder.node_count()
+    }
+
+    /// Number of committed connections
+    pub fn connection_count(&self) -> usize {
+        self.builder.connection_count()
+    }
+
+    /// Get a committed node by ID
+    pub fn get_node(&self, id: NodeId) -> Option<&LinguisticNode> {
+        self.builder.node(id)
+    }
+
+    /// Get the surface form of a committed node
+    pub fn get_node_word(&self, id: NodeId) -> Option<&str> {
+        self.builder.node(id).map(|node| self.builder.node_word(node))
+    }
+
+    /// Get a committed node for in-place updates
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut LinguisticNode> {
+        self.builder.node_mut(id)
+    }
+
+    /// Find committed nodes with the given surface form
+    pub fn find_nodes_by_word(&self, word: &str) -> &[NodeId] {
+        self.word_index.get(word).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Insert a single node
+    pub fn insert_node(&mut self, word: &str, layer: Layer, position: Coordinate3D) -> Result<NodeId> {
+        self.transaction(|tx| tx.insert_node(word, layer, position))
+    }
+
+    /// Insert a single connection between committed nodes
+    pub fn insert_connection(
+        &mut self,
+        from: NodeId,
+        to: NodeId,
+        connection_type: ConnectionType,
+        strength: f32,
+    ) -> Result<()> {
+        self.transaction(|tx| tx.insert_connection(from, to, connection_type, strength))
+    }
+
+    /// Run `f` against a staged batch of inserts and apply them all if it
+    /// succeeds.
+    ///
+    /// If `f` returns an error the staged inserts are discarded and the
+    /// database is unchanged. The whole batch is checked again before any
+    /// of it is applied, so commit never stops part-way.
+    pub fn transaction<T, F>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Transaction) -> Result<T>,
+    {
+        let mut tx = Transaction {
+            base_count: self.node_count(),
+            nodes: Vec::new(),
+            connections: Vec::new(),
+        };
+
+        let value = match f(&mut tx) {
+            Ok(value) => value,
+            Err(e) => {
+                debug!(
+                    staged_nodes = tx.nodes.len(),
+                    staged_connections = tx.connections.len(),
+                    "Transaction rolled back"
+                );
+                return Err(e);
+            }
+        };
+
+        self.commit(tx)?;
+        Ok(value)
+    }
+
+    /// Apply a transaction, or nothing if any staged insert is invalid
+    fn commit(&mut self, tx: Transaction) -> Result<()> {
+        debug!(
+            nodes = tx.nodes.len(),
+            connections = tx.connections.len(),
+            "Committing transaction"
+        );
+
+        // These are the only checks the builder makes, so applying can't fail after this
+        tx.validate(self.node_count())?;
+
+        let mut new_words = Vec::with_capacity(tx.nodes.len());
+        for (word, layer, position) in tx.nodes {
+            let id = self.builder.add_node_full(
+                &word,
+                layer,
+                position,
+                EtymologyOrigin::Unknown,
+                MorphemeType::Root,
+                NodeFlags::empty(),
+            )?;
+            new_words.push((word, id));
+        }
+        for (from, to, connection_type, strength) in tx.connections {
+            self.builder.add_connection(from, to, connection_type, strength)?;
+        }
+
+        // Index updates are deferred to here so a batch touches the map once per word
+        for (word, id) in new_words {
+            self.word_index.entry(word).or_default().push(id);
+        }
+        Ok(())
+    }
+
+    /// Write the committed contents to `path`
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<BuildReport> {
+        self.builder.build(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::error::LingoError;
+
+    #[test]
+    fn test_transaction_commit_and_rollback() {
+        let mut db = MutableDatabase::new();
+
+        let ids = db.transaction(|tx| {
+            let mut ids = Vec::new();
+            for i in 0..1000 {
+                let x = i as f32 / 1000.0;
+                ids.push(tx.insert_node(&format!("word{}", i), Layer::Words, Coordinate3D::new(x, 0.5, 0.5))?);
+            }
+            tx.insert_connection(ids[0], ids[999], ConnectionType::Analogy, 0.4)?;
+            Ok(ids)
+        }).unwrap();
+
+        assert_eq!(db.node_count(), 1000);
+        assert_eq!(db.connection_count(), 1);
+        assert_eq!(db.find_nodes_by_word("word0"), &[ids[0]]);
+        assert_eq!(db.find_nodes_by_word("word999"), &[ids[999]]);
+        assert!(db.get_node(ids[999]).is_some());
+
+        let result: Result<()> = db.transaction(|tx| {
+            for i in 0..1000 {
+                tx.insert_node(&format!("extra{}", i), Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5))?;
+            }
+            tx.insert_connection(ids[0], NodeId(5000), ConnectionType::Analogy, 0.4)
+        });
+
+        assert!(matches!(result, Err(LingoError::Build(BuildError::InvalidConnection { .. }))));
+        assert_eq!(db.node_count(), 1000);
+        assert_eq!(db.connection_count(), 1);
+        assert!(db.find_nodes_by_word("extra0").is_empty());
+        assert!(db.get_node(NodeId(1001)).is_none());
+    }
+
+    #[test]
+    fn test_invalid_commit_applies_nothing() {
+        let mut db = MutableDatabase::new();
+        db.insert_node("kept", Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5)).unwrap();
+
+        // Staged by hand to get past the checks in insert_connection
+        let tx = Transaction {
+            base_count: db.node_count(),
+            nodes: vec![("added".to_string(), Layer::Words, Coordinate3D::new(0.4, 0.5, 0.5))],
+            connections: vec![(NodeId(1), NodeId(9), ConnectionType::Analogy, 0.5)],
+        };
+
+        assert!(matches!(db.commit(tx), Err(LingoError::Build(BuildError::InvalidConnection { .. }))));
+        assert_eq!(db.node_count(), 1);
+        assert_eq!(db.connection_count(), 0);
+        assert!(db.find_nodes_by_word("added").is_empty());
+    }
+
+    #[test]
+    fn test_save_and_reload() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("mutable.lingo");
+
+        let mut db = MutableDatabase::new();
+        let a = db.insert_node("alpha", Layer::Words, Coordinate3D::new(0.1, 0.2, 0.5)).unwrap();
+        let b = db.insert_node("beta", Layer::Words, Coordinate3D::new(0.3, 0.2, 0.5)).unwrap();
+        db.insert_connection(a, b, ConnectionType::Collocation, 0.6).unwrap();
+        db.save(&path).unwrap();
+
+        let mapped = MemoryMappedDatabase::open(&path).unwrap();
+        let reloaded = MutableDatabase::from_database(&mapped).unwrap();
+        assert_eq!(reloaded.node_count(), 2);
+        assert_eq!(reloaded.connection_count(), 1);
+        assert_eq!(reloaded.find_nodes_by_word("beta"), &[b]);
+    }
+}
@@ -77,6 +77,52 @@ impl StringTable {
         std::str::from_utf8(bytes).map_err(|_| LingoError::InvalidUtf8)
     }
     
+    /// Number of distinct strings interned through `add_string`
+    pub fn entry_count(&self) -> usize {
+        self.string_cache.len()
+    }
+    
+    /// Find pairs of interned strings that are within one edit of each other.
+    ///
+    /// Exact duplicates are already collapsed by `add_string`; this reports
+    /// the near misses (trailing spaces, single-letter spelling variants) for
+    /// manual review. Pairs are sorted and each pair is ordered.
+    ///
+    /// Strings are indexed under themselves and every single-character
+    /// deletion of themselves. Two strings within one edit always share
+    /// one of those keys, so only strings in the same bucket are compared,
+    /// instead of every pair.
+    pub fn near_duplicates(&self) -> Vec<(String, String)> {
+        let mut buckets: HashMap<String, Vec<&str>> = HashMap::new();
+        for s in self.string_cache.keys() {
+            buckets.entry(s.clone()).or_default().push(s);
+            for (at, c) in s.char_indices() {
+                let mut deleted = String::with_capacity(s.len() - c.len_utf8());
+                deleted.push_str(&s[..at]);
+                deleted.push_str(&s[at + c.len_utf8()..]);
+                buckets.entry(deleted).or_default().push(s);
+            }
+        }
+        
+        let mut pairs = Vec::new();
+        for bucket in buckets.values_mut() {
+            // A string with a repeated letter lands in a bucket twice
+            bucket.sort_unstable();
+            bucket.dedup();
+            for (i, a) in bucket.iter().enumerate() {
+                for b in &bucket[i + 1..] {
+                    if within_one_edit(a, b) {
+                        pairs.push((a.to_string(), b.to_string()));
+                    }
+                }
+            }
+        }
+        // The same pair can meet in more than one bucket
+        pairs.sort();
+        pairs.dedup();
+        pairs
+    }
+    
     /// Get the total size of the string table
     pub fn size(&self) -> usize {
         self.data.len()
@@ -94,6 +140,25 @@ impl StringTable {
     }
 }
 
+/// Whether two distinct strings differ by exactly one insertion, deletion or substitution
+fn within_one_edit(a: &str, b: &str) -> bool {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (short, long) = if a.len() <= b.len() { (&a, &b) } else { (&b, &a) };
+    if long.len() - short.len() > 1 {
+        return false;
+    }
+    
+    let prefix = short.iter().zip(long.iter()).take_while(|(x, y)| x == y).count();
+    if short.len() == long.len() {
+        // Substitution: everything after the first mismatch must match
+        prefix < short.len() && short[prefix + 1..] == long[prefix + 1..]
+    } else {
+        // Insertion into the shorter string
+        short[prefix..] == long[prefix + 1..]
+    }
+}
+
 impl Default for StringTable {
     fn default() -> Self {
         Self::new()
@@ -208,6 +273,32 @@ mod tests {
         assert_eq!(table.get_string(offset2, 5u16).unwrap(), "world");
     }
     
+    #[test]
+    fn test_string_table_near_duplicates() {
+        let mut table = StringTable::new();
+        for s in ["colour", "color", "color ", "table", "cable", "tablet", "tables"] {
+            table.add_string(s).unwrap();
+        }
+        table.add_string("color").unwrap();
+        
+        assert_eq!(table.entry_count(), 7);
+        assert_eq!(table.near_duplicates(), vec![
+            ("cable".to_string(), "table".to_string()),
+            ("color".to_string(), "color ".to_string()),
+            ("color".to_string(), "colour".to_string()),
+            ("table".to_string(), "tables".to_string()),
+            ("table".to_string(), "tablet".to_string()),
+            ("tables".to_string(), "tablet".to_string()),
+        ]);
+        
+        // Transpositions share deletion keys but are two edits apart
+        let mut table = StringTable::new();
+        for s in ["form", "from", "café", "cafe", "caffè"] {
+            table.add_string(s).unwrap();
+        }
+        assert_eq!(table.near_duplicates(), vec![("cafe".to_string(), "café".to_string())]);
+    }
+    
     #[test]
     fn test_dictionary_compression() {
         let mut compressed = CompressedStringTable::new();
@@ -0,0 +1,777 @@
+//! Low-level writer for Lingo database files
+
+use crate::core::{
+    NodeId, LinguisticNode, OrthogonalConnection, ConnectionType, Coordinate3D, BoundingBox3D,
+    error::{BuildError, LingoError, Result},
+};
+use crate::index::{OctreeBuilder, OctreeNode, SpatialIndex};
+use crate::index::octree::OctreeFlags;
+use crate::storage::{FileFormatFlags, LingoFileHeader, MemoryMappedDatabase, StringTable};
+use crate::storage::file_format::{header_checksum, section_checksum};
+use crate::logging::{debug, info};
+use super::builder::check_new_node;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::slice;
+
+/// Bytes per serialized octree node
+const OCTREE_RECORD_SIZE: usize = 64;
+
+/// Number of layers, from `Letters` to `Domains`
+const LAYER_COUNT: usize = 7;
+
+/// Writes nodes, connections and strings to a `.lingo` file as given.
+///
+/// Unlike [`DatabaseBuilder`](crate::storage::DatabaseBuilder), which
+/// creates nodes from words and validates the result, the writer takes
+/// [`LinguisticNode`]s whose `word_offset`/`word_length` already point into
+/// its string table. That makes it suitable for copying or transforming an
+/// existing database and writing it back out.
+///
+/// Nodes get sequential IDs starting from 1. When the file is written the
+/// connections are grouped per source node, and an octree spatial index and
+/// a per-layer vertical index are stored after the string table.
+///
+/// # Appending
+///
+/// [`open_append`](Self::open_append) loads an existing file together with
+/// its stored octree. Appended nodes are inserted into that octree in place
+/// of a rebuild, and [`commit`](Self::commit) writes the result back.
+///
+/// The sections are contiguous, so every commit still rewrites the whole
+/// file: O(file size) I/O plus an O(n) pass for the vertical index, but no
+/// O(n log n) octree build. Batch appends and commit once per batch to
+/// amortize that cost.
+///
+/// Commits never modify the existing file. The new contents go to a
+/// temporary file beside it, which is synced and then renamed over the
+/// original. On Unix the rename is atomic, and readers that already mapped
+/// the old file keep seeing the old version until they reopen it. Windows
+/// refuses to replace a file that is still mapped, so close readers first.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use lingo::storage::{Database, DatabaseWriter};
+///
+/// # fn main() -> lingo::core::error::Result<()> {
+/// let db = Database::open("english.lingo")?;
+/// let mut writer = DatabaseWriter::from_database(&db)?;
+/// let extra = writer.add_string("lingo")?;
+/// # let _ = extra;
+/// writer.write("english-copy.lingo")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct DatabaseWriter {
+    /// Nodes in ID order
+    nodes: Vec<LinguisticNode>,
+    /// Outgoing connections as (source, connection)
+    connections: Vec<(NodeId, OrthogonalConnection)>,
+    /// Surface forms referenced by the nodes
+    strings: StringTable,
+    /// Octree loaded by `open_append` and patched as nodes are added
+    spatial_index: Option<SpatialIndex>,
+    /// File that `commit` replaces
+    append_path: Option<PathBuf>,
+    /// Nodes and connections added since the last commit
+    pending: usize,
+    /// Flags describing how the connections are stored
+    format_flags: FileFormatFlags,
+}
+
+impl DatabaseWriter {
+    /// Create an empty writer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy the nodes, connections and strings of an open database
+    pub fn from_database(db: &MemoryMappedDatabase) -> Result<Self> {
+        let mut writer = Self {
+            nodes: db.nodes().to_vec(),
+            connections: Vec::with_capacity(db.connection_count()),
+            strings: db.string_table()?,
+            // Connections are copied as stored, so keep how they were stored
+            format_flags: db.header().format_flags & FileFormatFlags::SYMMETRIC_STORED_ONCE,
+            ..Self::default()
+        };
+        for i in 0..db.node_count() {
+            let id = NodeId(i as u32 + 1);
+            writer.nodes[i].id = id;
+            for connection in db.get_node_connections(id)? {
+                writer.connections.push((id, *connection));
+            }
+        }
+        Ok(writer)
+    }
+
+    /// Open an existing file for appending.
+    ///
+    /// The stored octree is reused and patched by later appends. Files
+    /// written without an octree section get one built here.
+    pub fn open_append<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let db = MemoryMappedDatabase::open(path)?;
+        let mut writer = Self::from_database(&db)?;
+        let spatial_index = match decode_spatial_index(db.octree_bytes(), &writer.nodes) {
+            Some(index) => index,
+            None => {
+                debug!(path = %path.display(), "No usable octree section; building one");
+                build_spatial_index(&writer.nodes)
+            }
+        };
+        writer.spatial_index = Some(spatial_index);
+        writer.append_path = Some(path.to_path_buf());
+        Ok(writer)
+    }
+
+    /// Number of nodes added so far
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of connections added so far
+    pub fn connection_count(&self) -> usize {
+        self.connections.len()
+    }
+
+    /// Nodes and connections added since the file was opened or last committed
+    pub fn pending_appends(&self) -> usize {
+        self.pending
+    }
+
+    /// Store a string, returning its offset in the string table.
+    ///
+    /// Identical strings share an entry.
+    pub fn add_string(&mut self, s: &str) -> Result<u32> {
+        if s.len() > u16::MAX as usize {
+            return Err(BuildError::StringTooLong {
+                length: s.len(),
+                max: u16::MAX as usize,
+            }.into());
+        }
+        self.strings.add_string(s)
+    }
+
+    /// Add a node, returning the ID it was assigned.
+    ///
+    /// The node's `id` is overwritten, and its connection range is filled in
+    /// when the file is written. Its word must already be in the string table.
+    pub fn add_node(&mut self, mut node: LinguisticNode) -> Result<NodeId> {
+        let word = self.strings.get_string(node.word_offset, node.word_length)?;
+        check_new_node(self.nodes.len(), word, node.position)?;
+
+        let id = NodeId(self.nodes.len() as u32 + 1);
+        node.id = id;
+        if let Some(index) = &mut self.spatial_index {
+            index.insert(id, node.position);
+        }
+        self.nodes.push(node);
+        self.pending += 1;
+        Ok(id)
+    }
+
+    /// Append a node to an opened file (alias for `add_node`)
+    pub fn append_node(&mut self, node: LinguisticNode) -> Result<NodeId> {
+        self.add_node(node)
+    }
+
+    /// Add an outgoing connection from `from`.
+    ///
+    /// Both endpoints must have been added.
+    pub fn add_connection(&mut self, from: NodeId, connection: OrthogonalConnection) -> Result<()> {
+        let to = connection.target_node;
+        for id in [from, to] {
+            if id.0 == 0 || id.0 as usize > self.nodes.len() {
+                return Err(BuildError::InvalidConnection {
+                    reason: format!("{} -> {} references unknown node {}", from, to, id),
+                }.into());
+            }
+        }
+        self.connections.push((from, connection));
+        self.pending += 1;
+        Ok(())
+    }
+
+    /// Append a connection to an opened file (alias for `add_connection`)
+    pub fn append_connection(&mut self, from: NodeId, connection: OrthogonalConnection) -> Result<()> {
+        self.add_connection(from, connection)
+    }
+
+    /// Remove connections weaker than `min_strength` (0.0-1.0), returning
+    /// how many were removed.
+    ///
+    /// Mirrored pairs of a bidirectional type (see
+    /// [`ConnectionType::is_bidirectional`]), A→B and B→A with the same
+    /// type, are pruned together: both go once the stronger of the two
+    /// falls below the threshold, so a symmetric relation never survives in
+    /// one direction only. Other connections are judged on their own.
+    ///
+    /// A connection stored with exactly `min_strength` is kept.
+    pub fn prune_connections(&mut self, min_strength: f32) -> usize {
+        let threshold = OrthogonalConnection::new(NodeId(0), ConnectionType::Unknown, min_strength).strength;
+
+        // Strongest connection of each bidirectional (source, target, type)
+        let mut strongest: HashMap<(NodeId, NodeId, ConnectionType), u16> = HashMap::new();
+        for (from, connection) in &self.connections {
+            let connection_type = connection.connection_type;
+            if connection_type.is_bidirectional() {
+                let strength = strongest.entry((*from, connection.target_node, connection_type)).or_default();
+                *strength = (*strength).max(connection.strength);
+            }
+        }
+
+        let before = self.connections.len();
+        self.connections.retain(|(from, connection)| {
+            let mirror = strongest.get(&(connection.target_node, *from, connection.connection_type));
+            let strength = mirror.map_or(connection.strength, |&mirror| mirror.max(connection.strength));
+            strength >= threshold
+        });
+        let pruned = before - self.connections.len();
+        debug!(min_strength, pruned, remaining = self.connections.len(), "Pruned connections");
+        pruned
+    }
+
+    /// Multiply every connection's strength by `factor`, clamped to [0, 1].
+    ///
+    /// Applied periodically this ages a learned graph: connections that
+    /// aren't reinforced fade until [`prune_connections`](Self::prune_connections)
+    /// removes them.
+    pub fn decay_connections(&mut self, factor: f32) {
+        let factor = if factor.is_nan() { 1.0 } else { factor.clamp(0.0, 1.0) };
+        for (_, connection) in &mut self.connections {
+            connection.set_strength_normalized(connection.strength_normalized() * factor);
+        }
+    }
+
+    /// Write the database to `path`
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        info!(
+            path = %path.display(),
+            nodes = self.nodes.len(),
+            connections = self.connections.len(),
+            "Writing Lingo database"
+        );
+        write_database(
+            path,
+            self.nodes.clone(),
+            &self.connections,
+            &self.strings,
+            self.spatial_index.as_ref(),
+            self.format_flags,
+        )
+    }
+
+    /// Atomically replace the file given to [`open_append`](Self::open_append)
+    pub fn commit(&mut self) -> Result<()> {
+        let path = self.append_path.clone().ok_or_else(|| {
+            LingoError::Database("Writer was not opened for appending".to_string())
+        })?;
+
+        let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(".tmp");
+        let temp_path = path.with_file_name(temp_name);
+
+        let result = self.write(&temp_path).and_then(|()| {
+            File::open(&temp_path)?.sync_all()?;
+            fs::rename(&temp_path, &path)?;
+            Ok(())
+        });
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        result?;
+
+        debug!(path = %path.display(), appended = self.pending, "Committed appends");
+        self.pending = 0;
+        Ok(())
+    }
+}
+
+/// Lay out and write a complete database file.
+///
+/// `edges` are (source, connection) pairs in the order they should be stored
+/// for each source node.
+pub(crate) fn write_database(
+    path: &Path,
+    mut nodes: Vec<LinguisticNode>,
+    edges: &[(NodeId, OrthogonalConnection)],
+    strings: &StringTable,
+    spatial_index: Option<&SpatialIndex>,
+    format_flags: FileFormatFlags,
+) -> Result<()> {
+    // Group connections by source node, preserving insertion order
+    let mut grouped: Vec<Vec<OrthogonalConnection>> = vec![Vec::new(); nodes.len()];
+    for (from, conn) in edges {
+        grouped[from.0 as usize - 1].push(*conn);
+    }
+
+    let mut connections = Vec::with_capacity(edges.len());
+    for (node, conns) in nodes.iter_mut().zip(&grouped) {
+        if conns.len() > u16::MAX as usize {
+            let id = node.id;
+            return Err(BuildError::InvalidConnection {
+                reason: format!("{} has more than {} connections", id, u16::MAX),
+            }.into());
+        }
+        node.connections_offset = connections.len() as u32;
+        node.connections_count = conns.len() as u16;
+        connections.extend_from_slice(conns);
+    }
+
+    // Indices are encoded field by field, so only the raw sections need swapping
+    let built;
+    let spatial_index = match spatial_index {
+        Some(index) => index,
+        None => {
+            built = build_spatial_index(&nodes);
+            &built
+        }
+    };
+    let octree_bytes = encode_spatial_index(spatial_index);
+    let vertical_bytes = encode_vertical_index(&nodes);
+
+    // Files are little-endian regardless of the host
+    if cfg!(target_endian = "big") {
+        nodes.iter_mut().for_each(LinguisticNode::swap_bytes);
+        connections.iter_mut().for_each(OrthogonalConnection::swap_bytes);
+    }
+
+    let node_bytes = as_bytes(&nodes);
+    let connection_bytes = as_bytes(&connections);
+    let string_bytes = strings.as_bytes();
+
+    let header_size = mem::size_of::<LingoFileHeader>() as u64;
+    let mut header = LingoFileHeader::new();
+    header.format_flags = format_flags;
+    header.node_count = nodes.len() as u32;
+    header.connection_count = connections.len() as u32;
+    header.octree_depth = spatial_index.stats().max_depth;
+    header.layer_count = LAYER_COUNT as u8;
+    header.node_array_offset = header_size;
+    header.node_array_size = node_bytes.len() as u64;
+    header.connection_array_offset = header.node_array_offset + header.node_array_size;
+    header.connection_array_size = connection_bytes.len() as u64;
+    header.string_table_offset = header.connection_array_offset + header.connection_array_size;
+    header.string_table_size = string_bytes.len() as u64;
+    header.octree_offset = header.string_table_offset + header.string_table_size;
+    header.octree_size = octree_bytes.len() as u64;
+    header.vertical_index_offset = header.octree_offset + header.octree_size;
+    header.vertical_index_size = vertical_bytes.len() as u64;
+    header.file_size = header.vertical_index_offset + header.vertical_index_size;
+    header.creation_timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    header.data_checksum = section_checksum(&[node_bytes, connection_bytes]);
+    header.string_checksum = section_checksum(&[string_bytes]);
+    header.index_checksum = section_checksum(&[&octree_bytes, &vertical_bytes]);
+    let file_size = header.file_size;
+    if cfg!(target_endian = "big") {
+        header.swap_bytes();
+    }
+    // Hashed as stored, so the header is already in file byte order
+    header.header_checksum = header_checksum(as_bytes(slice::from_ref(&header))).to_le();
+
+    let mut file = BufWriter::new(File::create(path).map_err(LingoError::Io)?);
+    file.write_all(as_bytes(slice::from_ref(&header)))?;
+    file.write_all(node_bytes)?;
+    file.write_all(connection_bytes)?;
+    file.write_all(string_bytes)?;
+    file.write_all(&octree_bytes)?;
+    file.write_all(&vertical_bytes)?;
+    file.flush()?;
+
+    debug!(file_size = file_size, "Database written");
+    Ok(())
+}
+
+/// Build a fresh octree over all nodes
+fn build_spatial_index(nodes: &[LinguisticNode]) -> SpatialIndex {
+    let mut builder = OctreeBuilder::new();
+    for node in nodes {
+        builder.add_node(node.id, node.position);
+    }
+    builder.build()
+}
+
+/// Encode the octree section.
+///
+/// Layout: root index and record count (`u32` each), then one 64-byte
+/// record per octree node (bounds as six `f32`, eight child indices, item
+/// count `u16`, item offset `u32`, depth, flags), then the node IDs of every
+/// leaf. A leaf's item offset indexes into that trailing ID array.
+fn encode_spatial_index(index: &SpatialIndex) -> Vec<u8> {
+    let octants = index.nodes();
+    let mut bytes = Vec::with_capacity(8 + octants.len() * OCTREE_RECORD_SIZE);
+    put_u32(&mut bytes, index.root_index());
+    put_u32(&mut bytes, octants.len() as u32);
+
+    let mut leaf_ids = Vec::new();
+    for octant in octants {
+        let mut item_offset = 0;
+        if octant.flags.contains(OctreeFlags::IS_LEAF) {
+            item_offset = leaf_ids.len() as u32;
+            let start = leaf_ids.len();
+            leaf_ids.extend(index.leaf_entries(octant.node_offset).iter().map(|&(id, _)| id.0));
+            leaf_ids[start..].sort_unstable();
+        }
+
+        let record_start = bytes.len();
+        for v in [octant.bounds.min, octant.bounds.max].iter().flat_map(|c| [c.x, c.y, c.z]) {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+        for child in octant.children {
+            put_u32(&mut bytes, child);
+        }
+        bytes.extend_from_slice(&octant.node_count.to_le_bytes());
+        put_u32(&mut bytes, item_offset);
+        bytes.push(octant.depth);
+        bytes.push(octant.flags.bits());
+        debug_assert_eq!(bytes.len() - record_start, OCTREE_RECORD_SIZE);
+    }
+
+    for id in leaf_ids {
+        put_u32(&mut bytes, id);
+    }
+    bytes
+}
+
+/// Decode an octree section written by [`encode_spatial_index`].
+///
+/// Returns `None` if the section is missing, truncated, or doesn't match
+/// `nodes`, in which case the caller rebuilds the octree.
+fn decode_spatial_index(bytes: &[u8], nodes: &[LinguisticNode]) -> Option<SpatialIndex> {
+    let read_u32 = |at: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+    };
+    let read_f32 = |at: usize| read_u32(at).map(f32::from_bits);
+
+    let root_index = read_u32(0)?;
+    let count = read_u32(4)? as usize;
+    let ids_start = count.checked_mul(OCTREE_RECORD_SIZE)?.checked_add(8)?;
+    if bytes.len() != ids_start.checked_add(nodes.len() * 4)? || root_index as usize >= count.max(1) {
+        return None;
+    }
+
+    let mut octants = Vec::with_capacity(count);
+    let mut buckets = HashMap::new();
+    let mut total_items = 0;
+    for i in 0..count {
+        let at = 8 + i * OCTREE_RECORD_SIZE;
+        let coord = |at: usize| Some(Coordinate3D {
+            x: read_f32(at)?,
+            y: read_f32(at + 4)?,
+            z: read_f32(at + 8)?,
+        });
+        let mut children = [0u32; 8];
+        for (c, child) in children.iter_mut().enumerate() {
+            *child = read_u32(at + 24 + c * 4)?;
+            // Octants are stored parent first, so a child that doesn't come
+            // after its parent is a cycle that would hang insertion
+            if *child as usize >= count || (*child != 0 && *child as usize <= i) {
+                return None;
+            }
+        }
+        let node_count = u16::from_le_bytes([bytes[at + 56], bytes[at + 57]]);
+        let item_offset = read_u32(at + 58)? as usize;
+        let flags = OctreeFlags::from_bits_truncate(bytes[at + 63]);
+
+        let mut node_offset = 0;
+        if flags.contains(OctreeFlags::IS_LEAF) {
+            // Leaves are re-keyed by their own index, as the builder does
+            node_offset = i as u32;
+            let mut bucket = Vec::with_capacity(node_count as usize);
+            for k in 0..node_count as usize {
+                let id = NodeId(read_u32(ids_start + (item_offset + k) * 4)?);
+                let node = nodes.get((id.0 as usize).checked_sub(1)?)?;
+                bucket.push((id, node.position));
+            }
+            total_items += bucket.len();
+            buckets.insert(node_offset, bucket);
+        }
+
+        octants.push(OctreeNode {
+            bounds: BoundingBox3D::new(coord(at)?, coord(at + 12)?),
+            children,
+            node_count,
+            node_offset,
+            depth: bytes[at + 62],
+            flags,
+        });
+    }
+
+    // Leaf counts saturate at u16::MAX, so anything short of full coverage is rebuilt
+    (total_items == nodes.len()).then(|| SpatialIndex::from_parts(root_index, octants, buckets))
+}
+
+/// Encode the vertical index section.
+///
+/// Layout: a (start, count) `u32` pair per layer, lowest layer first, then
+/// every node ID grouped by layer in ascending order. Starts index into the
+/// ID array.
+fn encode_vertical_index(nodes: &[LinguisticNode]) -> Vec<u8> {
+    let mut by_layer: Vec<Vec<u32>> = vec![Vec::new(); LAYER_COUNT];
+    for node in nodes {
+        by_layer[node.layer as usize].push(node.id.0);
+    }
+
+    let mut bytes = Vec::with_capacity(LAYER_COUNT * 8 + nodes.len() * 4);
+    let mut start = 0;
+    for ids in &by_layer {
+        put_u32(&mut bytes, start);
+        put_u32(&mut bytes, ids.len() as u32);
+        start += ids.len() as u32;
+    }
+    for id in by_layer.into_iter().flatten() {
+        put_u32(&mut bytes, id);
+    }
+    bytes
+}
+
+fn put_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+/// View a slice of plain-old-data structures as raw bytes
+fn as_bytes<T>(items: &[T]) -> &[u8] {
+    // SAFETY: only used with the repr(C) on-disk structures, which contain
+    // no pointers and are fully initialised (padding fields are explicit)
+    unsafe { slice::from_raw_parts(items.as_ptr() as *const u8, mem::size_of_val(items)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Coordinate3D, Layer};
+    use crate::storage::Database;
+    use tempfile::TempDir;
+
+    fn read_u32(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_write_and_reopen_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("written.lingo");
+
+        let layers = [Layer::Morphemes, Layer::Words, Layer::Concepts];
+        let mut writer = DatabaseWriter::new();
+        let mut expected = Vec::new();
+        for i in 0..500u32 {
+            let word = format!("node{}", i % 450);
+            let position = Coordinate3D::new(
+                (i % 10) as f32 / 10.0 + 0.013,
+                (i / 10 % 10) as f32 / 10.0 + 0.027,
+                (i / 100) as f32 / 5.0 + 0.031,
+            );
+            let mut node = LinguisticNode::new(NodeId(0), layers[i as usize % 3], position);
+            node.word_offset = writer.add_string(&word).unwrap();
+            node.word_length = word.len() as u16;
+            node.frequency_rank = i * 7;
+            let id = writer.add_node(node).unwrap();
+            assert_eq!(id, NodeId(i + 1));
+            expected.push((word, position));
+        }
+        for i in 1..500 {
+            let connection = OrthogonalConnection::new(NodeId(i), ConnectionType::Synonymy, 0.6);
+            writer.add_connection(NodeId(i + 1), connection).unwrap();
+        }
+        writer.write(&path).unwrap();
+
+        let db = Database::open(&path).unwrap();
+        assert_eq!(db.node_count(), 500);
+        assert_eq!(db.connection_count(), 499);
+        for (i, node) in db.nodes().iter().enumerate() {
+            let id = NodeId(i as u32 + 1);
+            let node_id = node.id;
+            let position = node.position;
+            let rank = node.frequency_rank;
+            assert_eq!(node_id, id);
+            assert_eq!(position, expected[i].1);
+            assert_eq!(rank, i as u32 * 7);
+            assert_eq!(db.get_node_word(id).unwrap(), expected[i].0);
+        }
+        let conns = db.get_node_connections(NodeId(42)).unwrap();
+        assert_eq!(conns.len(), 1);
+        let target = conns[0].target_node;
+        assert_eq!(target, NodeId(41));
+
+        // Both index sections follow the string table and cover every node
+        let header = db.header();
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(bytes.len() as u64, { header.file_size });
+        assert!({ header.octree_size } > 8);
+        assert!(header.octree_depth > 0);
+        let octree = &bytes[header.octree_offset as usize..][..header.octree_size as usize];
+        let records = read_u32(octree, 4) as usize;
+        assert_eq!(octree.len(), 8 + records * OCTREE_RECORD_SIZE + 500 * 4);
+
+        let vertical = &bytes[header.vertical_index_offset as usize..][..header.vertical_index_size as usize];
+        let words = Layer::Words as usize;
+        let start = read_u32(vertical, words * 8) as usize;
+        let count = read_u32(vertical, words * 8 + 4) as usize;
+        assert_eq!(count, 167);
+        let first = read_u32(vertical, LAYER_COUNT * 8 + start * 4);
+        assert_eq!(first, 2);
+
+        // Writing an opened database back out preserves it
+        let copy_path = temp_dir.path().join("copy.lingo");
+        DatabaseWriter::from_database(&db).unwrap().write(&copy_path).unwrap();
+        let copy = Database::open(&copy_path).unwrap();
+        assert_eq!(copy.node_count(), db.node_count());
+        assert_eq!(copy.connection_count(), db.connection_count());
+        for i in 0..500 {
+            let id = NodeId(i + 1);
+            let (a, b) = (copy.get_node(id).unwrap().position, db.get_node(id).unwrap().position);
+            assert_eq!(a, b);
+            assert_eq!(copy.get_node_word(id).unwrap(), db.get_node_word(id).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_append_patches_index_and_replaces_file() {
+        use crate::storage::DatabaseBuilder;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("append.lingo");
+
+        let mut builder = DatabaseBuilder::new();
+        for i in 0..300 {
+            let position = Coordinate3D::new((i % 17) as f32 / 17.0, (i % 13) as f32 / 13.0, 0.52);
+            builder.add_node(&format!("old{}", i), Layer::Words, position).unwrap();
+        }
+        builder.build(&path).unwrap();
+        let reader = Database::open(&path).unwrap();
+        assert!(decode_spatial_index(reader.octree_bytes(), reader.nodes()).is_some());
+        assert!(decode_spatial_index(&reader.octree_bytes()[..40], reader.nodes()).is_none());
+
+        // An octant listed as its own child is rejected, so appends rebuild the tree
+        let mut cyclic = reader.octree_bytes().to_vec();
+        cyclic[8 + OCTREE_RECORD_SIZE + 24..8 + OCTREE_RECORD_SIZE + 28].copy_from_slice(&1u32.to_le_bytes());
+        assert!(decode_spatial_index(&cyclic, reader.nodes()).is_none());
+
+        let mut writer = DatabaseWriter::open_append(&path).unwrap();
+        let stored_octants = writer.spatial_index.as_ref().unwrap().nodes().len();
+        let mut appended = Vec::new();
+        for i in 0..50 {
+            let word = format!("new{}", i);
+            let position = Coordinate3D::new(0.9, i as f32 / 50.0, 0.97);
+            let mut node = LinguisticNode::new(NodeId(0), Layer::Domains, position);
+            node.word_offset = writer.add_string(&word).unwrap();
+            node.word_length = word.len() as u16;
+            appended.push((writer.append_node(node).unwrap(), position));
+        }
+        let (first, _) = appended[0];
+        writer.append_connection(first, OrthogonalConnection::new(NodeId(1), ConnectionType::Hypernymy, 0.7)).unwrap();
+        assert_eq!(writer.pending_appends(), 51);
+
+        // The loaded octree was extended rather than rebuilt
+        let index = writer.spatial_index.as_ref().unwrap();
+        assert!(index.nodes().len() >= stored_octants);
+        assert_eq!(index.stats().total_items, 350);
+        for &(id, position) in &appended {
+            assert!(index.find_within_radius(position, 1e-4).contains(&id));
+        }
+
+        writer.commit().unwrap();
+        assert_eq!(writer.pending_appends(), 0);
+        assert!(!temp_dir.path().join("append.lingo.tmp").exists());
+
+        // The reader mapped before the commit still sees the old file
+        assert_eq!(reader.node_count(), 300);
+        assert_eq!(reader.get_node_word(NodeId(300)).unwrap(), "old299");
+
+        let db = Database::open(&path).unwrap();
+        assert_eq!(db.node_count(), 350);
+        assert_eq!(db.get_node_word(NodeId(301)).unwrap(), "new0");
+        assert_eq!(db.get_node_connections(first).unwrap().len(), 1);
+
+        // The committed octree is stored and reloads with every node
+        let reopened = DatabaseWriter::open_append(&path).unwrap();
+        let index = reopened.spatial_index.as_ref().unwrap();
+        assert_eq!(index.stats().total_items, 350);
+        let (last, position) = appended[49];
+        assert_eq!(index.k_nearest(position, 1)[0].0, last);
+
+        assert!(DatabaseWriter::new().commit().is_err());
+    }
+
+    #[test]
+    fn test_writer_rejects_dangling_references() {
+        let mut writer = DatabaseWriter::new();
+        let mut node = LinguisticNode::new(NodeId(0), Layer::Words, Coordinate3D::new(0.5, 0.5, 0.5));
+        node.word_offset = 100;
+        node.word_length = 4;
+        assert!(writer.add_node(node).is_err());
+
+        node.word_offset = writer.add_string("word").unwrap();
+        let id = writer.add_node(node).unwrap();
+        let connection = OrthogonalConnection::new(NodeId(2), ConnectionType::Synonymy, 0.5);
+        assert!(writer.add_connection(id, connection).is_err());
+        assert_eq!(writer.connection_count(), 0);
+    }
+
+    #[test]
+    fn test_prune_and_decay_connections() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("pruned.lingo");
+
+        let mut writer = DatabaseWriter::new();
+        let ids: Vec<NodeId> = (0..4)
+            .map(|i| {
+                let word = format!("w{}", i);
+                let mut node = LinguisticNode::new(NodeId(0), Layer::Words, Coordinate3D::new(i as f32 / 4.0, 0.5, 0.5));
+                node.word_offset = writer.add_string(&word).unwrap();
+                node.word_length = word.len() as u16;
+                writer.add_node(node).unwrap()
+            })
+            .collect();
+        let mut connect = |from: usize, to: usize, connection_type, strength| {
+            let connection = OrthogonalConnection::new(ids[to], connection_type, strength);
+            writer.add_connection(ids[from], connection).unwrap();
+        };
+        connect(0, 1, ConnectionType::Derivation, 0.9);
+        connect(0, 2, ConnectionType::Derivation, 0.2);
+        connect(1, 2, ConnectionType::Hypernymy, 0.3);
+        // A weak direction of a synonym pair survives with its strong mirror
+        connect(2, 3, ConnectionType::Synonymy, 0.1);
+        connect(3, 2, ConnectionType::Synonymy, 0.8);
+        // Both directions weak: pruned together
+        connect(1, 3, ConnectionType::Antonymy, 0.2);
+        connect(3, 1, ConnectionType::Antonymy, 0.25);
+
+        assert_eq!(writer.prune_connections(0.3), 3);
+        assert_eq!(writer.connection_count(), 4);
+        writer.write(&path).unwrap();
+
+        let db = Database::open(&path).unwrap();
+        let targets = |id: NodeId| -> Vec<NodeId> {
+            db.get_node_connections(id).unwrap().iter().map(|c| c.target_node).collect()
+        };
+        assert_eq!(targets(ids[0]), vec![ids[1]]);
+        assert_eq!(targets(ids[1]), vec![ids[2]]);
+        assert_eq!(targets(ids[2]), vec![ids[3]]);
+        assert_eq!(targets(ids[3]), vec![ids[2]]);
+
+        // Halving twice leaves only the derivation (now 0.225) above 0.21
+        let mut writer = DatabaseWriter::from_database(&db).unwrap();
+        writer.decay_connections(0.5);
+        writer.decay_connections(0.5);
+        assert_eq!(writer.prune_connections(0.21), 3);
+        let (from, connection) = writer.connections[0];
+        assert_eq!(from, ids[0]);
+        assert!((connection.strength_normalized() - 0.225).abs() < 1e-3);
+
+        writer.decay_connections(0.0);
+        assert_eq!(writer.prune_connections(0.0), 0);
+        assert_eq!(writer.prune_connections(0.01), 1);
+    }
+}
@@ -0,0 +1,51 @@
+//! Fixtures for unit tests: small databases built into temporary directories
+
+use crate::core::{Coordinate3D, Layer};
+use crate::engine::LingoExecutor;
+use crate::storage::{DatabaseBuilder, LingoDatabase};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// Build `builder` into a fresh temporary directory.
+///
+/// The directory is removed when the returned `TempDir` is dropped, so keep
+/// it alive for as long as the file is in use.
+pub(crate) fn build_database(builder: &DatabaseBuilder) -> (TempDir, PathBuf) {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("test.lingo");
+    builder.build(&path).unwrap();
+    (dir, path)
+}
+
+/// Build `builder` and open the result
+pub(crate) fn open_database(builder: &DatabaseBuilder) -> (TempDir, LingoDatabase) {
+    let (dir, path) = build_database(builder);
+    let database = LingoDatabase::open(&path).unwrap();
+    (dir, database)
+}
+
+/// Build `builder` and load the result into a new executor
+pub(crate) fn executor_from(builder: &DatabaseBuilder) -> (TempDir, LingoExecutor) {
+    let (dir, path) = build_database(builder);
+    let mut executor = LingoExecutor::new();
+    executor.load_database(&path).unwrap();
+    (dir, executor)
+}
+
+/// Build `builder` and open it both directly and through an executor
+pub(crate) fn database_and_executor(builder: &DatabaseBuilder) -> (TempDir, LingoDatabase, LingoExecutor) {
+    let (dir, path) = build_database(builder);
+    let database = LingoDatabase::open(&path).unwrap();
+    let mut executor = LingoExecutor::new();
+    executor.load_database(&path).unwrap();
+    (dir, database, executor)
+}
+
+/// Executor over a database holding just `nodes`, with no connections
+pub(crate) fn executor_with(nodes: &[(&str, Layer, Coordinate3D)]) -> (TempDir, LingoExecutor) {
+    let mut builder = DatabaseBuilder::new();
+    for &(word, layer, position) in nodes {
+        builder.add_node(word, layer, position).unwrap();
+    }
+    executor_from(&builder)
+}
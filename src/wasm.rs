@@ -25,6 +25,12 @@
 //! // Create and load database
 //! const db = new LingoDatabase();
 //! await db.loadStandardEnglish();
+//! // or, without a filesystem:
+//! // db.loadFromBytes(new Uint8Array(await (await fetch("english.lingo")).arrayBuffer()));
+//!
+//! // Morphology
+//! const parts = db.decompose("unhappiness");  // [{surface_form: "un", ...}, ...]
+//! const mirrors = db.findMirrors("happy");
 //! 
 //! // Build and execute query
 //! const query = QueryBuilder.find("tech")
@@ -40,8 +46,12 @@ use wasm_bindgen::prelude::*;
 use js_sys::{Array, Object, Reflect};
 use serde::{Serialize, Deserialize};
 
+use std::sync::Arc;
+
 use crate::engine::LingoExecutor;
+use crate::mirroring::MirroringDecomposer;
 use crate::query::{QueryBuilder as CoreQueryBuilder, CompiledQuery};
+use crate::storage::Database as CoreDatabase;
 
 // Set up panic hook and allocator for WASM
 #[cfg(target_arch = "wasm32")]
@@ -125,6 +135,25 @@ impl WasmNode {
     }
 }
 
+/// One morpheme of a decomposed word, as returned by `decompose`
+#[derive(Serialize)]
+struct WasmMorpheme {
+    surface_form: String,
+    morpheme_type: String,
+    x: Option<f32>,
+    y: Option<f32>,
+    z: Option<f32>,
+}
+
+/// One mirror of a word, as returned by `findMirrors`
+#[derive(Serialize)]
+struct WasmMirror {
+    original: String,
+    mirror: String,
+    mirror_type: String,
+    confidence: f32,
+}
+
 /// Query results for JavaScript
 #[wasm_bindgen]
 pub struct WasmQueryResult {
@@ -248,6 +277,7 @@ pub struct WasmCompiledQuery {
 #[wasm_bindgen]
 pub struct LingoDatabase {
     executor: Option<LingoExecutor>,
+    decomposer: Option<MirroringDecomposer>,
 }
 
 #[wasm_bindgen]
@@ -257,49 +287,78 @@ impl LingoDatabase {
     pub fn new() -> LingoDatabase {
         LingoDatabase {
             executor: None,
+            decomposer: None,
         }
     }
 
-    /// Load a database from bytes (for web usage)
+    /// Load a database from the bytes of a `.lingo` file (for web usage),
+    /// e.g. a `Uint8Array` from `fetch`
     #[wasm_bindgen(js_name = loadFromBytes)]
-    pub fn load_from_bytes(&mut self, _bytes: &[u8]) -> Result<WasmResult, JsValue> {
-        // In a real implementation, we'd write bytes to a temporary file
-        // For now, let's return an error indicating this needs implementation
-        Err(JsValue::from_str("loadFromBytes not yet implemented - use loadStandardEnglish() instead"))
+    pub fn load_from_bytes(&mut self, bytes: &[u8]) -> Result<WasmResult, JsValue> {
+        match CoreDatabase::from_bytes(bytes) {
+            Ok(database) => self.install(Arc::new(database)),
+            Err(e) => Ok(WasmResult {
+                success: false,
+                error: Some(format!("Invalid database bytes: {}", e)),
+            }),
+        }
     }
 
     /// Load the standard English database (pre-built)
     #[wasm_bindgen(js_name = loadStandardEnglish)]
     pub fn load_standard_english(&mut self) -> Result<WasmResult, JsValue> {
-        // Look for pre-built English database
-        let mut executor = LingoExecutor::new();
-        match executor.load_database("english.lingo") {
-            Ok(_) => {
-                self.executor = Some(executor);
-                Ok(WasmResult {
-                    success: true,
-                    error: None,
-                })
-            }
-            Err(_) => {
-                // Try loading from current directory
-                match executor.load_database("./english.lingo") {
-                    Ok(_) => {
-                        self.executor = Some(executor);
-                        Ok(WasmResult {
-                            success: true,
-                            error: None,
-                        })
-                    }
-                    Err(e) => Ok(WasmResult {
-                        success: false,
-                        error: Some(format!("Pre-built English database not found. Please ensure 'english.lingo' is available: {}", e)),
-                    })
-                }
-            }
+        // Look for pre-built English database, then in the current directory
+        match CoreDatabase::open("english.lingo").or_else(|_| CoreDatabase::open("./english.lingo")) {
+            Ok(database) => self.install(Arc::new(database)),
+            Err(e) => Ok(WasmResult {
+                success: false,
+                error: Some(format!("Pre-built English database not found. Please ensure 'english.lingo' is available: {}", e)),
+            }),
         }
     }
 
+    /// Split a word into morphemes.
+    ///
+    /// Returns an array of `{surface_form, morpheme_type, x, y, z}`; the
+    /// coordinates are null for morphemes not in the database.
+    #[wasm_bindgen]
+    pub fn decompose(&mut self, word: &str) -> Result<JsValue, JsValue> {
+        let decomposer = self.decomposer.as_mut()
+            .ok_or_else(|| JsValue::from_str("Database not loaded. Call loadFromBytes() first."))?;
+
+        let morphemes: Vec<WasmMorpheme> = decomposer.decompose(word)
+            .into_iter()
+            .map(|morpheme| WasmMorpheme {
+                surface_form: morpheme.surface_form,
+                morpheme_type: format!("{:?}", morpheme.morpheme_type),
+                x: morpheme.position.map(|p| p.x),
+                y: morpheme.position.map(|p| p.y),
+                z: morpheme.position.map(|p| p.z),
+            })
+            .collect();
+        serde_wasm_bindgen::to_value(&morphemes).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Find the mirrors (opposites) of a word, most confident first.
+    ///
+    /// Returns an array of `{original, mirror, mirror_type, confidence}`.
+    #[wasm_bindgen(js_name = findMirrors)]
+    pub fn find_mirrors(&mut self, word: &str) -> Result<JsValue, JsValue> {
+        let decomposer = self.decomposer.as_mut()
+            .ok_or_else(|| JsValue::from_str("Database not loaded. Call loadFromBytes() first."))?;
+
+        let mirrors: Vec<WasmMirror> = decomposer.find_mirrors(word)
+            .into_iter()
+            .map(|pair| WasmMirror {
+                original: pair.original,
+                mirror: pair.mirror,
+                mirror_type: format!("{:?}", pair.mirror_type),
+                confidence: pair.confidence,
+            })
+            .collect();
+        serde_wasm_bindgen::to_value(&mirrors).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
     /// Execute a compiled query
     #[wasm_bindgen]
     pub fn execute(&mut self, query: &WasmCompiledQuery) -> Result<WasmQueryResult, JsValue> {
@@ -315,7 +374,7 @@ impl LingoDatabase {
                 // Convert NodeSet to actual nodes by getting them from the database
                 let wasm_nodes: Vec<WasmNode> = result.nodes.as_slice().iter().filter_map(|node_id| {
                     // Try to get the actual node from the database
-                    if let Some(ref db) = executor.database {
+                    if let Some(db) = executor.database() {
                         if let Ok(node) = db.get_node(*node_id) {
                             let word = db.get_node_word(*node_id).unwrap_or("unknown");
                             return Some(WasmNode {
@@ -367,7 +426,20 @@ impl LingoDatabase {
         
         Ok(stats.into())
     }
+}
 
+impl LingoDatabase {
+    /// Share a loaded database between the executor and the decomposer
+    fn install(&mut self, database: Arc<CoreDatabase>) -> Result<WasmResult, JsValue> {
+        let decomposer = MirroringDecomposer::new(Arc::clone(&database))
+            .map_err(|e| JsValue::from_str(&format!("Failed to set up morphology: {}", e)))?;
+        self.executor = Some(LingoExecutor::from_shared(database));
+        self.decomposer = Some(decomposer);
+        Ok(WasmResult {
+            success: true,
+            error: None,
+        })
+    }
 }
 
 /// Static methods for the QueryBuilder
@@ -406,6 +478,21 @@ export interface QueryResult {
   length: number;
 }
 
+export interface Morpheme {
+  surface_form: string;
+  morpheme_type: string;
+  x: number | null;
+  y: number | null;
+  z: number | null;
+}
+
+export interface MirrorResult {
+  original: string;
+  mirror: string;
+  mirror_type: string;
+  confidence: number;
+}
+
 export interface DatabaseStats {
   loaded: boolean;
   version: string;
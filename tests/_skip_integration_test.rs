@@ -171,7 +171,7 @@ fn test_exact_word_search() {
     assert_eq!(result.nodes.len(), 1);
     
     // Verify it's the right word
-    if let Some(db) = &executor.database {
+    if let Some(db) = executor.database() {
         let word = db.get_node_word(result.nodes.as_slice()[0]).unwrap();
         assert_eq!(word, "technical");
     }
@@ -197,7 +197,7 @@ fn test_spatial_similarity_search() {
     assert!(result.nodes.len() >= 2);
     
     // Check that we found technology
-    if let Some(db) = &executor.database {
+    if let Some(db) = executor.database() {
         let words: Vec<String> = result.nodes.as_slice()
             .iter()
             .filter_map(|id| db.get_node_word(*id).ok())
@@ -227,7 +227,7 @@ fn test_layer_navigation_up() {
     // Should find words containing "tech"
     assert!(result.nodes.len() >= 2);
     
-    if let Some(db) = &executor.database {
+    if let Some(db) = executor.database() {
         let words: Vec<String> = result.nodes.as_slice()
             .iter()
             .filter_map(|id| db.get_node_word(*id).ok())
@@ -279,7 +279,7 @@ fn test_complex_query_chain() {
     // Should find technical_concepts
     assert!(result.nodes.len() >= 1);
     
-    if let Some(db) = &executor.database {
+    if let Some(db) = executor.database() {
         let concepts: Vec<String> = result.nodes.as_slice()
             .iter()
             .filter_map(|id| db.get_node_word(*id).ok())
@@ -265,7 +265,7 @@ fn display_results(executor: &LingoExecutor, result: &lingo::engine::QueryResult
     println!("📊 Instructions executed: {}", result.instructions_executed);
     println!("🎯 Results found: {}", result.nodes.len());
     
-    if let Some(db) = &executor.database {
+    if let Some(db) = executor.database() {
         println!("\n   Results:");
         for (i, node_id) in result.nodes.as_slice().iter().enumerate() {
             if let Ok(word) = db.get_node_word(*node_id) {
@@ -374,7 +374,7 @@ fn test_showcase_lingo_database() {
     
     // Show database statistics
     print_header("DATABASE STATISTICS");
-    if let Some(db) = &executor.database {
+    if let Some(db) = executor.database() {
         println!("\n📈 Total nodes: {}", db.node_count());
         println!("🔗 Total connections: {}", db.connection_count());
         println!("💾 Database file size: {} bytes", std::fs::metadata(&db_path).unwrap().len());